@@ -0,0 +1,191 @@
+//! Live `pg_stat_activity` viewer for Classic mode.
+//!
+//! `:activity` shows other client backends in the result grid and re-runs the
+//! query on a fixed interval while the viewer stays open. The selected backend
+//! can be cancelled or terminated after a confirmation prompt.
+
+use std::time::{Duration, Instant};
+
+/// Query backing the activity grid. The first column must stay `pid`.
+pub(crate) const ACTIVITY_QUERY: &str = "SELECT pid, usename, datname, state, \
+     date_trunc('second', now() - query_start)::text AS duration, \
+     wait_event_type, query \
+     FROM pg_stat_activity \
+     WHERE pid <> pg_backend_pid() AND backend_type = 'client backend' \
+     ORDER BY query_start NULLS LAST, pid";
+
+/// Column names of [`ACTIVITY_QUERY`], used when no backend rows come back.
+pub(crate) const ACTIVITY_COLUMNS: [&str; 7] = [
+    "pid",
+    "usename",
+    "datname",
+    "state",
+    "duration",
+    "wait_event_type",
+    "query",
+];
+
+/// How often the viewer re-queries `pg_stat_activity`.
+pub(crate) const ACTIVITY_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Refresh bookkeeping for an open activity viewer.
+#[derive(Debug, Clone)]
+pub(crate) struct ActivityView {
+    /// When the most recent refresh was dispatched, if any.
+    pub(crate) last_refresh: Option<Instant>,
+    /// Whether a refresh is currently in flight.
+    pub(crate) refreshing: bool,
+}
+
+impl ActivityView {
+    pub(crate) fn new() -> Self {
+        Self {
+            last_refresh: None,
+            refreshing: false,
+        }
+    }
+
+    /// Returns true when no refresh is in flight and the interval has elapsed.
+    pub(crate) fn refresh_due(&self, now: Instant) -> bool {
+        !self.refreshing
+            && self
+                .last_refresh
+                .is_none_or(|at| now.saturating_duration_since(at) >= ACTIVITY_REFRESH_INTERVAL)
+    }
+
+    pub(crate) fn mark_dispatched(&mut self, now: Instant) {
+        self.last_refresh = Some(now);
+        self.refreshing = true;
+    }
+}
+
+/// The server-side function used to signal a backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendSignal {
+    /// `pg_cancel_backend`: cancel the backend's current query.
+    Cancel,
+    /// `pg_terminate_backend`: close the backend's connection.
+    Terminate,
+}
+
+impl BackendSignal {
+    pub(crate) fn function_name(self) -> &'static str {
+        match self {
+            BackendSignal::Cancel => "pg_cancel_backend",
+            BackendSignal::Terminate => "pg_terminate_backend",
+        }
+    }
+
+    pub(crate) fn sql(self, pid: i32) -> String {
+        format!("SELECT {}({pid})", self.function_name())
+    }
+
+    pub(crate) fn verb(self) -> &'static str {
+        match self {
+            BackendSignal::Cancel => "Cancel query on",
+            BackendSignal::Terminate => "Terminate",
+        }
+    }
+
+    pub(crate) fn past_tense(self) -> &'static str {
+        match self {
+            BackendSignal::Cancel => "Cancelled query on",
+            BackendSignal::Terminate => "Terminated",
+        }
+    }
+}
+
+/// Reads the backend pid from a row of the activity grid.
+pub(crate) fn row_pid(headers: &[String], row: &[String]) -> Option<i32> {
+    let col = headers.iter().position(|h| h == "pid")?;
+    row.get(col)?.trim().parse().ok()
+}
+
+/// Short one-line summary of a row for confirmation prompts.
+pub(crate) fn row_summary(headers: &[String], row: &[String]) -> String {
+    let cell = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h == name)
+            .and_then(|col| row.get(col))
+            .map(String::as_str)
+            .unwrap_or("")
+    };
+    let query: String = cell("query")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let query = if query.chars().count() > 60 {
+        format!("{}...", query.chars().take(57).collect::<String>())
+    } else {
+        query
+    };
+    match (cell("usename"), query.is_empty()) {
+        ("", true) => String::new(),
+        ("", false) => query,
+        (user, true) => user.to_string(),
+        (user, false) => format!("{user}: {query}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> Vec<String> {
+        ["pid", "usename", "datname", "state", "duration", "query"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn refresh_is_due_immediately_and_after_the_interval() {
+        let now = Instant::now();
+        let mut view = ActivityView::new();
+        assert!(view.refresh_due(now));
+
+        view.mark_dispatched(now);
+        assert!(!view.refresh_due(now + ACTIVITY_REFRESH_INTERVAL));
+
+        view.refreshing = false;
+        assert!(!view.refresh_due(now + Duration::from_millis(500)));
+        assert!(view.refresh_due(now + ACTIVITY_REFRESH_INTERVAL));
+    }
+
+    #[test]
+    fn row_pid_reads_the_pid_column() {
+        let row = vec!["4242".into(), "app".into(), "db".into()];
+        assert_eq!(row_pid(&headers(), &row), Some(4242));
+        assert_eq!(row_pid(&headers(), &["NULL".into()]), None);
+        assert_eq!(row_pid(&["state".into()], &row), None);
+    }
+
+    #[test]
+    fn signal_sql_uses_the_matching_function() {
+        assert_eq!(
+            BackendSignal::Cancel.sql(17),
+            "SELECT pg_cancel_backend(17)"
+        );
+        assert_eq!(
+            BackendSignal::Terminate.sql(17),
+            "SELECT pg_terminate_backend(17)"
+        );
+    }
+
+    #[test]
+    fn row_summary_collapses_whitespace_and_truncates() {
+        let row = vec![
+            "1".into(),
+            "app".into(),
+            "db".into(),
+            "active".into(),
+            "00:00:03".into(),
+            format!("SELECT\n  {}", "x".repeat(80)),
+        ];
+        let summary = row_summary(&headers(), &row);
+        assert!(summary.starts_with("app: SELECT xxx"));
+        assert!(summary.ends_with("..."));
+        assert_eq!(summary.chars().count(), "app: ".len() + 60);
+    }
+}
@@ -0,0 +1,756 @@
+//! Text of values received in PostgreSQL's binary format.
+//!
+//! Queries run with bound parameters go through the extended protocol, where
+//! tokio-postgres asks for binary results. Each value is turned back into the
+//! text `simple_query` would have shown, so the grid, copy and export treat
+//! both paths alike. Dates and times are decoded in the ISO style,
+//! `timestamptz` in UTC and the `reg*` types as OIDs, since the session's
+//! settings and catalog are not known here; see [`depends_on_session`].
+//! Types without a decoder are shown as `\x` hex, unless they come from
+//! outside `pg_catalog` and their bytes read as text, as `citext`'s do.
+
+use std::error::Error;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use tokio_postgres::types::{FromSql, Kind, Type};
+
+/// A value's bytes as the server sent them, for any column type.
+pub(crate) struct RawValue<'a>(pub(crate) &'a [u8]);
+
+impl<'a> FromSql<'a> for RawValue<'a> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(RawValue(raw))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+/// The text form of a non-NULL value of type `ty`.
+pub(crate) fn value_text(ty: &Type, raw: &[u8]) -> String {
+    decode(ty, raw).unwrap_or_else(|| fallback_text(ty, raw))
+}
+
+/// Whether the text of `ty` depends on the session: dates and times follow
+/// `DateStyle`, `interval` follows `IntervalStyle`, `timestamptz` also the
+/// `TimeZone`, `bytea` follows `bytea_output` and `regclass`, `regtype` and
+/// `regproc` show names. The text [`value_text`] gives them is valid input for
+/// the type under any of those settings, so the server can turn it into what
+/// `simple_query` would have shown.
+pub(crate) fn depends_on_session(ty: &Type) -> bool {
+    match ty.kind() {
+        Kind::Array(element) | Kind::Domain(element) | Kind::Range(element) => {
+            depends_on_session(element)
+        }
+        Kind::Composite(fields) => fields.iter().any(|field| depends_on_session(field.type_())),
+        _ => {
+            ty.schema() == "pg_catalog"
+                && matches!(
+                    ty.name(),
+                    "date"
+                        | "time"
+                        | "timetz"
+                        | "timestamp"
+                        | "timestamptz"
+                        | "interval"
+                        | "bytea"
+                        | "regclass"
+                        | "regtype"
+                        | "regproc"
+                )
+        }
+    }
+}
+
+/// Text for a value [`decode`] does not know. Built-in types like `money` or
+/// `point` send numbers that can happen to be valid UTF-8, so only types from
+/// extensions are taken as text, and only when it has no control characters.
+fn fallback_text(ty: &Type, raw: &[u8]) -> String {
+    let mut base = ty;
+    while let Kind::Domain(inner) = base.kind() {
+        base = inner;
+    }
+    let text_like = matches!(base.kind(), Kind::Simple) && base.schema() != "pg_catalog";
+    match std::str::from_utf8(raw) {
+        Ok(text)
+            if text_like
+                && !text
+                    .chars()
+                    .any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r')) =>
+        {
+            text.to_string()
+        }
+        _ => hex(raw),
+    }
+}
+
+fn hex(raw: &[u8]) -> String {
+    let mut text = String::with_capacity(2 + raw.len() * 2);
+    text.push_str("\\x");
+    for byte in raw {
+        text.push_str(&format!("{byte:02x}"));
+    }
+    text
+}
+
+fn decode(ty: &Type, raw: &[u8]) -> Option<String> {
+    match ty.kind() {
+        Kind::Array(element) => return array_text(element, raw),
+        Kind::Domain(base) => return decode(base, raw),
+        Kind::Range(element) => return range_text(element, raw),
+        Kind::Composite(fields) => {
+            let types: Vec<Type> = fields.iter().map(|field| field.type_().clone()).collect();
+            return record_text(raw, |index, _| types.get(index).cloned());
+        }
+        Kind::Enum(_) => return String::from_utf8(raw.to_vec()).ok(),
+        _ => {}
+    }
+    if ty.schema() != "pg_catalog" {
+        return None;
+    }
+    let mut bytes = Bytes(raw);
+    let text = match ty.name() {
+        "bool" => match bytes.take::<1>()? {
+            [0] => "f".to_string(),
+            _ => "t".to_string(),
+        },
+        "char" => char::from(bytes.take::<1>()?[0]).to_string(),
+        "int2" => i16::from_be_bytes(bytes.take()?).to_string(),
+        "int4" => i32::from_be_bytes(bytes.take()?).to_string(),
+        "int8" => i64::from_be_bytes(bytes.take()?).to_string(),
+        "oid" | "xid" | "cid" | "regclass" | "regtype" | "regproc" => {
+            u32::from_be_bytes(bytes.take()?).to_string()
+        }
+        "float4" => float_text(f64::from(f32::from_be_bytes(bytes.take()?)), 6, true),
+        "float8" => float_text(f64::from_be_bytes(bytes.take()?), 15, false),
+        "numeric" => numeric_text(&mut bytes)?,
+        "text" | "varchar" | "bpchar" | "name" | "unknown" | "json" | "xml" => {
+            return String::from_utf8(raw.to_vec()).ok();
+        }
+        "jsonb" => {
+            let (version, json) = raw.split_first()?;
+            return (*version == 1)
+                .then(|| String::from_utf8(json.to_vec()).ok())
+                .flatten();
+        }
+        "bytea" => return Some(hex(raw)),
+        "uuid" => uuid_text(bytes.take()?),
+        "date" => date_text(i32::from_be_bytes(bytes.take()?))?,
+        "time" => time_text(i64::from_be_bytes(bytes.take()?))?,
+        "timetz" => {
+            let time = time_text(i64::from_be_bytes(bytes.take()?))?;
+            // The server sends the offset west of UTC.
+            let offset = -i32::from_be_bytes(bytes.take()?);
+            format!("{time}{}", offset_text(offset))
+        }
+        "timestamp" => timestamp_text(i64::from_be_bytes(bytes.take()?))?,
+        "timestamptz" => {
+            let micros = i64::from_be_bytes(bytes.take()?);
+            let text = timestamp_text(micros)?;
+            if is_infinite(micros) {
+                text
+            } else {
+                format!("{text}+00")
+            }
+        }
+        "interval" => {
+            let micros = i64::from_be_bytes(bytes.take()?);
+            let days = i32::from_be_bytes(bytes.take()?);
+            let months = i32::from_be_bytes(bytes.take()?);
+            interval_text(months, days, micros)
+        }
+        "inet" | "cidr" => inet_text(&mut bytes)?,
+        "record" => return record_text(raw, |_, oid| Type::from_oid(oid)),
+        _ => return None,
+    };
+    bytes.0.is_empty().then_some(text)
+}
+
+/// Reads fixed-size big-endian fields off the front of a value.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.0.split_first_chunk::<N>()?;
+        self.0 = rest;
+        Some(*head)
+    }
+
+    fn take_slice(&mut self, len: usize) -> Option<&'a [u8]> {
+        let (head, rest) = self.0.split_at_checked(len)?;
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.take().map(i32::from_be_bytes)
+    }
+
+    /// A length-prefixed value; None inside for SQL NULL.
+    fn value(&mut self) -> Option<Option<&'a [u8]>> {
+        let len = self.i32()?;
+        if len < 0 {
+            return Some(None);
+        }
+        self.take_slice(usize::try_from(len).ok()?).map(Some)
+    }
+}
+
+/// Shortest round-trip digits, switching to an exponent where PostgreSQL does.
+fn float_text(value: f64, digits: i32, single: bool) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    let exponent = if value == 0.0 {
+        0
+    } else {
+        value.abs().log10().floor() as i32
+    };
+    // f32 values print their own shortest digits, not those of the widened f64.
+    let text = if single {
+        let value = value as f32;
+        if exponent < -4 || exponent >= digits {
+            format!("{value:e}")
+        } else {
+            format!("{value}")
+        }
+    } else if exponent < -4 || exponent >= digits {
+        format!("{value:e}")
+    } else {
+        format!("{value}")
+    };
+    match text.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let (sign, digits) = match exponent.strip_prefix('-') {
+                Some(digits) => ('-', digits),
+                None => ('+', exponent),
+            };
+            format!("{mantissa}e{sign}{digits:0>2}")
+        }
+        None => text,
+    }
+}
+
+fn numeric_text(bytes: &mut Bytes) -> Option<String> {
+    let ndigits = usize::try_from(i16::from_be_bytes(bytes.take()?)).ok()?;
+    let weight = i32::from(i16::from_be_bytes(bytes.take()?));
+    let sign = u16::from_be_bytes(bytes.take()?);
+    let scale = usize::from(u16::from_be_bytes(bytes.take()?));
+    let digits = (0..ndigits)
+        .map(|_| bytes.take().map(i16::from_be_bytes))
+        .collect::<Option<Vec<i16>>>()?;
+    match sign {
+        0x0000 | 0x4000 => {}
+        0xC000 => return Some("NaN".to_string()),
+        0xD000 => return Some("Infinity".to_string()),
+        0xF000 => return Some("-Infinity".to_string()),
+        _ => return None,
+    }
+    // Digits are base 10000; `weight` is the power of the first one.
+    let digit = |index: i32| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| digits.get(index))
+            .copied()
+            .unwrap_or(0)
+    };
+    let mut text = String::new();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+        for index in 1..=weight {
+            text.push_str(&format!("{:04}", digit(index)));
+        }
+    }
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut index = weight + 1;
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(index)));
+            index += 1;
+        }
+        fraction.truncate(scale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Some(text)
+}
+
+fn uuid_text(raw: [u8; 16]) -> String {
+    let hex: String = raw.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("2000-01-01 is a valid date")
+}
+
+fn is_infinite(micros: i64) -> bool {
+    micros == i64::MAX || micros == i64::MIN
+}
+
+/// `YYYY-MM-DD`, with ` BC` for years before 1.
+fn date_part(date: NaiveDate) -> String {
+    let text = date.format("%m-%d").to_string();
+    let year = chrono::Datelike::year(&date);
+    if year > 0 {
+        format!("{year:04}-{text}")
+    } else {
+        format!("{:04}-{text} BC", 1 - year)
+    }
+}
+
+fn date_text(days: i32) -> Option<String> {
+    match days {
+        i32::MAX => Some("infinity".to_string()),
+        i32::MIN => Some("-infinity".to_string()),
+        days => epoch()
+            .date()
+            .checked_add_signed(Duration::days(i64::from(days)))
+            .map(date_part),
+    }
+}
+
+/// `HH:MM:SS`, with as many fractional digits as the value needs.
+fn clock_text(hours: i64, minutes: i64, seconds: i64, micros: i64) -> String {
+    let mut text = format!("{hours:02}:{minutes:02}:{seconds:02}");
+    if micros > 0 {
+        let fraction = format!("{micros:06}");
+        text.push('.');
+        text.push_str(fraction.trim_end_matches('0'));
+    }
+    text
+}
+
+fn time_text(micros: i64) -> Option<String> {
+    // 24:00:00 is a valid time of day in PostgreSQL.
+    if !(0..=86_400_000_000).contains(&micros) {
+        return None;
+    }
+    let seconds = micros / 1_000_000;
+    Some(clock_text(
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        micros % 1_000_000,
+    ))
+}
+
+/// `+02`, `-05:30` or `+05:45:30` for an offset east of UTC in seconds.
+fn offset_text(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    let mut text = format!("{sign}{:02}", offset / 3600);
+    if offset % 3600 != 0 {
+        text.push_str(&format!(":{:02}", offset / 60 % 60));
+    }
+    if offset % 60 != 0 {
+        text.push_str(&format!(":{:02}", offset % 60));
+    }
+    text
+}
+
+fn timestamp_text(micros: i64) -> Option<String> {
+    match micros {
+        i64::MAX => Some("infinity".to_string()),
+        i64::MIN => Some("-infinity".to_string()),
+        micros => {
+            let timestamp = epoch().checked_add_signed(Duration::microseconds(micros))?;
+            let time = timestamp.time();
+            let micros = i64::from(chrono::Timelike::nanosecond(&time) / 1_000);
+            let seconds = i64::from(chrono::Timelike::num_seconds_from_midnight(&time));
+            let clock = clock_text(seconds / 3600, seconds / 60 % 60, seconds % 60, micros);
+            let date = date_part(timestamp.date());
+            Some(match date.strip_suffix(" BC") {
+                Some(date) => format!("{date} {clock} BC"),
+                None => format!("{date} {clock}"),
+            })
+        }
+    }
+}
+
+/// PostgreSQL's default `postgres` interval style: `1 year 2 mons 3 days 04:05:06`.
+fn interval_text(months: i32, days: i32, micros: i64) -> String {
+    let mut parts = Vec::new();
+    // After a negative unit, positive ones carry an explicit `+`.
+    let mut after_negative = false;
+    let mut unit = |count: i64, name: &str| {
+        if count == 0 {
+            return;
+        }
+        let sign = if after_negative && count > 0 { "+" } else { "" };
+        let plural = if count == 1 { "" } else { "s" };
+        parts.push(format!("{sign}{count} {name}{plural}"));
+        after_negative |= count < 0;
+    };
+    unit(i64::from(months / 12), "year");
+    unit(i64::from(months % 12), "mon");
+    unit(i64::from(days), "day");
+    if micros != 0 || parts.is_empty() {
+        let sign = if micros < 0 {
+            "-"
+        } else if after_negative {
+            "+"
+        } else {
+            ""
+        };
+        let micros = micros.unsigned_abs();
+        let seconds = micros / 1_000_000;
+        let clock = clock_text(
+            i64::try_from(seconds / 3600).unwrap_or(i64::MAX),
+            i64::try_from(seconds / 60 % 60).unwrap_or(0),
+            i64::try_from(seconds % 60).unwrap_or(0),
+            i64::try_from(micros % 1_000_000).unwrap_or(0),
+        );
+        parts.push(format!("{sign}{clock}"));
+    }
+    parts.join(" ")
+}
+
+fn inet_text(bytes: &mut Bytes) -> Option<String> {
+    let [family, bits, is_cidr, len] = bytes.take::<4>()?;
+    let address = bytes.take_slice(usize::from(len))?;
+    let (address, max_bits) = match family {
+        2 => (
+            Ipv4Addr::from(<[u8; 4]>::try_from(address).ok()?).to_string(),
+            32,
+        ),
+        3 => (
+            Ipv6Addr::from(<[u8; 16]>::try_from(address).ok()?).to_string(),
+            128,
+        ),
+        _ => return None,
+    };
+    Some(if is_cidr != 0 || bits != max_bits {
+        format!("{address}/{bits}")
+    } else {
+        address
+    })
+}
+
+/// An array element or record field, quoted the way PostgreSQL's text output
+/// quotes it when it would otherwise be ambiguous.
+fn quote_element(text: &str, special: &[char], null_word: bool) -> String {
+    let needs_quotes = text.is_empty()
+        || (null_word && text.eq_ignore_ascii_case("null"))
+        || text
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\\' || special.contains(&c));
+    if !needs_quotes {
+        return text.to_string();
+    }
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn array_text(element: &Type, raw: &[u8]) -> Option<String> {
+    let mut bytes = Bytes(raw);
+    let dimensions = usize::try_from(bytes.i32()?).ok()?;
+    let _has_nulls = bytes.i32()?;
+    let _element_oid = bytes.take::<4>()?;
+    let mut lengths = Vec::with_capacity(dimensions);
+    let mut bounds = Vec::with_capacity(dimensions);
+    for _ in 0..dimensions {
+        lengths.push(usize::try_from(bytes.i32()?).ok()?);
+        bounds.push(bytes.i32()?);
+    }
+    let count: usize = if dimensions == 0 {
+        0
+    } else {
+        lengths.iter().product()
+    };
+    let mut elements = Vec::with_capacity(count);
+    for _ in 0..count {
+        elements.push(match bytes.value()? {
+            Some(raw) => quote_element(&value_text(element, raw), &['{', '}', ','], true),
+            None => "NULL".to_string(),
+        });
+    }
+    if !bytes.0.is_empty() {
+        return None;
+    }
+    if dimensions == 0 {
+        return Some("{}".to_string());
+    }
+
+    fn nest(lengths: &[usize], elements: &mut std::vec::IntoIter<String>) -> String {
+        let Some((&length, inner)) = lengths.split_first() else {
+            return elements.next().unwrap_or_default();
+        };
+        let items: Vec<String> = (0..length).map(|_| nest(inner, elements)).collect();
+        format!("{{{}}}", items.join(","))
+    }
+    let body = nest(&lengths, &mut elements.into_iter());
+    // Arrays that don't start at index 1 show their bounds, like `[0:2]={...}`.
+    if bounds.iter().all(|&bound| bound == 1) {
+        return Some(body);
+    }
+    let dims: String = lengths
+        .iter()
+        .zip(&bounds)
+        .map(|(&length, &lower)| {
+            let upper = i64::from(lower) + i64::try_from(length).unwrap_or(0) - 1;
+            format!("[{lower}:{upper}]")
+        })
+        .collect();
+    Some(format!("{dims}={body}"))
+}
+
+fn range_text(element: &Type, raw: &[u8]) -> Option<String> {
+    const EMPTY: u8 = 0x01;
+    const LOWER_INCLUSIVE: u8 = 0x02;
+    const UPPER_INCLUSIVE: u8 = 0x04;
+    const LOWER_INFINITE: u8 = 0x08;
+    const UPPER_INFINITE: u8 = 0x10;
+
+    let mut bytes = Bytes(raw);
+    let [flags] = bytes.take::<1>()?;
+    if flags & EMPTY != 0 {
+        return Some("empty".to_string());
+    }
+    let mut bound = |infinite: u8| -> Option<String> {
+        if flags & infinite != 0 {
+            return Some(String::new());
+        }
+        let raw = bytes.value()??;
+        Some(quote_element(
+            &value_text(element, raw),
+            &['(', ')', '[', ']', ','],
+            false,
+        ))
+    };
+    let lower = bound(LOWER_INFINITE)?;
+    let upper = bound(UPPER_INFINITE)?;
+    let open = if flags & LOWER_INCLUSIVE != 0 {
+        '['
+    } else {
+        '('
+    };
+    let close = if flags & UPPER_INCLUSIVE != 0 {
+        ']'
+    } else {
+        ')'
+    };
+    Some(format!("{open}{lower},{upper}{close}"))
+}
+
+/// A row value, `(1,"a b",)`; `field_type` finds each field's type from its
+/// position or the oid sent with it.
+fn record_text(raw: &[u8], field_type: impl Fn(usize, u32) -> Option<Type>) -> Option<String> {
+    let mut bytes = Bytes(raw);
+    let count = usize::try_from(bytes.i32()?).ok()?;
+    let mut fields = Vec::with_capacity(count);
+    for index in 0..count {
+        let oid = u32::from_be_bytes(bytes.take()?);
+        fields.push(match bytes.value()? {
+            Some(raw) => {
+                let text = match field_type(index, oid) {
+                    Some(ty) => value_text(&ty, raw),
+                    None => hex(raw),
+                };
+                quote_element(&text, &['(', ')', ','], false)
+            }
+            None => String::new(),
+        });
+    }
+    bytes
+        .0
+        .is_empty()
+        .then(|| format!("({})", fields.join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(weight: i16, sign: u16, scale: u16, digits: &[i16]) -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend((digits.len() as i16).to_be_bytes());
+        raw.extend(weight.to_be_bytes());
+        raw.extend(sign.to_be_bytes());
+        raw.extend(scale.to_be_bytes());
+        for digit in digits {
+            raw.extend(digit.to_be_bytes());
+        }
+        raw
+    }
+
+    #[test]
+    fn scalars_render_like_the_text_protocol() {
+        assert_eq!(value_text(&Type::BOOL, &[1]), "t");
+        assert_eq!(value_text(&Type::INT4, &(-42i32).to_be_bytes()), "-42");
+        assert_eq!(
+            value_text(&Type::INT8, &i64::MAX.to_be_bytes()),
+            i64::MAX.to_string()
+        );
+        assert_eq!(value_text(&Type::FLOAT8, &0.1f64.to_be_bytes()), "0.1");
+        assert_eq!(value_text(&Type::FLOAT8, &1e20f64.to_be_bytes()), "1e+20");
+        assert_eq!(
+            value_text(&Type::FLOAT8, &1.5e-7f64.to_be_bytes()),
+            "1.5e-07"
+        );
+        assert_eq!(value_text(&Type::FLOAT4, &0.1f32.to_be_bytes()), "0.1");
+        assert_eq!(value_text(&Type::TEXT, "héllo".as_bytes()), "héllo");
+        assert_eq!(value_text(&Type::JSONB, b"\x01{\"a\": 1}"), "{\"a\": 1}");
+        assert_eq!(value_text(&Type::BYTEA, &[0xde, 0xad]), "\\xdead");
+        assert_eq!(
+            value_text(&Type::UUID, &[0xa0; 16]),
+            "a0a0a0a0-a0a0-a0a0-a0a0-a0a0a0a0a0a0"
+        );
+        assert_eq!(
+            value_text(&Type::INET, &[2, 24, 0, 4, 10, 0, 0, 0]),
+            "10.0.0.0/24"
+        );
+        assert_eq!(
+            value_text(&Type::INET, &[2, 32, 0, 4, 10, 0, 0, 1]),
+            "10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn numerics_keep_their_scale() {
+        // 12345.6780 is 1|2345|6780 with weight 1.
+        assert_eq!(
+            value_text(&Type::NUMERIC, &numeric(1, 0, 4, &[1, 2345, 6780])),
+            "12345.6780"
+        );
+        assert_eq!(
+            value_text(&Type::NUMERIC, &numeric(-1, 0x4000, 3, &[50])),
+            "-0.005"
+        );
+        assert_eq!(
+            value_text(&Type::NUMERIC, &numeric(2, 0, 0, &[7])),
+            "700000000"
+        );
+        assert_eq!(value_text(&Type::NUMERIC, &numeric(0, 0, 2, &[])), "0.00");
+        assert_eq!(
+            value_text(&Type::NUMERIC, &numeric(0, 0xC000, 0, &[])),
+            "NaN"
+        );
+    }
+
+    #[test]
+    fn dates_and_times_use_the_iso_style() {
+        assert_eq!(
+            value_text(&Type::DATE, &(-1i32).to_be_bytes()),
+            "1999-12-31"
+        );
+        assert_eq!(value_text(&Type::DATE, &i32::MAX.to_be_bytes()), "infinity");
+        let micros = (86_400 + 3_723) * 1_000_000i64 + 500_000;
+        assert_eq!(
+            value_text(&Type::TIMESTAMP, &micros.to_be_bytes()),
+            "2000-01-02 01:02:03.5"
+        );
+        assert_eq!(
+            value_text(&Type::TIMESTAMPTZ, &0i64.to_be_bytes()),
+            "2000-01-01 00:00:00+00"
+        );
+        assert_eq!(
+            value_text(&Type::TIME, &(45_296_000_001i64).to_be_bytes()),
+            "12:34:56.000001"
+        );
+
+        let mut interval = Vec::new();
+        interval.extend((3_723_000_000i64).to_be_bytes());
+        interval.extend(3i32.to_be_bytes());
+        interval.extend(14i32.to_be_bytes());
+        assert_eq!(
+            value_text(&Type::INTERVAL, &interval),
+            "1 year 2 mons 3 days 01:02:03"
+        );
+        assert_eq!(value_text(&Type::INTERVAL, &[0; 16]), "00:00:00");
+        let mut interval = Vec::new();
+        interval.extend((7_200_000_000i64).to_be_bytes());
+        interval.extend((-1i32).to_be_bytes());
+        interval.extend(0i32.to_be_bytes());
+        assert_eq!(value_text(&Type::INTERVAL, &interval), "-1 days +02:00:00");
+    }
+
+    #[test]
+    fn arrays_quote_elements_that_need_it() {
+        let mut raw = Vec::new();
+        raw.extend(1i32.to_be_bytes());
+        raw.extend(1i32.to_be_bytes());
+        raw.extend(25u32.to_be_bytes());
+        raw.extend(3i32.to_be_bytes());
+        raw.extend(1i32.to_be_bytes());
+        for element in [Some("a"), Some("b c"), None] {
+            match element {
+                Some(text) => {
+                    raw.extend((text.len() as i32).to_be_bytes());
+                    raw.extend(text.as_bytes());
+                }
+                None => raw.extend((-1i32).to_be_bytes()),
+            }
+        }
+        assert_eq!(value_text(&Type::TEXT_ARRAY, &raw), "{a,\"b c\",NULL}");
+
+        let mut empty = Vec::new();
+        empty.extend(0i32.to_be_bytes());
+        empty.extend(0i32.to_be_bytes());
+        empty.extend(23u32.to_be_bytes());
+        assert_eq!(value_text(&Type::INT4_ARRAY, &empty), "{}");
+    }
+
+    #[test]
+    fn unknown_binary_values_fall_back_to_hex() {
+        assert_eq!(
+            value_text(&Type::MONEY, &1234i64.to_be_bytes()),
+            "\\x00000000000004d2"
+        );
+        assert_eq!(value_text(&Type::INT4, &[0xff, 0xfe]), "\\xfffe");
+        // 1.00::money is 100 cents, all of it valid UTF-8.
+        assert_eq!(
+            value_text(&Type::MONEY, &100i64.to_be_bytes()),
+            "\\x0000000000000064"
+        );
+        assert_eq!(value_text(&Type::MACADDR, b"abcdef"), "\\x616263646566");
+
+        let citext = Type::new("citext".into(), 90_001, Kind::Simple, "public".into());
+        assert_eq!(value_text(&citext, b"Hello"), "Hello");
+        let hstore = Type::new("hstore".into(), 90_002, Kind::Simple, "public".into());
+        assert_eq!(value_text(&hstore, &[0, 0, 0, 0]), "\\x00000000");
+    }
+
+    #[test]
+    fn session_dependent_types_are_left_to_the_server() {
+        assert!(depends_on_session(&Type::TIMESTAMPTZ));
+        assert!(depends_on_session(&Type::TIMESTAMPTZ_ARRAY));
+        assert!(depends_on_session(&Type::TSTZ_RANGE));
+        assert!(depends_on_session(&Type::REGCLASS));
+        assert!(depends_on_session(&Type::REGTYPE_ARRAY));
+        assert!(depends_on_session(&Type::DATE));
+        assert!(depends_on_session(&Type::TIMESTAMP));
+        assert!(depends_on_session(&Type::INTERVAL_ARRAY));
+        assert!(depends_on_session(&Type::BYTEA));
+        assert!(!depends_on_session(&Type::OID));
+        assert!(!depends_on_session(&Type::NUMERIC));
+        assert!(!depends_on_session(&Type::TEXT_ARRAY));
+    }
+}
@@ -0,0 +1,328 @@
+//! Keyset-paginated table browsing for `:browse <table>` and the sidebar.
+//!
+//! Pages are read in primary-key order, optionally after one sort column, and
+//! each continuation page starts strictly after the last loaded row instead of
+//! using `OFFSET`. Deep pages therefore cost the same index range scan as the
+//! first one, and rows inserted meanwhile never shift or repeat a page.
+
+use super::result_transform::OrderDirection;
+use crate::ui::quote_identifier;
+
+/// A server-side sort chosen from the grid header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct BrowseSort {
+    pub(crate) column: String,
+    pub(crate) direction: OrderDirection,
+}
+
+/// Ordering values of the last loaded row; `None` is SQL NULL.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct KeysetPosition {
+    sort: Option<Option<String>>,
+    keys: Vec<String>,
+}
+
+/// The table being browsed and how far into it the grid has read.
+#[derive(Clone, Debug)]
+pub(crate) struct TableBrowse {
+    /// Quoted, optionally schema-qualified table used in the generated SQL.
+    table_ref: String,
+    /// Table name as typed, for status messages.
+    pub(crate) label: String,
+    /// Primary-key columns, in key order.
+    pub(crate) keys: Vec<String>,
+    pub(crate) sort: Option<BrowseSort>,
+    pub(crate) page_size: usize,
+    /// Bumped on every re-sort so pages of an older ordering are dropped.
+    pub(crate) generation: u64,
+    /// SQL of the first page, which runs as a regular Classic query.
+    pub(crate) first_page: String,
+    pub(crate) loading: bool,
+    pub(crate) done: bool,
+}
+
+impl TableBrowse {
+    pub(crate) fn new(
+        schema: Option<&str>,
+        table: &str,
+        keys: Vec<String>,
+        page_size: usize,
+    ) -> Self {
+        let table_ref = table_reference(schema, table);
+        let label = match schema {
+            Some(schema) => format!("{schema}.{table}"),
+            None => table.to_string(),
+        };
+        let mut browse = Self {
+            table_ref,
+            label,
+            keys,
+            sort: None,
+            page_size: page_size.max(1),
+            generation: 0,
+            first_page: String::new(),
+            loading: false,
+            done: false,
+        };
+        browse.first_page = browse.page_query(None);
+        browse
+    }
+
+    /// Restarts from the first page with a new ordering.
+    pub(crate) fn set_sort(&mut self, sort: Option<BrowseSort>) {
+        self.sort = sort;
+        self.generation += 1;
+        self.first_page = self.page_query(None);
+        self.loading = false;
+        self.done = false;
+    }
+
+    /// Cycles `column` through ascending, descending and back to key order.
+    pub(crate) fn toggle_sort(&mut self, column: &str) {
+        let next = match &self.sort {
+            Some(sort) if sort.column == column && sort.direction == OrderDirection::Asc => {
+                Some(OrderDirection::Desc)
+            }
+            Some(sort) if sort.column == column => None,
+            _ => Some(OrderDirection::Asc),
+        };
+        self.set_sort(next.map(|direction| BrowseSort {
+            column: column.to_string(),
+            direction,
+        }));
+    }
+
+    /// Human-readable ordering, e.g. `name desc, then id`.
+    pub(crate) fn order_label(&self) -> String {
+        let keys = self.keys.join(", ");
+        match &self.sort {
+            None => keys,
+            Some(sort) if self.sort_column().is_none() => {
+                format!("{keys} {}", sort.direction.label())
+            }
+            Some(sort) => format!("{} {}, then {keys}", sort.column, sort.direction.label()),
+        }
+    }
+
+    fn direction(&self) -> OrderDirection {
+        self.sort
+            .as_ref()
+            .map_or(OrderDirection::Asc, |sort| sort.direction)
+    }
+
+    /// The non-key sort column; sorting by a key column only flips the key order.
+    fn sort_column(&self) -> Option<&str> {
+        self.sort
+            .as_ref()
+            .map(|sort| sort.column.as_str())
+            .filter(|column| !self.keys.iter().any(|key| key == column))
+    }
+
+    /// Reads the ordering values of a loaded row.
+    pub(crate) fn position(
+        &self,
+        headers: &[String],
+        row: &[String],
+        nulls: &[bool],
+    ) -> Option<KeysetPosition> {
+        let value = |column: &str| {
+            let index = headers.iter().position(|header| header == column)?;
+            let value = row.get(index)?;
+            Some((!nulls.get(index).copied().unwrap_or(false)).then(|| value.clone()))
+        };
+        let sort = match self.sort_column() {
+            Some(column) => Some(value(column)?),
+            None => None,
+        };
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| value(key).flatten())
+            .collect::<Option<Vec<_>>>()?;
+        Some(KeysetPosition { sort, keys })
+    }
+
+    /// `SELECT` for the page after `after`, or the first page.
+    pub(crate) fn page_query(&self, after: Option<&KeysetPosition>) -> String {
+        let direction = self.direction();
+        let sort = self.sort_column().map(quote_identifier);
+        let keys: Vec<String> = self.keys.iter().map(|key| quote_identifier(key)).collect();
+        let order = sort
+            .iter()
+            .chain(&keys)
+            .map(|column| format!("{column} {}", direction.sql()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let predicate = after.map(|position| {
+            let after_op = match direction {
+                OrderDirection::Asc => ">",
+                OrderDirection::Desc => "<",
+            };
+            let values: Vec<String> = position.keys.iter().map(|v| quote_literal(v)).collect();
+            let keys_after = format!("({}) {after_op} ({})", keys.join(", "), values.join(", "));
+            match (&sort, &position.sort) {
+                (Some(column), Some(Some(value))) => {
+                    let value = quote_literal(value);
+                    // ASC sorts NULLs last and DESC sorts them first.
+                    let nulls_after = match direction {
+                        OrderDirection::Asc => format!(" OR {column} IS NULL"),
+                        OrderDirection::Desc => String::new(),
+                    };
+                    format!(
+                        "{column} {after_op} {value} OR ({column} = {value} AND {keys_after}){nulls_after}"
+                    )
+                }
+                (Some(column), Some(None)) => match direction {
+                    OrderDirection::Asc => format!("{column} IS NULL AND {keys_after}"),
+                    OrderDirection::Desc => {
+                        format!("({column} IS NULL AND {keys_after}) OR {column} IS NOT NULL")
+                    }
+                },
+                _ => keys_after,
+            }
+        });
+
+        let filter = predicate.map_or_else(String::new, |predicate| format!(" WHERE {predicate}"));
+        format!(
+            "SELECT * FROM {}{filter} ORDER BY {order} LIMIT {}",
+            self.table_ref, self.page_size
+        )
+    }
+}
+
+/// Quoted, optionally schema-qualified reference to `table`.
+pub(crate) fn table_reference(schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) => format!("{}.{}", quote_identifier(schema), quote_identifier(table)),
+        None => quote_identifier(table),
+    }
+}
+
+/// Untyped string literal; PostgreSQL coerces it to the compared column's type.
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn browse(keys: &[&str]) -> TableBrowse {
+        TableBrowse::new(
+            Some("public"),
+            "Users",
+            keys.iter().map(|key| key.to_string()).collect(),
+            50,
+        )
+    }
+
+    fn headers() -> Vec<String> {
+        ["id", "name"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn first_and_next_pages_follow_the_primary_key() {
+        let browse = browse(&["id"]);
+        assert_eq!(
+            browse.first_page,
+            "SELECT * FROM public.\"Users\" ORDER BY id ASC LIMIT 50"
+        );
+
+        let position = browse
+            .position(
+                &headers(),
+                &["7".to_string(), "O'Hara".to_string()],
+                &[false, false],
+            )
+            .unwrap();
+        assert_eq!(
+            browse.page_query(Some(&position)),
+            "SELECT * FROM public.\"Users\" WHERE (id) > ('7') ORDER BY id ASC LIMIT 50"
+        );
+    }
+
+    #[test]
+    fn composite_keys_compare_as_a_row() {
+        let browse = browse(&["tenant", "id"]);
+        let position = browse
+            .position(
+                &["tenant".to_string(), "id".to_string()],
+                &["a".to_string(), "3".to_string()],
+                &[false, false],
+            )
+            .unwrap();
+        assert_eq!(
+            browse.page_query(Some(&position)),
+            "SELECT * FROM public.\"Users\" WHERE (tenant, id) > ('a', '3') \
+             ORDER BY tenant ASC, id ASC LIMIT 50"
+        );
+    }
+
+    #[test]
+    fn header_sort_orders_by_the_column_then_the_key_and_handles_nulls() {
+        let mut browse = browse(&["id"]);
+        browse.toggle_sort("name");
+        assert_eq!(browse.generation, 1);
+        assert_eq!(browse.order_label(), "name asc, then id");
+        assert_eq!(
+            browse.first_page,
+            "SELECT * FROM public.\"Users\" ORDER BY name ASC, id ASC LIMIT 50"
+        );
+
+        let row = ["7".to_string(), "O'Hara".to_string()];
+        let position = browse.position(&headers(), &row, &[false, false]).unwrap();
+        assert_eq!(
+            browse.page_query(Some(&position)),
+            "SELECT * FROM public.\"Users\" WHERE name > 'O''Hara' OR (name = 'O''Hara' \
+             AND (id) > ('7')) OR name IS NULL ORDER BY name ASC, id ASC LIMIT 50"
+        );
+        let null_position = browse.position(&headers(), &row, &[false, true]).unwrap();
+        assert_eq!(
+            browse.page_query(Some(&null_position)),
+            "SELECT * FROM public.\"Users\" WHERE name IS NULL AND (id) > ('7') \
+             ORDER BY name ASC, id ASC LIMIT 50"
+        );
+
+        browse.toggle_sort("name");
+        assert_eq!(
+            browse.page_query(Some(&position)),
+            "SELECT * FROM public.\"Users\" WHERE name < 'O''Hara' OR (name = 'O''Hara' \
+             AND (id) < ('7')) ORDER BY name DESC, id DESC LIMIT 50"
+        );
+        assert_eq!(
+            browse.page_query(Some(&null_position)),
+            "SELECT * FROM public.\"Users\" WHERE (name IS NULL AND (id) < ('7')) \
+             OR name IS NOT NULL ORDER BY name DESC, id DESC LIMIT 50"
+        );
+
+        browse.toggle_sort("name");
+        assert_eq!(browse.sort, None);
+        assert_eq!(browse.order_label(), "id");
+    }
+
+    #[test]
+    fn sorting_by_a_key_column_only_flips_the_key_order() {
+        let mut browse = browse(&["id"]);
+        browse.toggle_sort("id");
+        browse.toggle_sort("id");
+        assert_eq!(browse.order_label(), "id desc");
+        assert_eq!(
+            browse.first_page,
+            "SELECT * FROM public.\"Users\" ORDER BY id DESC LIMIT 50"
+        );
+    }
+
+    #[test]
+    fn rows_missing_an_ordering_column_have_no_position() {
+        let browse = browse(&["uuid"]);
+        assert_eq!(
+            browse.position(
+                &headers(),
+                &["1".to_string(), "a".to_string()],
+                &[false, false]
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Recent grid copies kept for the `:clipboard` picker.
+//!
+//! Each copy replaces the system clipboard, so the ring remembers the last few
+//! cells and rows copied from a result grid and lets any of them be copied
+//! again.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// Characters of the copied text shown in the picker.
+const PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ClipboardEntry {
+    pub(crate) text: String,
+    time: DateTime<Local>,
+}
+
+impl ClipboardEntry {
+    /// One-line form for the `:clipboard` picker.
+    pub(crate) fn display(&self) -> String {
+        let lines = self.text.lines().count();
+        let preview: String = self
+            .text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+        let size = if lines > 1 {
+            format!("{lines} lines")
+        } else {
+            format!("{} chars", self.text.chars().count())
+        };
+        format!("{}  {size:>10}  {preview}", self.time.format("%H:%M:%S"))
+    }
+}
+
+/// Newest-first copies, without duplicates.
+#[derive(Debug)]
+pub(crate) struct ClipboardRing {
+    entries: VecDeque<ClipboardEntry>,
+    capacity: usize,
+}
+
+impl ClipboardRing {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records a copy; copying the same text again moves it to the front.
+    pub(crate) fn push(&mut self, text: &str, time: DateTime<Local>) {
+        if self.capacity == 0 || text.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry.text != text);
+        self.entries.push_front(ClipboardEntry {
+            text: text.to_string(),
+            time,
+        });
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Picker rows, newest first.
+    pub(crate) fn entries(&self) -> Vec<ClipboardEntry> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(ring: &ClipboardRing) -> Vec<String> {
+        ring.entries().into_iter().map(|entry| entry.text).collect()
+    }
+
+    #[test]
+    fn newest_copies_come_first_and_the_oldest_fall_off() {
+        let mut ring = ClipboardRing::new(3);
+        for text in ["a", "b", "c", "d"] {
+            ring.push(text, Local::now());
+        }
+        assert_eq!(texts(&ring), ["d", "c", "b"]);
+
+        ring.push("b", Local::now());
+        ring.push("", Local::now());
+        assert_eq!(texts(&ring), ["b", "d", "c"]);
+
+        let mut disabled = ClipboardRing::new(0);
+        disabled.push("a", Local::now());
+        assert!(disabled.is_empty());
+    }
+
+    #[test]
+    fn display_previews_the_text_on_one_line() {
+        let mut ring = ClipboardRing::new(5);
+        ring.push("id\tname\n1\tAda\n", Local::now());
+        ring.push("Ada", Local::now());
+        let labels: Vec<String> = ring.entries().iter().map(|e| e.display()).collect();
+        assert!(labels[0].ends_with("   3 chars  Ada"), "{}", labels[0]);
+        assert!(
+            labels[1].ends_with("   2 lines  id name 1 Ada"),
+            "{}",
+            labels[1]
+        );
+    }
+}
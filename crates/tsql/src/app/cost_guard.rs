@@ -0,0 +1,138 @@
+//! Planner-estimate guard run before Classic queries.
+//!
+//! When enabled, the editor query is first sent as `EXPLAIN (FORMAT JSON)`.
+//! Estimates above the configured limits are shown in a confirmation prompt
+//! before the real statement is executed.
+
+use super::sql_lexer;
+use crate::config::CostGuardConfig;
+
+/// The top-level planner estimate of one statement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanEstimate {
+    pub(crate) total_cost: f64,
+    pub(crate) plan_rows: f64,
+}
+
+impl PlanEstimate {
+    /// Human-readable descriptions of each exceeded limit; empty when within limits.
+    pub(crate) fn exceeded(&self, cfg: &CostGuardConfig) -> Vec<String> {
+        let mut exceeded = Vec::new();
+        if cfg.max_total_cost > 0.0 && self.total_cost > cfg.max_total_cost {
+            exceeded.push(format!(
+                "cost {} > {}",
+                format_estimate(self.total_cost),
+                format_estimate(cfg.max_total_cost)
+            ));
+        }
+        if cfg.max_plan_rows > 0.0 && self.plan_rows > cfg.max_plan_rows {
+            exceeded.push(format!(
+                "rows {} > {}",
+                format_estimate(self.plan_rows),
+                format_estimate(cfg.max_plan_rows)
+            ));
+        }
+        exceeded
+    }
+}
+
+/// Returns the EXPLAIN statement for `query`, or `None` when it cannot be
+/// explained without side effects (multiple statements, DDL, utility commands).
+pub(crate) fn explain_sql(query: &str) -> Option<String> {
+    let statement = sql_lexer::single_statement(query).ok()?;
+    let words = sql_lexer::code_words(statement, 1).ok()?;
+    let first = words.first()?;
+    matches!(
+        first.as_str(),
+        "SELECT" | "WITH" | "VALUES" | "TABLE" | "INSERT" | "UPDATE" | "DELETE" | "MERGE"
+    )
+    .then(|| format!("EXPLAIN (FORMAT JSON) {statement}"))
+}
+
+/// Extracts the root plan's `Total Cost` and `Plan Rows` from EXPLAIN JSON.
+pub(crate) fn parse_explain_json(json: &str) -> Result<PlanEstimate, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("invalid EXPLAIN output: {e}"))?;
+    let plan = value
+        .get(0)
+        .and_then(|entry| entry.get("Plan"))
+        .ok_or_else(|| "EXPLAIN output has no plan".to_string())?;
+    let number = |key: &str| {
+        plan.get(key)
+            .and_then(serde_json::Value::as_f64)
+            .ok_or_else(|| format!("EXPLAIN plan has no \"{key}\""))
+    };
+    Ok(PlanEstimate {
+        total_cost: number("Total Cost")?,
+        plan_rows: number("Plan Rows")?,
+    })
+}
+
+/// Formats a planner number compactly (`1234` -> `1.2k`, `5.6e6` -> `5.6M`).
+pub(crate) fn format_estimate(value: f64) -> String {
+    const UNITS: [(f64, &str); 3] = [(1e9, "G"), (1e6, "M"), (1e3, "k")];
+    for (scale, suffix) in UNITS {
+        if value >= scale {
+            return format!("{:.1}{suffix}", value / scale);
+        }
+    }
+    format!("{value:.0}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_sql_accepts_single_dml_and_queries_only() {
+        assert_eq!(
+            explain_sql("  select * from t;  ").as_deref(),
+            Some("EXPLAIN (FORMAT JSON) select * from t")
+        );
+        assert!(explain_sql("DELETE FROM t WHERE id = 1").is_some());
+        assert!(explain_sql("-- note\nWITH x AS (SELECT 1) SELECT * FROM x").is_some());
+        assert!(explain_sql("CREATE TABLE t (id int)").is_none());
+        assert!(explain_sql("VACUUM t").is_none());
+        assert!(explain_sql("SELECT 1; SELECT 2").is_none());
+        assert!(explain_sql("").is_none());
+    }
+
+    #[test]
+    fn parse_explain_json_reads_root_estimates() {
+        let json = r#"[{"Plan": {"Node Type": "Seq Scan", "Total Cost": 18334.0,
+            "Plan Rows": 1000000, "Plans": [{"Total Cost": 1.0, "Plan Rows": 1}]}}]"#;
+        assert_eq!(
+            parse_explain_json(json),
+            Ok(PlanEstimate {
+                total_cost: 18334.0,
+                plan_rows: 1_000_000.0,
+            })
+        );
+        assert!(parse_explain_json("[]").is_err());
+        assert!(parse_explain_json("not json").is_err());
+    }
+
+    #[test]
+    fn exceeded_reports_each_limit_and_ignores_zero() {
+        let estimate = PlanEstimate {
+            total_cost: 2_500_000.0,
+            plan_rows: 40.0,
+        };
+        let cfg = CostGuardConfig {
+            enabled: true,
+            max_total_cost: 1_000_000.0,
+            max_plan_rows: 10.0,
+        };
+        assert_eq!(
+            estimate.exceeded(&cfg),
+            vec!["cost 2.5M > 1.0M".to_string(), "rows 40 > 10".to_string()]
+        );
+
+        let unlimited = CostGuardConfig {
+            max_total_cost: 0.0,
+            max_plan_rows: 0.0,
+            ..cfg
+        };
+        assert!(estimate.exceeded(&unlimited).is_empty());
+    }
+}
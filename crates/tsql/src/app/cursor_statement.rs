@@ -0,0 +1,122 @@
+//! The statement under the editor cursor, for running one statement of a
+//! longer buffer.
+//!
+//! Statement boundaries come from the tree-sitter SQL grammar. Stretches it
+//! can't parse (PostgreSQL syntax it doesn't know, a half-typed statement) are
+//! split on semicolons outside literals and comments instead.
+
+use std::ops::Range;
+
+use super::sql_lexer::split_statements;
+
+/// Byte ranges of the statements in `source`, each including its terminating
+/// semicolon when it has one.
+fn statement_ranges(source: &str) -> Vec<Range<usize>> {
+    let mut parser = tree_sitter::Parser::new();
+    let tree = parser
+        .set_language(&tree_sitter_sequel::LANGUAGE.into())
+        .ok()
+        .and_then(|()| parser.parse(source, None));
+    let Some(tree) = tree else {
+        return fallback_ranges(source, 0..source.len());
+    };
+    let root = tree.root_node();
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.children(&mut cursor) {
+        match node.kind() {
+            "statement" => ranges.push(node.byte_range()),
+            ";" => match ranges.last_mut() {
+                Some(last) if last.end <= node.start_byte() => last.end = node.end_byte(),
+                _ => {}
+            },
+            "ERROR" => ranges.extend(fallback_ranges(source, node.byte_range())),
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Statements of `source[within]` split by the lexer; an unterminated literal
+/// leaves the whole stretch as one statement.
+fn fallback_ranges(source: &str, within: Range<usize>) -> Vec<Range<usize>> {
+    let text = &source[within.clone()];
+    let Ok(statements) = split_statements(text) else {
+        let trimmed = text.trim_start();
+        let start = within.start + (text.len() - trimmed.len());
+        let end = start + trimmed.trim_end().len();
+        return vec![Range { start, end }];
+    };
+    statements
+        .into_iter()
+        .map(|statement| {
+            let start = statement.as_ptr() as usize - text.as_ptr() as usize;
+            let mut end = start + statement.len();
+            let rest = &text[end..];
+            if rest.trim_start().starts_with(';') {
+                end += rest.len() - rest.trim_start().len() + 1;
+            }
+            within.start + start..within.start + end
+        })
+        .collect()
+}
+
+/// The statement around byte `offset` of `source`, without its semicolon.
+///
+/// A statement owns the text after the previous one up to and including its
+/// semicolon, so a cursor on or just after the `;` still picks it, and one on
+/// a comment or blank line above a statement picks the statement below.
+/// Trailing whitespace belongs to the last statement.
+pub(crate) fn statement_at(source: &str, offset: usize) -> Option<&str> {
+    let ranges = statement_ranges(source);
+    let range = ranges
+        .iter()
+        .find(|range| offset <= range.end)
+        .or(ranges.last())?;
+    let statement = source[range.clone()].trim();
+    let statement = statement.strip_suffix(';').unwrap_or(statement).trim_end();
+    (!statement.is_empty()).then_some(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUFFER: &str = "SELECT 1;\n\
+                          select * from users where name = 'a;b'\n  and id > 2;\n\n\
+                          -- bump\nUPDATE users SET x = 1 WHERE id = 3;\n\
+                          VALUES (1), (2); TABLE users ;\nSELECT 3\n";
+
+    fn at(needle: &str) -> Option<&'static str> {
+        statement_at(BUFFER, BUFFER.find(needle).unwrap())
+    }
+
+    #[test]
+    fn picks_the_statement_around_the_cursor() {
+        assert_eq!(statement_at(BUFFER, 0), Some("SELECT 1"));
+        assert_eq!(at(";\nselect"), Some("SELECT 1"));
+        assert_eq!(at("\nselect"), Some("SELECT 1"));
+        assert_eq!(
+            at("'a;b'"),
+            Some("select * from users where name = 'a;b'\n  and id > 2")
+        );
+        assert_eq!(at("-- bump"), Some("UPDATE users SET x = 1 WHERE id = 3"));
+        assert_eq!(at("x = 1"), Some("UPDATE users SET x = 1 WHERE id = 3"));
+        // The grammar has no VALUES or TABLE; the lexer splits those.
+        assert_eq!(at("(2)"), Some("VALUES (1), (2)"));
+        assert_eq!(at("TABLE"), Some("TABLE users"));
+        assert_eq!(at("SELECT 3"), Some("SELECT 3"));
+        assert_eq!(statement_at(BUFFER, BUFFER.len()), Some("SELECT 3"));
+    }
+
+    #[test]
+    fn half_typed_and_empty_buffers() {
+        assert_eq!(
+            statement_at("SELECT 1;\nSELECT 'open", 12),
+            Some("SELECT 'open")
+        );
+        assert_eq!(statement_at("SELECT 1;\nSELECT 'open", 3), Some("SELECT 1"));
+        assert_eq!(statement_at("", 0), None);
+        assert_eq!(statement_at("  -- nothing\n", 4), None);
+    }
+}
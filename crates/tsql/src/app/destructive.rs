@@ -0,0 +1,224 @@
+//! Row counts for `sql.confirm_destructive`.
+//!
+//! An `UPDATE` or `DELETE` from the editor is first rewritten into a
+//! `SELECT count(*)` over the same target, joined tables and `WHERE` clause,
+//! so the prompt can say how many rows the statement is about to touch.
+
+use std::ops::Range;
+
+use super::sql_lexer::{self, SqlSegmentKind};
+
+/// A keyword outside parentheses, literals and comments, with its byte range.
+struct Word {
+    text: String,
+    range: Range<usize>,
+}
+
+fn top_level_words(statement: &str) -> Option<Vec<Word>> {
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    for segment in sql_lexer::scan(statement).ok()? {
+        if segment.kind != SqlSegmentKind::Code {
+            continue;
+        }
+        let mut start = None;
+        for (offset, character) in statement[segment.range.clone()].char_indices() {
+            let index = segment.range.start + offset;
+            if character.is_ascii_alphanumeric() || character == '_' {
+                start.get_or_insert(index);
+                continue;
+            }
+            if let Some(start) = start.take() {
+                if depth == 0 {
+                    words.push(Word {
+                        text: statement[start..index].to_ascii_uppercase(),
+                        range: start..index,
+                    });
+                }
+            }
+            match character {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if let (Some(start), 0) = (start, depth) {
+            words.push(Word {
+                text: statement[start..segment.range.end].to_ascii_uppercase(),
+                range: start..segment.range.end,
+            });
+        }
+    }
+    Some(words)
+}
+
+/// The clauses of an `UPDATE`/`DELETE` that decide which rows it touches.
+struct Clauses<'a> {
+    target: &'a str,
+    joined: Option<&'a str>,
+    filter: Option<&'a str>,
+}
+
+/// Splits `statement` at the top-level `keywords`, returning the text after
+/// each one that occurs (in order) up to the next.
+fn clauses<'a>(statement: &'a str, words: &[Word], keywords: &[&str]) -> Vec<Option<&'a str>> {
+    let mut starts: Vec<Option<(usize, usize)>> = vec![None; keywords.len()];
+    let mut next = 0;
+    for (index, word) in words.iter().enumerate() {
+        let Some(position) = keywords[next..].iter().position(|k| *k == word.text) else {
+            continue;
+        };
+        // `a IS DISTINCT FROM b` in a SET list is not the FROM clause.
+        if word.text == "FROM" && index > 0 && words[index - 1].text == "DISTINCT" {
+            continue;
+        }
+        next += position;
+        starts[next] = Some((word.range.start, word.range.end));
+        next += 1;
+        if next == keywords.len() {
+            break;
+        }
+    }
+    (0..keywords.len())
+        .map(|index| {
+            let (_, start) = starts[index]?;
+            let end = starts[index + 1..]
+                .iter()
+                .flatten()
+                .map(|(keyword_start, _)| *keyword_start)
+                .next()
+                .unwrap_or(statement.len());
+            Some(statement[start..end].trim())
+        })
+        .collect()
+}
+
+fn parse(statement: &str) -> Option<Clauses<'_>> {
+    let words = top_level_words(statement)?;
+    let keyword = |index: usize| words.get(index).map(|word| word.text.as_str());
+    if words.iter().any(|word| word.text == "LIMIT") {
+        return None;
+    }
+    let (target_start, keywords): (usize, &[&str]) = match (keyword(0), keyword(1)) {
+        (Some("DELETE"), Some("FROM")) => (1, &["FROM", "USING", "WHERE", "RETURNING"]),
+        (Some("UPDATE"), _) => (0, &["UPDATE", "SET", "FROM", "WHERE", "RETURNING"]),
+        _ => return None,
+    };
+    let parts = clauses(statement, &words[target_start..], keywords);
+    let (target, joined, filter) = match parts.as_slice() {
+        [target, joined, filter, _] => (*target, *joined, *filter),
+        [target, _, joined, filter, _] => (*target, *joined, *filter),
+        _ => return None,
+    };
+    let target = target.filter(|target| !target.is_empty())?;
+    if let Some(filter) = filter {
+        let filter_words = top_level_words(filter)?;
+        let current_of = filter_words
+            .first()
+            .zip(filter_words.get(1))
+            .is_some_and(|(first, second)| first.text == "CURRENT" && second.text == "OF");
+        if filter.is_empty() || current_of {
+            return None;
+        }
+    }
+    Some(Clauses {
+        target,
+        joined: joined.filter(|joined| !joined.is_empty()),
+        filter,
+    })
+}
+
+/// `SELECT count(*)` of the rows `query` would update or delete, or `None`
+/// when it isn't a single `UPDATE`/`DELETE` without a `LIMIT`.
+///
+/// `USING`/`FROM` tables become an `EXISTS` subquery so that a row matching
+/// several joined rows is still counted once.
+pub(crate) fn count_sql(query: &str) -> Option<String> {
+    let statement = sql_lexer::single_statement(query).ok()?;
+    let Clauses {
+        target,
+        joined,
+        filter,
+    } = parse(statement)?;
+    let filter = filter.map(|filter| format!(" WHERE {filter}"));
+    let filter = filter.as_deref().unwrap_or_default();
+    Some(match joined {
+        Some(joined) => {
+            format!("SELECT count(*) FROM {target} WHERE EXISTS (SELECT FROM {joined}{filter})")
+        }
+        None => format!("SELECT count(*) FROM {target}{filter}"),
+    })
+}
+
+/// `12345` -> `12,345`.
+pub(crate) fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_the_rows_a_delete_or_update_would_touch() {
+        assert_eq!(
+            count_sql("delete from users where id > 10 and name <> 'where';").as_deref(),
+            Some("SELECT count(*) FROM users WHERE id > 10 and name <> 'where'")
+        );
+        assert_eq!(
+            count_sql("DELETE FROM ONLY logs AS l").as_deref(),
+            Some("SELECT count(*) FROM ONLY logs AS l")
+        );
+        assert_eq!(
+            count_sql("DELETE FROM orders o USING users u WHERE o.user_id = u.id RETURNING o.id")
+                .as_deref(),
+            Some(
+                "SELECT count(*) FROM orders o WHERE EXISTS \
+                 (SELECT FROM users u WHERE o.user_id = u.id)"
+            )
+        );
+        assert_eq!(
+            count_sql(
+                "UPDATE users SET name = (SELECT name FROM t WHERE t.id = 1), \
+                 flag = a IS DISTINCT FROM b\n-- why\nWHERE id IN (SELECT id FROM banned)"
+            )
+            .as_deref(),
+            Some("SELECT count(*) FROM users WHERE id IN (SELECT id FROM banned)")
+        );
+        assert_eq!(
+            count_sql("update t set x = s.x from s where s.id = t.id").as_deref(),
+            Some("SELECT count(*) FROM t WHERE EXISTS (SELECT FROM s WHERE s.id = t.id)")
+        );
+        assert_eq!(
+            count_sql("UPDATE t SET x = 1").as_deref(),
+            Some("SELECT count(*) FROM t")
+        );
+    }
+
+    #[test]
+    fn skips_everything_else() {
+        assert!(count_sql("SELECT * FROM users").is_none());
+        assert!(count_sql("INSERT INTO t VALUES (1)").is_none());
+        assert!(count_sql("DELETE FROM t WHERE id = 1; DELETE FROM u").is_none());
+        assert!(count_sql("DELETE FROM t WHERE CURRENT OF c").is_none());
+        assert!(count_sql("DELETE FROM t WHERE id = 1 LIMIT 5").is_none());
+        assert!(count_sql("DELETE FROM t WHERE").is_none());
+        assert!(count_sql("").is_none());
+    }
+
+    #[test]
+    fn format_count_groups_thousands() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(12_345), "12,345");
+        assert_eq!(format_count(1_000_000), "1,000,000");
+    }
+}
@@ -0,0 +1,229 @@
+//! Grid cell edits applied this session, for `:edits` and `:undo-edit`.
+//!
+//! Each edit keeps the value the cell held before it was edited. Undoing runs
+//! the inverse UPDATE, which only matches while the row is still found by its
+//! primary key and the cell still holds the value the edit stored.
+
+use chrono::{DateTime, Local};
+
+use crate::ui::quote_identifier;
+
+/// Characters of each value shown in the `:edits` picker.
+const PREVIEW_CHARS: usize = 30;
+
+/// A successful cell UPDATE of a row identified by its primary key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedEdit {
+    /// Source table as written in the edit's UPDATE.
+    pub(crate) table: String,
+    pub(crate) column: String,
+    /// Primary-key columns of the row and their text values after the edit.
+    pub(crate) key: Vec<(String, String)>,
+    /// Value before the edit, `None` for NULL.
+    pub(crate) original: Option<String>,
+    /// Value the server stored, `None` for NULL.
+    pub(crate) value: Option<String>,
+    pub(crate) applied_at: DateTime<Local>,
+    pub(crate) undone: bool,
+}
+
+impl AppliedEdit {
+    /// UPDATE restoring the original value, guarded on the edited one.
+    pub(crate) fn undo_sql(&self) -> String {
+        let column = quote_identifier(&self.column);
+        let mut conditions: Vec<String> = self
+            .key
+            .iter()
+            .map(|(name, value)| format!("{} = {}", quote_identifier(name), literal(Some(value))))
+            .collect();
+        if !self.key.iter().any(|(name, _)| *name == self.column) {
+            conditions.push(match &self.value {
+                Some(value) => format!("{column}::text = {}", literal(Some(value))),
+                None => format!("{column} IS NULL"),
+            });
+        }
+        format!(
+            "UPDATE {}\nSET {column} = {}\nWHERE {}\nRETURNING *",
+            self.table,
+            literal(self.original.as_deref()),
+            conditions.join(" AND ")
+        )
+    }
+
+    /// Whether the undo's primary-key condition matches `row` of a grid with `headers`.
+    pub(crate) fn matches_row(&self, headers: &[String], row: &[String]) -> bool {
+        self.key.iter().all(|(name, value)| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .and_then(|index| row.get(index))
+                .is_some_and(|cell| cell == value)
+        })
+    }
+}
+
+/// A text literal PostgreSQL casts to the column type, or `NULL`.
+fn literal(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("'{}'", value.replace('\'', "''")),
+        None => "NULL".to_string(),
+    }
+}
+
+fn preview(value: Option<&str>) -> String {
+    let Some(value) = value else {
+        return "NULL".to_string();
+    };
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.chars().count() > PREVIEW_CHARS {
+        let mut value: String = value.chars().take(PREVIEW_CHARS - 1).collect();
+        value.push('…');
+        value
+    } else {
+        value
+    }
+}
+
+/// An `:edits` picker row pointing back into the log.
+#[derive(Clone, Debug)]
+pub(crate) struct EditLogEntry {
+    pub(crate) index: usize,
+    label: String,
+}
+
+impl EditLogEntry {
+    pub(crate) fn display(&self) -> String {
+        self.label.clone()
+    }
+}
+
+/// Oldest-first list of the edits applied on the current connection.
+#[derive(Debug, Default)]
+pub(crate) struct EditLog {
+    edits: Vec<AppliedEdit>,
+}
+
+impl EditLog {
+    pub(crate) fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&AppliedEdit> {
+        self.edits.get(index)
+    }
+
+    pub(crate) fn record(&mut self, edit: AppliedEdit) {
+        self.edits.push(edit);
+    }
+
+    /// The newest edit that was not undone yet.
+    pub(crate) fn last_undoable(&self) -> Option<usize> {
+        self.edits.iter().rposition(|edit| !edit.undone)
+    }
+
+    pub(crate) fn mark_undone(&mut self, index: usize) {
+        if let Some(edit) = self.edits.get_mut(index) {
+            edit.undone = true;
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.edits.clear();
+    }
+
+    /// Picker rows, oldest first, numbered in the order the edits were applied.
+    pub(crate) fn picker_entries(&self) -> Vec<EditLogEntry> {
+        self.edits
+            .iter()
+            .enumerate()
+            .map(|(index, edit)| {
+                let mut label = format!(
+                    "#{}  {}  {}.{}  {} -> {}",
+                    index + 1,
+                    edit.applied_at.format("%H:%M:%S"),
+                    edit.table,
+                    edit.column,
+                    preview(edit.original.as_deref()),
+                    preview(edit.value.as_deref())
+                );
+                if edit.undone {
+                    label.push_str("  (undone)");
+                }
+                EditLogEntry { index, label }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn edit(column: &str, original: Option<&str>, value: Option<&str>) -> AppliedEdit {
+        AppliedEdit {
+            table: "public.users".to_string(),
+            column: column.to_string(),
+            key: vec![("id".to_string(), "7".to_string())],
+            original: original.map(str::to_string),
+            value: value.map(str::to_string),
+            applied_at: Local.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap(),
+            undone: false,
+        }
+    }
+
+    #[test]
+    fn undo_sql_restores_the_original_while_the_edited_value_is_stored() {
+        assert_eq!(
+            edit("name", Some("O'Brien"), Some("Obrien")).undo_sql(),
+            "UPDATE public.users\nSET name = 'O''Brien'\nWHERE id = '7' AND name::text = 'Obrien'\nRETURNING *"
+        );
+        assert_eq!(
+            edit("Note", None, Some("")).undo_sql(),
+            "UPDATE public.users\nSET \"Note\" = NULL\nWHERE id = '7' AND \"Note\"::text = ''\nRETURNING *"
+        );
+        assert_eq!(
+            edit("email", Some("a@x.io"), None).undo_sql(),
+            "UPDATE public.users\nSET email = 'a@x.io'\nWHERE id = '7' AND email IS NULL\nRETURNING *"
+        );
+        // Editing a key column moved the row; the key already holds the edited value.
+        assert_eq!(
+            edit("id", Some("3"), Some("7")).undo_sql(),
+            "UPDATE public.users\nSET id = '3'\nWHERE id = '7'\nRETURNING *"
+        );
+
+        let headers = ["id".to_string(), "name".to_string()];
+        let edit = edit("name", None, None);
+        assert!(edit.matches_row(&headers, &["7".to_string(), "x".to_string()]));
+        assert!(!edit.matches_row(&headers, &["8".to_string(), "x".to_string()]));
+        assert!(!edit.matches_row(&headers[1..], &["x".to_string()]));
+    }
+
+    #[test]
+    fn picker_entries_list_edits_oldest_first_and_mark_undone_ones() {
+        let mut log = EditLog::default();
+        assert_eq!(log.last_undoable(), None);
+        log.record(edit("name", Some("Ann"), Some("Anne")));
+        log.record(edit("bio", None, Some(&"long text ".repeat(10))));
+        assert_eq!(log.last_undoable(), Some(1));
+
+        log.mark_undone(1);
+        assert_eq!(log.last_undoable(), Some(0));
+        let labels: Vec<_> = log
+            .picker_entries()
+            .iter()
+            .map(EditLogEntry::display)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "#1  09:30:00  public.users.name  Ann -> Anne",
+                "#2  09:30:00  public.users.bio  NULL -> long text long text long text…  (undone)",
+            ]
+        );
+    }
+}
@@ -130,6 +130,48 @@ pub(crate) fn classify_transaction_control(sql: &str) -> TransactionControl {
     }
 }
 
+/// Whether `sql` writes data or schema and can run inside a transaction
+/// block, so `sql.autocommit = false` opens one for it. Buffers that manage
+/// their own transactions, or call code that might, are left alone.
+pub(crate) fn needs_autocommit_transaction(sql: &str) -> bool {
+    let Ok(statements) = super::sql_lexer::split_statements(sql) else {
+        return false;
+    };
+    let mut writes = false;
+    for statement in statements {
+        if classify_transaction_control(statement) != TransactionControl::Other {
+            return false;
+        }
+        let Ok(words) = super::sql_lexer::code_words(statement, usize::MAX) else {
+            return false;
+        };
+        let Some(first) = words.first() else {
+            continue;
+        };
+        // PostgreSQL refuses these inside a transaction block.
+        let standalone = words.iter().any(|word| word == "CONCURRENTLY")
+            || (matches!(first.as_str(), "CREATE" | "ALTER" | "DROP")
+                && words.get(1).is_some_and(|word| {
+                    matches!(
+                        word.as_str(),
+                        "DATABASE" | "TABLESPACE" | "SYSTEM" | "SUBSCRIPTION"
+                    )
+                }));
+        if standalone {
+            return false;
+        }
+        writes |= match first.as_str() {
+            "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "TRUNCATE" | "CREATE" | "ALTER" | "DROP"
+            | "GRANT" | "REVOKE" | "COMMENT" => true,
+            "WITH" => words
+                .iter()
+                .any(|word| matches!(word.as_str(), "INSERT" | "UPDATE" | "DELETE" | "MERGE")),
+            _ => false,
+        };
+    }
+    writes
+}
+
 fn is_savepoint_rollback(words: &[String]) -> bool {
     let rest = if words
         .first()
@@ -221,4 +263,30 @@ mod tests {
         let state = TransactionState::Idle.after_execution("SELECT $$hello;world$$ AS value", true);
         assert_eq!(state, TransactionState::Idle);
     }
+
+    #[test]
+    fn autocommit_wraps_only_writes_that_fit_in_a_transaction() {
+        assert!(needs_autocommit_transaction(
+            "UPDATE t SET a = 1 WHERE id = 2;"
+        ));
+        assert!(needs_autocommit_transaction(
+            "-- cleanup\nSELECT 1; DELETE FROM t"
+        ));
+        assert!(needs_autocommit_transaction(
+            "WITH gone AS (DELETE FROM t RETURNING *) SELECT count(*) FROM gone"
+        ));
+        assert!(needs_autocommit_transaction("create table t (id int)"));
+
+        assert!(!needs_autocommit_transaction(
+            "SELECT 'update' FROM \"delete\""
+        ));
+        assert!(!needs_autocommit_transaction("BEGIN; UPDATE t SET a = 1"));
+        assert!(!needs_autocommit_transaction("UPDATE t SET a = 1; COMMIT"));
+        assert!(!needs_autocommit_transaction("CALL archive_orders()"));
+        assert!(!needs_autocommit_transaction(
+            "CREATE INDEX CONCURRENTLY t_a ON t (a)"
+        ));
+        assert!(!needs_autocommit_transaction("DROP DATABASE scratch"));
+        assert!(!needs_autocommit_transaction(""));
+    }
 }
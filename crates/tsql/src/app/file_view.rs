@@ -0,0 +1,416 @@
+//! Local CSV/TSV files shown in the Classic grid by `:open` and `tsql view`.
+//!
+//! A file has no source query to rerun, so the result transformations that
+//! normally compile to SQL (filters, sorting, grouping, column picks) are
+//! evaluated over the loaded rows instead.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::result_transform::{
+    validate_transform, FilterOp, FilterValue, OrderDirection, ResultTransform,
+};
+
+/// A delimited file and its rows as read from disk.
+#[derive(Clone, Debug)]
+pub(crate) struct FileView {
+    pub(crate) path: PathBuf,
+    pub(crate) headers: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+}
+
+impl FileView {
+    /// File name for status messages and the result title.
+    pub(crate) fn label(&self) -> String {
+        self.path.file_name().map_or_else(
+            || self.path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+
+    /// Stands in for the source query in the result history and title.
+    pub(crate) fn query(&self) -> String {
+        format!("-- file {}", self.path.display())
+    }
+
+    /// Headers and rows after `spec`, laid out like the SQL transform's result.
+    pub(crate) fn transformed(
+        &self,
+        spec: &ResultTransform,
+    ) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+        validate_transform(self.headers.len(), spec)?;
+        let mut rows: Vec<&Vec<String>> = Vec::with_capacity(self.rows.len());
+        for row in &self.rows {
+            if filters_match(row, spec)? {
+                rows.push(row);
+            }
+        }
+
+        if let Some(ordinal) = spec.group_count {
+            // Groups keep first-seen order, so equal counts stay in file order.
+            let mut groups: Vec<(String, usize)> = Vec::new();
+            let mut index: HashMap<&str, usize> = HashMap::new();
+            for row in rows {
+                let value = row[ordinal].as_str();
+                match index.get(value) {
+                    Some(&group) => groups[group].1 += 1,
+                    None => {
+                        index.insert(value, groups.len());
+                        groups.push((value.to_string(), 1));
+                    }
+                }
+            }
+            match spec.orders.first() {
+                Some(order) => {
+                    groups.sort_by(|a, b| directed(order.direction, compare(&a.0, &b.0)))
+                }
+                None => groups.sort_by_key(|group| std::cmp::Reverse(group.1)),
+            }
+            let headers = vec![self.headers[ordinal].clone(), "count".to_string()];
+            let rows = groups
+                .into_iter()
+                .map(|(value, count)| vec![value, count.to_string()])
+                .collect();
+            return Ok((headers, rows));
+        }
+
+        if !spec.orders.is_empty() {
+            rows.sort_by(|a, b| {
+                spec.orders
+                    .iter()
+                    .map(|order| {
+                        directed(
+                            order.direction,
+                            compare(&a[order.ordinal], &b[order.ordinal]),
+                        )
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        let projection = if spec.projection.is_empty() {
+            (0..self.headers.len()).collect()
+        } else {
+            spec.projection.clone()
+        };
+        let headers = projection
+            .iter()
+            .map(|&ordinal| self.headers[ordinal].clone())
+            .collect();
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                projection
+                    .iter()
+                    .map(|&ordinal| row[ordinal].clone())
+                    .collect()
+            })
+            .collect();
+        Ok((headers, rows))
+    }
+}
+
+fn directed(direction: OrderDirection, ordering: Ordering) -> Ordering {
+    match direction {
+        OrderDirection::Asc => ordering,
+        OrderDirection::Desc => ordering.reverse(),
+    }
+}
+
+/// Reads `path` as TSV when it ends in `.tsv` or `.tab`, and as CSV otherwise.
+pub(crate) fn load_file(path: &Path) -> Result<FileView> {
+    let is_tsv = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tsv") || ext.eq_ignore_ascii_case("tab"));
+    load_delimited(path, is_tsv)
+}
+
+/// Reads `path` as TSV or CSV, whatever its extension.
+///
+/// The first record is the header. Rows are padded to the widest record, and
+/// columns past the header are named `column<n>`.
+pub(crate) fn load_delimited(path: &Path, is_tsv: bool) -> Result<FileView> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    let mut records = if is_tsv {
+        parse_tsv(content)
+    } else {
+        parse_csv(content).with_context(|| format!("Failed to parse {}", path.display()))?
+    };
+    anyhow::ensure!(!records.is_empty(), "{} is empty", path.display());
+
+    let mut headers = records.remove(0);
+    let width = records
+        .iter()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+        .max(headers.len());
+    for ordinal in headers.len()..width {
+        headers.push(format!("column{}", ordinal + 1));
+    }
+    for record in &mut records {
+        record.resize(width, String::new());
+    }
+    Ok(FileView {
+        path: path.to_path_buf(),
+        headers,
+        rows: records,
+    })
+}
+
+/// RFC 4180 CSV: `"` quotes a field, `""` is a quote inside it, and quoted
+/// fields may span lines. Blank lines are skipped.
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut quote_line = 0;
+    let mut chars = content.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if quoted {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                '\n' => {
+                    line += 1;
+                    field.push(ch);
+                }
+                _ => field.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' if field.is_empty() => {
+                quoted = true;
+                quote_line = line;
+            }
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                end_record(&mut records, &mut record, &mut field);
+            }
+            _ => field.push(ch),
+        }
+    }
+    anyhow::ensure!(
+        !quoted,
+        "unterminated quoted field starting on line {quote_line}"
+    );
+    end_record(&mut records, &mut record, &mut field);
+    Ok(records)
+}
+
+fn end_record(records: &mut Vec<Vec<String>>, record: &mut Vec<String>, field: &mut String) {
+    if record.is_empty() && field.is_empty() {
+        return;
+    }
+    record.push(std::mem::take(field));
+    records.push(std::mem::take(record));
+}
+
+/// Tab-separated lines, with `\t`, `\n`, `\r` and `\\` escapes inside fields.
+fn parse_tsv(content: &str) -> Vec<Vec<String>> {
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split('\t').map(unescape_tsv).collect())
+        .collect()
+}
+
+fn unescape_tsv(field: &str) -> String {
+    let mut value = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => value.push('\t'),
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('\\') => value.push('\\'),
+            Some(other) => {
+                value.push('\\');
+                value.push(other);
+            }
+            None => value.push('\\'),
+        }
+    }
+    value
+}
+
+/// Numbers compare numerically, everything else as text.
+fn compare(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+fn filters_match(row: &[String], spec: &ResultTransform) -> Result<bool, String> {
+    for filter in &spec.filters {
+        let cell = row[filter.ordinal].as_str();
+        // File cells are never NULL.
+        let matched = match (&filter.op, &filter.value) {
+            (FilterOp::IsNull, _) | (FilterOp::Eq, FilterValue::Null) => false,
+            (FilterOp::IsNotNull, _) | (FilterOp::Ne, FilterValue::Null) => true,
+            (_, FilterValue::Null) => {
+                return Err("NULL only supports equality or IS NULL filters".to_string())
+            }
+            (op, value) => {
+                let value = match value {
+                    FilterValue::Text(value) | FilterValue::Decimal(value) => value.clone(),
+                    FilterValue::Integer(value) => value.to_string(),
+                    FilterValue::Boolean(value) => value.to_string(),
+                    FilterValue::Null => unreachable!("NULL filters were handled above"),
+                };
+                let (lower_cell, lower_value) = (cell.to_lowercase(), value.to_lowercase());
+                let ordering = || match filter.value {
+                    FilterValue::Boolean(_) => lower_cell.cmp(&lower_value),
+                    _ => compare(cell, &value),
+                };
+                match op {
+                    FilterOp::Eq => ordering().is_eq(),
+                    FilterOp::Ne => ordering().is_ne(),
+                    FilterOp::Lt => ordering().is_lt(),
+                    FilterOp::Lte => ordering().is_le(),
+                    FilterOp::Gt => ordering().is_gt(),
+                    FilterOp::Gte => ordering().is_ge(),
+                    FilterOp::Contains => lower_cell.contains(&lower_value),
+                    FilterOp::NotContains => !lower_cell.contains(&lower_value),
+                    FilterOp::StartsWith => lower_cell.starts_with(&lower_value),
+                    FilterOp::NotStartsWith => !lower_cell.starts_with(&lower_value),
+                    FilterOp::EndsWith => lower_cell.ends_with(&lower_value),
+                    FilterOp::NotEndsWith => !lower_cell.ends_with(&lower_value),
+                    FilterOp::IsNull | FilterOp::IsNotNull => {
+                        unreachable!("NULL checks were handled above")
+                    }
+                }
+            }
+        };
+        if !matched {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::result_transform::{ResultFilter, ResultOrder};
+    use tempfile::tempdir;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn view() -> FileView {
+        FileView {
+            path: PathBuf::from("orders.csv"),
+            headers: strings(&["id", "amount", "city"]),
+            rows: vec![
+                strings(&["1", "9.5", "Lisbon"]),
+                strings(&["2", "10", "porto"]),
+                strings(&["3", "120", "Lisbon"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn csv_quotes_span_lines_and_short_rows_are_padded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("people.csv");
+        fs::write(
+            &path,
+            "\u{feff}name,note\r\n\"Doe, Jane\",\"said \"\"hi\"\"\nthen left\"\r\n\r\nBob\n",
+        )
+        .unwrap();
+        let view = load_file(&path).unwrap();
+        assert_eq!(view.label(), "people.csv");
+        assert_eq!(view.headers, ["name", "note"]);
+        assert_eq!(
+            view.rows,
+            [
+                strings(&["Doe, Jane", "said \"hi\"\nthen left"]),
+                strings(&["Bob", ""])
+            ]
+        );
+
+        fs::write(&path, "a,b\n\"open,1\n").unwrap();
+        let error = load_file(&path).unwrap_err();
+        assert!(format!("{error:#}").contains("unterminated quoted field starting on line 2"));
+    }
+
+    #[test]
+    fn tsv_files_split_on_tabs_and_unescape() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.tsv");
+        fs::write(&path, "kind\tdetail\nlogin\ta\\tb\\nc\nlogout\t\textra\n").unwrap();
+        let view = load_file(&path).unwrap();
+        assert_eq!(view.headers, ["kind", "detail", "column3"]);
+        assert_eq!(
+            view.rows,
+            [
+                strings(&["login", "a\tb\nc", ""]),
+                strings(&["logout", "", "extra"])
+            ]
+        );
+    }
+
+    #[test]
+    fn transforms_filter_sort_project_and_group_in_memory() {
+        let view = view();
+        let mut spec = ResultTransform::default();
+        spec.add_filter(ResultFilter {
+            ordinal: 1,
+            op: FilterOp::Gte,
+            value: FilterValue::Integer(10),
+        });
+        spec.set_order(1, OrderDirection::Desc, false);
+        spec.set_projection(vec![2, 1]);
+        let (headers, rows) = view.transformed(&spec).unwrap();
+        assert_eq!(headers, ["city", "amount"]);
+        assert_eq!(
+            rows,
+            [strings(&["Lisbon", "120"]), strings(&["porto", "10"])]
+        );
+
+        let mut spec = ResultTransform::default();
+        spec.add_filter(ResultFilter {
+            ordinal: 2,
+            op: FilterOp::Contains,
+            value: FilterValue::Text("LIS".to_string()),
+        });
+        assert_eq!(view.transformed(&spec).unwrap().1.len(), 2);
+
+        let mut spec = ResultTransform::default();
+        spec.set_group_count(Some(2));
+        let (headers, rows) = view.transformed(&spec).unwrap();
+        assert_eq!(headers, ["city", "count"]);
+        assert_eq!(rows, [strings(&["Lisbon", "2"]), strings(&["porto", "1"])]);
+
+        spec.orders = vec![ResultOrder {
+            ordinal: 2,
+            direction: OrderDirection::Asc,
+        }];
+        assert_eq!(view.transformed(&spec).unwrap().1[0], ["Lisbon", "2"]);
+        spec.orders[0].ordinal = 0;
+        assert!(view.transformed(&spec).is_err());
+    }
+}
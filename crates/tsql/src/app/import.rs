@@ -0,0 +1,277 @@
+//! `:import csv|tsv|json <path> <table>`: loading a local file into a table.
+//!
+//! File columns are matched to the table's by name; when some don't match,
+//! the mapping form lets the user pick a target or skip them. Rows are then
+//! sent with `COPY ... FROM STDIN (FORMAT csv)` in batches, so the status line
+//! can follow along.
+
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use futures_util::SinkExt;
+use tokio_postgres::Client;
+
+use super::file_view;
+use crate::ui::quote_identifier;
+use crate::util::format_pg_error;
+
+/// Rows per chunk sent to `COPY`; progress is reported after each one.
+const BATCH_ROWS: usize = 1000;
+
+/// File formats `:import` reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ImportFormat {
+    Csv,
+    Tsv,
+    /// An array of objects, or one object per line.
+    Json,
+}
+
+impl ImportFormat {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" => Some(Self::Tsv),
+            "json" | "ndjson" | "jsonl" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The records of an import file. Empty CSV/TSV fields and JSON nulls are None.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ImportData {
+    pub(crate) headers: Vec<String>,
+    pub(crate) rows: Vec<Vec<Option<String>>>,
+}
+
+pub(crate) fn read_import_file(path: &Path, format: ImportFormat) -> Result<ImportData> {
+    if format == ImportFormat::Json {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        return json_records(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()));
+    }
+    let view = file_view::load_delimited(path, format == ImportFormat::Tsv)?;
+    let rows = view
+        .rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|value| (!value.is_empty()).then_some(value))
+                .collect()
+        })
+        .collect();
+    Ok(ImportData {
+        headers: view.headers,
+        rows,
+    })
+}
+
+/// Objects of a JSON array or of JSON lines. Keys become columns in the order
+/// first seen; nested objects and arrays are kept as JSON text.
+fn json_records(content: &str) -> Result<ImportData> {
+    let objects: Vec<serde_json::Value> = match serde_json::from_str(content) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(object @ serde_json::Value::Object(_)) => vec![object],
+        Ok(_) => anyhow::bail!("expected an array of objects"),
+        Err(_) => content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).with_context(|| format!("line {}", index + 1))
+            })
+            .collect::<Result<_>>()?,
+    };
+
+    let mut data = ImportData::default();
+    for (index, object) in objects.iter().enumerate() {
+        let object = object
+            .as_object()
+            .with_context(|| format!("record {} is not an object", index + 1))?;
+        for key in object.keys() {
+            if !data.headers.contains(key) {
+                data.headers.push(key.clone());
+            }
+        }
+    }
+    data.rows = objects
+        .iter()
+        .filter_map(serde_json::Value::as_object)
+        .map(|object| {
+            data.headers
+                .iter()
+                .map(|key| match object.get(key) {
+                    None | Some(serde_json::Value::Null) => None,
+                    Some(serde_json::Value::String(text)) => Some(text.clone()),
+                    Some(value) => Some(value.to_string()),
+                })
+                .collect()
+        })
+        .collect();
+    Ok(data)
+}
+
+fn normalized(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// The table column each file column goes to, matched by name ignoring case,
+/// spaces and punctuation.
+pub(crate) fn match_columns(headers: &[String], columns: &[String]) -> Vec<Option<usize>> {
+    let mut mapping: Vec<Option<usize>> = Vec::with_capacity(headers.len());
+    for header in headers {
+        let header = normalized(header);
+        let target = columns
+            .iter()
+            .position(|column| normalized(column) == header)
+            .filter(|target| !mapping.contains(&Some(*target)));
+        mapping.push(target);
+    }
+    mapping
+}
+
+/// `COPY` for the mapped columns, in file order.
+pub(crate) fn copy_sql(table: &str, columns: &[String], mapping: &[Option<usize>]) -> String {
+    let targets: Vec<String> = mapping
+        .iter()
+        .flatten()
+        .map(|&column| quote_identifier(&columns[column]))
+        .collect();
+    format!(
+        "COPY {table} ({}) FROM STDIN WITH (FORMAT csv)",
+        targets.join(", ")
+    )
+}
+
+/// CSV for the mapped fields of `rows`. Values are always quoted, so an empty
+/// string stays one; an unquoted empty field is NULL.
+fn encode_rows(rows: &[Vec<Option<String>>], mapping: &[Option<usize>]) -> Vec<u8> {
+    let mut out = String::new();
+    for row in rows {
+        let fields = mapping
+            .iter()
+            .zip(row)
+            .filter(|(target, _)| target.is_some())
+            .map(|(_, value)| value);
+        for (index, value) in fields.enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            if let Some(value) = value {
+                out.push('"');
+                out.push_str(&value.replace('"', "\"\""));
+                out.push('"');
+            }
+        }
+        out.push('\n');
+    }
+    out.into_bytes()
+}
+
+/// Runs `copy` and streams the mapped `rows` into it, calling `progress` with
+/// the rows sent so far after each batch. Returns the rows the server loaded.
+pub(crate) async fn copy_rows(
+    client: &Client,
+    copy: &str,
+    rows: &[Vec<Option<String>>],
+    mapping: &[Option<usize>],
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(usize),
+) -> Result<u64, String> {
+    let sink = client
+        .copy_in::<_, Cursor<Vec<u8>>>(copy)
+        .await
+        .map_err(|e| format_pg_error(&e))?;
+    let mut sink = std::pin::pin!(sink);
+    let mut sent = 0;
+    for batch in rows.chunks(BATCH_ROWS) {
+        // Dropping the sink unfinished aborts the COPY.
+        if cancelled.load(Ordering::Acquire) {
+            return Err("Import cancelled".to_string());
+        }
+        sink.send(Cursor::new(encode_rows(batch, mapping)))
+            .await
+            .map_err(|e| format_pg_error(&e))?;
+        sent += batch.len();
+        progress(sent);
+    }
+    sink.as_mut()
+        .finish()
+        .await
+        .map_err(|e| format_pg_error(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn json_arrays_and_lines_become_rows() {
+        let array =
+            r#"[{"id": 1, "name": "a", "tags": ["x"]}, {"id": 2, "name": null, "extra": true}]"#;
+        let data = json_records(array).unwrap();
+        assert_eq!(data.headers, strings(&["id", "name", "tags", "extra"]));
+        assert_eq!(
+            data.rows,
+            vec![
+                vec![
+                    Some("1".to_string()),
+                    Some("a".to_string()),
+                    Some(r#"["x"]"#.to_string()),
+                    None
+                ],
+                vec![Some("2".to_string()), None, None, Some("true".to_string())],
+            ]
+        );
+        let lines = "{\"id\": 1}\n\n{\"id\": 2}\n";
+        assert_eq!(json_records(lines).unwrap().rows.len(), 2);
+        assert!(json_records("[1, 2]").is_err());
+        assert!(json_records("{\"id\": 1}\nnot json").is_err());
+    }
+
+    #[test]
+    fn columns_match_by_normalized_name_once() {
+        let columns = strings(&["id", "first_name", "Email"]);
+        assert_eq!(
+            match_columns(
+                &strings(&["ID", "First Name", "email", "id", "age"]),
+                &columns
+            ),
+            vec![Some(0), Some(1), Some(2), None, None]
+        );
+    }
+
+    #[test]
+    fn copy_sends_mapped_fields_with_nulls_unquoted() {
+        let columns = strings(&["id", "Name"]);
+        let mapping = vec![Some(1), None, Some(0)];
+        assert_eq!(
+            copy_sql("public.users", &columns, &mapping),
+            "COPY public.users (\"Name\", id) FROM STDIN WITH (FORMAT csv)"
+        );
+        let rows = vec![
+            vec![
+                Some("say \"hi\"".to_string()),
+                Some("x".to_string()),
+                Some("1".to_string()),
+            ],
+            vec![None, None, Some(String::new())],
+        ];
+        assert_eq!(
+            String::from_utf8(encode_rows(&rows, &mapping)).unwrap(),
+            "\"say \"\"hi\"\"\",\"1\"\n,\"\"\n"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! `:listen` channels and the NOTIFY payloads they bring.
+//!
+//! LISTEN belongs to the database session, so the channels are tied to the
+//! connection they were issued on and forgotten once it is replaced. Received
+//! payloads outlive connections until `:notifications clear`.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// Payloads kept for `:notifications`; older ones are dropped.
+const CAPACITY: usize = 500;
+
+/// One NOTIFY delivered to this session.
+#[derive(Debug, Clone)]
+pub struct PgNotification {
+    pub channel: String,
+    pub payload: String,
+    pub process_id: i32,
+    time: DateTime<Local>,
+}
+
+impl PgNotification {
+    pub fn new(channel: impl Into<String>, payload: impl Into<String>, process_id: i32) -> Self {
+        Self {
+            channel: channel.into(),
+            payload: payload.into(),
+            process_id,
+            time: Local::now(),
+        }
+    }
+
+    /// One-line form for the `:notifications` picker.
+    pub(crate) fn display(&self) -> String {
+        format!(
+            "{}  {}  {}  (pid {})",
+            self.time.format("%H:%M:%S"),
+            self.channel,
+            self.payload.replace('\n', " "),
+            self.process_id
+        )
+    }
+
+    /// Status line text announcing it, e.g. `NOTIFY orders: 42`.
+    pub(crate) fn summary(&self) -> String {
+        let payload = self.payload.lines().next().unwrap_or_default();
+        if payload.is_empty() {
+            format!("NOTIFY {}", self.channel)
+        } else {
+            format!("NOTIFY {}: {payload}", self.channel)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Listens {
+    /// Connection generation `channels` were issued on.
+    generation: u64,
+    channels: Vec<String>,
+    received: VecDeque<PgNotification>,
+    unread: usize,
+}
+
+impl Listens {
+    /// Channels listened to on connection `generation`.
+    pub(crate) fn channels(&self, generation: u64) -> &[String] {
+        if generation == self.generation {
+            &self.channels
+        } else {
+            &[]
+        }
+    }
+
+    pub(crate) fn listen(&mut self, generation: u64, channel: &str) {
+        self.switch_to(generation);
+        if !self.channels.iter().any(|listened| listened == channel) {
+            self.channels.push(channel.to_string());
+        }
+    }
+
+    /// Stops listening to `channel`, or to every channel with `None`.
+    pub(crate) fn unlisten(&mut self, generation: u64, channel: Option<&str>) {
+        self.switch_to(generation);
+        match channel {
+            Some(channel) => self.channels.retain(|listened| listened != channel),
+            None => self.channels.clear(),
+        }
+    }
+
+    fn switch_to(&mut self, generation: u64) {
+        if generation != self.generation {
+            self.generation = generation;
+            self.channels.clear();
+        }
+    }
+
+    pub(crate) fn receive(&mut self, notification: PgNotification) {
+        self.received.push_back(notification);
+        if self.received.len() > CAPACITY {
+            self.received.pop_front();
+        }
+        self.unread = (self.unread + 1).min(self.received.len());
+    }
+
+    /// Notifications not yet seen in `:notifications`.
+    pub(crate) fn unread(&self) -> usize {
+        self.unread
+    }
+
+    /// Newest-first payloads for `:notifications`; they count as read from here on.
+    pub(crate) fn take_log(&mut self) -> Vec<PgNotification> {
+        self.unread = 0;
+        self.received.iter().rev().cloned().collect()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.received.clear();
+        self.unread = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels_belong_to_their_connection() {
+        let mut listens = Listens::default();
+        listens.listen(1, "orders");
+        listens.listen(1, "jobs");
+        listens.listen(1, "orders");
+        assert_eq!(listens.channels(1), ["orders", "jobs"]);
+        listens.unlisten(1, Some("orders"));
+        assert_eq!(listens.channels(1), ["jobs"]);
+
+        // A new connection starts without the old session's LISTENs.
+        assert!(listens.channels(2).is_empty());
+        listens.listen(2, "audit");
+        assert_eq!(listens.channels(2), ["audit"]);
+        assert!(listens.channels(1).is_empty());
+        listens.unlisten(2, None);
+        assert!(listens.channels(2).is_empty());
+    }
+
+    #[test]
+    fn received_payloads_are_unread_until_listed() {
+        let mut listens = Listens::default();
+        listens.receive(PgNotification::new("orders", "{\"id\": 1}\nmore", 42));
+        listens.receive(PgNotification::new("jobs", "", 43));
+        assert_eq!(listens.unread(), 2);
+
+        let log = listens.take_log();
+        assert_eq!(listens.unread(), 0);
+        assert_eq!(log[0].summary(), "NOTIFY jobs");
+        assert_eq!(log[1].summary(), "NOTIFY orders: {\"id\": 1}");
+        assert!(log[1]
+            .display()
+            .ends_with("  orders  {\"id\": 1} more  (pid 42)"));
+
+        for index in 0..CAPACITY + 3 {
+            listens.receive(PgNotification::new("bulk", index.to_string(), 1));
+        }
+        assert_eq!(listens.unread(), CAPACITY);
+        let log = listens.take_log();
+        assert_eq!(log.len(), CAPACITY);
+        assert_eq!(log[0].payload, (CAPACITY + 2).to_string());
+        listens.clear();
+        assert!(listens.take_log().is_empty());
+    }
+}
@@ -1,23 +1,52 @@
+mod activity;
 #[allow(clippy::module_inception)]
 mod app;
+mod binary_values;
+mod browse;
+mod clipboard_ring;
+mod cost_guard;
+mod cursor_statement;
+mod destructive;
+mod edit_log;
 mod execution;
+mod file_view;
+mod import;
+mod listen;
 mod notebook;
 mod notebook_export;
 mod notebook_run;
+mod notifications;
+mod pending_changes;
+mod perf;
 mod pg_snapshot;
+mod prepared;
+mod query_params;
+mod reconnect;
 mod refinement;
+mod result_history;
+mod result_info;
 mod result_transform;
+mod server_notices;
+mod source_script;
+mod split_buffer;
+mod sql_format;
 mod sql_lexer;
+mod stash;
 mod state;
+mod template_vars;
 
+pub use activity::BackendSignal;
 pub use app::{encode_schema_id_component, App, DbEvent, DbSession, QueryResult, SharedClient};
+pub use cost_guard::PlanEstimate;
 pub use execution::{
     ActiveExecution, CellId, ExecutionContext, ExecutionId, ExecutionTarget, TransactionState,
 };
+pub use listen::PgNotification;
 pub use notebook::{NotebookCell, NotebookFocus, NotebookState};
 pub use pg_snapshot::PgTempSnapshot;
 pub use refinement::{
     RefinementAvailability, RefinementUnavailableReason, ResultVersion, RetainedResult,
     RetainedResultHandle,
 };
+pub use server_notices::ServerNotice;
 pub use state::{DbStatus, Focus, Mode, PanelDirection, SidebarSection, WorkspaceMode};
@@ -1,9 +1,13 @@
-//! Bounded, atomic exports for retained PostgreSQL notebook results.
+//! Bounded, atomic exports for result grids and retained PostgreSQL notebook results.
 
+use std::borrow::Cow;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
 use futures_util::TryStreamExt;
 use tokio::fs::{self, File};
 use tokio::io::{AsyncWriteExt, BufWriter};
@@ -12,7 +16,12 @@ use uuid::Uuid;
 
 use super::pg_snapshot::{validate_snapshot_identity, PgTempSnapshot};
 use super::refinement::ResultVersion;
-use crate::ui::quote_identifier;
+use crate::config::{ExportConfig, ExportEncoding};
+use crate::ui::{quote_identifier, GridModel, MASKED_VALUE};
+
+/// Rows formatted and written at a time by a grid export, and the progress
+/// reporting interval of both exports.
+pub(crate) const EXPORT_CHUNK_ROWS: usize = 1_000;
 
 /// File format for a streamed notebook-result export.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -34,16 +43,116 @@ impl NotebookExportFormat {
     }
 }
 
+/// Regional formatting of CSV and TSV exports (`[export]`). JSON and SQL keep
+/// values as the database wrote them, since programs parse those.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ExportLocale {
+    pub(crate) decimal_separator: char,
+    pub(crate) csv_delimiter: char,
+    /// strftime format for the date part of dates and timestamps.
+    pub(crate) date_format: Option<String>,
+    /// Start the file with a UTF-8 byte order mark.
+    pub(crate) bom: bool,
+}
+
+impl Default for ExportLocale {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            csv_delimiter: ',',
+            date_format: None,
+            bom: false,
+        }
+    }
+}
+
+impl ExportLocale {
+    pub(crate) fn from_config(config: &ExportConfig) -> Result<Self, String> {
+        let date_format = Some(config.date_format.trim()).filter(|format| !format.is_empty());
+        if let Some(format) = date_format {
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("Invalid export.date_format: {format}"));
+            }
+        }
+        Ok(Self {
+            decimal_separator: config.decimal_separator,
+            csv_delimiter: config.csv_delimiter,
+            date_format: date_format.map(str::to_string),
+            bom: config.encoding == ExportEncoding::Utf8Bom,
+        })
+    }
+
+    /// `value` of a column of `col_type` as this locale writes it. Columns
+    /// of unknown type are judged by the value's shape.
+    fn localize<'a>(&self, value: &'a str, col_type: &str) -> Cow<'a, str> {
+        let col_type = col_type.trim().to_ascii_lowercase();
+        let known = !col_type.is_empty();
+        let numeric = if known {
+            is_numeric_type(&col_type) && value.parse::<f64>().is_ok_and(f64::is_finite)
+        } else {
+            is_plain_decimal(value)
+        };
+        if numeric && self.decimal_separator != '.' {
+            return Cow::Owned(value.replacen('.', &self.decimal_separator.to_string(), 1));
+        }
+        let dated = !known || col_type == "date" || col_type.starts_with("timestamp");
+        match &self.date_format {
+            Some(format) if dated => {
+                reformat_date(value, format).map_or(Cow::Borrowed(value), Cow::Owned)
+            }
+            _ => Cow::Borrowed(value),
+        }
+    }
+}
+
+fn is_numeric_type(col_type: &str) -> bool {
+    matches!(
+        col_type,
+        "numeric" | "decimal" | "real" | "double precision" | "float4" | "float8" | "money"
+    ) || col_type.starts_with("numeric(")
+        || col_type.starts_with("decimal(")
+}
+
+/// `-12.5`, but not `1.2.3`, `1e5` or `.5`.
+fn is_plain_decimal(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    digits.split_once('.').is_some_and(|(whole, fraction)| {
+        !whole.is_empty()
+            && !fraction.is_empty()
+            && whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+    })
+}
+
+/// A `YYYY-MM-DD` date, or a timestamp starting with one, with the date part
+/// written in `format`; the time and zone are kept as they are.
+fn reformat_date(value: &str, format: &str) -> Option<String> {
+    let (date, rest) = (value.get(..10)?, value.get(10..)?);
+    if !(rest.is_empty() || rest.starts_with([' ', 'T'])) {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(format!("{}{rest}", date.format(format)))
+}
+
 /// Stream a retained snapshot to a temporary sibling file, then atomically replace the target.
 ///
 /// Rows are formatted and written as they arrive; a full retained result is never held in memory.
+/// `progress` receives the number of rows written every [`EXPORT_CHUNK_ROWS`] rows.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn export_snapshot(
     client: &Client,
     snapshot: &PgTempSnapshot,
+    masked_cols: &[bool],
+    col_types: &[String],
     version: ResultVersion,
     path: &Path,
     format: &NotebookExportFormat,
+    locale: &ExportLocale,
     cancelled: &Arc<AtomicBool>,
+    progress: &mut (dyn FnMut(usize) + Send),
 ) -> Result<usize, String> {
     if cancelled.load(Ordering::Acquire) {
         return Err("export cancelled".to_string());
@@ -57,19 +166,28 @@ pub(crate) async fn export_snapshot(
     let columns = snapshot
         .public_columns
         .iter()
-        .map(|column| format!("\"{}\"", column.replace('"', "\"\"")))
+        .enumerate()
+        .map(|(index, column)| {
+            let column = format!("\"{}\"", column.replace('"', "\"\""));
+            // Masked columns are replaced on the server, keeping NULLs.
+            if masked_cols.get(index).copied().unwrap_or(false) {
+                format!(
+                    "CASE WHEN {column} IS NULL THEN NULL ELSE '{MASKED_VALUE}' END AS {column}"
+                )
+            } else {
+                column
+            }
+        })
         .collect::<Vec<_>>()
         .join(", ");
     let ordinal = format!("__tsql_row_ordinal_{:016x}", version.source_execution.0);
     let query = format!("SELECT {columns} FROM {qualified} ORDER BY \"{ordinal}\"");
 
-    let file_name = path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .filter(|name| !name.is_empty())
-        .ok_or_else(|| format!("invalid export path: {}", path.display()))?;
-    let temporary = path.with_file_name(format!(".{file_name}.{}.part", Uuid::new_v4().simple()));
-    let result = write_snapshot(client, &query, snapshot, &temporary, format, cancelled).await;
+    let temporary = temporary_path(path)?;
+    let result = write_snapshot(
+        client, &query, snapshot, col_types, &temporary, format, locale, cancelled, progress,
+    )
+    .await;
     match result {
         Ok(rows) => {
             if let Err(error) = fs::rename(&temporary, path).await {
@@ -85,19 +203,33 @@ pub(crate) async fn export_snapshot(
     }
 }
 
+/// Hidden sibling file an export is written to before replacing `path`.
+fn temporary_path(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| format!("invalid export path: {}", path.display()))?;
+    Ok(path.with_file_name(format!(".{file_name}.{}.part", Uuid::new_v4().simple())))
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn write_snapshot(
     client: &Client,
     query: &str,
     snapshot: &PgTempSnapshot,
+    col_types: &[String],
     path: &PathBuf,
     format: &NotebookExportFormat,
+    locale: &ExportLocale,
     cancelled: &Arc<AtomicBool>,
+    progress: &mut (dyn FnMut(usize) + Send),
 ) -> Result<usize, String> {
     let file = File::create(path)
         .await
         .map_err(|error| format!("failed to create {}: {error}", path.display()))?;
     let mut writer = BufWriter::new(file);
-    write_header(&mut writer, &snapshot.public_columns, format).await?;
+    write_header(&mut writer, &snapshot.public_columns, format, locale).await?;
 
     let stream = client
         .simple_query_raw(query)
@@ -117,8 +249,35 @@ async fn write_snapshot(
             let values = (0..row.len())
                 .map(|index| row.get(index))
                 .collect::<Vec<_>>();
-            write_row(&mut writer, &snapshot.public_columns, &values, format, rows).await?;
+            let values = match format {
+                NotebookExportFormat::Csv | NotebookExportFormat::Tsv => values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        value.map(|value| {
+                            let col_type = col_types.get(index).map_or("", String::as_str);
+                            locale.localize(value, col_type)
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                NotebookExportFormat::Json | NotebookExportFormat::Sql { .. } => values
+                    .iter()
+                    .map(|value| value.map(Cow::Borrowed))
+                    .collect(),
+            };
+            write_row(
+                &mut writer,
+                &snapshot.public_columns,
+                &values,
+                format,
+                locale,
+                rows,
+            )
+            .await?;
             rows = rows.saturating_add(1);
+            if rows % EXPORT_CHUNK_ROWS == 0 {
+                progress(rows);
+            }
         }
     }
     if matches!(format, NotebookExportFormat::Json) {
@@ -138,18 +297,22 @@ async fn write_header(
     writer: &mut BufWriter<File>,
     headers: &[String],
     format: &NotebookExportFormat,
+    locale: &ExportLocale,
 ) -> Result<(), String> {
+    let delimiter = locale.csv_delimiter;
     let line = match format {
         NotebookExportFormat::Csv => format!(
-            "{}\n",
+            "{}{}\n",
+            bom(format, locale),
             headers
                 .iter()
-                .map(|header| escape_delimited(header, ','))
+                .map(|header| escape_delimited(header, delimiter))
                 .collect::<Vec<_>>()
-                .join(",")
+                .join(&delimiter.to_string())
         ),
         NotebookExportFormat::Tsv => format!(
-            "{}\n",
+            "{}{}\n",
+            bom(format, locale),
             headers
                 .iter()
                 .map(|header| escape_tsv(header))
@@ -167,8 +330,9 @@ async fn write_header(
 async fn write_row(
     writer: &mut BufWriter<File>,
     headers: &[String],
-    values: &[Option<&str>],
+    values: &[Option<Cow<'_, str>>],
     format: &NotebookExportFormat,
+    locale: &ExportLocale,
     row_index: usize,
 ) -> Result<(), String> {
     let line = match format {
@@ -176,15 +340,18 @@ async fn write_row(
             "{}\n",
             values
                 .iter()
-                .map(|value| escape_delimited(value.unwrap_or_default(), ','))
+                .map(|value| escape_delimited(
+                    value.as_deref().unwrap_or_default(),
+                    locale.csv_delimiter
+                ))
                 .collect::<Vec<_>>()
-                .join(",")
+                .join(&locale.csv_delimiter.to_string())
         ),
         NotebookExportFormat::Tsv => format!(
             "{}\n",
             values
                 .iter()
-                .map(|value| escape_tsv(value.unwrap_or_default()))
+                .map(|value| escape_tsv(value.as_deref().unwrap_or_default()))
                 .collect::<Vec<_>>()
                 .join("\t")
         ),
@@ -220,7 +387,7 @@ async fn write_row(
             let values = values
                 .iter()
                 .map(|value| {
-                    value.map_or_else(
+                    value.as_deref().map_or_else(
                         || "NULL".to_string(),
                         |value| format!("'{}'", value.replace('\'', "''")),
                     )
@@ -236,6 +403,168 @@ async fn write_row(
         .map_err(|error| format!("failed to write export row: {error}"))
 }
 
+/// Writes the `indices` rows of a loaded grid to a temporary sibling file in
+/// chunks, then atomically replaces `path`. The output matches the grid's own
+/// CSV/TSV/JSON/SQL formatting, with `locale` applied to CSV and TSV;
+/// `progress` receives the rows written so far.
+pub(crate) fn export_grid(
+    grid: &GridModel,
+    indices: &[usize],
+    path: &Path,
+    format: &NotebookExportFormat,
+    locale: &ExportLocale,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(usize),
+) -> Result<usize, String> {
+    let temporary = temporary_path(path)?;
+    match write_grid(
+        grid,
+        indices,
+        &temporary,
+        format,
+        locale,
+        cancelled,
+        &mut progress,
+    ) {
+        Ok(()) => {
+            if let Err(error) = std::fs::rename(&temporary, path) {
+                let _ = std::fs::remove_file(&temporary);
+                return Err(format!("failed to replace {}: {error}", path.display()));
+            }
+            Ok(indices.len())
+        }
+        Err(error) => {
+            let _ = std::fs::remove_file(&temporary);
+            Err(error)
+        }
+    }
+}
+
+fn write_grid(
+    grid: &GridModel,
+    indices: &[usize],
+    path: &Path,
+    format: &NotebookExportFormat,
+    locale: &ExportLocale,
+    cancelled: &AtomicBool,
+    progress: &mut impl FnMut(usize),
+) -> Result<(), String> {
+    let file = std::fs::File::create(path)
+        .map_err(|error| format!("failed to create {}: {error}", path.display()))?;
+    let mut writer = std::io::BufWriter::new(file);
+    let mut write = |text: &str| {
+        writer
+            .write_all(text.as_bytes())
+            .map_err(|error| format!("failed to write export: {error}"))
+    };
+
+    write(bom(format, locale))?;
+    let json = matches!(format, NotebookExportFormat::Json);
+    if json {
+        write("[\n")?;
+    }
+    // An empty export still gets its CSV/TSV header line.
+    let chunks: Vec<&[usize]> = if indices.is_empty() {
+        vec![indices]
+    } else {
+        indices.chunks(EXPORT_CHUNK_ROWS).collect()
+    };
+    let mut written = 0usize;
+    let mut wrote_any = false;
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        if cancelled.load(Ordering::Acquire) {
+            return Err("export cancelled".to_string());
+        }
+        let text = match format {
+            NotebookExportFormat::Csv => delimited_rows(
+                grid,
+                chunk,
+                index == 0,
+                locale,
+                locale.csv_delimiter,
+                escape_delimited,
+            ),
+            // Like `rows_as_tsv`, values are written unescaped.
+            NotebookExportFormat::Tsv => {
+                delimited_rows(grid, chunk, index == 0, locale, '\t', |value, _| {
+                    value.to_string()
+                })
+            }
+            NotebookExportFormat::Sql { table } => grid.rows_as_sql_inserts(chunk, table),
+            NotebookExportFormat::Json => grid
+                .rows_as_json(chunk)
+                .strip_prefix("[\n")
+                .and_then(|objects| objects.strip_suffix("\n]"))
+                .unwrap_or_default()
+                .to_string(),
+        };
+        if !text.is_empty() {
+            if wrote_any {
+                write(if json { ",\n" } else { "\n" })?;
+            }
+            write(&text)?;
+            wrote_any = true;
+        }
+        written += chunk.len();
+        progress(written);
+    }
+    if json {
+        write("\n]")?;
+    }
+    writer
+        .flush()
+        .map_err(|error| format!("failed to flush export: {error}"))
+}
+
+/// CSV or TSV lines for the `indices` rows, as `GridModel::rows_as_csv` and
+/// `rows_as_tsv` format them but with `locale` applied.
+fn delimited_rows(
+    grid: &GridModel,
+    indices: &[usize],
+    include_headers: bool,
+    locale: &ExportLocale,
+    delimiter: char,
+    escape: fn(&str, char) -> String,
+) -> String {
+    let separator = delimiter.to_string();
+    let mut lines = Vec::new();
+    if include_headers && !grid.headers.is_empty() {
+        let headers: Vec<String> = grid
+            .headers
+            .iter()
+            .map(|header| escape(header, delimiter))
+            .collect();
+        lines.push(headers.join(&separator));
+    }
+    for &row in indices {
+        let Some(values) = grid.shown_row(row) else {
+            continue;
+        };
+        let fields: Vec<String> = values
+            .iter()
+            .enumerate()
+            .map(|(col, value)| {
+                if grid.cell_is_null(row, col) {
+                    escape(value, delimiter)
+                } else {
+                    let col_type = grid.col_type(col).unwrap_or_default();
+                    escape(&locale.localize(value, col_type), delimiter)
+                }
+            })
+            .collect();
+        lines.push(fields.join(&separator));
+    }
+    lines.join("\n")
+}
+
+/// The byte order mark a CSV or TSV file starts with, if `locale` wants one.
+fn bom(format: &NotebookExportFormat, locale: &ExportLocale) -> &'static str {
+    match format {
+        NotebookExportFormat::Csv | NotebookExportFormat::Tsv if locale.bom => "\u{feff}",
+        _ => "",
+    }
+}
+
 fn escape_delimited(value: &str, delimiter: char) -> String {
     if value.contains([delimiter, '"', '\n', '\r']) {
         format!("\"{}\"", value.replace('"', "\"\""))
@@ -275,10 +604,15 @@ mod tests {
     use std::sync::atomic::AtomicBool;
     use std::sync::Arc;
 
-    use super::{escape_delimited, escape_json, escape_tsv, export_snapshot, NotebookExportFormat};
+    use super::{
+        escape_delimited, escape_json, escape_tsv, export_grid, export_snapshot, ExportLocale,
+        NotebookExportFormat, EXPORT_CHUNK_ROWS,
+    };
     use crate::app::execution::{CellId, ExecutionId};
     use crate::app::pg_snapshot::PgTempSnapshot;
     use crate::app::refinement::ResultVersion;
+    use crate::config::{ExportConfig, ExportEncoding};
+    use crate::ui::GridModel;
 
     #[test]
     fn delimited_fields_quote_special_characters() {
@@ -298,6 +632,182 @@ mod tests {
         );
     }
 
+    #[test]
+    fn chunked_grid_export_matches_the_grid_formatting() {
+        let rows = EXPORT_CHUNK_ROWS * 2 + 3;
+        let grid = GridModel::new(
+            vec!["id".to_string(), "note".to_string()],
+            (0..rows)
+                .map(|row| vec![row.to_string(), format!("a,\"{row}\"")])
+                .collect(),
+        );
+        let indices: Vec<usize> = (0..rows).collect();
+        let directory = tempfile::tempdir().unwrap();
+        let cancelled = AtomicBool::new(false);
+
+        for (format, expected) in [
+            (NotebookExportFormat::Csv, grid.rows_as_csv(&indices, true)),
+            (NotebookExportFormat::Tsv, grid.rows_as_tsv(&indices, true)),
+            (NotebookExportFormat::Json, grid.rows_as_json(&indices)),
+            (
+                NotebookExportFormat::Sql {
+                    table: "t".to_string(),
+                },
+                grid.rows_as_sql_inserts(&indices, "t"),
+            ),
+        ] {
+            let path = directory.path().join(format.label());
+            let mut reported = Vec::new();
+            let written = export_grid(
+                &grid,
+                &indices,
+                &path,
+                &format,
+                &ExportLocale::default(),
+                &cancelled,
+                |done| reported.push(done),
+            )
+            .unwrap();
+
+            assert_eq!(written, rows);
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), expected);
+            assert_eq!(
+                reported,
+                [EXPORT_CHUNK_ROWS, EXPORT_CHUNK_ROWS * 2, rows],
+                "{}",
+                format.label()
+            );
+        }
+    }
+
+    #[test]
+    fn csv_and_tsv_exports_follow_the_export_locale() {
+        let mut grid = GridModel::new(
+            vec![
+                "amount".to_string(),
+                "day".to_string(),
+                "at".to_string(),
+                "version".to_string(),
+                "guess".to_string(),
+            ],
+            vec![
+                vec![
+                    "1234.5".to_string(),
+                    "2024-03-09".to_string(),
+                    "2024-03-09 13:45:00.25+01".to_string(),
+                    "1.5".to_string(),
+                    "-0.75".to_string(),
+                ],
+                vec![
+                    "NULL".to_string(),
+                    "infinity".to_string(),
+                    "NULL".to_string(),
+                    "Café".to_string(),
+                    "1.2.3".to_string(),
+                ],
+            ],
+        )
+        .with_col_types(vec![
+            "numeric".to_string(),
+            "date".to_string(),
+            "timestamp with time zone".to_string(),
+            "text".to_string(),
+            String::new(),
+        ]);
+        grid.null_cells[1][0] = true;
+        grid.null_cells[1][2] = true;
+        let locale = ExportLocale::from_config(&ExportConfig {
+            decimal_separator: ',',
+            csv_delimiter: ';',
+            date_format: "%d.%m.%Y".to_string(),
+            encoding: ExportEncoding::Utf8Bom,
+        })
+        .unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let cancelled = AtomicBool::new(false);
+
+        let csv = directory.path().join("result.csv");
+        export_grid(
+            &grid,
+            &[0, 1],
+            &csv,
+            &NotebookExportFormat::Csv,
+            &locale,
+            &cancelled,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&csv).unwrap(),
+            "\u{feff}amount;day;at;version;guess\n\
+             1234,5;09.03.2024;09.03.2024 13:45:00.25+01;1.5;-0,75\n\
+             NULL;infinity;NULL;Café;1.2.3"
+        );
+
+        let tsv = directory.path().join("result.tsv");
+        export_grid(
+            &grid,
+            &[0],
+            &tsv,
+            &NotebookExportFormat::Tsv,
+            &locale,
+            &cancelled,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&tsv).unwrap(),
+            "\u{feff}amount\tday\tat\tversion\tguess\n\
+             1234,5\t09.03.2024\t09.03.2024 13:45:00.25+01\t1.5\t-0,75"
+        );
+
+        // JSON is for programs, so it keeps the database's formatting.
+        let json = directory.path().join("result.json");
+        export_grid(
+            &grid,
+            &[0],
+            &json,
+            &NotebookExportFormat::Json,
+            &locale,
+            &cancelled,
+            |_| {},
+        )
+        .unwrap();
+        let json = std::fs::read_to_string(&json).unwrap();
+        assert!(json.starts_with('['));
+        assert!(json.contains("\"amount\": 1234.5"));
+
+        let error = ExportLocale::from_config(&ExportConfig {
+            date_format: "%d.%Q".to_string(),
+            ..ExportConfig::default()
+        })
+        .unwrap_err();
+        assert_eq!(error, "Invalid export.date_format: %d.%Q");
+    }
+
+    #[test]
+    fn cancelled_grid_export_keeps_the_existing_file() {
+        let grid = GridModel::new(vec!["id".to_string()], vec![vec!["1".to_string()]]);
+        let directory = tempfile::tempdir().unwrap();
+        let target = directory.path().join("result.csv");
+        std::fs::write(&target, "keep me").unwrap();
+
+        let error = export_grid(
+            &grid,
+            &[0],
+            &target,
+            &NotebookExportFormat::Csv,
+            &ExportLocale::default(),
+            &AtomicBool::new(true),
+            |_| {},
+        )
+        .unwrap_err();
+
+        assert_eq!(error, "export cancelled");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "keep me");
+        assert_eq!(std::fs::read_dir(directory.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn format_labels_are_stable() {
         assert_eq!(NotebookExportFormat::Csv.label(), "CSV");
@@ -375,10 +885,14 @@ mod tests {
             export_snapshot(
                 &client,
                 &snapshot,
+                &[],
+                &[],
                 version,
                 &csv,
                 &NotebookExportFormat::Csv,
+                &ExportLocale::default(),
                 &cancelled,
+                &mut |_| {},
             )
             .await
             .unwrap(),
@@ -398,10 +912,14 @@ mod tests {
             export_snapshot(
                 &client,
                 &snapshot,
+                &[],
+                &[],
                 version,
                 &tsv,
                 &NotebookExportFormat::Tsv,
+                &ExportLocale::default(),
                 &cancelled,
+                &mut |_| {},
             )
             .await
             .unwrap(),
@@ -421,10 +939,14 @@ mod tests {
             export_snapshot(
                 &client,
                 &snapshot,
+                &[],
+                &[],
                 version,
                 &json,
                 &NotebookExportFormat::Json,
+                &ExportLocale::default(),
                 &cancelled,
+                &mut |_| {},
             )
             .await
             .unwrap(),
@@ -452,12 +974,16 @@ mod tests {
             export_snapshot(
                 &client,
                 &snapshot,
+                &[],
+                &[],
                 version,
                 &sql,
                 &NotebookExportFormat::Sql {
                     table: "reporting.user \"copy\"".to_string()
                 },
+                &ExportLocale::default(),
                 &cancelled,
+                &mut |_| {},
             )
             .await
             .unwrap(),
@@ -480,10 +1006,14 @@ mod tests {
         let error = export_snapshot(
             &client,
             &snapshot,
+            &[],
+            &[],
             version,
             &destination_directory,
             &NotebookExportFormat::Csv,
+            &ExportLocale::default(),
             &cancelled,
+            &mut |_| {},
         )
         .await
         .unwrap_err();
@@ -540,10 +1070,14 @@ mod tests {
         let error = export_snapshot(
             &client,
             &snapshot,
+            &[],
+            &[],
             version,
             &target,
             &NotebookExportFormat::Csv,
+            &ExportLocale::default(),
             &cancelled,
+            &mut |_| {},
         )
         .await
         .unwrap_err();
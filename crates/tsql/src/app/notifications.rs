@@ -0,0 +1,195 @@
+//! Notification log behind the status line, toasts and `:messages`.
+//!
+//! The app keeps reporting through `last_status` / `last_error`; every change
+//! to either is recorded here after each input or database event, so messages
+//! that replace each other quickly (connect, schema loaded, copy) stay visible
+//! as stacked toasts and in the `:messages` log.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+/// Messages kept for `:messages`.
+const LOG_CAPACITY: usize = 200;
+/// Toasts shown at once.
+const MAX_TOASTS: usize = 3;
+
+/// Status prefixes reported as warnings rather than plain information.
+const WARNING_PREFIXES: &[&str] = &["Cannot ", "Failed", "Invalid ", "Not connected", "Unknown "];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn for_status(message: &str) -> Self {
+        if WARNING_PREFIXES
+            .iter()
+            .any(|prefix| message.starts_with(prefix))
+        {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warn",
+            Severity::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Notification {
+    pub(crate) message: String,
+    pub(crate) severity: Severity,
+    time: DateTime<Local>,
+    at: Instant,
+}
+
+impl Notification {
+    /// One-line form for the `:messages` picker.
+    pub(crate) fn display(&self) -> String {
+        format!(
+            "{}  {:<5}  {}",
+            self.time.format("%H:%M:%S"),
+            self.severity.label(),
+            self.message.replace('\n', " ")
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Notifications {
+    log: VecDeque<Notification>,
+    seen_status: Option<String>,
+    seen_error: Option<String>,
+}
+
+impl Notifications {
+    /// Records `status` and `error` if they changed since the last call, and
+    /// returns whether either did.
+    pub(crate) fn observe(
+        &mut self,
+        status: Option<&str>,
+        error: Option<&str>,
+        now: Instant,
+    ) -> bool {
+        let mut changed = false;
+        if status != self.seen_status.as_deref() {
+            self.seen_status = status.map(str::to_string);
+            if let Some(status) = status {
+                self.push(status, Severity::for_status(status), now);
+            }
+            changed = true;
+        }
+        if error != self.seen_error.as_deref() {
+            self.seen_error = error.map(str::to_string);
+            if let Some(error) = error {
+                self.push(error, Severity::Error, now);
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    fn push(&mut self, message: &str, severity: Severity, now: Instant) {
+        self.log.push_back(Notification {
+            message: message.to_string(),
+            severity,
+            time: Local::now(),
+            at: now,
+        });
+        while self.log.len() > LOG_CAPACITY {
+            self.log.pop_front();
+        }
+    }
+
+    /// Newest-first log for `:messages`.
+    pub(crate) fn log(&self) -> Vec<Notification> {
+        self.log.iter().rev().cloned().collect()
+    }
+
+    /// Recent non-error messages, oldest first, for the toast stack. Errors
+    /// already get their own popup.
+    pub(crate) fn toasts(&self, now: Instant, timeout: Duration) -> Vec<&Notification> {
+        let mut toasts: Vec<_> = self
+            .log
+            .iter()
+            .rev()
+            .filter(|n| n.severity != Severity::Error)
+            .take_while(|n| now.saturating_duration_since(n.at) < timeout)
+            .take(MAX_TOASTS)
+            .collect();
+        toasts.reverse();
+        toasts
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.log.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observe_records_each_change_once() {
+        let mut notifications = Notifications::default();
+        let now = Instant::now();
+        assert!(notifications.observe(Some("Connected"), None, now));
+        assert!(!notifications.observe(Some("Connected"), None, now));
+        notifications.observe(Some("Schema loaded"), None, now);
+        notifications.observe(None, Some("boom"), now);
+
+        let log: Vec<_> = notifications
+            .log()
+            .into_iter()
+            .map(|n| (n.message, n.severity))
+            .collect();
+        assert_eq!(
+            log,
+            [
+                ("boom".to_string(), Severity::Error),
+                ("Schema loaded".to_string(), Severity::Info),
+                ("Connected".to_string(), Severity::Info),
+            ]
+        );
+    }
+
+    #[test]
+    fn failures_are_warnings() {
+        assert_eq!(Severity::for_status("Failed to copy"), Severity::Warning);
+        assert_eq!(Severity::for_status("Cannot transform"), Severity::Warning);
+        assert_eq!(Severity::for_status("Copied 3 rows"), Severity::Info);
+    }
+
+    #[test]
+    fn toasts_stack_recent_messages_and_expire() {
+        let mut notifications = Notifications::default();
+        let start = Instant::now();
+        for (i, message) in ["one", "two", "three", "four"].iter().enumerate() {
+            notifications.observe(Some(message), None, start + Duration::from_millis(i as u64));
+        }
+        notifications.observe(Some("four"), Some("error"), start);
+
+        let timeout = Duration::from_secs(3);
+        let toasts: Vec<_> = notifications
+            .toasts(start + Duration::from_millis(10), timeout)
+            .iter()
+            .map(|n| n.message.as_str())
+            .collect();
+        assert_eq!(toasts, ["two", "three", "four"]);
+        assert!(notifications
+            .toasts(start + Duration::from_secs(4), timeout)
+            .is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+//! Changes made inside an open PostgreSQL transaction, for `[n pending]` and
+//! `:pending`: grid edits, and editor statements that write when
+//! `sql.autocommit = false`.
+//!
+//! Each tracked change runs right after its own `SAVEPOINT`, so rolling back to a
+//! change undoes it and every change made after it while keeping earlier ones.
+
+use super::execution::{classify_transaction_control, TransactionControl, TransactionState};
+
+/// An edit statement that succeeded inside the current transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingChange {
+    /// Savepoint created immediately before the statement ran.
+    pub(crate) savepoint: String,
+    pub(crate) sql: String,
+}
+
+/// A `:pending` picker row pointing back into the tracked changes.
+#[derive(Clone, Debug)]
+pub(crate) struct PendingChangeEntry {
+    pub(crate) index: usize,
+    label: String,
+}
+
+impl PendingChangeEntry {
+    pub(crate) fn display(&self) -> String {
+        self.label.clone()
+    }
+}
+
+/// Oldest-first list of the edits made since the transaction began.
+#[derive(Debug, Default)]
+pub(crate) struct PendingChanges {
+    changes: Vec<PendingChange>,
+    next_savepoint: u64,
+}
+
+impl PendingChanges {
+    pub(crate) fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&PendingChange> {
+        self.changes.get(index)
+    }
+
+    /// Name for the savepoint guarding the next edit.
+    pub(crate) fn next_savepoint(&mut self) -> String {
+        self.next_savepoint += 1;
+        format!("tsql_pending_{}", self.next_savepoint)
+    }
+
+    pub(crate) fn record(&mut self, change: PendingChange) {
+        self.changes.push(change);
+    }
+
+    /// Forgets `index` and every later change after `ROLLBACK TO` its savepoint.
+    pub(crate) fn rolled_back_to(&mut self, index: usize) -> usize {
+        let removed = self.changes.len().saturating_sub(index);
+        self.changes.truncate(index);
+        removed
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.changes.clear();
+    }
+
+    /// Drops the tracked changes once a user statement ends their transaction.
+    ///
+    /// Any transaction-control statement counts, because `COMMIT AND CHAIN`
+    /// keeps a transaction open but settles everything made before it.
+    pub(crate) fn after_execution(&mut self, sql: &str, state: TransactionState) {
+        let in_transaction = matches!(state, TransactionState::Active | TransactionState::Failed);
+        if !in_transaction || classify_transaction_control(sql) != TransactionControl::Other {
+            self.clear();
+        }
+    }
+
+    /// Picker rows, oldest first, numbered in execution order.
+    pub(crate) fn picker_entries(&self) -> Vec<PendingChangeEntry> {
+        self.changes
+            .iter()
+            .enumerate()
+            .map(|(index, change)| PendingChangeEntry {
+                index,
+                label: format!(
+                    "#{}  {}",
+                    index + 1,
+                    change.sql.split_whitespace().collect::<Vec<_>>().join(" ")
+                ),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(changes: &mut PendingChanges, sql: &str) {
+        let savepoint = changes.next_savepoint();
+        changes.record(PendingChange {
+            savepoint,
+            sql: sql.to_string(),
+        });
+    }
+
+    #[test]
+    fn savepoints_are_unique_and_rollback_drops_later_changes() {
+        let mut changes = PendingChanges::default();
+        change(&mut changes, "UPDATE t SET a = 1");
+        change(&mut changes, "UPDATE t SET a = 2");
+        change(&mut changes, "UPDATE t SET a = 3");
+
+        assert_eq!(changes.get(1).unwrap().savepoint, "tsql_pending_2");
+        assert_eq!(changes.rolled_back_to(1), 2);
+        assert_eq!(changes.len(), 1);
+
+        // Savepoint names are never reused within a session.
+        change(&mut changes, "UPDATE t SET a = 4");
+        assert_eq!(changes.get(1).unwrap().savepoint, "tsql_pending_4");
+    }
+
+    #[test]
+    fn transaction_control_or_leaving_the_transaction_clears_changes() {
+        let mut changes = PendingChanges::default();
+        change(&mut changes, "UPDATE t SET a = 1");
+
+        changes.after_execution("SELECT 1", TransactionState::Active);
+        changes.after_execution("SELECT 1/0", TransactionState::Failed);
+        changes.after_execution("ROLLBACK TO SAVEPOINT mine", TransactionState::Active);
+        assert_eq!(changes.len(), 1);
+
+        changes.after_execution("COMMIT AND CHAIN", TransactionState::Active);
+        assert!(changes.is_empty());
+
+        change(&mut changes, "UPDATE t SET a = 2");
+        changes.after_execution("SELECT 1", TransactionState::Unknown);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn picker_entries_number_changes_oldest_first() {
+        let mut changes = PendingChanges::default();
+        change(&mut changes, "UPDATE t\n  SET a = 1\n  WHERE id = 1");
+        change(&mut changes, "UPDATE t SET a = 2");
+
+        let labels: Vec<_> = changes
+            .picker_entries()
+            .iter()
+            .map(PendingChangeEntry::display)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "#1  UPDATE t SET a = 1 WHERE id = 1",
+                "#2  UPDATE t SET a = 2"
+            ]
+        );
+    }
+}
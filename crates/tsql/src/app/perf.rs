@@ -0,0 +1,115 @@
+//! Frame timings for the `:debug perf` overlay.
+//!
+//! The run loop times each frame's editor highlighting, result rendering and
+//! whole draw, plus how long an input event waited until the frame showing
+//! its effect was drawn.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Frames kept for the averages and maxima.
+const WINDOW: usize = 120;
+
+/// Timings of one drawn frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct FrameSample {
+    pub(crate) draw: Duration,
+    pub(crate) highlight: Duration,
+    pub(crate) grid: Duration,
+    /// Input to drawn frame, for frames that follow an input event.
+    pub(crate) latency: Option<Duration>,
+}
+
+/// Recent frame samples, newest last.
+#[derive(Debug, Default)]
+pub(crate) struct PerfStats {
+    samples: VecDeque<FrameSample>,
+    frames: u64,
+}
+
+impl PerfStats {
+    pub(crate) fn push(&mut self, sample: FrameSample) {
+        if self.samples.len() == WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.frames += 1;
+    }
+
+    /// Overlay rows: `last / avg / max` of each timing over the window.
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let row = |label: &str, values: Vec<Duration>| {
+            let Some(last) = values.last().copied() else {
+                return format!("{label:<10} -");
+            };
+            let total: Duration = values.iter().sum();
+            let avg = total / values.len() as u32;
+            let max = values.iter().max().copied().unwrap_or_default();
+            format!(
+                "{label:<10} {:>8} {:>8} {:>8}",
+                millis(last),
+                millis(avg),
+                millis(max)
+            )
+        };
+        let field = |get: fn(&FrameSample) -> Duration| -> Vec<Duration> {
+            self.samples.iter().map(get).collect()
+        };
+        vec![
+            format!(
+                "{:<10} {:>8} {:>8} {:>8}",
+                format!("#{}", self.frames),
+                "last",
+                "avg",
+                "max"
+            ),
+            row("draw", field(|sample| sample.draw)),
+            row("highlight", field(|sample| sample.highlight)),
+            row("grid", field(|sample| sample.grid)),
+            row(
+                "latency",
+                self.samples
+                    .iter()
+                    .filter_map(|sample| sample.latency)
+                    .collect(),
+            ),
+        ]
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_show_last_average_and_max_over_the_window() {
+        let mut stats = PerfStats::default();
+        assert_eq!(stats.lines()[1], "draw       -");
+
+        let ms = Duration::from_millis;
+        stats.push(FrameSample {
+            draw: ms(4),
+            highlight: ms(1),
+            grid: ms(2),
+            latency: Some(ms(6)),
+        });
+        stats.push(FrameSample {
+            draw: ms(2),
+            ..FrameSample::default()
+        });
+        let lines = stats.lines();
+        assert_eq!(lines[0], "#2             last      avg      max");
+        assert_eq!(lines[1], "draw         2.00ms   3.00ms   4.00ms");
+        assert_eq!(lines[4], "latency      6.00ms   6.00ms   6.00ms");
+
+        for _ in 0..WINDOW {
+            stats.push(FrameSample::default());
+        }
+        assert_eq!(stats.samples.len(), WINDOW);
+        assert!(stats.lines()[1].ends_with("0.00ms"));
+    }
+}
@@ -353,6 +353,7 @@ pub(crate) async fn execute(
             elapsed: started.elapsed(),
             source_table: None,
             primary_keys: Vec::new(),
+            foreign_keys: Vec::new(),
             col_types: statement
                 .columns()
                 .iter()
@@ -434,6 +435,7 @@ async fn execute_preview_fallback(
             elapsed: started.elapsed(),
             source_table: None,
             primary_keys: Vec::new(),
+            foreign_keys: Vec::new(),
             col_types,
         },
         retained: None,
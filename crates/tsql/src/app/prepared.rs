@@ -0,0 +1,346 @@
+//! Named prepared statements for `:prepare` and `:exec`.
+//!
+//! Statements come from `[sql.prepared]` and `:prepare <name> <sql>`. Each one
+//! is `PREPARE`d on every PostgreSQL connection, so `:exec <name> <arg>...`
+//! only sends `EXECUTE` with the arguments.
+
+use std::collections::BTreeMap;
+
+use super::sql_lexer::{scan, SqlSegmentKind};
+use crate::ui::quote_identifier;
+
+/// A statement of the library and what the server reported about it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PreparedStatement {
+    pub(crate) sql: String,
+    /// Parameter types, once prepared on the current connection.
+    pub(crate) param_types: Option<Vec<String>>,
+}
+
+/// Statements by name, in name order.
+#[derive(Debug, Default)]
+pub(crate) struct PreparedLibrary {
+    statements: BTreeMap<String, PreparedStatement>,
+}
+
+impl PreparedLibrary {
+    pub(crate) fn from_config(statements: &BTreeMap<String, String>) -> Self {
+        let mut library = Self::default();
+        for (name, sql) in statements {
+            library.define(name, sql);
+        }
+        library
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&PreparedStatement> {
+        self.statements.get(name)
+    }
+
+    /// Adds or replaces `name`; it is unprepared until the server confirms it.
+    pub(crate) fn define(&mut self, name: &str, sql: &str) {
+        self.statements.insert(
+            name.to_string(),
+            PreparedStatement {
+                sql: sql.trim().trim_end_matches(';').trim_end().to_string(),
+                param_types: None,
+            },
+        );
+    }
+
+    /// Name and SQL of every statement, to prepare them on a new connection.
+    pub(crate) fn definitions(&self) -> Vec<(String, String)> {
+        self.statements
+            .iter()
+            .map(|(name, statement)| (name.clone(), statement.sql.clone()))
+            .collect()
+    }
+
+    /// Forgets what the previous connection reported.
+    pub(crate) fn reset_connection(&mut self) {
+        for statement in self.statements.values_mut() {
+            statement.param_types = None;
+        }
+    }
+
+    pub(crate) fn set_param_types(&mut self, name: &str, param_types: Vec<String>) {
+        if let Some(statement) = self.statements.get_mut(name) {
+            statement.param_types = Some(param_types);
+        }
+    }
+
+    /// `name(integer, text)` once prepared, else `name($1, $2)` from the SQL.
+    pub(crate) fn signature(&self, name: &str) -> Option<String> {
+        let statement = self.get(name)?;
+        let params = match &statement.param_types {
+            Some(types) => types.join(", "),
+            None => (1..=placeholder_count(&statement.sql))
+                .map(|n| format!("${n}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        Some(format!("{name}({params})"))
+    }
+
+    /// Every signature, for the bare `:prepare` listing.
+    pub(crate) fn signatures(&self) -> Vec<String> {
+        self.statements
+            .keys()
+            .filter_map(|name| self.signature(name))
+            .collect()
+    }
+
+    fn names_starting_with<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+        self.statements
+            .keys()
+            .map(String::as_str)
+            .filter(move |name| name.starts_with(prefix))
+    }
+
+    /// Tab completion of the statement name in `exec <name>` or `prepare <name>`.
+    ///
+    /// A unique match completes the name and a space; several matches extend
+    /// the input to their longest common prefix.
+    pub(crate) fn complete_command(&self, input: &str) -> Option<String> {
+        let (command, partial) = name_argument(input)?;
+        let mut matches = self.names_starting_with(partial);
+        let first = matches.next()?;
+        let mut common = first;
+        let mut unique = true;
+        for name in matches {
+            unique = false;
+            let shared = common
+                .char_indices()
+                .zip(name.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(common.len().min(name.len()), |((index, _), _)| index);
+            common = &common[..shared];
+        }
+        if unique {
+            Some(format!("{command} {first} "))
+        } else {
+            (common.len() > partial.len()).then(|| format!("{command} {common}"))
+        }
+    }
+
+    /// Argument hint for the command prompt while typing `exec <name> ...`.
+    pub(crate) fn command_hint(&self, input: &str) -> Option<String> {
+        let rest = input.strip_prefix("exec ")?.trim_start();
+        let (name, has_args) = match rest.split_once(char::is_whitespace) {
+            Some((name, _)) => (name, true),
+            None => (rest, false),
+        };
+        if let Some(signature) = self.signature(name) {
+            return Some(signature);
+        }
+        if has_args {
+            return None;
+        }
+        let names: Vec<&str> = self.names_starting_with(name).collect();
+        (!names.is_empty()).then(|| names.join("  "))
+    }
+}
+
+/// The command and partial name of `exec <partial>` or `prepare <partial>`.
+fn name_argument(input: &str) -> Option<(&str, &str)> {
+    let (command, rest) = input.split_once(' ')?;
+    if !matches!(command, "exec" | "prepare") {
+        return None;
+    }
+    let partial = rest.trim_start();
+    (!partial.contains(char::is_whitespace)).then_some((command, partial))
+}
+
+/// Highest `$n` placeholder outside literals and comments.
+fn placeholder_count(sql: &str) -> usize {
+    let Ok(segments) = scan(sql) else {
+        return 0;
+    };
+    segments
+        .iter()
+        .filter(|segment| segment.kind == SqlSegmentKind::Code)
+        .flat_map(|segment| sql[segment.range.clone()].split('$').skip(1))
+        .filter_map(|after| {
+            let digits: String = after.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse::<usize>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Splits `:exec` arguments on whitespace. `'...'` quotes an argument (with
+/// `''` for a quote) and a bare `NULL` passes SQL NULL.
+pub(crate) fn parse_args(input: &str) -> Result<Vec<Option<String>>, String> {
+    let mut args = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        if ch == '\'' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('\'') if chars.peek() == Some(&'\'') => {
+                        chars.next();
+                        value.push('\'');
+                    }
+                    Some('\'') => break,
+                    Some(ch) => value.push(ch),
+                    None => return Err("Unterminated quoted argument".to_string()),
+                }
+            }
+            args.push(Some(value));
+            continue;
+        }
+        let mut word = String::new();
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() {
+                break;
+            }
+            word.push(ch);
+            chars.next();
+        }
+        args.push((!word.eq_ignore_ascii_case("null")).then_some(word));
+    }
+    Ok(args)
+}
+
+/// `PREPARE` statement for a library entry.
+pub(crate) fn prepare_sql(name: &str, sql: &str) -> String {
+    format!("PREPARE {} AS {sql}", quote_identifier(name))
+}
+
+/// `DEALLOCATE` statement, to replace an already prepared name.
+pub(crate) fn deallocate_sql(name: &str) -> String {
+    format!("DEALLOCATE {}", quote_identifier(name))
+}
+
+/// Looks up a prepared name; returns a row when it exists on the connection.
+pub(crate) fn exists_sql(name: &str) -> String {
+    format!(
+        "SELECT 1 FROM pg_catalog.pg_prepared_statements WHERE name = '{}'",
+        name.replace('\'', "''")
+    )
+}
+
+/// Parameter types of a prepared name, one row per parameter in order.
+pub(crate) fn param_types_sql(name: &str) -> String {
+    format!(
+        "SELECT p.param_type::text FROM pg_catalog.pg_prepared_statements s, \
+         unnest(s.parameter_types) WITH ORDINALITY AS p(param_type, n) \
+         WHERE s.name = '{}' ORDER BY p.n",
+        name.replace('\'', "''")
+    )
+}
+
+/// `EXECUTE` with untyped literals, which PostgreSQL coerces to the parameter types.
+pub(crate) fn execute_sql(name: &str, args: &[Option<String>]) -> String {
+    let name = quote_identifier(name);
+    if args.is_empty() {
+        return format!("EXECUTE {name}");
+    }
+    let args: Vec<String> = args
+        .iter()
+        .map(|arg| match arg {
+            Some(value) => format!("'{}'", value.replace('\'', "''")),
+            None => "NULL".to_string(),
+        })
+        .collect();
+    format!("EXECUTE {name}({})", args.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> PreparedLibrary {
+        PreparedLibrary::from_config(&BTreeMap::from([
+            (
+                "user_by_email".to_string(),
+                "SELECT * FROM users WHERE email = $1;".to_string(),
+            ),
+            (
+                "users_since".to_string(),
+                "SELECT * FROM users WHERE created_at >= $2 AND note <> '$9' AND id > $1"
+                    .to_string(),
+            ),
+            ("orders".to_string(), "SELECT * FROM orders".to_string()),
+        ]))
+    }
+
+    #[test]
+    fn arguments_are_sent_as_literals_or_null() {
+        let args = parse_args("42  'O''Reilly Media' null ''").unwrap();
+        assert_eq!(
+            args,
+            [
+                Some("42".to_string()),
+                Some("O'Reilly Media".to_string()),
+                None,
+                Some(String::new())
+            ]
+        );
+        assert_eq!(
+            execute_sql("user_by_email", &args),
+            "EXECUTE user_by_email('42', 'O''Reilly Media', NULL, '')"
+        );
+        assert_eq!(execute_sql("Orders", &[]), "EXECUTE \"Orders\"");
+        assert!(parse_args("'open").is_err());
+    }
+
+    #[test]
+    fn signatures_use_server_types_once_prepared() {
+        let mut library = library();
+        assert_eq!(
+            library.get("user_by_email").unwrap().sql,
+            "SELECT * FROM users WHERE email = $1"
+        );
+        assert_eq!(
+            library.signature("users_since").as_deref(),
+            Some("users_since($1, $2)")
+        );
+        assert_eq!(library.signature("orders").as_deref(), Some("orders()"));
+
+        library.set_param_types("users_since", vec!["integer".into(), "date".into()]);
+        assert_eq!(
+            library.signature("users_since").as_deref(),
+            Some("users_since(integer, date)")
+        );
+        library.reset_connection();
+        assert_eq!(library.get("users_since").unwrap().param_types, None);
+    }
+
+    #[test]
+    fn names_complete_in_exec_and_prepare() {
+        let library = library();
+        assert_eq!(
+            library.complete_command("exec o").as_deref(),
+            Some("exec orders ")
+        );
+        assert_eq!(
+            library.complete_command("prepare u").as_deref(),
+            Some("prepare user")
+        );
+        assert_eq!(library.complete_command("exec user"), None);
+        assert_eq!(library.complete_command("exec orders 1"), None);
+        assert_eq!(library.complete_command("export o"), None);
+
+        assert_eq!(
+            library.command_hint("exec user").as_deref(),
+            Some("user_by_email  users_since")
+        );
+        assert_eq!(
+            library
+                .command_hint("exec user_by_email 'a@b.c'")
+                .as_deref(),
+            Some("user_by_email($1)")
+        );
+        assert_eq!(library.command_hint("exec nope 1"), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! `$1` and `:name` parameters of an editor query.
+//!
+//! A query with placeholders asks for their values before it runs, and the
+//! values are bound over the extended protocol instead of being spliced into
+//! the SQL. `:name` placeholders are numbered after the highest `$n`. Anything
+//! inside literals, quoted identifiers and comments is left alone, and so are
+//! `::` casts.
+
+use std::ops::Range;
+
+use tokio_postgres::types::Type;
+
+use super::sql_lexer::{scan, SqlSegmentKind};
+use crate::ui::quote_identifier;
+
+/// A parameter the query needs a value for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QueryParam {
+    Positional(usize),
+    Named(String),
+}
+
+impl QueryParam {
+    /// `$1` or `:name`, as written in the query.
+    pub(crate) fn label(&self) -> String {
+        match self {
+            QueryParam::Positional(n) => format!("${n}"),
+            QueryParam::Named(name) => format!(":{name}"),
+        }
+    }
+}
+
+struct Occurrence {
+    range: Range<usize>,
+    param: QueryParam,
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Every placeholder in `sql` with its byte range, in order.
+fn occurrences(sql: &str) -> Vec<Occurrence> {
+    if !sql.contains(['$', ':']) {
+        return Vec::new();
+    }
+    // An unterminated literal (e.g. while typing) has nothing to bind.
+    let Ok(segments) = scan(sql) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for segment in segments
+        .iter()
+        .filter(|segment| segment.kind == SqlSegmentKind::Code)
+    {
+        let code = &sql[segment.range.clone()];
+        let mut previous = None;
+        for (offset, c) in code.char_indices() {
+            let follows_word = previous.is_some_and(|p: char| is_identifier_char(p) || p == ':');
+            previous = Some(c);
+            if follows_word || !matches!(c, '$' | ':') {
+                continue;
+            }
+            let rest = &code[offset + 1..];
+            let len = if c == '$' {
+                rest.find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len())
+            } else if rest.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+                rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len())
+            } else {
+                0
+            };
+            let token = &rest[..len];
+            let param = match c {
+                '$' => match token.parse::<usize>() {
+                    Ok(n) if n > 0 => QueryParam::Positional(n),
+                    _ => continue,
+                },
+                _ if len > 0 => QueryParam::Named(token.to_string()),
+                _ => continue,
+            };
+            let start = segment.range.start + offset;
+            found.push(Occurrence {
+                range: start..start + 1 + len,
+                param,
+            });
+        }
+    }
+    found
+}
+
+/// The parameters `sql` needs, in binding order: `$1` up to the highest `$n`
+/// (including unused ones in between), then each `:name` as first used.
+pub(crate) fn params(sql: &str) -> Vec<QueryParam> {
+    let occurrences = occurrences(sql);
+    let highest = occurrences
+        .iter()
+        .filter_map(|occurrence| match occurrence.param {
+            QueryParam::Positional(n) => Some(n),
+            QueryParam::Named(_) => None,
+        })
+        .max()
+        .unwrap_or(0);
+    let mut params: Vec<QueryParam> = (1..=highest).map(QueryParam::Positional).collect();
+    for occurrence in occurrences {
+        if matches!(occurrence.param, QueryParam::Named(_)) && !params.contains(&occurrence.param) {
+            params.push(occurrence.param);
+        }
+    }
+    params
+}
+
+fn rewrite(sql: &str, mut replacement: impl FnMut(&QueryParam) -> String) -> String {
+    let mut rewritten = String::with_capacity(sql.len());
+    let mut copied = 0;
+    for occurrence in occurrences(sql) {
+        rewritten.push_str(&sql[copied..occurrence.range.start]);
+        rewritten.push_str(&replacement(&occurrence.param));
+        copied = occurrence.range.end;
+    }
+    rewritten.push_str(&sql[copied..]);
+    rewritten
+}
+
+/// `sql` with each `:name` replaced by its `$n` in `params`.
+pub(crate) fn numbered_sql(sql: &str, params: &[QueryParam]) -> String {
+    rewrite(sql, |param| match param {
+        QueryParam::Positional(n) => format!("${n}"),
+        QueryParam::Named(_) => {
+            let n = params.iter().position(|p| p == param).unwrap_or(0) + 1;
+            format!("${n}")
+        }
+    })
+}
+
+/// Numbered `sql` whose parameters are all sent as text, each cast to the
+/// type the server inferred for it. This is how a quoted literal in its place
+/// would be read, so any type with a text form can be bound.
+pub(crate) fn text_cast_sql(numbered: &str, types: &[Type]) -> String {
+    rewrite(numbered, |param| {
+        let label = param.label();
+        let ty = match param {
+            QueryParam::Positional(n) => n.checked_sub(1).and_then(|index| types.get(index)),
+            QueryParam::Named(_) => None,
+        };
+        match ty {
+            Some(ty) if *ty != Type::UNKNOWN => format!(
+                "CAST({label} AS {}.{})",
+                quote_identifier(ty.schema()),
+                quote_identifier(ty.name())
+            ),
+            _ => label,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_outside_literals_and_casts_are_found() {
+        let sql = "SELECT $2, :name::text, ':skip', \"$9\" -- :also\n\
+                   FROM t WHERE a = :name AND b = :other AND c$1 = x::int";
+        assert_eq!(
+            params(sql),
+            [
+                QueryParam::Positional(1),
+                QueryParam::Positional(2),
+                QueryParam::Named("name".to_string()),
+                QueryParam::Named("other".to_string()),
+            ]
+        );
+        assert_eq!(params("SELECT $$ $1 $$, 1 := 2"), []);
+        assert_eq!(params("SELECT 'open"), []);
+        assert_eq!(
+            params("SELECT $1 FROM t").first().map(QueryParam::label),
+            Some("$1".to_string())
+        );
+    }
+
+    #[test]
+    fn named_placeholders_are_numbered_after_positional_ones() {
+        let sql = "SELECT * FROM t WHERE a = :a AND b = $1 AND c = :a::int AND d = :b";
+        let params = params(sql);
+        let numbered = numbered_sql(sql, &params);
+        assert_eq!(
+            numbered,
+            "SELECT * FROM t WHERE a = $2 AND b = $1 AND c = $2::int AND d = $3"
+        );
+        assert_eq!(
+            text_cast_sql(&numbered, &[Type::INT4, Type::TEXT, Type::UNKNOWN]),
+            "SELECT * FROM t WHERE a = CAST($2 AS pg_catalog.text) AND b = \
+             CAST($1 AS pg_catalog.int4) AND c = CAST($2 AS pg_catalog.text)::int AND d = $3"
+        );
+    }
+}
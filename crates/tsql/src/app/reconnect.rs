@@ -0,0 +1,67 @@
+//! Reconnecting after the main connection is lost.
+//!
+//! Attempts are spaced by a delay that doubles each time, up to a cap, and
+//! stop after `connection.reconnect_attempts`. LISTEN channels belong to the
+//! lost session, so they are issued again once a new one is open.
+
+use std::time::Duration;
+
+/// A reconnect in progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Reconnect {
+    /// URL of the lost connection.
+    pub(crate) conn_str: String,
+    /// Saved connection it was opened from, if any.
+    pub(crate) name: Option<String>,
+    /// Channels listened to on the lost connection.
+    pub(crate) channels: Vec<String>,
+    /// Attempts started or scheduled so far.
+    pub(crate) attempt: u32,
+    pub(crate) max_attempts: u32,
+}
+
+impl Reconnect {
+    pub(crate) fn new(
+        conn_str: String,
+        name: Option<String>,
+        channels: Vec<String>,
+        max_attempts: u32,
+    ) -> Self {
+        Self {
+            conn_str,
+            name,
+            channels,
+            attempt: 0,
+            max_attempts,
+        }
+    }
+
+    /// Status line text, e.g. `RECONNECTING (2/5)`.
+    pub(crate) fn label(&self) -> String {
+        format!("RECONNECTING ({}/{})", self.attempt, self.max_attempts)
+    }
+}
+
+/// Wait before attempt `attempt` (1-based): `initial`, doubled for every
+/// attempt before it, never more than `max`.
+pub(crate) fn backoff(attempt: u32, initial: Duration, max: Duration) -> Duration {
+    let doublings = attempt.saturating_sub(1).min(31);
+    initial.saturating_mul(1 << doublings).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_double_up_to_the_cap() {
+        let initial = Duration::from_millis(500);
+        let max = Duration::from_secs(5);
+        let delays: Vec<u128> = (1..=6)
+            .map(|attempt| backoff(attempt, initial, max).as_millis())
+            .collect();
+        assert_eq!(delays, [500, 1000, 2000, 4000, 5000, 5000]);
+        assert_eq!(backoff(200, initial, max), max);
+        assert_eq!(backoff(0, initial, max), initial);
+    }
+}
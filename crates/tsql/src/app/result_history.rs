@@ -0,0 +1,272 @@
+//! Recent Classic result sets kept in memory for `[g` / `]g` and `:results`.
+//!
+//! Every new Classic result is pushed as a snapshot. Flipping to an older
+//! snapshot restores its grid, originating query and transform state without
+//! running anything against the database.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::result_info::ResultOrigin;
+use super::result_transform::ResultTransform;
+use crate::ui::{GridModel, GridState};
+
+/// A Classic result set together with the query that produced it.
+#[derive(Clone)]
+pub(crate) struct ResultSnapshot {
+    /// SQL that produced the grid, including any result transform.
+    pub(crate) query: String,
+    pub(crate) grid: GridModel,
+    pub(crate) grid_state: GridState,
+    pub(crate) command_tag: Option<String>,
+    pub(crate) elapsed: Option<Duration>,
+    /// Untransformed query and headers, so sorting/filtering keeps working.
+    pub(crate) base_query: Option<String>,
+    pub(crate) base_headers: Vec<String>,
+    pub(crate) transform: ResultTransform,
+    pub(crate) origin: ResultOrigin,
+}
+
+impl ResultSnapshot {
+    /// One-line form of the originating query for labels and pickers.
+    pub(crate) fn summary(&self, max_chars: usize) -> String {
+        let query = self.base_query.as_deref().unwrap_or(&self.query);
+        let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        if query.chars().count() > max_chars {
+            let truncated: String = query.chars().take(max_chars.saturating_sub(1)).collect();
+            format!("{truncated}…")
+        } else {
+            query
+        }
+    }
+}
+
+/// A `:results` picker row pointing back into the history.
+#[derive(Clone, Debug)]
+pub(crate) struct ResultHistoryEntry {
+    pub(crate) index: usize,
+    label: String,
+}
+
+impl ResultHistoryEntry {
+    pub(crate) fn display(&self) -> String {
+        self.label.clone()
+    }
+}
+
+/// Bounded, oldest-first list of result snapshots with the one on screen marked.
+pub(crate) struct ResultHistory {
+    entries: VecDeque<ResultSnapshot>,
+    capacity: usize,
+    current: Option<usize>,
+}
+
+impl ResultHistory {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            current: None,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Index of the snapshot currently shown in the grid.
+    pub(crate) fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&ResultSnapshot> {
+        self.entries.get(index)
+    }
+
+    /// Records a new result as the newest, current snapshot.
+    pub(crate) fn push(&mut self, snapshot: ResultSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.push_back(snapshot);
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+        self.current = Some(self.entries.len() - 1);
+    }
+
+    /// Replaces the current snapshot, e.g. after a refresh or a loaded page.
+    pub(crate) fn replace_current(&mut self, snapshot: ResultSnapshot) {
+        match self.current.and_then(|index| self.entries.get_mut(index)) {
+            Some(entry) => *entry = snapshot,
+            None => self.push(snapshot),
+        }
+    }
+
+    /// Stores the live grid of the shown snapshot, which drifts from the
+    /// recorded one as pages load, cells are edited and the cursor moves.
+    pub(crate) fn store_current(
+        &mut self,
+        grid: GridModel,
+        grid_state: GridState,
+        command_tag: Option<String>,
+    ) {
+        if let Some(entry) = self.current.and_then(|index| self.entries.get_mut(index)) {
+            entry.grid = grid;
+            entry.grid_state = grid_state;
+            entry.command_tag = command_tag;
+        }
+    }
+
+    /// Index `delta` steps away from the current snapshot, if it exists.
+    pub(crate) fn offset(&self, delta: isize) -> Option<usize> {
+        // A detached grid sits just past the newest snapshot.
+        let from = self.current.unwrap_or(self.entries.len());
+        let target = from.checked_add_signed(delta)?;
+        (target < self.entries.len()).then_some(target)
+    }
+
+    /// Forgets which snapshot is shown, e.g. while the grid holds meta output.
+    pub(crate) fn detach(&mut self) {
+        self.current = None;
+    }
+
+    /// Picker rows, newest first, with the shown snapshot marked.
+    pub(crate) fn picker_entries(&self) -> Vec<ResultHistoryEntry> {
+        let total = self.entries.len();
+        self.entries
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, snapshot)| {
+                let marker = if self.current == Some(index) {
+                    '*'
+                } else {
+                    ' '
+                };
+                let rows = snapshot
+                    .command_tag
+                    .clone()
+                    .unwrap_or_else(|| format!("{} rows", snapshot.grid.rows.len()));
+                ResultHistoryEntry {
+                    index,
+                    label: format!(
+                        "{marker}{}/{total}  {rows}  {}",
+                        index + 1,
+                        snapshot.summary(80)
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// The shown snapshot, for facts learned after it was recorded.
+    pub(crate) fn current_mut(&mut self) -> Option<&mut ResultSnapshot> {
+        self.current.and_then(|index| self.entries.get_mut(index))
+    }
+
+    /// Marks `index` as shown and returns its snapshot.
+    pub(crate) fn select(&mut self, index: usize) -> Option<&ResultSnapshot> {
+        let snapshot = self.entries.get(index)?;
+        self.current = Some(index);
+        Some(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(query: &str) -> ResultSnapshot {
+        ResultSnapshot {
+            query: query.to_string(),
+            grid: GridModel::new(vec!["n".to_string()], vec![vec!["1".to_string()]]),
+            grid_state: GridState::default(),
+            command_tag: None,
+            elapsed: None,
+            base_query: Some(query.to_string()),
+            base_headers: vec!["n".to_string()],
+            transform: ResultTransform::default(),
+            origin: ResultOrigin::default(),
+        }
+    }
+
+    #[test]
+    fn push_keeps_the_newest_entries_up_to_capacity() {
+        let mut history = ResultHistory::new(2);
+        history.push(snapshot("SELECT 1"));
+        history.push(snapshot("SELECT 2"));
+        history.push(snapshot("SELECT 3"));
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.current(), Some(1));
+        let queries: Vec<_> = history.entries.iter().map(|s| s.query.as_str()).collect();
+        assert_eq!(queries, ["SELECT 2", "SELECT 3"]);
+    }
+
+    #[test]
+    fn zero_capacity_keeps_nothing() {
+        let mut history = ResultHistory::new(0);
+        history.push(snapshot("SELECT 1"));
+        assert_eq!(history.len(), 0);
+        assert_eq!(history.offset(-1), None);
+    }
+
+    #[test]
+    fn offset_and_select_walk_between_snapshots() {
+        let mut history = ResultHistory::new(5);
+        history.push(snapshot("SELECT 1"));
+        history.push(snapshot("SELECT 2"));
+
+        assert_eq!(history.offset(1), None);
+        let previous = history.offset(-1).unwrap();
+        assert_eq!(history.select(previous).unwrap().query, "SELECT 1");
+        assert_eq!(history.current(), Some(0));
+        assert_eq!(history.offset(-1), None);
+        assert_eq!(history.offset(1), Some(1));
+
+        let state = GridState {
+            cursor_row: 3,
+            ..Default::default()
+        };
+        history.store_current(
+            GridModel::new(vec!["n".to_string()], vec![vec!["9".to_string()]]),
+            state,
+            Some("SELECT 9".to_string()),
+        );
+        let stored = history.get(0).unwrap();
+        assert_eq!(stored.grid.cell(0, 0), Some("9"));
+        assert_eq!(stored.grid_state.cursor_row, 3);
+        assert_eq!(stored.query, "SELECT 1");
+
+        history.replace_current(snapshot("SELECT 1 -- refreshed"));
+        assert_eq!(history.get(0).unwrap().query, "SELECT 1 -- refreshed");
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn picker_entries_list_newest_first_and_mark_the_current_one() {
+        let mut history = ResultHistory::new(5);
+        history.push(snapshot("SELECT 1"));
+        history.push(snapshot("SELECT 2"));
+        history.select(0);
+
+        let labels: Vec<_> = history
+            .picker_entries()
+            .iter()
+            .map(ResultHistoryEntry::display)
+            .collect();
+        assert_eq!(labels, [" 2/2  1 rows  SELECT 2", "*1/2  1 rows  SELECT 1"]);
+
+        history.detach();
+        assert_eq!(history.offset(-1), Some(1));
+        assert_eq!(history.picker_entries()[1].index, 0);
+    }
+
+    #[test]
+    fn summary_collapses_whitespace_and_truncates() {
+        let snap = snapshot("SELECT *\n  FROM   users\nWHERE id = 1");
+        assert_eq!(snap.summary(80), "SELECT * FROM users WHERE id = 1");
+        assert_eq!(snap.summary(10), "SELECT * …");
+    }
+}
@@ -0,0 +1,268 @@
+//! The `:info` panel describing the Classic result on screen.
+//!
+//! Result history keeps several results around, so the panel spells out
+//! where the shown one came from: its SQL, connection, timing, how many rows
+//! were loaded and the declared type and nullability of each column.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::ui::GridModel;
+
+/// SQL lines shown before the rest is elided.
+const MAX_SQL_LINES: usize = 5;
+
+/// Where and when a Classic result was produced, kept with its snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ResultOrigin {
+    /// Saved connection name, else the sanitized connection URL.
+    pub(crate) connection: Option<String>,
+    pub(crate) finished_at: Option<DateTime<Local>>,
+    /// The row limit (`connection.max_rows`) cut the result short.
+    pub(crate) truncated: bool,
+    /// How many rows the whole result has, when known.
+    pub(crate) total_rows: Option<TotalRows>,
+}
+
+/// The size of a result beyond the rows that were loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TotalRows {
+    /// The source table's `reltuples`, as of its last ANALYZE.
+    Estimated(u64),
+    /// A `COUNT(*)` of the query, run by `:count`.
+    Exact(u64),
+}
+
+/// The row count in the results title, e.g. `2000 of ~1.2M rows` while only
+/// part of the result is loaded.
+pub(crate) fn rows_badge(loaded: usize, complete: bool, total: Option<TotalRows>) -> String {
+    match total {
+        _ if complete => format!("{loaded} rows"),
+        Some(TotalRows::Exact(total)) => format!("{loaded} of {total} rows"),
+        Some(TotalRows::Estimated(total)) if total > loaded as u64 => {
+            format!("{loaded} of ~{} rows", abbreviate_count(total))
+        }
+        _ => format!("{loaded} rows"),
+    }
+}
+
+/// `1234567` as `1.2M`: estimates are only good to a couple of digits.
+fn abbreviate_count(count: u64) -> String {
+    for (scale, suffix) in [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")] {
+        if count >= scale {
+            let value = count as f64 / scale as f64;
+            return if value < 10.0 {
+                format!("{value:.1}{suffix}")
+            } else {
+                format!("{value:.0}{suffix}")
+            };
+        }
+    }
+    count.to_string()
+}
+
+/// Everything the panel shows about the current result.
+pub(crate) struct ResultInfo<'a> {
+    pub(crate) query: Option<&'a str>,
+    pub(crate) origin: &'a ResultOrigin,
+    pub(crate) elapsed: Option<Duration>,
+    pub(crate) command_tag: Option<&'a str>,
+    /// A cursor still holds rows that load on scroll.
+    pub(crate) more_rows: bool,
+    pub(crate) grid: &'a GridModel,
+}
+
+impl ResultInfo<'_> {
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut sql = self
+            .query
+            .unwrap_or("-")
+            .trim()
+            .lines()
+            .filter(|line| !line.trim().is_empty());
+        for (index, line) in sql.by_ref().take(MAX_SQL_LINES).enumerate() {
+            let label = if index == 0 { "SQL" } else { "" };
+            lines.push(format!("{label:<11}{}", line.trim_end()));
+        }
+        let elided = sql.count();
+        if elided > 0 {
+            lines.push(format!("{:<11}… {elided} more lines", ""));
+        }
+
+        lines.push(format!(
+            "{:<11}{}",
+            "Connection",
+            self.origin.connection.as_deref().unwrap_or("-")
+        ));
+        let finished = match (self.origin.finished_at, self.elapsed) {
+            (Some(at), Some(elapsed)) => format!(
+                "{} in {}ms",
+                at.format("%Y-%m-%d %H:%M:%S"),
+                elapsed.as_millis()
+            ),
+            (Some(at), None) => at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            (None, Some(elapsed)) => format!("in {}ms", elapsed.as_millis()),
+            (None, None) => "-".to_string(),
+        };
+        lines.push(format!("{:<11}{finished}", "Finished"));
+
+        let rows = self.grid.rows.len();
+        let rows = if self.origin.truncated {
+            format!("{rows} returned, truncated at the row limit")
+        } else if self.more_rows {
+            format!("{rows} loaded, more on scroll")
+        } else {
+            format!("{rows} returned")
+        };
+        lines.push(format!("{:<11}{rows}", "Rows"));
+        match self.origin.total_rows {
+            Some(TotalRows::Estimated(total)) => {
+                lines.push(format!("{:<11}~{total} (table estimate)", "Total"));
+            }
+            Some(TotalRows::Exact(total)) => {
+                lines.push(format!("{:<11}{total} (counted)", "Total"));
+            }
+            None => {}
+        }
+        if let Some(tag) = self.command_tag {
+            lines.push(format!("{:<11}{tag}", "Command"));
+        }
+        if let Some(table) = self.grid.source_table.as_deref() {
+            lines.push(format!("{:<11}{table}", "Table"));
+        }
+
+        lines.push(String::new());
+        let name_width = self
+            .grid
+            .headers
+            .iter()
+            .map(|header| header.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Column".len());
+        let type_width = self
+            .grid
+            .col_types
+            .iter()
+            .map(|col_type| col_type.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Type".len());
+        lines.push(format!(
+            "{:<name_width$}  {:<type_width$}  Null",
+            "Column", "Type"
+        ));
+        for (col, header) in self.grid.headers.iter().enumerate() {
+            let col_type = self
+                .grid
+                .col_types
+                .get(col)
+                .filter(|col_type| !col_type.is_empty())
+                .map_or("?", String::as_str);
+            let nullable = match self.grid.col_nullable.get(col).copied().flatten() {
+                Some(true) => "yes",
+                Some(false) => "no",
+                None => "?",
+            };
+            lines.push(format!(
+                "{header:<name_width$}  {col_type:<type_width$}  {nullable}"
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn lines_describe_the_query_origin_rows_and_columns() {
+        let mut grid = GridModel::new(
+            vec!["id".to_string(), "total".to_string()],
+            vec![vec!["1".to_string(), "9.50".to_string()]],
+        )
+        .with_source_table(Some("public.orders".to_string()))
+        .with_col_types(vec!["integer".to_string(), String::new()]);
+        grid.col_nullable = vec![Some(false), None];
+        let origin = ResultOrigin {
+            connection: Some("prod".to_string()),
+            finished_at: Some(Local.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap()),
+            truncated: true,
+            total_rows: Some(TotalRows::Exact(1)),
+        };
+        let info = ResultInfo {
+            query: Some("SELECT id, total\nFROM orders\n"),
+            origin: &origin,
+            elapsed: Some(Duration::from_millis(42)),
+            command_tag: Some("1 rows"),
+            more_rows: false,
+            grid: &grid,
+        };
+
+        assert_eq!(
+            info.lines(),
+            [
+                "SQL        SELECT id, total",
+                "           FROM orders",
+                "Connection prod",
+                "Finished   2026-10-16 09:30:00 in 42ms",
+                "Rows       1 returned, truncated at the row limit",
+                "Total      1 (counted)",
+                "Command    1 rows",
+                "Table      public.orders",
+                "",
+                "Column  Type     Null",
+                "id      integer  no",
+                "total   ?        ?",
+            ]
+        );
+    }
+
+    #[test]
+    fn long_queries_are_elided() {
+        let grid = GridModel::empty();
+        let origin = ResultOrigin::default();
+        let query = (1..=8)
+            .map(|n| format!("SELECT {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let info = ResultInfo {
+            query: Some(&query),
+            origin: &origin,
+            elapsed: None,
+            command_tag: None,
+            more_rows: true,
+            grid: &grid,
+        };
+        let lines = info.lines();
+        assert_eq!(lines[5], "           … 3 more lines");
+        assert_eq!(lines[6], "Connection -");
+        assert_eq!(lines[7], "Finished   -");
+        assert_eq!(lines[8], "Rows       0 loaded, more on scroll");
+    }
+
+    #[test]
+    fn rows_badge_shows_the_total_while_rows_are_missing() {
+        let estimate = Some(TotalRows::Estimated(1_234_567));
+        assert_eq!(rows_badge(2000, false, estimate), "2000 of ~1.2M rows");
+        assert_eq!(
+            rows_badge(2000, false, Some(TotalRows::Estimated(45_000))),
+            "2000 of ~45K rows"
+        );
+        assert_eq!(
+            rows_badge(2000, false, Some(TotalRows::Exact(1_234_567))),
+            "2000 of 1234567 rows"
+        );
+        // A stale estimate below what is already loaded says nothing useful.
+        assert_eq!(
+            rows_badge(2000, false, Some(TotalRows::Estimated(10))),
+            "2000 rows"
+        );
+        assert_eq!(rows_badge(2000, false, None), "2000 rows");
+        assert_eq!(rows_badge(12, true, estimate), "12 rows");
+    }
+}
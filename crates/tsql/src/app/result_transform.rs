@@ -198,14 +198,14 @@ pub(crate) enum OrderDirection {
 }
 
 impl OrderDirection {
-    fn sql(self) -> &'static str {
+    pub(crate) fn sql(self) -> &'static str {
         match self {
             Self::Asc => "ASC",
             Self::Desc => "DESC",
         }
     }
 
-    fn label(self) -> &'static str {
+    pub(crate) fn label(self) -> &'static str {
         match self {
             Self::Asc => "asc",
             Self::Desc => "desc",
@@ -305,7 +305,22 @@ pub(crate) fn compile_result_transform(
     Ok(sql)
 }
 
-fn validate_transform(column_count: usize, spec: &ResultTransform) -> Result<(), String> {
+/// Wraps one read-only, row-producing query in an exact `COUNT(*)` of its rows.
+pub(crate) fn compile_row_count(base_sql: &str) -> Result<String, String> {
+    let source = sql_lexer::single_statement(base_sql)?;
+    if !pg_snapshot::is_snapshot_candidate(source) {
+        return Err("only a single read-only, row-returning query can be counted".to_string());
+    }
+    Ok(format!(
+        "SELECT COUNT(*)\nFROM (\n{source}\n) AS {}",
+        quote_identifier("__tsql_result")
+    ))
+}
+
+pub(crate) fn validate_transform(
+    column_count: usize,
+    spec: &ResultTransform,
+) -> Result<(), String> {
     let validate = |ordinal: usize| {
         if ordinal >= column_count {
             Err(format!(
@@ -663,6 +678,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn row_count_wraps_only_read_only_sources() {
+        assert_eq!(
+            compile_row_count("SELECT * FROM users WHERE active;").unwrap(),
+            "SELECT COUNT(*)\nFROM (\nSELECT * FROM users WHERE active\n) AS \"__tsql_result\""
+        );
+        assert!(compile_row_count("DELETE FROM users RETURNING *").is_err());
+        assert!(compile_row_count("SELECT 1; SELECT 2").is_err());
+    }
+
     #[test]
     fn accepts_valid_row_returning_expression_shapes() {
         for source in [
@@ -0,0 +1,167 @@
+//! Server notices for the messages pane below the classic results.
+//!
+//! PostgreSQL sends `RAISE NOTICE` output and warnings such as truncated
+//! identifiers outside of query results, on the connection itself. They are
+//! collected per query and listed beside the results it produced.
+
+use tokio_postgres::error::DbError;
+
+/// Notices kept per query; later ones are counted but dropped.
+const CAPACITY: usize = 500;
+/// Pane rows shown while expanded, borders excluded.
+const MAX_VISIBLE: usize = 6;
+
+/// One `NOTICE`, `WARNING`, `INFO`, ... message from the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerNotice {
+    pub severity: String,
+    pub message: String,
+    pub detail: Option<String>,
+    pub hint: Option<String>,
+}
+
+impl ServerNotice {
+    pub fn from_db_error(notice: &DbError) -> Self {
+        Self {
+            severity: notice.severity().to_string(),
+            message: notice.message().to_string(),
+            detail: notice.detail().map(str::to_string),
+            hint: notice.hint().map(str::to_string),
+        }
+    }
+
+    /// `SEVERITY: message`, followed by indented detail and hint lines.
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{}: {}", self.severity, self.message)];
+        lines.extend(
+            self.detail
+                .iter()
+                .map(|detail| format!("  DETAIL: {detail}")),
+        );
+        lines.extend(self.hint.iter().map(|hint| format!("  HINT: {hint}")));
+        lines
+    }
+}
+
+/// Notices of the current classic query and whether the pane is collapsed.
+#[derive(Debug, Default)]
+pub(crate) struct ServerNotices {
+    notices: Vec<ServerNotice>,
+    dropped: usize,
+    pub(crate) collapsed: bool,
+}
+
+impl ServerNotices {
+    pub(crate) fn push(&mut self, notice: ServerNotice) {
+        if self.notices.len() == CAPACITY {
+            self.dropped += 1;
+        } else {
+            self.notices.push(notice);
+        }
+    }
+
+    /// Forgets the notices of the previous query; collapsing is kept.
+    pub(crate) fn clear(&mut self) {
+        self.notices.clear();
+        self.dropped = 0;
+    }
+
+    /// Pane title, e.g. `Messages (2 notices, 1 warning)`.
+    pub(crate) fn title(&self) -> String {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for notice in &self.notices {
+            let severity = notice.severity.to_lowercase();
+            match counts.iter_mut().find(|(name, _)| *name == severity) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((severity, 1)),
+            }
+        }
+        let summary = counts
+            .iter()
+            .map(|(name, count)| {
+                if *count == 1 {
+                    format!("1 {name}")
+                } else {
+                    format!("{count} {name}s")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.dropped > 0 {
+            format!("Messages ({summary}, {} more dropped)", self.dropped)
+        } else {
+            format!("Messages ({summary})")
+        }
+    }
+
+    /// Pane lines, newest last.
+    pub(crate) fn lines(&self) -> Vec<String> {
+        self.notices.iter().flat_map(ServerNotice::lines).collect()
+    }
+
+    /// Height of the pane in rows, borders included; 0 without notices.
+    pub(crate) fn height(&self) -> u16 {
+        if self.notices.is_empty() {
+            0
+        } else if self.collapsed {
+            1
+        } else {
+            self.lines().len().min(MAX_VISIBLE) as u16 + 2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notice(severity: &str, message: &str) -> ServerNotice {
+        ServerNotice {
+            severity: severity.to_string(),
+            message: message.to_string(),
+            detail: None,
+            hint: None,
+        }
+    }
+
+    #[test]
+    fn notices_are_summarized_and_listed_with_details() {
+        let mut notices = ServerNotices::default();
+        assert_eq!(notices.height(), 0);
+
+        notices.push(notice("NOTICE", "step 1"));
+        notices.push(ServerNotice {
+            hint: Some("Use a shorter name.".to_string()),
+            ..notice("NOTICE", "identifier will be truncated")
+        });
+        notices.push(notice("WARNING", "there is no transaction in progress"));
+        assert_eq!(notices.title(), "Messages (2 notices, 1 warning)");
+        assert_eq!(
+            notices.lines(),
+            [
+                "NOTICE: step 1",
+                "NOTICE: identifier will be truncated",
+                "  HINT: Use a shorter name.",
+                "WARNING: there is no transaction in progress",
+            ]
+        );
+        assert_eq!(notices.height(), 6);
+
+        notices.collapsed = true;
+        assert_eq!(notices.height(), 1);
+        notices.clear();
+        assert!(notices.notices.is_empty());
+        assert!(notices.collapsed);
+    }
+
+    #[test]
+    fn notices_past_the_capacity_are_counted() {
+        let mut notices = ServerNotices::default();
+        for index in 0..CAPACITY + 2 {
+            notices.push(notice("NOTICE", &index.to_string()));
+        }
+        assert_eq!(notices.lines().len(), CAPACITY);
+        assert_eq!(notices.title(), "Messages (500 notices, 2 more dropped)");
+        assert_eq!(notices.height(), MAX_VISIBLE as u16 + 2);
+    }
+}
@@ -0,0 +1,326 @@
+//! `:source <file.sql>`: running a SQL file statement by statement, like
+//! psql's `\i`.
+//!
+//! The file is split client-side so each statement reports its own outcome.
+//! After a failure the run either stops or keeps going (`sql.source_on_error`),
+//! and `sql.source_single_transaction` wraps it in a transaction that any
+//! failure rolls back.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio_postgres::{Client, SimpleQueryMessage};
+
+use super::sql_lexer::split_statements;
+use crate::config::SourceOnError;
+use crate::ui::GridModel;
+use crate::util::format_pg_error;
+
+/// Characters of each statement shown in the report grid.
+const PREVIEW_CHARS: usize = 200;
+
+/// One statement of a sourced file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SourceStatement {
+    /// 1-based line the statement (or its leading comment) starts on.
+    pub(crate) line: usize,
+    pub(crate) sql: String,
+}
+
+/// Splits a file into statements, keeping the line each one starts on.
+pub(crate) fn source_statements(text: &str) -> Result<Vec<SourceStatement>, String> {
+    Ok(split_statements(text)?
+        .into_iter()
+        .map(|sql| {
+            // The statements are slices of `text`, so their offset locates them.
+            let offset = sql.as_ptr() as usize - text.as_ptr() as usize;
+            SourceStatement {
+                line: text[..offset].matches('\n').count() + 1,
+                sql: sql.to_string(),
+            }
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SourceOptions {
+    pub(crate) on_error: SourceOnError,
+    pub(crate) single_transaction: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StatementOutcome {
+    /// Succeeded, affecting or returning `rows` rows.
+    Ok {
+        rows: u64,
+    },
+    Failed(String),
+    /// Skipped after a failure or cancel.
+    NotRun,
+}
+
+/// How a single-transaction run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SourceTransaction {
+    /// The run was not wrapped in a transaction.
+    None,
+    Committed,
+    RolledBack,
+    /// `COMMIT` failed (e.g. a deferred constraint), so nothing was kept.
+    CommitFailed(String),
+}
+
+/// Outcome of every statement of a `:source` run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceReport {
+    /// File name shown in the status line.
+    pub(crate) label: String,
+    pub(crate) statements: Vec<(SourceStatement, StatementOutcome)>,
+    pub(crate) transaction: SourceTransaction,
+    pub(crate) cancelled: bool,
+}
+
+impl SourceReport {
+    /// Status line, e.g. `Sourced setup.sql: 3 ok, 1 failed, 2 not run (rolled back)`.
+    pub(crate) fn summary(&self) -> String {
+        let count = |matches: fn(&StatementOutcome) -> bool| {
+            self.statements
+                .iter()
+                .filter(|(_, outcome)| matches(outcome))
+                .count()
+        };
+        let mut parts = vec![format!(
+            "{} ok",
+            count(|outcome| matches!(outcome, StatementOutcome::Ok { .. }))
+        )];
+        let failed = count(|outcome| matches!(outcome, StatementOutcome::Failed(_)));
+        if failed > 0 {
+            parts.push(format!("{failed} failed"));
+        }
+        let not_run = count(|outcome| *outcome == StatementOutcome::NotRun);
+        if not_run > 0 {
+            parts.push(format!("{not_run} not run"));
+        }
+        let mut summary = format!("Sourced {}: {}", self.label, parts.join(", "));
+        if self.cancelled {
+            summary.push_str(" (cancelled)");
+        }
+        match self.transaction {
+            SourceTransaction::None => {}
+            SourceTransaction::Committed => summary.push_str(" (committed)"),
+            SourceTransaction::RolledBack | SourceTransaction::CommitFailed(_) => {
+                summary.push_str(" (rolled back)")
+            }
+        }
+        summary
+    }
+
+    /// The first error, with the statement number and line it came from.
+    pub(crate) fn first_error(&self) -> Option<String> {
+        let failure =
+            self.statements
+                .iter()
+                .enumerate()
+                .find_map(|(index, (statement, outcome))| match outcome {
+                    StatementOutcome::Failed(error) => Some(format!(
+                        "Statement {} (line {}) of {} failed: {error}",
+                        index + 1,
+                        statement.line,
+                        self.label
+                    )),
+                    _ => None,
+                });
+        match &self.transaction {
+            SourceTransaction::CommitFailed(error) if failure.is_none() => {
+                Some(format!("COMMIT of {} failed: {error}", self.label))
+            }
+            _ => failure,
+        }
+    }
+
+    /// One row per statement: number, line, result and a one-line preview.
+    pub(crate) fn grid(&self) -> GridModel {
+        let headers = ["#", "line", "result", "statement"]
+            .iter()
+            .map(|header| header.to_string())
+            .collect();
+        let rows = self
+            .statements
+            .iter()
+            .enumerate()
+            .map(|(index, (statement, outcome))| {
+                let result = match outcome {
+                    StatementOutcome::Ok { rows: 0 } => "OK".to_string(),
+                    StatementOutcome::Ok { rows } => format!("OK ({rows} rows)"),
+                    StatementOutcome::Failed(error) => {
+                        format!("ERROR: {}", error.lines().next().unwrap_or_default())
+                    }
+                    StatementOutcome::NotRun => "not run".to_string(),
+                };
+                let sql = statement
+                    .sql
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                vec![
+                    (index + 1).to_string(),
+                    statement.line.to_string(),
+                    result,
+                    sql.chars().take(PREVIEW_CHARS).collect(),
+                ]
+            })
+            .collect();
+        GridModel::new(headers, rows)
+    }
+}
+
+/// Runs `statements` in order, calling `progress(index, total)` before each.
+/// Statements after a stopping failure, or after `cancelled` is set, are not
+/// run. Errs only when the wrapping `BEGIN` fails.
+pub(crate) async fn run_source(
+    client: &Client,
+    label: String,
+    statements: Vec<SourceStatement>,
+    options: SourceOptions,
+    cancelled: &AtomicBool,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<SourceReport, String> {
+    if options.single_transaction {
+        client
+            .simple_query("BEGIN")
+            .await
+            .map_err(|e| format_pg_error(&e))?;
+    }
+
+    let total = statements.len();
+    let mut stopped = false;
+    let mut outcomes = Vec::with_capacity(total);
+    for (index, statement) in statements.into_iter().enumerate() {
+        if stopped || cancelled.load(Ordering::Acquire) {
+            outcomes.push((statement, StatementOutcome::NotRun));
+            continue;
+        }
+        progress(index, total);
+        let outcome = match client.simple_query(&statement.sql).await {
+            Ok(messages) => StatementOutcome::Ok {
+                rows: messages
+                    .iter()
+                    .filter_map(|message| match message {
+                        SimpleQueryMessage::CommandComplete(rows) => Some(*rows),
+                        _ => None,
+                    })
+                    .sum(),
+            },
+            Err(e) => {
+                // An aborted transaction rejects everything until it ends.
+                stopped = options.single_transaction || options.on_error == SourceOnError::Stop;
+                StatementOutcome::Failed(format_pg_error(&e))
+            }
+        };
+        outcomes.push((statement, outcome));
+    }
+
+    let cancelled = cancelled.load(Ordering::Acquire);
+    let transaction = if !options.single_transaction {
+        SourceTransaction::None
+    } else if stopped || cancelled {
+        let _ = client.simple_query("ROLLBACK").await;
+        SourceTransaction::RolledBack
+    } else {
+        match client.simple_query("COMMIT").await {
+            Ok(_) => SourceTransaction::Committed,
+            Err(e) => SourceTransaction::CommitFailed(format_pg_error(&e)),
+        }
+    };
+    Ok(SourceReport {
+        label,
+        statements: outcomes,
+        transaction,
+        cancelled,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(line: usize, sql: &str) -> SourceStatement {
+        SourceStatement {
+            line,
+            sql: sql.to_string(),
+        }
+    }
+
+    #[test]
+    fn statements_keep_the_line_they_start_on() {
+        let text = "CREATE TABLE t (id int);\n\n-- seed\nINSERT INTO t\n  VALUES (1);\nSELECT ';'";
+        assert_eq!(
+            source_statements(text).unwrap(),
+            [
+                statement(1, "CREATE TABLE t (id int)"),
+                statement(3, "-- seed\nINSERT INTO t\n  VALUES (1)"),
+                statement(6, "SELECT ';'"),
+            ]
+        );
+        assert!(source_statements("SELECT 'open").is_err());
+    }
+
+    #[test]
+    fn report_summarizes_outcomes_and_lists_each_statement() {
+        let report = SourceReport {
+            label: "seed.sql".to_string(),
+            statements: vec![
+                (
+                    statement(1, "INSERT INTO t\n  VALUES (1), (2)"),
+                    StatementOutcome::Ok { rows: 2 },
+                ),
+                (
+                    statement(3, "INSERT INTO nope VALUES (1)"),
+                    StatementOutcome::Failed(
+                        "relation \"nope\" does not exist [42P01]\nPOSITION: 13".to_string(),
+                    ),
+                ),
+                (statement(4, "DROP TABLE t"), StatementOutcome::NotRun),
+            ],
+            transaction: SourceTransaction::RolledBack,
+            cancelled: false,
+        };
+
+        assert_eq!(
+            report.summary(),
+            "Sourced seed.sql: 1 ok, 1 failed, 1 not run (rolled back)"
+        );
+        assert_eq!(
+            report.first_error().as_deref(),
+            Some(
+                "Statement 2 (line 3) of seed.sql failed: \
+                 relation \"nope\" does not exist [42P01]\nPOSITION: 13"
+            )
+        );
+        let grid = report.grid();
+        assert_eq!(grid.headers, ["#", "line", "result", "statement"]);
+        assert_eq!(
+            grid.rows,
+            [
+                ["1", "1", "OK (2 rows)", "INSERT INTO t VALUES (1), (2)"],
+                [
+                    "2",
+                    "3",
+                    "ERROR: relation \"nope\" does not exist [42P01]",
+                    "INSERT INTO nope VALUES (1)"
+                ],
+                ["3", "4", "not run", "DROP TABLE t"],
+            ]
+        );
+
+        let committed = SourceReport {
+            statements: report.statements[..1].to_vec(),
+            transaction: SourceTransaction::CommitFailed("deferred".to_string()),
+            ..report
+        };
+        assert_eq!(committed.summary(), "Sourced seed.sql: 1 ok (rolled back)");
+        assert_eq!(
+            committed.first_error().as_deref(),
+            Some("COMMIT of seed.sql failed: deferred")
+        );
+    }
+}
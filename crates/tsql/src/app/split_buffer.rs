@@ -0,0 +1,116 @@
+//! `Ctrl+W s`: a second query buffer shown above or below the one being edited.
+//!
+//! `App::editor` stays the buffer that keys, completion and execution work on.
+//! The split holds the other one with its own scroll position, and moving to
+//! the other pane swaps the two.
+
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use tui_syntax::Highlighter;
+
+use crate::ui::QueryEditor;
+
+pub(crate) struct SplitBuffer {
+    pub(crate) editor: QueryEditor,
+    pub(crate) scroll: (u16, u16),
+    /// Whether the active buffer is the lower pane.
+    pub(crate) active_below: bool,
+    /// Where the other pane was last drawn, for mouse clicks.
+    pub(crate) area: Option<Rect>,
+    /// The other buffer's text and its highlighting, which only changes while
+    /// it is the active one.
+    highlighted: Option<(String, Vec<Line<'static>>)>,
+}
+
+impl SplitBuffer {
+    /// A split whose empty buffer sits below the current one; `switch` to it
+    /// to start editing.
+    pub(crate) fn new() -> Self {
+        Self {
+            editor: QueryEditor::new(),
+            scroll: (0, 0),
+            active_below: false,
+            area: None,
+            highlighted: None,
+        }
+    }
+
+    /// The active and the other pane of the query `area`, halved by height.
+    pub(crate) fn panes(&self, area: Rect) -> (Rect, Rect) {
+        let top_height = area.height / 2;
+        let top = Rect {
+            height: top_height,
+            ..area
+        };
+        let bottom = Rect {
+            y: area.y + top_height,
+            height: area.height - top_height,
+            ..area
+        };
+        if self.active_below {
+            (bottom, top)
+        } else {
+            (top, bottom)
+        }
+    }
+
+    /// Makes the other buffer the active one.
+    pub(crate) fn switch(&mut self, editor: &mut QueryEditor, scroll: &mut (u16, u16)) {
+        std::mem::swap(&mut self.editor, editor);
+        std::mem::swap(&mut self.scroll, scroll);
+        self.active_below = !self.active_below;
+    }
+
+    /// Highlighted lines of the other buffer.
+    pub(crate) fn highlighted_lines(
+        &mut self,
+        highlighter: &mut Highlighter,
+    ) -> Vec<Line<'static>> {
+        let text = self.editor.text();
+        match &self.highlighted {
+            Some((highlighted, lines)) if *highlighted == text => lines.clone(),
+            _ => {
+                let lines = highlighter.highlight("sql", &text).unwrap_or_else(|_| {
+                    text.lines()
+                        .map(|line| Line::from(line.to_string()))
+                        .collect()
+                });
+                self.highlighted = Some((text, lines.clone()));
+                lines
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_swaps_buffers_scroll_and_panes() {
+        let mut editor = QueryEditor::new();
+        editor.set_text("SELECT * FROM reference".to_string());
+        let mut scroll = (4, 0);
+        let mut split = SplitBuffer::new();
+        let area = Rect::new(0, 2, 40, 11);
+
+        // The new buffer goes below, so the current one becomes the top pane.
+        split.switch(&mut editor, &mut scroll);
+        assert_eq!(editor.text(), "");
+        assert_eq!(scroll, (0, 0));
+        assert_eq!(split.editor.text(), "SELECT * FROM reference");
+        assert_eq!(split.scroll, (4, 0));
+        assert_eq!(
+            split.panes(area),
+            (Rect::new(0, 7, 40, 6), Rect::new(0, 2, 40, 5))
+        );
+
+        split.switch(&mut editor, &mut scroll);
+        assert_eq!(editor.text(), "SELECT * FROM reference");
+        assert_eq!(scroll, (4, 0));
+        assert_eq!(
+            split.panes(area),
+            (Rect::new(0, 2, 40, 5), Rect::new(0, 7, 40, 6))
+        );
+    }
+}
@@ -0,0 +1,767 @@
+//! Pretty-printing for `:format`.
+//!
+//! Each clause starts a line with its contents indented below it. Lists
+//! break at top-level commas, and AND/OR conditions go one per line.
+//! Parentheses around a subquery open an indented block; other parentheses
+//! (calls, IN lists, column lists) stay on one line. Literals, quoted
+//! identifiers and comments are copied unchanged.
+
+use std::ops::Range;
+
+use super::sql_lexer::{scan, SqlSegmentKind};
+use crate::config::{CommaStyle, KeywordCase, SqlFormatConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Word,
+    Quoted,
+    Literal,
+    Punct,
+    LineComment,
+    BlockComment,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    /// No whitespace separates it from the previous token.
+    glued: bool,
+    /// A line break comes before it in the source.
+    newline_before: bool,
+}
+
+impl Token<'_> {
+    fn is_word(&self, word: &str) -> bool {
+        self.kind == Kind::Word && self.text.eq_ignore_ascii_case(word)
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        self.kind == Kind::Punct && self.text == punct
+    }
+
+    fn is_comment(&self) -> bool {
+        matches!(self.kind, Kind::LineComment | Kind::BlockComment)
+    }
+}
+
+/// Words whose case `keyword_case` applies to.
+const KEYWORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BEGIN",
+    "BETWEEN",
+    "BY",
+    "CASCADE",
+    "CASE",
+    "CAST",
+    "CHECK",
+    "COLUMN",
+    "COMMIT",
+    "CONFLICT",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DO",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "EXPLAIN",
+    "FALSE",
+    "FETCH",
+    "FILTER",
+    "FIRST",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IF",
+    "ILIKE",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LAST",
+    "LATERAL",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NEXT",
+    "NOT",
+    "NOTHING",
+    "NULL",
+    "NULLS",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "PRIMARY",
+    "RECURSIVE",
+    "REFERENCES",
+    "RETURNING",
+    "RIGHT",
+    "ROLLBACK",
+    "ROWS",
+    "SELECT",
+    "SET",
+    "SOME",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRUE",
+    "TRUNCATE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "VIEW",
+    "WHEN",
+    "WHERE",
+    "WINDOW",
+    "WITH",
+];
+
+/// Keywords written like function calls, without a space before `(`.
+const CALL_KEYWORDS: &[&str] = &["ANY", "CAST", "SOME"];
+
+/// Words that start a join, e.g. `LEFT OUTER JOIN`.
+const JOIN_WORDS: &[&str] = &[
+    "CROSS", "FULL", "INNER", "JOIN", "LEFT", "NATURAL", "OUTER", "RIGHT",
+];
+
+/// First words of a subquery, which puts its parentheses on their own lines.
+const SUBQUERY_WORDS: &[&str] = &["DELETE", "INSERT", "SELECT", "UPDATE", "VALUES", "WITH"];
+
+const OPERATOR_CHARS: &str = "+-*/<>=~!@#%^&|`?";
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token<'_>>, String> {
+    let mut tokens = Vec::new();
+    let mut last_end = 0;
+    let mut push = |kind: Kind, range: Range<usize>| {
+        let gap = &sql[last_end..range.start];
+        tokens.push(Token {
+            kind,
+            text: &sql[range.clone()],
+            glued: gap.is_empty(),
+            newline_before: gap.contains('\n'),
+        });
+        last_end = range.end;
+    };
+    for segment in scan(sql)? {
+        let range = segment.range;
+        let kind = match segment.kind {
+            SqlSegmentKind::SingleQuoted | SqlSegmentKind::DollarQuoted => Kind::Literal,
+            SqlSegmentKind::DoubleQuoted => Kind::Quoted,
+            SqlSegmentKind::LineComment => {
+                let text = sql[range.clone()].trim_end();
+                push(Kind::LineComment, range.start..range.start + text.len());
+                continue;
+            }
+            SqlSegmentKind::BlockComment => Kind::BlockComment,
+            SqlSegmentKind::Code => {
+                let code = &sql[range.clone()];
+                let mut offset = 0;
+                while let Some(c) = code[offset..].chars().next() {
+                    if c.is_whitespace() {
+                        offset += c.len_utf8();
+                        continue;
+                    }
+                    let (kind, len) = code_token(code, offset, c);
+                    push(kind, range.start + offset..range.start + offset + len);
+                    offset += len;
+                }
+                continue;
+            }
+        };
+        push(kind, range);
+    }
+    Ok(tokens)
+}
+
+/// Kind and byte length of the token starting with `c` at `offset` of a code
+/// segment.
+fn code_token(code: &str, offset: usize, c: char) -> (Kind, usize) {
+    let rest = &code[offset..];
+    let run = |skip: usize, keep: fn(char) -> bool| {
+        skip + rest[skip..].find(|c| !keep(c)).unwrap_or(rest.len() - skip)
+    };
+    let next = rest[c.len_utf8()..].chars().next();
+    if c.is_alphabetic() || c == '_' {
+        let len = run(0, |c| c.is_alphanumeric() || c == '_' || c == '$');
+        // `U&'...'`: the `&` belongs to the literal's prefix.
+        if len == 1 && matches!(c, 'u' | 'U') && &rest[1..] == "&" {
+            return (Kind::Word, 2);
+        }
+        (Kind::Word, len)
+    } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+        let mut len = run(0, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        let exponent_sign = rest[len..].starts_with(['+', '-'])
+            && rest[..len].ends_with(['e', 'E'])
+            && rest[len + 1..].starts_with(|c: char| c.is_ascii_digit());
+        if exponent_sign {
+            len = run(len + 1, |c| c.is_ascii_digit());
+        }
+        (Kind::Literal, len)
+    } else if c == '$' && next.is_some_and(|n| n.is_ascii_digit()) {
+        (Kind::Word, run(1, |c| c.is_ascii_digit()))
+    } else if rest.starts_with("::") {
+        (Kind::Punct, 2)
+    } else if c == ':' && next.is_some_and(|n| n.is_alphabetic() || n == '_') {
+        (Kind::Word, run(1, |c| c.is_alphanumeric() || c == '_'))
+    } else if OPERATOR_CHARS.contains(c) {
+        let mut operator = &rest[..run(0, |c| OPERATOR_CHARS.contains(c))];
+        // As in PostgreSQL, `=-1` is `=` and `-1`.
+        while operator.len() > 1
+            && operator.ends_with(['+', '-'])
+            && !operator.contains(['~', '!', '@', '#', '%', '^', '&', '|', '`', '?'])
+        {
+            operator = &operator[..operator.len() - 1];
+        }
+        (Kind::Punct, operator.len())
+    } else {
+        (Kind::Punct, c.len_utf8())
+    }
+}
+
+/// A statement, or the inside of a pair of parentheses.
+struct Frame {
+    /// Indent level of clause keywords.
+    base: usize,
+    /// Subqueries get their own clause layout; other parentheses stay inline.
+    block: bool,
+    /// Indent level of the line holding a block's `(`.
+    open_indent: usize,
+    /// A clause keyword has been written, so its contents sit at `base + 1`.
+    in_clause: bool,
+    /// The statement's first keyword (`SELECT`, `UPDATE`, ...), uppercased.
+    leading: Option<String>,
+    /// Open CASE expressions, whose ANDs stay on their line.
+    case_depth: usize,
+    /// A BETWEEN waits for its AND.
+    between: bool,
+}
+
+impl Frame {
+    fn block(base: usize, open_indent: usize) -> Self {
+        Self {
+            base,
+            block: true,
+            open_indent,
+            in_clause: false,
+            leading: None,
+            case_depth: 0,
+            between: false,
+        }
+    }
+
+    fn item_indent(&self) -> usize {
+        self.base + usize::from(self.in_clause)
+    }
+}
+
+/// Output, one line at a time. Line breaks are deferred until more text
+/// arrives, so a trailing comment can still join the line it followed.
+struct Writer {
+    out: String,
+    line: String,
+    /// Indent level of the line being written, or of the next one.
+    indent: usize,
+    width: usize,
+    /// The next text starts a new line.
+    pending: bool,
+}
+
+impl Writer {
+    fn newline(&mut self, indent: usize) {
+        self.pending = true;
+        self.indent = indent;
+    }
+
+    fn flush_line(&mut self) {
+        if !self.line.is_empty() {
+            self.out.push_str(self.line.trim_end());
+            self.out.push('\n');
+            self.line.clear();
+        }
+        self.pending = false;
+    }
+
+    fn blank_line(&mut self) {
+        self.flush_line();
+        self.out.push('\n');
+        self.indent = 0;
+    }
+
+    fn push(&mut self, text: &str, space: bool) {
+        if self.pending {
+            self.flush_line();
+        }
+        if self.line.is_empty() {
+            self.line.push_str(&" ".repeat(self.indent * self.width));
+        } else if space {
+            self.line.push(' ');
+        }
+        self.line.push_str(text);
+    }
+
+    /// Appends to the current line even when a line break is pending.
+    fn push_trailing(&mut self, text: &str) {
+        self.line.push(' ');
+        self.line.push_str(text);
+    }
+}
+
+struct Formatter<'a> {
+    tokens: Vec<Token<'a>>,
+    config: &'a SqlFormatConfig,
+    writer: Writer,
+    frames: Vec<Frame>,
+    /// The last two code tokens written, most recent first.
+    prev: [Option<Token<'a>>; 2],
+    /// A `;` was written; the next statement starts after a blank line.
+    statement_ended: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn frame(&mut self) -> &mut Frame {
+        self.frames
+            .last_mut()
+            .expect("the statement frame is never popped")
+    }
+
+    /// The next code token after `index`, skipping comments.
+    fn next_code(&self, index: usize) -> Option<&Token<'a>> {
+        self.tokens[index + 1..]
+            .iter()
+            .find(|token| !token.is_comment())
+    }
+
+    /// Whether words `index..index + words.len()` are `words`.
+    fn words_at(&self, index: usize, words: &[&str]) -> bool {
+        words.iter().enumerate().all(|(offset, word)| {
+            self.tokens
+                .get(index + offset)
+                .is_some_and(|token| token.is_word(word))
+        })
+    }
+
+    fn cased(&self, token: &Token) -> String {
+        let qualified = self.prev[0].is_some_and(|prev| prev.is_punct("."));
+        if token.kind != Kind::Word || qualified || !is_keyword(token.text) {
+            return token.text.to_string();
+        }
+        match self.config.keyword_case {
+            KeywordCase::Upper => token.text.to_ascii_uppercase(),
+            KeywordCase::Lower => token.text.to_ascii_lowercase(),
+            KeywordCase::Preserve => token.text.to_string(),
+        }
+    }
+
+    /// Whether a space goes between the previous code token and `token`.
+    fn space_before(&self, token: &Token) -> bool {
+        let Some(prev) = self.prev[0] else {
+            return false;
+        };
+        if token.kind == Kind::Punct
+            && matches!(token.text, "," | ";" | ")" | "]" | "." | "::" | ":" | "[")
+        {
+            return false;
+        }
+        if prev.kind == Kind::Punct && matches!(prev.text, "(" | "[" | "." | "::" | ":") {
+            return false;
+        }
+        // After a name, `f(x)` is a call and `t (a, b)` a column list; keep
+        // whichever spacing was typed.
+        if token.is_punct("(") {
+            return match prev.kind {
+                Kind::Word
+                    if CALL_KEYWORDS
+                        .iter()
+                        .any(|keyword| keyword.eq_ignore_ascii_case(prev.text)) =>
+                {
+                    false
+                }
+                Kind::Word if is_keyword(prev.text) => true,
+                Kind::Word | Kind::Quoted => !token.glued,
+                _ => true,
+            };
+        }
+        // E'...', B'...', U&'...'
+        if token.glued && prev.kind == Kind::Word && token.kind == Kind::Literal {
+            return false;
+        }
+        // Unary minus or plus: no operand before it.
+        if prev.kind == Kind::Punct && matches!(prev.text, "-" | "+") {
+            let operand = self.prev[1].is_some_and(|before| match before.kind {
+                Kind::Word => {
+                    !is_keyword(before.text)
+                        || ["NULL", "TRUE", "FALSE", "END"]
+                            .iter()
+                            .any(|word| word.eq_ignore_ascii_case(before.text))
+                }
+                Kind::Quoted | Kind::Literal => true,
+                Kind::Punct => matches!(before.text, ")" | "]"),
+                _ => false,
+            });
+            return operand;
+        }
+        true
+    }
+
+    fn write(&mut self, token: Token<'a>) {
+        let space = self.space_before(&token);
+        let text = self.cased(&token);
+        self.writer.push(&text, space);
+        self.prev = [Some(token), self.prev[0]];
+    }
+
+    /// Writes `count` words starting at `index` on a line of their own at the
+    /// frame's base, with the clause contents indented on the next line.
+    fn clause(&mut self, index: usize, count: usize) -> usize {
+        let base = self.frame().base;
+        self.writer.newline(base);
+        for offset in 0..count {
+            self.write(self.tokens[index + offset]);
+        }
+        let frame = self.frame();
+        frame.in_clause = true;
+        frame.between = false;
+        self.writer.newline(base + 1);
+        count
+    }
+
+    /// Writes `count` words at `index` starting a new line at `indent`, with
+    /// what follows on the same line.
+    fn line_start(&mut self, index: usize, count: usize, indent: usize) -> usize {
+        self.writer.newline(indent);
+        for offset in 0..count {
+            self.write(self.tokens[index + offset]);
+        }
+        count
+    }
+
+    /// Handles the word at `index` and returns how many tokens it consumed.
+    fn word(&mut self, index: usize) -> usize {
+        let token = self.tokens[index];
+        let upper = token.text.to_ascii_uppercase();
+        let after_dot = self.prev[0].is_some_and(|prev| prev.is_punct("."));
+        let frame = self.frames.last().expect("statement frame");
+        if !frame.block || after_dot {
+            self.write(token);
+            return 1;
+        }
+        let base = frame.base;
+        let in_clause = frame.in_clause;
+        let in_case = frame.case_depth > 0;
+        let leading = frame.leading.clone();
+        let statement_start = leading.is_none()
+            || (leading.as_deref() == Some("WITH")
+                && self.prev[0].is_some_and(|prev| prev.is_punct(")")));
+        let prev_upper = self.prev[0]
+            .filter(|prev| prev.kind == Kind::Word)
+            .map(|prev| prev.text.to_ascii_uppercase());
+
+        let consumed = match upper.as_str() {
+            "SELECT" | "WHERE" | "HAVING" | "WINDOW" | "RETURNING" => self.clause(index, 1),
+            "FROM" if prev_upper.as_deref() != Some("DISTINCT") => self.clause(index, 1),
+            "VALUES" if prev_upper.as_deref() != Some("DEFAULT") => self.clause(index, 1),
+            "GROUP" | "ORDER" if self.words_at(index + 1, &["BY"]) => self.clause(index, 2),
+            "SET" if matches!(leading.as_deref(), Some("UPDATE" | "INSERT")) => {
+                self.clause(index, 1)
+            }
+            "WITH" if statement_start => {
+                let recursive = self.words_at(index + 1, &["RECURSIVE"]);
+                self.clause(index, 1 + usize::from(recursive))
+            }
+            "INSERT" if statement_start && self.words_at(index + 1, &["INTO"]) => {
+                self.clause(index, 2)
+            }
+            "DELETE" if statement_start && self.words_at(index + 1, &["FROM"]) => {
+                self.clause(index, 2)
+            }
+            "UPDATE" if statement_start => self.clause(index, 1),
+            "LIMIT" | "OFFSET" | "FETCH" => {
+                self.frame().in_clause = false;
+                self.line_start(index, 1, base)
+            }
+            "UNION" | "INTERSECT" | "EXCEPT" => {
+                let all =
+                    self.words_at(index + 1, &["ALL"]) || self.words_at(index + 1, &["DISTINCT"]);
+                let consumed = self.line_start(index, 1 + usize::from(all), base);
+                self.frame().in_clause = false;
+                self.writer.newline(base);
+                consumed
+            }
+            "FOR"
+                if ["UPDATE", "SHARE", "NO"]
+                    .iter()
+                    .any(|word| self.words_at(index + 1, &[word])) =>
+            {
+                self.frame().in_clause = false;
+                self.line_start(index, 1, base)
+            }
+            "ON" if self.words_at(index + 1, &["CONFLICT"]) => {
+                self.frame().in_clause = false;
+                self.line_start(index, 2, base)
+            }
+            _ if in_clause && JOIN_WORDS.contains(&upper.as_str()) => {
+                let count = self.tokens[index..]
+                    .iter()
+                    .take_while(|token| {
+                        token.kind == Kind::Word
+                            && JOIN_WORDS
+                                .iter()
+                                .any(|word| word.eq_ignore_ascii_case(token.text))
+                    })
+                    .count();
+                if self.tokens[index + count - 1].is_word("JOIN") {
+                    self.line_start(index, count, base + 1)
+                } else {
+                    self.write(token);
+                    1
+                }
+            }
+            "AND" if self.frame().between => {
+                self.frame().between = false;
+                self.write(token);
+                1
+            }
+            "AND" | "OR" if in_clause && !in_case => self.line_start(index, 1, base + 1),
+            _ => {
+                let frame = self.frame();
+                match upper.as_str() {
+                    "BETWEEN" => frame.between = true,
+                    "CASE" => frame.case_depth += 1,
+                    "END" => frame.case_depth = frame.case_depth.saturating_sub(1),
+                    _ => {}
+                }
+                self.write(token);
+                1
+            }
+        };
+        let frame = self.frame();
+        if frame.leading.is_none() || (statement_start && SUBQUERY_WORDS.contains(&upper.as_str()))
+        {
+            frame.leading = Some(upper);
+        }
+        consumed
+    }
+
+    fn punct(&mut self, index: usize) {
+        let token = self.tokens[index];
+        match token.text {
+            "(" => {
+                let subquery = self.next_code(index).is_some_and(|next| {
+                    next.kind == Kind::Word
+                        && SUBQUERY_WORDS
+                            .iter()
+                            .any(|word| word.eq_ignore_ascii_case(next.text))
+                });
+                self.write(token);
+                let indent = self.writer.indent;
+                let frame = if subquery {
+                    Frame::block(indent + 1, indent)
+                } else {
+                    Frame {
+                        block: false,
+                        ..Frame::block(self.frame().base, indent)
+                    }
+                };
+                self.frames.push(frame);
+            }
+            ")" => {
+                if self.frames.len() > 1 {
+                    let frame = self.frames.pop().expect("more than one frame");
+                    if frame.block {
+                        self.writer.newline(frame.open_indent);
+                    }
+                }
+                self.write(token);
+            }
+            "," => {
+                let frame = self.frame();
+                let breaks = frame.block && frame.in_clause && frame.case_depth == 0;
+                let indent = frame.item_indent();
+                if !breaks {
+                    self.write(token);
+                } else if self.config.comma_style == CommaStyle::Leading {
+                    self.writer.newline(indent);
+                    self.write(token);
+                } else {
+                    self.write(token);
+                    self.writer.newline(indent);
+                }
+            }
+            ";" => {
+                self.write(token);
+                self.frames = vec![Frame::block(0, 0)];
+                self.statement_ended = true;
+            }
+            _ => self.write(token),
+        }
+    }
+
+    fn comment(&mut self, token: Token<'a>) {
+        if !token.newline_before && !self.writer.line.is_empty() {
+            self.writer.push_trailing(token.text);
+        } else {
+            if !self.writer.pending {
+                let indent = self.frame().item_indent();
+                self.writer.newline(indent);
+            }
+            self.writer.push(token.text, true);
+        }
+        if token.kind == Kind::LineComment {
+            self.writer.newline(self.writer.indent);
+        }
+    }
+
+    fn run(mut self) -> String {
+        let mut index = 0;
+        while index < self.tokens.len() {
+            let token = self.tokens[index];
+            // A comment on the line of the `;` stays there.
+            let trailing_comment = token.is_comment() && !token.newline_before;
+            if self.statement_ended && !trailing_comment {
+                self.statement_ended = false;
+                self.writer.blank_line();
+                self.prev = [None, None];
+            }
+            match token.kind {
+                Kind::LineComment | Kind::BlockComment => self.comment(token),
+                Kind::Word => {
+                    index += self.word(index);
+                    continue;
+                }
+                Kind::Punct => self.punct(index),
+                Kind::Quoted | Kind::Literal => self.write(token),
+            }
+            index += 1;
+        }
+        self.writer.flush_line();
+        self.writer.out.trim_end().to_string()
+    }
+}
+
+/// `sql` laid out according to `config`.
+pub(crate) fn format_sql(sql: &str, config: &SqlFormatConfig) -> Result<String, String> {
+    let formatter = Formatter {
+        tokens: tokenize(sql)?,
+        config,
+        writer: Writer {
+            out: String::new(),
+            line: String::new(),
+            indent: 0,
+            width: config.indent_width,
+            pending: false,
+        },
+        frames: vec![Frame::block(0, 0)],
+        prev: [None, None],
+        statement_ended: false,
+    };
+    Ok(formatter.run())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(sql: &str) -> String {
+        format_sql(sql, &SqlFormatConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn clauses_lists_and_conditions_get_their_own_lines() {
+        assert_eq!(
+            format(
+                "select u.id, count(*) as n from users u left join orders o on o.user_id = u.id \
+                 where u.active and o.total between 1 and 10 or u.name like 'a%' \
+                 group by u.id order by n desc limit 5;select 1"
+            ),
+            "SELECT\n  u.id,\n  count(*) AS n\nFROM\n  users u\n  LEFT JOIN orders o ON o.user_id = u.id\n\
+             WHERE\n  u.active\n  AND o.total BETWEEN 1 AND 10\n  OR u.name LIKE 'a%'\n\
+             GROUP BY\n  u.id\nORDER BY\n  n DESC\nLIMIT 5;\n\nSELECT\n  1"
+        );
+    }
+
+    #[test]
+    fn subqueries_are_indented_and_other_parentheses_stay_inline() {
+        assert_eq!(
+            format(
+                "SELECT * FROM t WHERE id IN (SELECT id FROM u WHERE x = -1) \
+                 AND y = ANY(ARRAY[1, 2]) AND z::text = E'a\\'b'"
+            ),
+            "SELECT\n  *\nFROM\n  t\nWHERE\n  id IN (\n    SELECT\n      id\n    FROM\n      u\n\
+             \x20   WHERE\n      x = -1\n  )\n  AND y = ANY(ARRAY[1, 2])\n  AND z::text = E'a\\'b'"
+        );
+        assert_eq!(
+            format("insert into t (a, b) values (1, 'x'), ($1, :name) returning a"),
+            "INSERT INTO\n  t (a, b)\nVALUES\n  (1, 'x'),\n  ($1, :name)\nRETURNING\n  a"
+        );
+        assert_eq!(
+            format("update t set a = 1, b = case when c and d then 2 end where id = 3"),
+            "UPDATE\n  t\nSET\n  a = 1,\n  b = CASE WHEN c AND d THEN 2 END\nWHERE\n  id = 3"
+        );
+    }
+
+    #[test]
+    fn comments_and_literals_are_kept() {
+        assert_eq!(
+            format("-- top\nselect a, -- first\n /* b */ b from \"Weird Table\" where s = 'select, from'"),
+            "-- top\nSELECT\n  a, -- first\n  /* b */ b\nFROM\n  \"Weird Table\"\nWHERE\n  s = 'select, from'"
+        );
+        assert!(format_sql("select 'open", &SqlFormatConfig::default()).is_err());
+    }
+
+    #[test]
+    fn keyword_case_indent_and_leading_commas_follow_config() {
+        let config = SqlFormatConfig {
+            keyword_case: KeywordCase::Lower,
+            indent_width: 4,
+            comma_style: CommaStyle::Leading,
+        };
+        assert_eq!(
+            format_sql("SELECT a, b FROM t", &config).unwrap(),
+            "select\n    a\n    , b\nfrom\n    t"
+        );
+        let config = SqlFormatConfig {
+            keyword_case: KeywordCase::Preserve,
+            ..SqlFormatConfig::default()
+        };
+        assert_eq!(
+            format_sql("Select a From t", &config).unwrap(),
+            "Select\n  a\nFrom\n  t"
+        );
+    }
+}
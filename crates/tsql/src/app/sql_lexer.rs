@@ -163,6 +163,39 @@ pub(crate) fn single_statement(source: &str) -> Result<&str, String> {
     Ok(terminal_semicolon.map_or(source, |index| source[..index].trim_end()))
 }
 
+/// Splits a script on semicolons outside literals and comments. Statements
+/// keep their leading comments but not the semicolon; empty and comment-only
+/// pieces are dropped.
+pub(crate) fn split_statements(source: &str) -> Result<Vec<&str>, String> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    for segment in scan(source)? {
+        match segment.kind {
+            SqlSegmentKind::Code => {
+                for (offset, character) in source[segment.range.clone()].char_indices() {
+                    let index = segment.range.start + offset;
+                    if character == ';' {
+                        if has_code {
+                            statements.push(source[start..index].trim());
+                        }
+                        start = index + 1;
+                        has_code = false;
+                    } else if !character.is_whitespace() {
+                        has_code = true;
+                    }
+                }
+            }
+            SqlSegmentKind::LineComment | SqlSegmentKind::BlockComment => {}
+            _ => has_code = true,
+        }
+    }
+    if has_code {
+        statements.push(source[start..].trim());
+    }
+    Ok(statements)
+}
+
 /// Replaces comments with whitespace while preserving source byte offsets and newlines.
 pub(crate) fn mask_comments(source: &str) -> Result<String, String> {
     let mut masked = String::with_capacity(source.len());
@@ -279,6 +312,22 @@ mod tests {
         assert!(!masked.contains("nested"));
     }
 
+    #[test]
+    fn splits_scripts_outside_literals_and_comments() {
+        let source = "SELECT 'a;b';\n-- users; all of them\nSELECT * FROM users ;;\n\
+                      DO $$BEGIN PERFORM 1; END$$ /* done; */; -- trailing;\n";
+        assert_eq!(
+            split_statements(source).unwrap(),
+            [
+                "SELECT 'a;b'",
+                "-- users; all of them\nSELECT * FROM users",
+                "DO $$BEGIN PERFORM 1; END$$ /* done; */",
+            ]
+        );
+        assert_eq!(split_statements("SELECT 1").unwrap(), ["SELECT 1"]);
+        assert!(split_statements("SELECT 'open; SELECT 2").is_err());
+    }
+
     #[test]
     fn rejects_multiple_and_unterminated_statements() {
         for source in [
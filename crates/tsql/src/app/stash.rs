@@ -0,0 +1,184 @@
+//! `:stash <name>`: the shown result as a session-scoped TEMP TABLE.
+//!
+//! A live result is re-created on the server from the query that produced it,
+//! so every row is included even when only the first pages were loaded. A grid
+//! with no query to re-run (a restored result, a file, `EXPLAIN` output, or
+//! one whose query took `$1` / `:name` parameters) is copied from the values
+//! it holds.
+
+use super::query_params;
+use super::sql_lexer::{code_words, single_statement};
+use crate::ui::{quote_identifier, sql_literal, GridModel};
+
+/// Rows per INSERT when copying a grid's values.
+const INSERT_BATCH: usize = 500;
+
+/// The query as the body of `CREATE TABLE ... AS`, when it is a single
+/// statement that only reads and needs no parameters.
+pub(crate) fn materializable(query: &str) -> Option<&str> {
+    let query = single_statement(query).ok()?;
+    // The values it was run with are not kept.
+    if !query_params::params(query).is_empty() {
+        return None;
+    }
+    let words = code_words(query, usize::MAX).ok()?;
+    let reads = ["SELECT", "WITH", "VALUES", "TABLE"].contains(&words.first()?.as_str());
+    // A data-modifying CTE would make its changes a second time.
+    let modifies = words
+        .iter()
+        .any(|word| matches!(word.as_str(), "INSERT" | "UPDATE" | "DELETE" | "MERGE"));
+    (reads && !modifies).then_some(query)
+}
+
+/// Statements replacing temp table `name` with the rows of `query`.
+pub(crate) fn from_query(name: &str, query: &str) -> String {
+    let table = quote_identifier(name);
+    // The query goes on its own line so a trailing `--` comment stays inside it.
+    format!("DROP TABLE IF EXISTS pg_temp.{table};\nCREATE TEMP TABLE {table} AS\n{query}\n")
+}
+
+/// Statements replacing temp table `name` with the rows loaded in `grid`.
+pub(crate) fn from_grid(name: &str, grid: &GridModel) -> String {
+    let table = quote_identifier(name);
+    let types: Vec<&str> = (0..grid.headers.len())
+        .map(|col| column_type(grid.col_type(col).unwrap_or_default()))
+        .collect();
+    let columns: Vec<String> = column_names(&grid.headers)
+        .iter()
+        .zip(&types)
+        .map(|(column, col_type)| format!("{} {col_type}", quote_identifier(column)))
+        .collect();
+    let mut sql = format!(
+        "DROP TABLE IF EXISTS pg_temp.{table};\nCREATE TEMP TABLE {table} ({});",
+        columns.join(", ")
+    );
+    for start in (0..grid.rows.len()).step_by(INSERT_BATCH) {
+        let end = (start + INSERT_BATCH).min(grid.rows.len());
+        let values: Vec<String> = (start..end)
+            .map(|row| {
+                let literals: Vec<String> = types
+                    .iter()
+                    .enumerate()
+                    .map(|(col, col_type)| match grid.cell(row, col) {
+                        // Stored values: masking only hides them on screen.
+                        Some(value) if !grid.cell_is_null(row, col) => sql_literal(value, col_type),
+                        _ => "NULL".to_string(),
+                    })
+                    .collect();
+                format!("({})", literals.join(", "))
+            })
+            .collect();
+        sql.push_str(&format!(
+            "\nINSERT INTO {table} VALUES\n{};",
+            values.join(",\n")
+        ));
+    }
+    sql
+}
+
+/// Result headers made unique, since a table cannot repeat a column name
+/// the way `SELECT 1, 1` repeats `?column?`.
+fn column_names(headers: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(headers.len());
+    for header in headers {
+        let mut name = header.clone();
+        let mut suffix = 2;
+        while names.contains(&name) {
+            name = format!("{header}_{suffix}");
+            suffix += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// The column type for a grid column type; text when it is unknown or not a
+/// name a column definition accepts.
+fn column_type(col_type: &str) -> &str {
+    match col_type.trim() {
+        "" | "ARRAY" | "USER-DEFINED" => "text",
+        col_type => col_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_single_row_returning_statements_are_materialized() {
+        assert_eq!(
+            materializable("  SELECT ';' AS semi FROM t;  \n"),
+            Some("SELECT ';' AS semi FROM t")
+        );
+        assert_eq!(
+            materializable("with x as (select 1) select * from x"),
+            Some("with x as (select 1) select * from x")
+        );
+        assert_eq!(
+            materializable("(VALUES (1), (2))"),
+            Some("(VALUES (1), (2))")
+        );
+        assert_eq!(materializable("SELECT 1; SELECT 2"), None);
+        assert_eq!(materializable("EXPLAIN SELECT 1"), None);
+        assert_eq!(materializable("UPDATE t SET a = 1 RETURNING *"), None);
+        assert_eq!(
+            materializable("WITH gone AS (DELETE FROM t RETURNING *) SELECT * FROM gone"),
+            None
+        );
+        assert_eq!(materializable(""), None);
+
+        assert_eq!(
+            from_query("Recent Users", "SELECT * FROM users -- newest"),
+            "DROP TABLE IF EXISTS pg_temp.\"Recent Users\";\n\
+             CREATE TEMP TABLE \"Recent Users\" AS\nSELECT * FROM users -- newest\n"
+        );
+    }
+
+    #[test]
+    fn parameterised_queries_are_not_materialized() {
+        assert_eq!(materializable("SELECT * FROM users WHERE id = $1"), None);
+        assert_eq!(
+            materializable("SELECT * FROM users WHERE name = :name"),
+            None
+        );
+        assert_eq!(
+            materializable("SELECT '$1', 1::int AS n"),
+            Some("SELECT '$1', 1::int AS n")
+        );
+    }
+
+    #[test]
+    fn grids_are_copied_with_their_column_types() {
+        let mut grid = GridModel::new(
+            vec!["id".to_string(), "tags".to_string(), "id".to_string()],
+            vec![
+                vec!["1".to_string(), "{a,b}".to_string(), "007".to_string()],
+                vec!["2".to_string(), "NULL".to_string(), "it's".to_string()],
+            ],
+        )
+        .with_col_types(vec![
+            "integer".to_string(),
+            "ARRAY".to_string(),
+            String::new(),
+        ]);
+        grid.null_cells[1][1] = true;
+
+        assert_eq!(
+            from_grid("stash", &grid),
+            "DROP TABLE IF EXISTS pg_temp.stash;\n\
+             CREATE TEMP TABLE stash (id integer, tags text, id_2 text);\n\
+             INSERT INTO stash VALUES\n\
+             (1, '{a,b}', '007'),\n\
+             (2, NULL, 'it''s');"
+        );
+
+        let grid = GridModel::new(
+            vec!["n".to_string()],
+            (0..INSERT_BATCH + 1).map(|n| vec![n.to_string()]).collect(),
+        );
+        assert_eq!(from_grid("many", &grid).matches("INSERT INTO").count(), 2);
+        let empty = GridModel::new(vec!["n".to_string()], Vec::new());
+        assert!(!from_grid("none", &empty).contains("INSERT"));
+    }
+}
@@ -0,0 +1,245 @@
+//! `{{name}}` placeholders filled from the variables set with `:let`.
+//!
+//! Expansion is plain text substitution before the query is sent, so
+//! `:let env = 'prod'` turns `{{env}}` into the literal `'prod'` and
+//! `:let cols = id, email` into a column list. Placeholders inside comments
+//! are left as written. The editor asks for the values of unset variables
+//! before running a query; elsewhere a query using one is not run.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use super::sql_lexer::{scan, SqlSegmentKind};
+
+/// A `{{name}}` in a query, with its byte range including the braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Placeholder {
+    pub(crate) range: Range<usize>,
+    pub(crate) name: String,
+}
+
+/// Editor columns of a placeholder on one line, for highlighting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlaceholderMark {
+    pub(crate) row: usize,
+    pub(crate) columns: Range<usize>,
+    pub(crate) defined: bool,
+}
+
+/// Variables of one connection, by name.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TemplateVariables {
+    values: BTreeMap<String, String>,
+}
+
+impl TemplateVariables {
+    pub(crate) fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn set(&mut self, name: &str, value: &str) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> bool {
+        self.values.remove(name).is_some()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// `env = 'prod', lim = 10`, in name order.
+    pub(crate) fn summary(&self) -> String {
+        self.values
+            .iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parses `:let` arguments: `name = value`, or a bare `name` to show it.
+pub(crate) fn parse_let(args: &str) -> Result<(String, Option<String>), String> {
+    let (name, value) = match args.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.trim())),
+        None => (args.trim(), None),
+    };
+    if !is_variable_name(name) {
+        return Err(format!(
+            "Invalid variable name '{name}': use letters, digits and _"
+        ));
+    }
+    match value {
+        Some("") => Err(format!("Usage: :let {name} = <value>")),
+        value => Ok((name.to_string(), value.map(str::to_string))),
+    }
+}
+
+/// Placeholders in `source` outside of comments, in order.
+pub(crate) fn placeholders(source: &str) -> Vec<Placeholder> {
+    if !source.contains("{{") {
+        return Vec::new();
+    }
+    // An unterminated literal (e.g. while typing) leaves nothing to skip.
+    let comments: Vec<Range<usize>> = scan(source)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|segment| {
+            matches!(
+                segment.kind,
+                SqlSegmentKind::LineComment | SqlSegmentKind::BlockComment
+            )
+        })
+        .map(|segment| segment.range)
+        .collect();
+
+    let mut found = Vec::new();
+    let mut from = 0;
+    while let Some(offset) = source[from..].find("{{") {
+        let start = from + offset;
+        from = start + 2;
+        if comments.iter().any(|comment| comment.contains(&start)) {
+            continue;
+        }
+        let Some(close) = source[from..].find("}}") else {
+            break;
+        };
+        let name = source[from..from + close].trim();
+        if is_variable_name(name) {
+            found.push(Placeholder {
+                range: start..from + close + 2,
+                name: name.to_string(),
+            });
+            from += close + 2;
+        }
+    }
+    found
+}
+
+/// Names of the variables `source` uses that are not set, once each in order.
+pub(crate) fn unset(source: &str, variables: &TemplateVariables) -> Vec<String> {
+    let mut missing: Vec<String> = Vec::new();
+    for placeholder in placeholders(source) {
+        if variables.get(&placeholder.name).is_none() && !missing.contains(&placeholder.name) {
+            missing.push(placeholder.name);
+        }
+    }
+    missing
+}
+
+/// `source` with every placeholder replaced by its value. Errs listing the
+/// unset variables.
+pub(crate) fn expand(source: &str, variables: &TemplateVariables) -> Result<String, String> {
+    let missing = unset(source, variables);
+    if !missing.is_empty() {
+        let names = missing
+            .iter()
+            .map(|name| format!("{{{{{name}}}}}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!(
+            "Variable not set: {names} (set it with :let <name> = <value>)"
+        ));
+    }
+
+    let mut expanded = String::with_capacity(source.len());
+    let mut copied = 0;
+    for placeholder in &placeholders(source) {
+        expanded.push_str(&source[copied..placeholder.range.start]);
+        expanded.push_str(variables.get(&placeholder.name).unwrap_or_default());
+        copied = placeholder.range.end;
+    }
+    expanded.push_str(&source[copied..]);
+    Ok(expanded)
+}
+
+/// Where the placeholders of the editor text are, and whether each is set.
+pub(crate) fn placeholder_marks(
+    source: &str,
+    variables: Option<&TemplateVariables>,
+) -> Vec<PlaceholderMark> {
+    placeholders(source)
+        .into_iter()
+        .map(|placeholder| {
+            let before = &source[..placeholder.range.start];
+            let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+            let column = source[line_start..placeholder.range.start].chars().count();
+            PlaceholderMark {
+                row: before.matches('\n').count(),
+                columns: column..column + source[placeholder.range.clone()].chars().count(),
+                defined: variables.is_some_and(|vars| vars.get(&placeholder.name).is_some()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn let_arguments_name_a_variable_and_its_value() {
+        assert_eq!(
+            parse_let("env = 'prod'"),
+            Ok(("env".to_string(), Some("'prod'".to_string())))
+        );
+        assert_eq!(parse_let(" since_1 "), Ok(("since_1".to_string(), None)));
+        assert!(parse_let("1x = 2").is_err());
+        assert!(parse_let("my-var = 2").is_err());
+        assert_eq!(
+            parse_let("env ="),
+            Err("Usage: :let env = <value>".to_string())
+        );
+    }
+
+    #[test]
+    fn placeholders_expand_outside_comments_and_unset_ones_are_reported() {
+        let mut variables = TemplateVariables::default();
+        variables.set("env", "'prod'");
+        variables.set("lim", "10");
+        variables.set("pat", "beta");
+        let sql = "-- {{note}}\nSELECT * FROM t\nWHERE env = {{env}} AND tag LIKE '%{{ pat }}%'\nLIMIT {{lim}}";
+        assert_eq!(
+            expand(sql, &variables).unwrap(),
+            "-- {{note}}\nSELECT * FROM t\nWHERE env = 'prod' AND tag LIKE '%beta%'\nLIMIT 10"
+        );
+        assert_eq!(
+            expand("SELECT '{{1,2}}'::int[]", &variables).unwrap(),
+            "SELECT '{{1,2}}'::int[]"
+        );
+        assert_eq!(
+            unset("SELECT {{a}}, {{env}}, {{b}}, {{a}}", &variables),
+            ["a", "b"]
+        );
+        assert_eq!(
+            expand("SELECT {{a}}, {{b}}, {{a}}", &variables),
+            Err("Variable not set: {{a}}, {{b}} (set it with :let <name> = <value>)".to_string())
+        );
+
+        assert_eq!(
+            placeholder_marks("SELECT 'é', {{env}}\n  /* {{x}} */ {{x}}", Some(&variables)),
+            [
+                PlaceholderMark {
+                    row: 0,
+                    columns: 12..19,
+                    defined: true,
+                },
+                PlaceholderMark {
+                    row: 1,
+                    columns: 14..19,
+                    defined: false,
+                },
+            ]
+        );
+        assert_eq!(variables.summary(), "env = 'prod', lim = 10, pat = beta");
+    }
+}
@@ -10,12 +10,61 @@ pub enum ClipboardBackendChoice {
     Disabled,
     Arboard,
     WlCopy { cmd: PathBuf },
+    Osc52,
+    Command { argv: Vec<String> },
 }
 
-pub fn choose_backend(cfg: &ClipboardConfig) -> Result<ClipboardBackendChoice> {
-    match cfg.backend {
-        ClipboardBackend::Disabled => Ok(ClipboardBackendChoice::Disabled),
+/// Resolves the configured backend followed by its fallbacks, in the order
+/// they should be tried. `auto` expands to its detected candidates and
+/// duplicates are dropped; a disabled clipboard yields an empty chain.
+pub fn backend_chain(cfg: &ClipboardConfig) -> Vec<Result<ClipboardBackendChoice>> {
+    if cfg.backend == ClipboardBackend::Disabled {
+        return Vec::new();
+    }
+    let primary = if cfg.backend == ClipboardBackend::Auto {
+        auto_backends(cfg)
+    } else {
+        vec![cfg.backend]
+    };
+
+    let mut backends: Vec<ClipboardBackend> = Vec::new();
+    for backend in primary.into_iter().chain(cfg.fallback.iter().copied()) {
+        if matches!(backend, ClipboardBackend::Auto | ClipboardBackend::Disabled)
+            || backends.contains(&backend)
+        {
+            continue;
+        }
+        backends.push(backend);
+    }
+    backends
+        .into_iter()
+        .map(|backend| resolve_backend(backend, cfg))
+        .collect()
+}
+
+fn auto_backends(cfg: &ClipboardConfig) -> Vec<ClipboardBackend> {
+    let mut backends = Vec::new();
+    if cfg!(target_os = "linux") && is_wayland_session() && find_in_path(&cfg.wl_copy_cmd).is_some()
+    {
+        backends.push(ClipboardBackend::WlCopy);
+    }
+    // Over SSH arboard talks to a forwarded or missing display and can
+    // "succeed" without reaching the user's clipboard.
+    if is_ssh_session() {
+        backends.push(ClipboardBackend::Osc52);
+    }
+    backends.push(ClipboardBackend::Arboard);
+    backends
+}
+
+fn resolve_backend(
+    backend: ClipboardBackend,
+    cfg: &ClipboardConfig,
+) -> Result<ClipboardBackendChoice> {
+    match backend {
+        ClipboardBackend::Auto | ClipboardBackend::Disabled => Ok(ClipboardBackendChoice::Disabled),
         ClipboardBackend::Arboard => Ok(ClipboardBackendChoice::Arboard),
+        ClipboardBackend::Osc52 => Ok(ClipboardBackendChoice::Osc52),
         ClipboardBackend::WlCopy => {
             let cmd = find_in_path(&cfg.wl_copy_cmd).ok_or_else(|| {
                 anyhow!(
@@ -25,13 +74,14 @@ pub fn choose_backend(cfg: &ClipboardConfig) -> Result<ClipboardBackendChoice> {
             })?;
             Ok(ClipboardBackendChoice::WlCopy { cmd })
         }
-        ClipboardBackend::Auto => {
-            if cfg!(target_os = "linux") && is_wayland_session() {
-                if let Some(cmd) = find_in_path(&cfg.wl_copy_cmd) {
-                    return Ok(ClipboardBackendChoice::WlCopy { cmd });
-                }
+        ClipboardBackend::Command => {
+            let argv = shlex::split(&cfg.command).unwrap_or_default();
+            if argv.is_empty() {
+                return Err(anyhow!(
+                    "Clipboard backend command selected, but clipboard.command is empty"
+                ));
             }
-            Ok(ClipboardBackendChoice::Arboard)
+            Ok(ClipboardBackendChoice::Command { argv })
         }
     }
 }
@@ -46,28 +96,108 @@ pub fn copy_with_wl_copy(text: &str, cfg: &ClipboardConfig, cmd: &Path) -> Resul
         command.arg("-n");
     }
 
+    pipe_to_command(command, "wl-copy", text)
+}
+
+/// Copies by piping `text` into a user-configured command such as `xclip` or `pbcopy`.
+pub fn copy_with_command(text: &str, argv: &[String]) -> Result<()> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| anyhow!("Clipboard command is empty"))?;
+    let mut command = Command::new(program);
+    command.args(args);
+    pipe_to_command(command, program, text)
+}
+
+/// Copies by asking the terminal to set the clipboard via OSC 52.
+///
+/// The terminal gives no acknowledgement, so this only fails when the payload
+/// is too large or stdout cannot be written.
+pub fn copy_with_osc52(text: &str, cfg: &ClipboardConfig) -> Result<()> {
+    let sequence = osc52_sequence(
+        text,
+        cfg.osc52_max_bytes,
+        std::env::var_os("TMUX").is_some(),
+    )?;
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|e| anyhow!("Failed to write OSC 52 sequence: {}", e))
+}
+
+/// Builds the OSC 52 "set clipboard" sequence, wrapped for tmux passthrough when needed.
+pub fn osc52_sequence(text: &str, max_bytes: usize, tmux: bool) -> Result<String> {
+    let encoded = base64_encode(text.as_bytes());
+    if max_bytes > 0 && encoded.len() > max_bytes {
+        return Err(anyhow!(
+            "OSC 52 payload is {} bytes, above clipboard.osc52_max_bytes ({})",
+            encoded.len(),
+            max_bytes
+        ));
+    }
+    let sequence = format!("\x1b]52;c;{encoded}\x07");
+    if tmux {
+        // tmux forwards DCS passthrough with every inner ESC doubled.
+        Ok(format!(
+            "\x1bPtmux;{}\x1b\\",
+            sequence.replace('\x1b', "\x1b\x1b")
+        ))
+    } else {
+        Ok(sequence)
+    }
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        out.push(char::from(ALPHABET[(n >> 18) as usize & 63]));
+        out.push(char::from(ALPHABET[(n >> 12) as usize & 63]));
+        out.push(if chunk.len() > 1 {
+            char::from(ALPHABET[(n >> 6) as usize & 63])
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            char::from(ALPHABET[n as usize & 63])
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+fn pipe_to_command(mut command: Command, name: &str, text: &str) -> Result<()> {
     let mut child = command
         .stdin(Stdio::piped())
         .stdout(Stdio::null())
         .stderr(Stdio::piped())
         .spawn()
-        .map_err(|e| anyhow!("Failed to start wl-copy: {}", e))?;
+        .map_err(|e| anyhow!("Failed to start {}: {}", name, e))?;
 
     if let Some(mut stdin) = child.stdin.take() {
         stdin
             .write_all(text.as_bytes())
-            .map_err(|e| anyhow!("Failed to write to wl-copy stdin: {}", e))?;
+            .map_err(|e| anyhow!("Failed to write to {} stdin: {}", name, e))?;
     }
 
-    // `wl-copy` may keep running to serve clipboard requests (selection ownership).
-    // It typically forks into the background, but we should never block the TUI waiting for it.
+    // Clipboard owners like `wl-copy` and `xclip` may keep running to serve
+    // clipboard requests (selection ownership). They typically fork into the
+    // background, but we should never block the TUI waiting for them.
     // We only wait briefly to catch immediate failures; otherwise we detach and reap in a thread
     // to avoid zombie processes once it exits.
     let deadline = Instant::now() + Duration::from_millis(250);
     loop {
         match child
             .try_wait()
-            .map_err(|e| anyhow!("Failed to check wl-copy status: {}", e))?
+            .map_err(|e| anyhow!("Failed to check {} status: {}", name, e))?
         {
             Some(status) => {
                 if status.success() {
@@ -85,9 +215,9 @@ pub fn copy_with_wl_copy(text: &str, cfg: &ClipboardConfig, cmd: &Path) -> Resul
                 let stderr = String::from_utf8_lossy(&stderr_bytes);
                 let stderr = stderr.trim();
                 if stderr.is_empty() {
-                    return Err(anyhow!("wl-copy failed with exit status {}", status));
+                    return Err(anyhow!("{} failed with exit status {}", name, status));
                 }
-                return Err(anyhow!("wl-copy failed: {}", stderr));
+                return Err(anyhow!("{} failed: {}", name, stderr));
             }
             None => {
                 if Instant::now() >= deadline {
@@ -114,6 +244,10 @@ fn is_wayland_session() -> bool {
     }
 }
 
+fn is_ssh_session() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
 fn find_in_path(cmd: &str) -> Option<PathBuf> {
     let cmd_path = Path::new(cmd);
     // Check if cmd looks like a path (contains separator). On Unix, backslash is a
@@ -172,6 +306,10 @@ mod tests {
             wl_copy_cmd: "wl-copy".to_string(),
             wl_copy_primary: false,
             wl_copy_trim_newline: false,
+            fallback: vec![ClipboardBackend::Osc52],
+            command: String::new(),
+            osc52_max_bytes: 100_000,
+            history: 0,
         }
     }
 
@@ -181,11 +319,84 @@ mod tests {
         cfg.backend = ClipboardBackend::WlCopy;
         cfg.wl_copy_cmd = "definitely-not-a-real-wl-copy-binary".to_string();
 
-        let err = choose_backend(&cfg).unwrap_err().to_string();
+        let err = backend_chain(&cfg).remove(0).unwrap_err().to_string();
         assert!(err.contains("wl-copy selected"));
         assert!(err.contains("not found"));
     }
 
+    #[test]
+    fn chain_appends_fallbacks_without_duplicates() {
+        let mut cfg = base_cfg();
+        cfg.backend = ClipboardBackend::Arboard;
+        cfg.fallback = vec![
+            ClipboardBackend::Osc52,
+            ClipboardBackend::Arboard,
+            ClipboardBackend::Auto,
+            ClipboardBackend::Disabled,
+            ClipboardBackend::Command,
+        ];
+        cfg.command = "xclip -selection 'clip board'".to_string();
+
+        let chain: Vec<_> = backend_chain(&cfg)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            chain,
+            vec![
+                ClipboardBackendChoice::Arboard,
+                ClipboardBackendChoice::Osc52,
+                ClipboardBackendChoice::Command {
+                    argv: vec![
+                        "xclip".to_string(),
+                        "-selection".to_string(),
+                        "clip board".to_string()
+                    ]
+                },
+            ]
+        );
+
+        cfg.backend = ClipboardBackend::Disabled;
+        assert!(backend_chain(&cfg).is_empty());
+    }
+
+    #[test]
+    fn command_backend_requires_a_command() {
+        let mut cfg = base_cfg();
+        cfg.backend = ClipboardBackend::Command;
+        let err = backend_chain(&cfg).remove(0).unwrap_err().to_string();
+        assert!(err.contains("clipboard.command is empty"));
+    }
+
+    #[test]
+    fn osc52_sequence_encodes_and_wraps_for_tmux() {
+        assert_eq!(
+            osc52_sequence("hello", 0, false).unwrap(),
+            "\x1b]52;c;aGVsbG8=\x07"
+        );
+        assert_eq!(
+            osc52_sequence("hi", 0, true).unwrap(),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+        let err = osc52_sequence("hello", 4, false).unwrap_err().to_string();
+        assert!(err.contains("osc52_max_bytes"));
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        for (input, expected) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64_encode(input.as_bytes()), expected);
+        }
+    }
+
     #[cfg(unix)]
     fn write_executable(path: &Path, contents: &str) {
         use std::os::unix::fs::PermissionsExt;
@@ -227,6 +438,26 @@ mod tests {
         copy_with_wl_copy("hello", &cfg, &fake).unwrap();
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn copy_with_command_pipes_text_to_stdin() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("copied");
+        let fake = dir.path().join("fake-copy");
+        write_executable(&fake, "#!/bin/sh\ncat >\"$1\"\n");
+
+        let argv = vec![
+            fake.to_string_lossy().into_owned(),
+            out.to_string_lossy().into_owned(),
+        ];
+        copy_with_command("copied text", &argv).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while std::fs::read_to_string(&out).unwrap_or_default() != "copied text" {
+            assert!(Instant::now() < deadline, "command never received the text");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     #[test]
     #[serial]
     #[cfg(target_os = "linux")]
@@ -261,7 +492,7 @@ mod tests {
         std::env::set_var("WAYLAND_DISPLAY", "wayland-1");
 
         let cfg = base_cfg();
-        let choice = choose_backend(&cfg).unwrap();
+        let choice = backend_chain(&cfg).remove(0).unwrap();
         assert!(matches!(choice, ClipboardBackendChoice::WlCopy { .. }));
     }
 }
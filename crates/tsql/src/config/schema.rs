@@ -1,5 +1,7 @@
 //! Configuration schema definitions.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Root configuration structure
@@ -17,6 +19,12 @@ pub struct Config {
     pub sql: SqlConfig,
     /// Clipboard settings
     pub clipboard: ClipboardConfig,
+    /// Regional formatting of `:export` files
+    pub export: ExportConfig,
+    /// Long-running query notifications
+    pub notifications: NotificationsConfig,
+    /// Planner-estimate confirmation before running queries
+    pub cost_guard: CostGuardConfig,
     /// Keymap customizations
     pub keymap: KeymapConfig,
     /// Update checking settings
@@ -73,6 +81,46 @@ pub enum SnapshotMode {
     Off,
 }
 
+/// How the Classic workspace arranges the query editor and results grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PaneLayout {
+    /// Editor above the results grid
+    #[default]
+    Stacked,
+    /// Editor on the left, results grid on the right
+    SideBySide,
+}
+
+impl PaneLayout {
+    pub fn toggled(self) -> Self {
+        match self {
+            PaneLayout::Stacked => PaneLayout::SideBySide,
+            PaneLayout::SideBySide => PaneLayout::Stacked,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PaneLayout::Stacked => "stacked",
+            PaneLayout::SideBySide => "side-by-side",
+        }
+    }
+}
+
+/// Whether the UI is drawn in color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorChoice {
+    /// Color unless the `NO_COLOR` environment variable is set
+    #[default]
+    Auto,
+    /// Color even when `NO_COLOR` is set
+    Always,
+    /// Bold, underline and reverse video only (same as `--no-color`)
+    Never,
+}
+
 /// Display-related settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
@@ -93,6 +141,22 @@ pub struct DisplayConfig {
     pub null_indicator: String,
     /// Built-in or custom theme name
     pub theme: String,
+    /// Draw with colors, or with bold/underline/reverse only
+    pub color: ColorChoice,
+    /// Set the terminal window title to the active connection and database
+    pub terminal_title: bool,
+    /// Render URL cells as OSC 8 hyperlinks
+    pub hyperlinks: bool,
+    /// Default Classic layout: editor above results, or side by side
+    pub layout: PaneLayout,
+    /// Recent Classic result sets kept for `[g`/`]g` and `:results` (0 disables)
+    pub result_history: usize,
+    /// How long earlier status messages stay stacked as toasts, in milliseconds (0 disables)
+    pub toast_timeout_ms: u64,
+    /// Column name patterns (`*` wildcard) whose values are masked until `:unmask`
+    pub mask_columns: Vec<String>,
+    /// Leading grid columns kept in view while scrolling right (`zp` changes it)
+    pub pinned_columns: usize,
 }
 
 impl Default for DisplayConfig {
@@ -106,6 +170,14 @@ impl Default for DisplayConfig {
             show_null_indicator: true,
             null_indicator: "NULL".to_string(),
             theme: "default".to_string(),
+            color: ColorChoice::default(),
+            terminal_title: true,
+            hyperlinks: true,
+            layout: PaneLayout::default(),
+            result_history: 10,
+            toast_timeout_ms: 4000,
+            mask_columns: Vec::new(),
+            pinned_columns: 0,
         }
     }
 }
@@ -130,8 +202,20 @@ pub struct EditorConfig {
     pub completion_delay_ms: u32,
     /// Maximum history entries to keep
     pub max_history: usize,
+    /// Keep each saved connection's query history in its own file
+    pub history_per_connection: bool,
     /// Persist session state (query, connection, UI state) between launches
     pub persist_session: bool,
+    /// Seconds between auto-saved editor drafts (0 disables them)
+    pub draft_interval_secs: u64,
+    /// Accept comments, trailing commas, single quotes and unquoted keys
+    /// when editing JSON cells
+    pub json_relaxed: bool,
+    /// How edited JSON is written back to the database
+    pub json_save_format: JsonSaveFormat,
+    /// JSON Schema files checked before saving a JSON cell, keyed by
+    /// `table.column` or `column` (`*` matches any run of characters)
+    pub json_schemas: BTreeMap<String, String>,
 }
 
 impl Default for EditorConfig {
@@ -145,11 +229,29 @@ impl Default for EditorConfig {
             auto_completion: true,
             completion_delay_ms: 100,
             max_history: 1000,
+            history_per_connection: false,
             persist_session: true,
+            draft_interval_secs: 30,
+            json_relaxed: true,
+            json_save_format: JsonSaveFormat::Keep,
+            json_schemas: BTreeMap::new(),
         }
     }
 }
 
+/// How the JSON cell editor writes a value back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonSaveFormat {
+    /// Save strict JSON as typed; relaxed input is pretty-printed
+    #[default]
+    Keep,
+    /// Pretty-print with two-space indentation
+    Pretty,
+    /// A single line without whitespace
+    Compact,
+}
+
 /// Connection-related settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
@@ -160,12 +262,26 @@ pub struct ConnectionConfig {
     pub connect_timeout_secs: u32,
     /// Query timeout in seconds (0 = no timeout)
     pub query_timeout_secs: u32,
-    /// Maximum rows to fetch (0 = no limit)
+    /// Maximum rows to fetch per batch (0 = default of 2000)
     pub max_rows: usize,
     /// Auto-reconnect on connection loss
     pub auto_reconnect: bool,
+    /// Reconnect attempts before giving up
+    pub reconnect_attempts: u32,
+    /// Wait before the first reconnect attempt in milliseconds; it doubles
+    /// with each further attempt
+    pub reconnect_delay_ms: u64,
+    /// Longest wait between reconnect attempts in seconds
+    pub reconnect_max_delay_secs: u64,
+    /// Seconds between `SELECT 1` pings on an idle connection (0 = off);
+    /// none are sent while a transaction may be open
+    pub keepalive_secs: u64,
     /// Enable 1Password CLI (`op`) support for `password_onepassword` refs.
     pub enable_onepassword: bool,
+    /// Open a second PostgreSQL connection for schema and key lookups.
+    pub metadata_connection: bool,
+    /// Remember each connection's schema on disk for instant completion.
+    pub schema_cache: bool,
 }
 
 impl Default for ConnectionConfig {
@@ -176,7 +292,13 @@ impl Default for ConnectionConfig {
             query_timeout_secs: 0,
             max_rows: 0,
             auto_reconnect: true,
+            reconnect_attempts: 5,
+            reconnect_delay_ms: 500,
+            reconnect_max_delay_secs: 30,
+            keepalive_secs: 0,
             enable_onepassword: false,
+            metadata_connection: true,
+            schema_cache: true,
         }
     }
 }
@@ -193,6 +315,14 @@ pub struct ClipboardConfig {
     pub wl_copy_primary: bool,
     /// Trim trailing newline for `wl-copy` (passes `-n`).
     pub wl_copy_trim_newline: bool,
+    /// Backends tried in order when `backend` fails.
+    pub fallback: Vec<ClipboardBackend>,
+    /// External copy command for the `command` backend; receives the text on stdin.
+    pub command: String,
+    /// Largest payload sent through OSC 52, in bytes (0 = unlimited).
+    pub osc52_max_bytes: usize,
+    /// Grid copies kept for `:clipboard` (0 = keep none).
+    pub history: usize,
 }
 
 impl Default for ClipboardConfig {
@@ -202,24 +332,132 @@ impl Default for ClipboardConfig {
             wl_copy_cmd: "wl-copy".to_string(),
             wl_copy_primary: false,
             wl_copy_trim_newline: false,
+            fallback: vec![ClipboardBackend::Osc52],
+            command: String::new(),
+            osc52_max_bytes: 100_000,
+            history: 20,
+        }
+    }
+}
+
+/// Regional formatting of CSV and TSV exports, independent of how the grid
+/// shows values. JSON and SQL exports always keep the database's formatting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    /// Decimal separator written in numbers (e.g. `,` for most of Europe)
+    pub decimal_separator: char,
+    /// Field separator of CSV files (e.g. `;` where `,` is the decimal separator)
+    pub csv_delimiter: char,
+    /// strftime format for the date part of dates and timestamps (empty keeps `YYYY-MM-DD`)
+    pub date_format: String,
+    /// Text encoding of the file
+    pub encoding: ExportEncoding,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            csv_delimiter: ',',
+            date_format: String::new(),
+            encoding: ExportEncoding::Utf8,
         }
     }
 }
 
+/// Text encoding of exported files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportEncoding {
+    /// Plain UTF-8
+    #[default]
+    #[serde(alias = "utf-8")]
+    Utf8,
+    /// UTF-8 starting with a byte order mark, which Excel needs to detect it
+    #[serde(alias = "utf-8-bom")]
+    Utf8Bom,
+}
+
 /// Clipboard backend selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ClipboardBackend {
-    /// Auto-detect: prefer `wl-copy` on Wayland when available, otherwise use arboard.
+    /// Auto-detect: prefer `wl-copy` on Wayland when available and OSC 52 over
+    /// SSH, then arboard.
     Auto,
-    /// Always use arboard.
+    /// Always use arboard, the OS clipboard (`system` is accepted too).
+    #[serde(alias = "system")]
     Arboard,
     /// Always use `wl-copy`.
     WlCopy,
+    /// OSC 52 escape sequence; the terminal owns the clipboard (works over SSH).
+    Osc52,
+    /// The configured external `command` (e.g. `xclip -selection clipboard`, `pbcopy`).
+    Command,
     /// Disable clipboard support.
     Disabled,
 }
 
+/// Notifications for long-running queries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Notify only when a query ran at least this long (0 = disabled)
+    pub min_duration_secs: u64,
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Desktop notification backend
+    pub desktop: DesktopNotification,
+    /// Also notify while the terminal has focus
+    pub when_focused: bool,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            min_duration_secs: 10,
+            bell: true,
+            desktop: DesktopNotification::Off,
+            when_focused: false,
+        }
+    }
+}
+
+/// Confirmation for queries the planner estimates as expensive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostGuardConfig {
+    /// Run `EXPLAIN (FORMAT JSON)` before executing editor queries
+    pub enabled: bool,
+    /// Confirm when the estimated total cost exceeds this (0 = no limit)
+    pub max_total_cost: f64,
+    /// Confirm when the estimated row count exceeds this (0 = no limit)
+    pub max_plan_rows: f64,
+}
+
+impl Default for CostGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_total_cost: 1_000_000.0,
+            max_plan_rows: 1_000_000.0,
+        }
+    }
+}
+
+/// Desktop notification backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DesktopNotification {
+    /// No desktop notification.
+    Off,
+    /// OSC 777 escape sequence (rxvt, foot, WezTerm, kitty, Ghostty).
+    Osc777,
+    /// Spawn `notify-send`.
+    NotifySend,
+}
+
 /// Keymap customization settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(default)]
@@ -405,6 +643,23 @@ pub struct SqlConfig {
     pub identifier_style: IdentifierStyle,
     /// Default LIMIT for generated SELECT templates.
     pub default_select_limit: u32,
+    /// Show the generated UPDATE for confirmation before grid edits run.
+    pub confirm_updates: bool,
+    /// Count the rows an editor UPDATE/DELETE would touch and confirm before running it.
+    pub confirm_destructive: bool,
+    /// Named statements prepared on every PostgreSQL connection, for `:exec`.
+    pub prepared: BTreeMap<String, String>,
+    /// What `:source` does after a statement fails.
+    pub source_on_error: SourceOnError,
+    /// Run `:source` files inside a single transaction, rolled back on error.
+    pub source_single_transaction: bool,
+    /// Commit each statement as it runs. When off, statements that write open
+    /// a transaction that stays pending until `:commit` or `:rollback`.
+    pub autocommit: bool,
+    /// How editor queries are sent to PostgreSQL.
+    pub protocol: QueryProtocol,
+    /// Layout used by `:format`.
+    pub format: SqlFormatConfig,
 }
 
 impl Default for SqlConfig {
@@ -412,10 +667,82 @@ impl Default for SqlConfig {
         Self {
             identifier_style: IdentifierStyle::Minimal,
             default_select_limit: 100,
+            confirm_updates: true,
+            confirm_destructive: false,
+            prepared: BTreeMap::new(),
+            source_on_error: SourceOnError::Stop,
+            source_single_transaction: false,
+            autocommit: true,
+            protocol: QueryProtocol::Simple,
+            format: SqlFormatConfig::default(),
+        }
+    }
+}
+
+/// Configuration for the `[sql.format]` section, used by `:format`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SqlFormatConfig {
+    /// Case of SQL keywords.
+    pub keyword_case: KeywordCase,
+    /// Spaces per indentation level.
+    pub indent_width: usize,
+    /// Where commas go when a list is broken over lines.
+    pub comma_style: CommaStyle,
+}
+
+impl Default for SqlFormatConfig {
+    fn default() -> Self {
+        Self {
+            keyword_case: KeywordCase::Upper,
+            indent_width: 2,
+            comma_style: CommaStyle::Trailing,
         }
     }
 }
 
+/// Keyword case used by `:format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordCase {
+    Upper,
+    Lower,
+    /// Leave keywords as typed.
+    Preserve,
+}
+
+/// Comma placement used by `:format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommaStyle {
+    /// `a,` at the end of each line.
+    Trailing,
+    /// `, b` at the start of each following line.
+    Leading,
+}
+
+/// Wire protocol for editor queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryProtocol {
+    /// Send the text as-is; every value comes back as text. Large SELECTs
+    /// are paged through a cursor.
+    Simple,
+    /// Prepare single statements and read typed columns, so the grid knows
+    /// each column's PostgreSQL type. Results stop at `max_rows`.
+    Extended,
+}
+
+/// How `:source` continues after a failed statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceOnError {
+    /// Skip the rest of the file.
+    Stop,
+    /// Run the remaining statements anyway.
+    Continue,
+}
+
 /// Identifier formatting style used for generated SQL.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -449,25 +776,66 @@ show_row_numbers = false
 default_column_width = 30
 null_indicator = "<null>"
 theme = "dracula"
+color = "never"
+terminal_title = false
+hyperlinks = false
+layout = "side-by-side"
+result_history = 3
+toast_timeout_ms = 0
+mask_columns = ["password", "*_token"]
+pinned_columns = 1
 
 [editor]
 tab_size = 2
 expand_tabs = false
 max_history = 500
+history_per_connection = true
+draft_interval_secs = 10
+json_relaxed = false
+json_save_format = "compact"
+
+[editor.json_schemas]
+"users.preferences" = "~/schemas/preferences.json"
+"*_settings" = "/etc/tsql/settings.schema.json"
 
 [connection]
 default_url = "postgres://localhost/mydb"
 connect_timeout_secs = 5
 max_rows = 10000
+reconnect_attempts = 3
+reconnect_delay_ms = 250
+keepalive_secs = 60
+schema_cache = false
 
 [clipboard]
 backend = "wl-copy"
 wl_copy_primary = true
 wl_copy_trim_newline = true
+fallback = ["command", "osc52"]
+command = "xclip -selection clipboard"
+osc52_max_bytes = 0
+history = 5
+
+[export]
+decimal_separator = ","
+csv_delimiter = ";"
+date_format = "%d.%m.%Y"
+encoding = "utf-8-bom"
 
 [keymap]
 vim_mode = true
 
+[notifications]
+min_duration_secs = 30
+bell = false
+desktop = "notify-send"
+when_focused = true
+
+[cost_guard]
+enabled = true
+max_total_cost = 50000.0
+max_plan_rows = 0.0
+
 [updates]
 enabled = true
 check_on_startup = true
@@ -492,6 +860,21 @@ max_columns_per_table = 12
 system_prompt_postgres = "Only output PostgreSQL."
 system_prompt_mongo = "Only output Mongo syntax."
 
+[sql]
+source_on_error = "continue"
+source_single_transaction = true
+autocommit = false
+confirm_destructive = true
+protocol = "extended"
+
+[sql.format]
+keyword_case = "lower"
+indent_width = 4
+comma_style = "leading"
+
+[sql.prepared]
+user_by_email = "SELECT * FROM users WHERE email = $1"
+
 [[keymap.normal]]
 key = "ctrl+s"
 action = "save_query"
@@ -515,11 +898,28 @@ description = "Focus the next pane"
         assert_eq!(config.display.default_column_width, 30);
         assert_eq!(config.display.null_indicator, "<null>");
         assert_eq!(config.display.theme, "dracula");
+        assert!(!config.display.terminal_title);
+        assert!(!config.display.hyperlinks);
+        assert_eq!(config.display.layout, PaneLayout::SideBySide);
+        assert_eq!(config.display.color, ColorChoice::Never);
+        assert_eq!(config.display.result_history, 3);
+        assert_eq!(config.display.toast_timeout_ms, 0);
+        assert_eq!(config.display.mask_columns, ["password", "*_token"]);
+        assert_eq!(config.display.pinned_columns, 1);
 
         // Editor
         assert_eq!(config.editor.tab_size, 2);
         assert!(!config.editor.expand_tabs);
         assert_eq!(config.editor.max_history, 500);
+        assert!(config.editor.history_per_connection);
+        assert_eq!(config.editor.draft_interval_secs, 10);
+        assert!(!config.editor.json_relaxed);
+        assert_eq!(config.editor.json_save_format, JsonSaveFormat::Compact);
+        assert_eq!(
+            config.editor.json_schemas.get("users.preferences"),
+            Some(&"~/schemas/preferences.json".to_string())
+        );
+        assert_eq!(config.editor.json_schemas.len(), 2);
 
         // Connection
         assert_eq!(
@@ -528,11 +928,57 @@ description = "Focus the next pane"
         );
         assert_eq!(config.connection.connect_timeout_secs, 5);
         assert_eq!(config.connection.max_rows, 10000);
+        assert!(!config.connection.schema_cache);
+        assert_eq!(config.connection.reconnect_attempts, 3);
+        assert_eq!(config.connection.reconnect_delay_ms, 250);
+        assert_eq!(config.connection.reconnect_max_delay_secs, 30);
+        assert_eq!(config.connection.keepalive_secs, 60);
 
         // Clipboard
         assert_eq!(config.clipboard.backend, ClipboardBackend::WlCopy);
         assert!(config.clipboard.wl_copy_primary);
         assert!(config.clipboard.wl_copy_trim_newline);
+        assert_eq!(
+            config.clipboard.fallback,
+            vec![ClipboardBackend::Command, ClipboardBackend::Osc52]
+        );
+        assert_eq!(config.clipboard.command, "xclip -selection clipboard");
+        assert_eq!(config.clipboard.osc52_max_bytes, 0);
+        assert_eq!(config.clipboard.history, 5);
+
+        // Export
+        assert_eq!(config.export.decimal_separator, ',');
+        assert_eq!(config.export.csv_delimiter, ';');
+        assert_eq!(config.export.date_format, "%d.%m.%Y");
+        assert_eq!(config.export.encoding, ExportEncoding::Utf8Bom);
+
+        // Notifications
+        assert_eq!(config.notifications.min_duration_secs, 30);
+        assert!(!config.notifications.bell);
+        assert_eq!(
+            config.notifications.desktop,
+            DesktopNotification::NotifySend
+        );
+        assert!(config.notifications.when_focused);
+
+        // Cost guard
+        assert!(config.cost_guard.enabled);
+        assert_eq!(config.cost_guard.max_total_cost, 50000.0);
+        assert_eq!(config.cost_guard.max_plan_rows, 0.0);
+
+        // SQL
+        assert_eq!(
+            config.sql.prepared.get("user_by_email").map(String::as_str),
+            Some("SELECT * FROM users WHERE email = $1")
+        );
+        assert_eq!(config.sql.source_on_error, SourceOnError::Continue);
+        assert!(config.sql.source_single_transaction);
+        assert!(!config.sql.autocommit);
+        assert!(config.sql.confirm_destructive);
+        assert_eq!(config.sql.protocol, QueryProtocol::Extended);
+        assert_eq!(config.sql.format.keyword_case, KeywordCase::Lower);
+        assert_eq!(config.sql.format.indent_width, 4);
+        assert_eq!(config.sql.format.comma_style, CommaStyle::Leading);
 
         // Keymap
         assert!(config.keymap.vim_mode);
@@ -605,4 +1051,19 @@ description = "Focus the next pane"
 
         assert_eq!(config.display.theme, "github_light");
     }
+
+    #[test]
+    fn test_clipboard_backend_accepts_system_for_arboard() {
+        let config: Config = toml::from_str(
+            r#"
+            [clipboard]
+            backend = "system"
+            fallback = ["osc52"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.clipboard.backend, ClipboardBackend::Arboard);
+        assert_eq!(config.clipboard.fallback, vec![ClipboardBackend::Osc52]);
+    }
 }
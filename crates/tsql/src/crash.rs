@@ -0,0 +1,125 @@
+//! Crash reports written when tsql panics.
+//!
+//! The app records its editor buffers here as they change, so the panic hook
+//! can save them together with the backtrace after restoring the terminal.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+
+use crate::config::config_dir;
+
+/// Editor buffers as of the last frame.
+static BUFFERS: Mutex<Option<CrashBuffers>> = Mutex::new(None);
+
+/// Unsaved editor content kept for a crash report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrashBuffers {
+    pub editor: String,
+    pub notebook_cells: Vec<String>,
+}
+
+/// Records the current buffers; skipped if the panic hook holds the lock.
+pub fn record_buffers(buffers: CrashBuffers) {
+    if let Ok(mut recorded) = BUFFERS.try_lock() {
+        if recorded.as_ref() != Some(&buffers) {
+            *recorded = Some(buffers);
+        }
+    }
+}
+
+/// The last recorded buffers. Never blocks, since it runs in the panic hook.
+pub fn recorded_buffers() -> CrashBuffers {
+    match BUFFERS.try_lock() {
+        Ok(recorded) => recorded.clone().unwrap_or_default(),
+        Err(std::sync::TryLockError::Poisoned(poisoned)) => {
+            poisoned.into_inner().clone().unwrap_or_default()
+        }
+        Err(std::sync::TryLockError::WouldBlock) => CrashBuffers::default(),
+    }
+}
+
+/// Text of a crash report.
+pub fn crash_report(
+    time: DateTime<Local>,
+    panic: &str,
+    backtrace: &str,
+    buffers: &CrashBuffers,
+) -> String {
+    let mut report = format!(
+        "tsql {} crashed at {}\n\n{panic}\n\nBacktrace:\n{backtrace}\n",
+        env!("CARGO_PKG_VERSION"),
+        time.format("%Y-%m-%d %H:%M:%S %:z")
+    );
+    if !buffers.editor.trim().is_empty() {
+        report.push_str("\n-- Query editor\n");
+        report.push_str(&buffers.editor);
+        report.push('\n');
+    }
+    for (index, source) in buffers.notebook_cells.iter().enumerate() {
+        report.push_str(&format!("\n-- Notebook cell {}\n", index + 1));
+        report.push_str(source);
+        report.push('\n');
+    }
+    report
+}
+
+/// Directory for crash reports (`<config_dir>/crashes`, else the temp dir).
+pub fn crash_dir() -> PathBuf {
+    config_dir().map_or_else(std::env::temp_dir, |dir| dir.join("crashes"))
+}
+
+/// Writes `report` to a new file in `dir` and returns its path. Reports can
+/// contain queries, so the file is owner-readable on Unix.
+pub fn write_crash_report(dir: &Path, time: DateTime<Local>, report: &str) -> Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create crash directory: {}", dir.display()))?;
+    let path = dir.join(format!(
+        "tsql-crash-{}-{}.log",
+        time.format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    ));
+    let mut file = fs::File::create(&path)
+        .with_context(|| format!("Failed to create crash report: {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+    file.write_all(report.as_bytes())
+        .with_context(|| format!("Failed to write crash report: {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn reports_include_the_panic_backtrace_and_buffers() {
+        let time = Local.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+        let buffers = CrashBuffers {
+            editor: "SELECT * FROM users".to_string(),
+            notebook_cells: vec!["SELECT 1".to_string()],
+        };
+        let report = crash_report(time, "panicked at src/ui/grid.rs:1:1", "0: main", &buffers);
+        assert!(report.contains("crashed at 2026-10-16 09:30:00"));
+        assert!(report.contains("panicked at src/ui/grid.rs:1:1\n\nBacktrace:\n0: main\n"));
+        assert!(report
+            .ends_with("\n-- Query editor\nSELECT * FROM users\n\n-- Notebook cell 1\nSELECT 1\n"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_crash_report(dir.path(), time, &report).unwrap();
+        assert!(path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("tsql-crash-20261016-093000-"));
+        assert_eq!(fs::read_to_string(path).unwrap(), report);
+    }
+}
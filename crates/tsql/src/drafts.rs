@@ -0,0 +1,179 @@
+//! Editor drafts auto-saved while tsql runs, for recovery after a crash.
+//!
+//! The session is only written on a clean exit. Meanwhile each running
+//! instance keeps `<config_dir>/drafts/<pid>.json` up to date with its editor
+//! buffers and removes it when it exits cleanly, so a draft left by another
+//! process is what a crash or a lost terminal would otherwise have discarded.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::config::config_dir;
+
+/// Characters of the editor buffer shown in the `:drafts` picker.
+const PREVIEW_CHARS: usize = 80;
+
+/// Unsaved editor buffers of one tsql process.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Draft {
+    pub saved_at: DateTime<Utc>,
+    /// Classic query editor content.
+    #[serde(default)]
+    pub editor: String,
+    /// Non-empty notebook cell sources, in document order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notebook_cells: Vec<String>,
+    /// File the draft was read from.
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl Draft {
+    pub fn new(editor: String, notebook_cells: Vec<String>, saved_at: DateTime<Utc>) -> Self {
+        Self {
+            saved_at,
+            editor,
+            notebook_cells,
+            path: PathBuf::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.editor.trim().is_empty() && self.notebook_cells.is_empty()
+    }
+
+    /// One-line form for the `:drafts` picker.
+    pub fn display(&self) -> String {
+        let source = if self.editor.trim().is_empty() {
+            self.notebook_cells.first().map_or("", String::as_str)
+        } else {
+            &self.editor
+        };
+        let preview: String = source
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+        let cells = match self.notebook_cells.len() {
+            0 => String::new(),
+            1 => " +1 cell".to_string(),
+            n => format!(" +{n} cells"),
+        };
+        format!(
+            "{}{cells}  {preview}",
+            self.saved_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+        )
+    }
+}
+
+/// Returns the drafts directory (`<config_dir>/drafts`).
+pub fn drafts_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("drafts"))
+}
+
+/// Draft file of the process `pid` inside `dir`.
+pub fn draft_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("{pid}.json"))
+}
+
+/// Writes a draft atomically; it is owner-readable on Unix.
+pub fn save_draft_to_path(draft: &Draft, path: &Path) -> Result<()> {
+    let parent = path
+        .parent()
+        .context("Draft path has no parent directory")?;
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create drafts directory: {}", parent.display()))?;
+
+    let content = serde_json::to_string_pretty(draft).context("Failed to serialize draft")?;
+    let mut tmp = NamedTempFile::new_in(parent)
+        .with_context(|| format!("Failed to create temp draft file in: {}", parent.display()))?;
+    tmp.write_all(content.as_bytes())
+        .context("Failed to write temp draft file")?;
+    tmp.persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to persist draft file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+/// Removes a draft file; a missing file is not an error.
+pub fn remove_draft(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove draft file: {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Drafts in `dir` other than `own`, newest first. Unreadable and empty
+/// drafts are skipped.
+pub fn load_drafts_from(dir: &Path, own: &Path) -> Vec<Draft> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut drafts: Vec<Draft> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path != own && path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            let mut draft: Draft = serde_json::from_str(&content).ok()?;
+            draft.path = path;
+            (!draft.is_empty()).then_some(draft)
+        })
+        .collect();
+    drafts.sort_by_key(|draft| std::cmp::Reverse(draft.saved_at));
+    drafts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn drafts_of_other_processes_load_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let own = draft_path(dir.path(), 1);
+        let at = |hour| Utc.with_ymd_and_hms(2026, 10, 16, hour, 0, 0).unwrap();
+
+        save_draft_to_path(&Draft::new("SELECT own".into(), Vec::new(), at(9)), &own).unwrap();
+        let older = Draft::new("SELECT 1".into(), Vec::new(), at(7));
+        save_draft_to_path(&older, &draft_path(dir.path(), 2)).unwrap();
+        let newer = Draft::new(String::new(), vec!["SELECT 2".into()], at(8));
+        save_draft_to_path(&newer, &draft_path(dir.path(), 3)).unwrap();
+        let empty = Draft::new("  ".into(), Vec::new(), at(8));
+        save_draft_to_path(&empty, &draft_path(dir.path(), 4)).unwrap();
+        fs::write(draft_path(dir.path(), 5), "not json").unwrap();
+
+        let drafts = load_drafts_from(dir.path(), &own);
+        assert_eq!(
+            drafts
+                .iter()
+                .map(|draft| draft.saved_at)
+                .collect::<Vec<_>>(),
+            [at(8), at(7)]
+        );
+        assert_eq!(drafts[0].notebook_cells, ["SELECT 2"]);
+        assert_eq!(drafts[1].path, draft_path(dir.path(), 2));
+        assert!(drafts[0].display().ends_with(" +1 cell  SELECT 2"));
+
+        remove_draft(&drafts[1].path).unwrap();
+        remove_draft(&drafts[1].path).unwrap();
+        assert_eq!(load_drafts_from(dir.path(), &own).len(), 1);
+    }
+}
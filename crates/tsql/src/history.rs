@@ -2,6 +2,7 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -11,7 +12,7 @@ use nucleo_matcher::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::config::history_path;
+use crate::config::{config_dir, history_path};
 
 const MAX_SAVED_SNIPPETS: usize = 512;
 
@@ -25,20 +26,125 @@ pub struct HistoryEntry {
     /// Optional connection string (sanitized - no passwords).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connection: Option<String>,
+    /// Name of the saved connection the query ran on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection_name: Option<String>,
+    /// How long the query took, once it has finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+    /// Rows returned or affected, when the query succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<u64>,
+    /// Whether the query succeeded; None until it finishes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub succeeded: Option<bool>,
     /// Whether this entry is pinned (immune to pruning).
     #[serde(default)]
     pub pinned: bool,
+    /// Tags from `-- tag:` comments in the query.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl HistoryEntry {
     pub fn new(query: String, connection: Option<String>) -> Self {
         Self {
+            tags: query_tags(&query),
             query,
             timestamp: Utc::now(),
             connection,
+            connection_name: None,
+            elapsed_ms: None,
+            rows: None,
+            succeeded: None,
             pinned: false,
         }
     }
+
+    /// Picker text: outcome, time, rows and connection columns, then the
+    /// query after its tags as `#tag`. Columns are blank while unknown.
+    pub fn display(&self) -> String {
+        let status = match self.succeeded {
+            Some(true) => "✓",
+            Some(false) => "✗",
+            None => " ",
+        };
+        let elapsed = self
+            .elapsed_ms
+            .map_or_else(String::new, |ms| format!("{ms}ms"));
+        let rows = match self.rows {
+            Some(1) => "1 row".to_string(),
+            Some(rows) => format!("{rows} rows"),
+            None => String::new(),
+        };
+        let mut connection: String = self
+            .connection_name
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .take(CONNECTION_COLUMN)
+            .collect();
+        if self
+            .connection_name
+            .as_ref()
+            .is_some_and(|name| name.chars().count() > CONNECTION_COLUMN)
+        {
+            connection.pop();
+            connection.push('…');
+        }
+        format!(
+            "{status} {elapsed:>8} {rows:>10}  {connection:<CONNECTION_COLUMN$}  {}",
+            tagged_display(&self.tags, &self.query)
+        )
+    }
+
+    /// Whether the query ran on the connection with saved `name` or, for
+    /// entries without a name, on the sanitized `connection` string.
+    pub fn ran_on(&self, name: Option<&str>, connection: Option<&str>) -> bool {
+        match (self.connection_name.as_deref(), name) {
+            (Some(entry_name), Some(name)) => entry_name == name,
+            _ => connection.is_some() && self.connection.as_deref() == connection,
+        }
+    }
+}
+
+/// Width of the connection name column in the history picker.
+const CONNECTION_COLUMN: usize = 12;
+
+/// Tags declared by `-- tag: billing` or `-- tags: billing, reports` comment
+/// lines, lowercased and in order of appearance.
+pub fn query_tags(query: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for line in query.lines() {
+        let Some(comment) = line.trim_start().strip_prefix("--") else {
+            continue;
+        };
+        let Some((key, value)) = comment.split_once(':') else {
+            continue;
+        };
+        if !matches!(key.trim().to_lowercase().as_str(), "tag" | "tags") {
+            continue;
+        }
+        for tag in value.split(|c: char| c == ',' || c.is_whitespace()) {
+            let tag = tag.trim_start_matches('#').to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+    tags
+}
+
+fn tagged_display(tags: &[String], text: &str) -> String {
+    if tags.is_empty() {
+        return text.to_string();
+    }
+    let tags = tags
+        .iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{tags}  {text}")
 }
 
 /// A named, reusable query saved independently of execution history.
@@ -55,6 +161,18 @@ pub struct SavedQuerySnippet {
     pub created_at: DateTime<Utc>,
     /// When this snippet was most recently updated.
     pub updated_at: DateTime<Utc>,
+    /// Tags from `-- tag:` comments in the query.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl SavedQuerySnippet {
+    /// Picker text: name and one-line query preview, after its tags as `#tag`.
+    pub fn display(&self) -> String {
+        let query = self.query.split_whitespace().collect::<Vec<_>>().join(" ");
+        let preview = query.chars().take(80).collect::<String>();
+        tagged_display(&self.tags, &format!("{}  {}", self.name, preview))
+    }
 }
 
 /// The history file format.
@@ -89,12 +207,100 @@ pub struct HistoryMatch {
     pub indices: Vec<u32>,
 }
 
+/// Returns a connection's own history file
+/// (`<config_dir>/histories/<name>.json`).
+pub fn connection_history_path(connection: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("histories").join(history_file_name(connection)))
+}
+
+/// File name for a connection's history, keeping only filename-safe characters.
+fn history_file_name(connection: &str) -> String {
+    let stem: String = connection
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{stem}.json")
+}
+
+/// Reads a history file; a missing file has no entries or snippets.
+fn read_history_file(path: &Path) -> Result<HistoryFile> {
+    if !path.exists() {
+        return Ok(HistoryFile::default());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read history file: {}", path.display()))?;
+    let mut file: HistoryFile = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse history file: {}", path.display()))?;
+
+    // Entries saved before tags existed still carry their tag comments.
+    for entry in file
+        .entries
+        .iter_mut()
+        .filter(|entry| entry.tags.is_empty())
+    {
+        entry.tags = query_tags(&entry.query);
+    }
+    for snippet in file
+        .snippets
+        .iter_mut()
+        .filter(|snippet| snippet.tags.is_empty())
+    {
+        snippet.tags = query_tags(&snippet.query);
+    }
+    Ok(file)
+}
+
+fn write_history_file(
+    path: &Path,
+    entries: &[HistoryEntry],
+    snippets: &[SavedQuerySnippet],
+) -> Result<()> {
+    // Ensure parent directory exists.
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let file = HistoryFile {
+        version: 1,
+        entries: entries.to_vec(),
+        snippets: snippets.to_vec(),
+    };
+
+    let content = serde_json::to_string_pretty(&file).context("Failed to serialize history")?;
+
+    fs::write(path, content)
+        .with_context(|| format!("Failed to write history file: {}", path.display()))
+}
+
+/// Enforce the max_entries limit on load, skipping pinned entries when pruning.
+fn prune_loaded(entries: &mut Vec<HistoryEntry>, max_entries: usize) {
+    while entries.len() > max_entries {
+        if let Some(oldest_unpinned) = entries.iter().position(|e| !e.pinned) {
+            entries.remove(oldest_unpinned);
+        } else {
+            break;
+        }
+    }
+}
+
 /// Manages query history with persistence and fuzzy search.
 pub struct History {
     entries: Vec<HistoryEntry>,
     snippets: Vec<SavedQuerySnippet>,
     max_entries: usize,
     path: PathBuf,
+    /// A connection's own file holding `entries` instead of `path`.
+    entries_path: Option<PathBuf>,
+    /// The entries of `path` while `entries_path` is in use.
+    shared_entries: Vec<HistoryEntry>,
     dirty: bool,
 }
 
@@ -107,37 +313,24 @@ impl History {
 
     /// Load history from a specific path.
     pub fn load_from_path(path: &Path, max_entries: usize) -> Result<Self> {
-        let (entries, mut snippets) = if path.exists() {
-            let content = fs::read_to_string(path)
-                .with_context(|| format!("Failed to read history file: {}", path.display()))?;
-
-            let file: HistoryFile = serde_json::from_str(&content)
-                .with_context(|| format!("Failed to parse history file: {}", path.display()))?;
-
-            (file.entries, file.snippets)
-        } else {
-            (Vec::new(), Vec::new())
-        };
+        let HistoryFile {
+            mut entries,
+            mut snippets,
+            ..
+        } = read_history_file(path)?;
 
         if snippets.len() > MAX_SAVED_SNIPPETS {
             snippets.drain(..snippets.len() - MAX_SAVED_SNIPPETS);
         }
-
-        // Enforce max_entries limit on load, skipping pinned entries when pruning.
-        let mut entries = entries;
-        while entries.len() > max_entries {
-            if let Some(oldest_unpinned) = entries.iter().position(|e| !e.pinned) {
-                entries.remove(oldest_unpinned);
-            } else {
-                break;
-            }
-        }
+        prune_loaded(&mut entries, max_entries);
 
         Ok(Self {
             entries,
             snippets,
             max_entries,
             path: path.to_path_buf(),
+            entries_path: None,
+            shared_entries: Vec::new(),
             dirty: false,
         })
     }
@@ -149,32 +342,61 @@ impl History {
             snippets: Vec::new(),
             max_entries,
             path: PathBuf::new(),
+            entries_path: None,
+            shared_entries: Vec::new(),
             dirty: false,
         }
     }
 
-    /// Save history to disk.
-    pub fn save(&mut self) -> Result<()> {
-        if !self.dirty || self.path.as_os_str().is_empty() {
+    /// Keep entries in a connection's own file at `path`, or in the shared
+    /// history file again when None. Snippets always stay in the shared file.
+    /// A connection file that doesn't exist yet starts with the shared
+    /// entries `belongs` picks.
+    pub fn use_entries_file(
+        &mut self,
+        path: Option<PathBuf>,
+        belongs: impl Fn(&HistoryEntry) -> bool,
+    ) -> Result<()> {
+        if path == self.entries_path {
             return Ok(());
         }
-
-        // Ensure parent directory exists.
-        if let Some(parent) = self.path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        self.save()?;
+        if self.entries_path.take().is_some() {
+            self.entries = std::mem::take(&mut self.shared_entries);
         }
+        let Some(path) = path else {
+            return Ok(());
+        };
 
-        let file = HistoryFile {
-            version: 1,
-            entries: self.entries.clone(),
-            snippets: self.snippets.clone(),
+        let mut entries = if path.exists() {
+            read_history_file(&path)?.entries
+        } else {
+            self.dirty = true;
+            self.entries
+                .iter()
+                .filter(|entry| belongs(entry))
+                .cloned()
+                .collect()
         };
+        prune_loaded(&mut entries, self.max_entries);
+        self.shared_entries = std::mem::replace(&mut self.entries, entries);
+        self.entries_path = Some(path);
+        Ok(())
+    }
 
-        let content = serde_json::to_string_pretty(&file).context("Failed to serialize history")?;
+    /// Save history to disk.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.dirty || self.path.as_os_str().is_empty() {
+            return Ok(());
+        }
 
-        fs::write(&self.path, content)
-            .with_context(|| format!("Failed to write history file: {}", self.path.display()))?;
+        match &self.entries_path {
+            Some(entries_path) => {
+                write_history_file(entries_path, &self.entries, &[])?;
+                write_history_file(&self.path, &self.shared_entries, &self.snippets)?;
+            }
+            None => write_history_file(&self.path, &self.entries, &self.snippets)?,
+        }
 
         self.dirty = false;
         Ok(())
@@ -182,12 +404,29 @@ impl History {
 
     /// Add a query to history.
     pub fn push(&mut self, query: String, connection: Option<String>) {
+        self.push_with_tags(query, connection, None, &[]);
+    }
+
+    /// Add a query to history with `extra_tags` besides its own.
+    fn push_with_tags(
+        &mut self,
+        query: String,
+        connection: Option<String>,
+        connection_name: Option<String>,
+        extra_tags: &[String],
+    ) {
         let trimmed = query.trim();
         if trimmed.is_empty() {
             return;
         }
 
-        let entry = HistoryEntry::new(trimmed.to_string(), connection);
+        let mut entry = HistoryEntry::new(trimmed.to_string(), connection);
+        entry.connection_name = connection_name;
+        for tag in extra_tags {
+            if !entry.tags.contains(tag) {
+                entry.tags.push(tag.clone());
+            }
+        }
         self.entries.push(entry);
 
         // Enforce max_entries limit, but never remove pinned entries.
@@ -205,6 +444,40 @@ impl History {
         self.dirty = true;
     }
 
+    /// Add an executed buffer. When it holds several statements each one gets
+    /// its own entry, so search finds it, followed by the whole script. The
+    /// statements share the script's tags.
+    pub fn push_script(
+        &mut self,
+        script: String,
+        statements: &[&str],
+        connection: Option<String>,
+        connection_name: Option<String>,
+    ) {
+        if statements.len() > 1 {
+            let tags = query_tags(&script);
+            for statement in statements {
+                self.push_with_tags(
+                    statement.to_string(),
+                    connection.clone(),
+                    connection_name.clone(),
+                    &tags,
+                );
+            }
+        }
+        self.push_with_tags(script, connection, connection_name, &[]);
+    }
+
+    /// Record how the most recently added query ended.
+    pub fn record_outcome(&mut self, elapsed: Duration, rows: Option<u64>, succeeded: bool) {
+        if let Some(entry) = self.entries.last_mut() {
+            entry.elapsed_ms = Some(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX));
+            entry.rows = rows;
+            entry.succeeded = Some(succeeded);
+            self.dirty = true;
+        }
+    }
+
     /// Toggle the pinned state of the entry at the given index.
     pub fn toggle_pin(&mut self, index: usize) {
         if let Some(entry) = self.entries.get_mut(index) {
@@ -254,6 +527,7 @@ impl History {
             snippet.query = query.to_string();
             snippet.connection = connection;
             snippet.updated_at = now;
+            snippet.tags = query_tags(query);
         } else {
             anyhow::ensure!(
                 self.snippets.len() < MAX_SAVED_SNIPPETS,
@@ -265,6 +539,7 @@ impl History {
                 connection,
                 created_at: now,
                 updated_at: now,
+                tags: query_tags(query),
             });
         }
         self.dirty = true;
@@ -313,6 +588,18 @@ impl History {
         matches.into_iter().map(|(_, snippet)| snippet).collect()
     }
 
+    /// Tags used by history entries, sorted.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .entries
+            .iter()
+            .flat_map(|entry| entry.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
     /// Get the number of entries.
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -473,6 +760,110 @@ mod tests {
         assert_eq!(history.entries()[0].query, "SELECT 1");
     }
 
+    #[test]
+    fn test_scripts_add_each_statement_then_the_script() {
+        let mut history = History::new_empty(100);
+        let script = "SELECT * FROM users;\nDELETE FROM sessions;";
+        history.push_script(
+            script.to_string(),
+            &["SELECT * FROM users", "DELETE FROM sessions"],
+            None,
+            None,
+        );
+        history.push_script("SELECT 1;".to_string(), &["SELECT 1"], None, None);
+
+        let queries: Vec<&str> = history.entries().iter().map(|e| e.query.as_str()).collect();
+        assert_eq!(
+            queries,
+            [
+                "SELECT * FROM users",
+                "DELETE FROM sessions",
+                script,
+                "SELECT 1;"
+            ]
+        );
+        assert!(history
+            .search("sessions")
+            .iter()
+            .any(|found| found.entry.query == "DELETE FROM sessions"));
+    }
+
+    #[test]
+    fn test_query_tags_come_from_tag_comments() {
+        let query = "-- tag: Billing\n  -- tags: reports, #billing nightly\n-- note: x\nSELECT 1 -- tag: inline";
+        assert_eq!(query_tags(query), ["billing", "reports", "nightly"]);
+        assert!(query_tags("SELECT 'tag: no'").is_empty());
+
+        let mut history = History::new_empty(100);
+        history.push("SELECT 2".to_string(), None);
+        history.push_script(
+            "-- tag: ops\nSELECT 3;\nSELECT 4;".to_string(),
+            &["-- tag: ops\nSELECT 3", "SELECT 4"],
+            None,
+            None,
+        );
+        let tags: Vec<String> = history.entries().iter().map(|e| e.tags.join(",")).collect();
+        assert_eq!(tags, ["", "ops", "ops", "ops"]);
+        assert_eq!(history.tags(), ["ops"]);
+        assert!(history.entries()[2].display().ends_with("  #ops  SELECT 4"));
+    }
+
+    #[test]
+    fn test_outcome_and_connection_are_recorded_and_shown() {
+        let path = temp_path();
+        {
+            let mut history = History::load_from_path(&path, 100).unwrap();
+            history.push_script(
+                "SELECT 1".to_string(),
+                &["SELECT 1"],
+                Some("postgres://localhost/app".to_string()),
+                Some("a-very-long-name".to_string()),
+            );
+            history.record_outcome(Duration::from_millis(42), Some(1), true);
+            history.push("SELECT nope".to_string(), None);
+            history.record_outcome(Duration::from_millis(3), None, false);
+            history.save().unwrap();
+        }
+
+        let history = History::load_from_path(&path, 100).unwrap();
+        let [ok, failed] = history.entries() else {
+            panic!("expected two entries");
+        };
+        assert_eq!(
+            (ok.elapsed_ms, ok.rows, ok.succeeded),
+            (Some(42), Some(1), Some(true))
+        );
+        assert_eq!(
+            ok.display(),
+            "✓     42ms      1 row  a-very-long…  SELECT 1"
+        );
+        assert_eq!(
+            failed.display(),
+            "✗      3ms                           SELECT nope"
+        );
+        assert!(ok.ran_on(Some("a-very-long-name"), None));
+        assert!(!ok.ran_on(Some("other"), Some("postgres://localhost/app")));
+        assert!(!failed.ran_on(None, None));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_tags_are_backfilled_on_load() {
+        let path = temp_path();
+        fs::write(
+            &path,
+            r#"{"version":1,"entries":[{"query":"-- tag: billing\nSELECT 1","timestamp":"2026-10-16T09:30:00Z","connection":null}]}"#,
+        )
+        .unwrap();
+
+        let history = History::load_from_path(&path, 100).unwrap();
+        assert_eq!(history.entries()[0].tags, ["billing"]);
+        assert_eq!(history.tags(), ["billing"]);
+
+        fs::remove_file(&path).ok();
+    }
+
     #[test]
     fn test_search_empty_pattern_returns_all_reversed() {
         let mut history = History::new_empty(100);
@@ -683,6 +1074,49 @@ mod tests {
         assert!(history.snippets().is_empty());
     }
 
+    #[test]
+    fn connection_files_keep_their_own_entries_and_share_snippets() {
+        assert_eq!(history_file_name("prod/eu db"), "prod_eu_db.json");
+
+        let path = temp_path();
+        let prod_path = temp_path();
+        let ran_on_prod = |entry: &HistoryEntry| entry.ran_on(Some("prod"), None);
+        {
+            let mut history = History::load_from_path(&path, 100).unwrap();
+            history.push_script("SELECT 'prod'".to_string(), &[], None, Some("prod".into()));
+            history.push_script("SELECT 'dev'".to_string(), &[], None, Some("dev".into()));
+            history
+                .save_snippet("daily", "SELECT 1".into(), None)
+                .unwrap();
+
+            // The first switch copies the connection's entries over.
+            history
+                .use_entries_file(Some(prod_path.clone()), ran_on_prod)
+                .unwrap();
+            assert_eq!(history.len(), 1);
+            history.push_script("SELECT 2".to_string(), &[], None, Some("prod".into()));
+            history.use_entries_file(None, ran_on_prod).unwrap();
+            assert_eq!(history.len(), 2);
+            history
+                .use_entries_file(Some(prod_path.clone()), ran_on_prod)
+                .unwrap();
+        }
+
+        let mut history = History::load_from_path(&path, 100).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.snippets().len(), 1);
+        history
+            .use_entries_file(Some(prod_path.clone()), |_| true)
+            .unwrap();
+        let queries: Vec<&str> = history.entries().iter().map(|e| e.query.as_str()).collect();
+        assert_eq!(queries, ["SELECT 'prod'", "SELECT 2"]);
+        assert_eq!(history.snippets().len(), 1);
+
+        drop(history);
+        fs::remove_file(&path).ok();
+        fs::remove_file(&prod_path).ok();
+    }
+
     #[test]
     fn legacy_history_without_snippets_still_loads() {
         let path = temp_path();
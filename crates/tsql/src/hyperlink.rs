@@ -0,0 +1,86 @@
+//! URL detection, OSC 8 hyperlinks, and opening links in a browser.
+
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Returns the URL stored in a cell, if the whole (trimmed) value is one.
+///
+/// Only `http`/`https` links are recognised so arbitrary schemes stored in the
+/// database are never handed to the system opener.
+pub fn cell_url(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let lower = value.get(..8).unwrap_or(value).to_ascii_lowercase();
+    let rest = if lower.starts_with("https://") {
+        &value[8..]
+    } else if lower.starts_with("http://") {
+        &value[7..]
+    } else {
+        return None;
+    };
+    let valid = !rest.is_empty()
+        && !rest.starts_with('/')
+        && !value.chars().any(|c| c.is_whitespace() || c.is_control());
+    valid.then_some(value)
+}
+
+/// Escape that starts an OSC 8 hyperlink to `url`.
+pub fn osc8_open(url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\")
+}
+
+/// Escape that ends the current OSC 8 hyperlink.
+pub const OSC8_CLOSE: &str = "\x1b]8;;\x1b\\";
+
+/// Opens `url` with the platform's default handler.
+pub fn open_in_browser(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_http_and_https_values() {
+        assert_eq!(
+            cell_url("https://example.com/a?b=1"),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(
+            cell_url("  HTTP://example.com "),
+            Some("HTTP://example.com")
+        );
+    }
+
+    #[test]
+    fn rejects_other_schemes_and_prose() {
+        assert_eq!(cell_url("ftp://example.com"), None);
+        assert_eq!(cell_url("javascript:alert(1)"), None);
+        assert_eq!(cell_url("https://"), None);
+        assert_eq!(cell_url("https:///etc/passwd"), None);
+        assert_eq!(cell_url("see https://example.com"), None);
+        assert_eq!(cell_url("https://example.com\x1b]8;;"), None);
+        assert_eq!(cell_url("http"), None);
+    }
+
+    #[test]
+    fn osc8_wraps_the_target() {
+        assert_eq!(osc8_open("https://x.io"), "\x1b]8;;https://x.io\x1b\\");
+        assert_eq!(OSC8_CLOSE, "\x1b]8;;\x1b\\");
+    }
+}
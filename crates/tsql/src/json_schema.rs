@@ -0,0 +1,369 @@
+//! A JSON Schema subset for checking JSON cell edits before they are saved.
+//!
+//! Covers the keywords that describe a document's shape: `type`, `enum`,
+//! `const`, `properties`, `required`, `additionalProperties`, `items`, the
+//! length, count and range limits, `allOf`/`anyOf`/`oneOf`/`not`, and local
+//! `$ref`s such as `#/$defs/address`. Other keywords, like `pattern` and
+//! `format`, are ignored rather than guessed at.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Deepest schema nesting followed, so a `$ref` cycle cannot recurse forever.
+const MAX_DEPTH: usize = 64;
+
+/// Reads a schema file.
+pub fn load(path: &Path) -> Result<Value, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("cannot read: {e}"))?;
+    let schema: Value = serde_json::from_str(&text).map_err(|e| format!("invalid JSON: {e}"))?;
+    if schema.is_object() || schema.is_boolean() {
+        Ok(schema)
+    } else {
+        Err("a schema must be an object".to_string())
+    }
+}
+
+/// Everything `value` does wrong against `schema`, one message per problem,
+/// each starting with the JSON pointer of the offending value.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut validator = Validator {
+        root: schema,
+        errors: Vec::new(),
+    };
+    validator.check(schema, value, "", 0);
+    validator.errors
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    errors: Vec<String>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, path: &str, message: impl Into<String>) {
+        let path = if path.is_empty() { "/" } else { path };
+        self.errors.push(format!("{path}: {}", message.into()));
+    }
+
+    /// Whether `value` matches `schema`, for the combinators.
+    fn matches(&self, schema: &'a Value, value: &Value, depth: usize) -> bool {
+        let mut probe = Validator {
+            root: self.root,
+            errors: Vec::new(),
+        };
+        probe.check(schema, value, "", depth);
+        probe.errors.is_empty()
+    }
+
+    fn check(&mut self, schema: &'a Value, value: &Value, path: &str, depth: usize) {
+        if depth > MAX_DEPTH {
+            self.error(path, "schema nests too deeply (a $ref cycle?)");
+            return;
+        }
+        let schema_object = match schema {
+            Value::Bool(false) => {
+                self.error(path, "is not allowed");
+                return;
+            }
+            Value::Object(object) => object,
+            _ => return,
+        };
+        let keyword = |name: &str| schema_object.get(name);
+
+        if let Some(reference) = keyword("$ref").and_then(Value::as_str) {
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => self.check(target, value, path, depth + 1),
+                None => self.error(path, format!("cannot resolve $ref {reference}")),
+            }
+        }
+
+        if let Some(types) = keyword("type") {
+            let allowed: Vec<&str> = match types {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => Vec::new(),
+            };
+            if !allowed.is_empty() && !allowed.iter().any(|name| has_type(value, name)) {
+                // Every other keyword would only repeat the mismatch.
+                self.error(
+                    path,
+                    format!(
+                        "expected {}, found {}",
+                        allowed.join(" or "),
+                        type_name(value)
+                    ),
+                );
+                return;
+            }
+        }
+        if let Some(options) = keyword("enum").and_then(Value::as_array) {
+            if !options.contains(value) {
+                let options: Vec<String> = options.iter().map(Value::to_string).collect();
+                self.error(path, format!("must be one of {}", options.join(", ")));
+            }
+        }
+        if let Some(expected) = keyword("const") {
+            if value != expected {
+                self.error(path, format!("must be {expected}"));
+            }
+        }
+
+        match value {
+            Value::Object(object) => {
+                if let Some(required) = keyword("required").and_then(Value::as_array) {
+                    for name in required.iter().filter_map(Value::as_str) {
+                        if !object.contains_key(name) {
+                            self.error(path, format!("missing required property \"{name}\""));
+                        }
+                    }
+                }
+                let count = object.len() as f64;
+                if let Some(min) = keyword("minProperties").and_then(Value::as_f64) {
+                    if count < min {
+                        self.error(path, format!("needs at least {min} properties"));
+                    }
+                }
+                if let Some(max) = keyword("maxProperties").and_then(Value::as_f64) {
+                    if count > max {
+                        self.error(path, format!("allows at most {max} properties"));
+                    }
+                }
+                let properties = keyword("properties").and_then(Value::as_object);
+                for (name, child) in object {
+                    let child_path =
+                        format!("{path}/{}", name.replace('~', "~0").replace('/', "~1"));
+                    match (
+                        properties.and_then(|p| p.get(name)),
+                        keyword("additionalProperties"),
+                    ) {
+                        (Some(property), _) => self.check(property, child, &child_path, depth + 1),
+                        (None, Some(Value::Bool(false))) => {
+                            self.error(&child_path, "is not an allowed property");
+                        }
+                        (None, Some(additional)) => {
+                            self.check(additional, child, &child_path, depth + 1);
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+            Value::Array(items) => {
+                let count = items.len() as f64;
+                if let Some(min) = keyword("minItems").and_then(Value::as_f64) {
+                    if count < min {
+                        self.error(path, format!("needs at least {min} items"));
+                    }
+                }
+                if let Some(max) = keyword("maxItems").and_then(Value::as_f64) {
+                    if count > max {
+                        self.error(path, format!("allows at most {max} items"));
+                    }
+                }
+                if keyword("uniqueItems") == Some(&Value::Bool(true)) {
+                    let repeated = items
+                        .iter()
+                        .enumerate()
+                        .any(|(index, item)| items[..index].contains(item));
+                    if repeated {
+                        self.error(path, "items must be unique");
+                    }
+                }
+                match keyword("items") {
+                    // Positional schemas, as in draft 7 tuples.
+                    Some(Value::Array(schemas)) => {
+                        for (index, (item, item_schema)) in items.iter().zip(schemas).enumerate() {
+                            self.check(item_schema, item, &format!("{path}/{index}"), depth + 1);
+                        }
+                    }
+                    Some(item_schema) => {
+                        for (index, item) in items.iter().enumerate() {
+                            self.check(item_schema, item, &format!("{path}/{index}"), depth + 1);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            Value::String(text) => {
+                let length = text.chars().count() as f64;
+                if let Some(min) = keyword("minLength").and_then(Value::as_f64) {
+                    if length < min {
+                        self.error(path, format!("must be at least {min} characters"));
+                    }
+                }
+                if let Some(max) = keyword("maxLength").and_then(Value::as_f64) {
+                    if length > max {
+                        self.error(path, format!("must be at most {max} characters"));
+                    }
+                }
+            }
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                let limit = |name: &str| keyword(name).and_then(Value::as_f64);
+                if let Some(min) = limit("minimum").filter(|&min| number < min) {
+                    self.error(path, format!("must be at least {min}"));
+                }
+                if let Some(max) = limit("maximum").filter(|&max| number > max) {
+                    self.error(path, format!("must be at most {max}"));
+                }
+                if let Some(min) = limit("exclusiveMinimum").filter(|&min| number <= min) {
+                    self.error(path, format!("must be greater than {min}"));
+                }
+                if let Some(max) = limit("exclusiveMaximum").filter(|&max| number >= max) {
+                    self.error(path, format!("must be less than {max}"));
+                }
+                if let Some(step) = limit("multipleOf").filter(|&step| step > 0.0) {
+                    if ((number / step) - (number / step).round()).abs() > 1e-9 {
+                        self.error(path, format!("must be a multiple of {step}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(schemas) = keyword("allOf").and_then(Value::as_array) {
+            for sub in schemas {
+                self.check(sub, value, path, depth + 1);
+            }
+        }
+        if let Some(schemas) = keyword("anyOf").and_then(Value::as_array) {
+            if !schemas
+                .iter()
+                .any(|sub| self.matches(sub, value, depth + 1))
+            {
+                self.error(path, "does not match any of the allowed shapes (anyOf)");
+            }
+        }
+        if let Some(schemas) = keyword("oneOf").and_then(Value::as_array) {
+            let matched = schemas
+                .iter()
+                .filter(|sub| self.matches(sub, value, depth + 1))
+                .count();
+            if matched != 1 {
+                self.error(
+                    path,
+                    format!("must match exactly one allowed shape (oneOf), matches {matched}"),
+                );
+            }
+        }
+        if let Some(sub) = keyword("not") {
+            if self.matches(sub, value, depth + 1) {
+                self.error(path, "matches a shape it must not (not)");
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value
+            .as_f64()
+            .is_some_and(|number| number.fract() == 0.0 && number.is_finite()),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn preferences_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["theme"],
+            "additionalProperties": false,
+            "properties": {
+                "theme": {"enum": ["light", "dark"]},
+                "font_size": {"type": "integer", "minimum": 8, "maximum": 32},
+                "tags": {"type": "array", "items": {"type": "string", "minLength": 2}},
+                "home": {"$ref": "#/$defs/address"}
+            },
+            "$defs": {
+                "address": {"type": "object", "required": ["city"]}
+            }
+        })
+    }
+
+    #[test]
+    fn valid_documents_pass() {
+        let value =
+            json!({"theme": "dark", "font_size": 14, "tags": ["ab"], "home": {"city": "Lisbon"}});
+        assert_eq!(
+            validate(&preferences_schema(), &value),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn each_violation_is_reported_at_its_path() {
+        let value = json!({
+            "font_size": 14.5,
+            "tags": ["", 3],
+            "home": {},
+            "colour": "red"
+        });
+        assert_eq!(
+            validate(&preferences_schema(), &value),
+            [
+                "/: missing required property \"theme\"",
+                "/font_size: expected integer, found number",
+                "/tags/0: must be at least 2 characters",
+                "/tags/1: expected string, found number",
+                "/home: missing required property \"city\"",
+                "/colour: is not an allowed property",
+            ]
+        );
+        assert_eq!(
+            validate(&preferences_schema(), &json!([])),
+            ["/: expected object, found array"]
+        );
+    }
+
+    #[test]
+    fn combinators_and_ref_cycles() {
+        let schema =
+            json!({"oneOf": [{"type": "string"}, {"type": "integer"}, {"type": "number"}]});
+        assert!(validate(&schema, &json!("x")).is_empty());
+        assert_eq!(
+            validate(&schema, &json!(3)),
+            ["/: must match exactly one allowed shape (oneOf), matches 2"]
+        );
+        let schema = json!({"anyOf": [{"type": "null"}, {"minimum": 0}], "not": {"const": 7}});
+        assert!(validate(&schema, &json!(null)).is_empty());
+        assert_eq!(
+            validate(&schema, &json!(7)),
+            ["/: matches a shape it must not (not)"]
+        );
+        assert_eq!(
+            validate(&schema, &json!(-1)),
+            ["/: does not match any of the allowed shapes (anyOf)"]
+        );
+
+        let cycle = json!({"$ref": "#"});
+        assert_eq!(
+            validate(&cycle, &json!(1)),
+            ["/: schema nests too deeply (a $ref cycle?)"]
+        );
+    }
+}
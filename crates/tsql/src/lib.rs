@@ -2,8 +2,17 @@ pub mod ai;
 pub mod app;
 mod clipboard;
 pub mod config;
+pub mod crash;
+mod drafts;
 pub mod history;
+mod hyperlink;
+mod json_schema;
+mod notes;
+mod notify;
+mod schema_store;
 pub mod session;
+mod snapshots;
+mod ssh_tunnel;
 pub mod ui;
 pub mod update;
 pub mod util;
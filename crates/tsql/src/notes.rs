@@ -0,0 +1,109 @@
+//! Per-connection scratch notes opened with `:notes`.
+//!
+//! Notes are plain markdown files under `<config_dir>/notes/`, one per saved
+//! connection (ad-hoc connections share `default.md`).
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tempfile::NamedTempFile;
+
+use crate::config::config_dir;
+
+/// File stem used when no saved connection is active.
+const DEFAULT_NOTES: &str = "default";
+
+/// Returns the notes file for a connection (`<config_dir>/notes/<name>.md`).
+pub fn notes_path(connection: Option<&str>) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("notes").join(notes_file_name(connection)))
+}
+
+/// File name for a connection's notes, keeping only filename-safe characters.
+fn notes_file_name(connection: Option<&str>) -> String {
+    let stem: String = connection
+        .unwrap_or("")
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        format!("{DEFAULT_NOTES}.md")
+    } else {
+        format!("{stem}.md")
+    }
+}
+
+/// Loads notes from `path`. A missing file is an empty note.
+pub fn load_notes_from_path(path: &Path) -> Result<String> {
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(path)
+        .with_context(|| format!("Failed to read notes file: {}", path.display()))
+}
+
+/// Saves notes atomically. Notes often hold ids and data from the database,
+/// so the file is owner-readable on Unix.
+pub fn save_notes_to_path(notes: &str, path: &Path) -> Result<()> {
+    let parent = path
+        .parent()
+        .context("Notes path has no parent directory")?;
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create notes directory: {}", parent.display()))?;
+
+    let mut tmp = NamedTempFile::new_in(parent)
+        .with_context(|| format!("Failed to create temp notes file in: {}", parent.display()))?;
+    tmp.write_all(notes.as_bytes())
+        .context("Failed to write temp notes file")?;
+    tmp.flush().context("Failed to flush temp notes file")?;
+    tmp.persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to persist notes file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn file_names_are_per_connection_and_filename_safe() {
+        assert_eq!(notes_file_name(Some("prod-db")), "prod-db.md");
+        assert_eq!(notes_file_name(Some("../etc/passwd")), "___etc_passwd.md");
+        assert_eq!(notes_file_name(Some("  ")), "default.md");
+        assert_eq!(notes_file_name(None), "default.md");
+    }
+
+    #[test]
+    fn missing_notes_load_empty() {
+        let dir = tempdir().unwrap();
+        let notes = load_notes_from_path(&dir.path().join("nope.md")).unwrap();
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn notes_round_trip_and_create_the_directory() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes").join("local.md");
+        save_notes_to_path("# Findings\n- order 42 is stuck", &path).unwrap();
+        assert_eq!(
+            load_notes_from_path(&path).unwrap(),
+            "# Findings\n- order 42 is stuck"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Bell and desktop notifications for long-running queries.
+
+use crate::config::{DesktopNotification, NotificationsConfig};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Returns true when a query that ran for `elapsed` should notify.
+///
+/// Terminals that never report focus changes are treated as focused, so by
+/// default nothing fires unless focus reporting works or `when_focused` is set.
+pub fn should_notify(cfg: &NotificationsConfig, elapsed: Duration, focused: bool) -> bool {
+    if cfg.min_duration_secs == 0 || (!cfg.bell && cfg.desktop == DesktopNotification::Off) {
+        return false;
+    }
+    if focused && !cfg.when_focused {
+        return false;
+    }
+    elapsed >= Duration::from_secs(cfg.min_duration_secs)
+}
+
+/// Escape bytes that ring the bell and/or raise an OSC 777 notification.
+pub fn escape_sequence(cfg: &NotificationsConfig, title: &str, body: &str) -> String {
+    let mut out = String::new();
+    if cfg.desktop == DesktopNotification::Osc777 {
+        out.push_str(&format!(
+            "\x1b]777;notify;{};{}\x07",
+            sanitize(title),
+            sanitize(body)
+        ));
+    }
+    if cfg.bell {
+        out.push('\x07');
+    }
+    out
+}
+
+/// Sends the configured notification. Failures are ignored: a missing
+/// `notify-send` must never interrupt the query result.
+pub fn notify(cfg: &NotificationsConfig, title: &str, body: &str) {
+    let seq = escape_sequence(cfg, title, body);
+    if !seq.is_empty() {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(seq.as_bytes());
+        let _ = stdout.flush();
+    }
+    if cfg.desktop == DesktopNotification::NotifySend {
+        // `--` keeps a body that starts with `--`, like a SQL comment, from
+        // being read as an option.
+        let child = Command::new("notify-send")
+            .arg("--app-name=tsql")
+            .arg("--")
+            .arg(title)
+            .arg(body)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        // Reap it off the UI thread so it does not linger as a zombie.
+        if let Ok(mut child) = child {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+    }
+}
+
+/// Strips control characters and the `;` field separator from OSC payloads.
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_control() || c == ';' { ' ' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg() -> NotificationsConfig {
+        NotificationsConfig::default()
+    }
+
+    #[test]
+    fn notifies_only_past_the_threshold_while_unfocused() {
+        let cfg = cfg();
+        assert!(!should_notify(&cfg, Duration::from_secs(9), false));
+        assert!(should_notify(&cfg, Duration::from_secs(10), false));
+        assert!(!should_notify(&cfg, Duration::from_secs(60), true));
+
+        let focused = NotificationsConfig {
+            when_focused: true,
+            ..cfg.clone()
+        };
+        assert!(should_notify(&focused, Duration::from_secs(60), true));
+    }
+
+    #[test]
+    fn zero_threshold_or_no_backend_disables_notifications() {
+        let disabled = NotificationsConfig {
+            min_duration_secs: 0,
+            ..cfg()
+        };
+        assert!(!should_notify(&disabled, Duration::from_secs(600), false));
+
+        let silent = NotificationsConfig {
+            bell: false,
+            ..cfg()
+        };
+        assert!(!should_notify(&silent, Duration::from_secs(600), false));
+    }
+
+    #[test]
+    fn escape_sequence_combines_osc777_and_bell() {
+        assert_eq!(escape_sequence(&cfg(), "tsql", "done"), "\x07");
+
+        let osc = NotificationsConfig {
+            desktop: DesktopNotification::Osc777,
+            bell: false,
+            ..cfg()
+        };
+        assert_eq!(
+            escape_sequence(&osc, "tsql", "3 rows; 12s\n"),
+            "\x1b]777;notify;tsql;3 rows  12s \x07"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Completion schema remembered per connection between runs.
+//!
+//! Loading the live schema of a large database takes a while after
+//! connecting. The tables and columns of the last load are kept in
+//! `<config_dir>/schema/<connection>.json` and shown right away, until the
+//! refresh started on connect replaces them.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::config::config_dir;
+use crate::ui::TableInfo;
+
+/// Current schema cache file version.
+const SCHEMA_CACHE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct StoredSchema {
+    version: u32,
+    saved_at: DateTime<Utc>,
+    tables: Vec<TableInfo>,
+}
+
+/// Returns the cache file of a connection (`<config_dir>/schema/<key>.json`).
+pub fn schema_cache_path(connection: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("schema").join(cache_file_name(connection)))
+}
+
+/// File name for a connection's cache, keeping only filename-safe characters.
+fn cache_file_name(connection: &str) -> String {
+    let stem: String = connection
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{stem}.json")
+}
+
+/// Loads the cached tables, `None` when nothing was cached yet or the file
+/// was written by a newer tsql.
+pub fn load_schema_from_path(path: &Path) -> Result<Option<Vec<TableInfo>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read schema cache: {}", path.display()))?;
+    let stored: StoredSchema = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse schema cache: {}", path.display()))?;
+    Ok((stored.version <= SCHEMA_CACHE_VERSION).then_some(stored.tables))
+}
+
+/// Saves the tables atomically. Schema names can be sensitive, so the file
+/// is owner-readable on Unix.
+pub fn save_schema_to_path(tables: Vec<TableInfo>, path: &Path) -> Result<()> {
+    let parent = path
+        .parent()
+        .context("Schema cache path has no parent directory")?;
+    fs::create_dir_all(parent).with_context(|| {
+        format!(
+            "Failed to create schema cache directory: {}",
+            parent.display()
+        )
+    })?;
+
+    let stored = StoredSchema {
+        version: SCHEMA_CACHE_VERSION,
+        saved_at: Utc::now(),
+        tables,
+    };
+    let json = serde_json::to_string(&stored).context("Failed to serialize schema cache")?;
+    let mut tmp = NamedTempFile::new_in(parent).with_context(|| {
+        format!(
+            "Failed to create temp schema cache file in: {}",
+            parent.display()
+        )
+    })?;
+    tmp.write_all(json.as_bytes())
+        .context("Failed to write temp schema cache file")?;
+    tmp.flush()
+        .context("Failed to flush temp schema cache file")?;
+    tmp.persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to persist schema cache file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::ColumnInfo;
+    use tempfile::tempdir;
+
+    #[test]
+    fn file_names_are_filename_safe() {
+        assert_eq!(cache_file_name("prod-db"), "prod-db.json");
+        assert_eq!(
+            cache_file_name("postgres@localhost:5432/app"),
+            "postgres_localhost_5432_app.json"
+        );
+        assert_eq!(cache_file_name("../etc"), "___etc.json");
+    }
+
+    #[test]
+    fn cached_tables_round_trip_and_newer_versions_are_ignored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("schema").join("local.json");
+        assert!(load_schema_from_path(&path).unwrap().is_none());
+
+        let tables = vec![TableInfo {
+            schema: "public".to_string(),
+            name: "users".to_string(),
+            columns: vec![ColumnInfo {
+                name: "email".to_string(),
+                data_type: "text".to_string(),
+            }],
+        }];
+        save_schema_to_path(tables, &path).unwrap();
+        let loaded = load_schema_from_path(&path).unwrap().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "users");
+        assert_eq!(loaded[0].columns[0].data_type, "text");
+
+        let newer = fs::read_to_string(&path)
+            .unwrap()
+            .replace("\"version\":1", "\"version\":2");
+        fs::write(&path, newer).unwrap();
+        assert!(load_schema_from_path(&path).unwrap().is_none());
+    }
+}
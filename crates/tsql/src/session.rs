@@ -10,7 +10,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tempfile::NamedTempFile;
 
-use crate::config::config_dir;
+use crate::config::{config_dir, PaneLayout};
 
 /// Current session file schema version.
 const SESSION_VERSION: u32 = 2;
@@ -220,6 +220,10 @@ pub struct SessionState {
     #[serde(default = "default_workspace")]
     pub workspace: String,
 
+    /// Classic layout chosen with `:layout`, when it differs from the config default.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub layout: Option<PaneLayout>,
+
     /// Notebook sources and document UI state.
     #[serde(default)]
     pub notebook: NotebookSession,
@@ -233,6 +237,7 @@ impl Default for SessionState {
             schema_expanded: Vec::new(),
             sidebar_visible: false,
             workspace: default_workspace(),
+            layout: None,
             notebook: NotebookSession::default(),
         }
     }
@@ -581,9 +586,30 @@ mod tests {
         assert!(loaded.schema_expanded.is_empty());
         assert!(!loaded.sidebar_visible); // default false
         assert_eq!(loaded.workspace, "classic");
+        assert!(loaded.layout.is_none());
         assert!(loaded.notebook.cells.is_empty());
     }
 
+    #[test]
+    fn test_layout_round_trip_and_omitted_when_unset() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        save_session_to_path(&SessionState::default(), &path).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("layout"));
+
+        let state = SessionState {
+            layout: Some(PaneLayout::SideBySide),
+            ..SessionState::default()
+        };
+        save_session_to_path(&state, &path).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains(r#""layout": "side-by-side""#));
+        let loaded = load_session_from_path(&path).unwrap();
+        assert_eq!(loaded.layout, Some(PaneLayout::SideBySide));
+    }
+
     #[test]
     fn test_notebook_state_round_trip_excludes_runtime_outputs() {
         let dir = tempdir().unwrap();
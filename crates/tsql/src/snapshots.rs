@@ -0,0 +1,191 @@
+//! Named result snapshots saved with `:snapshot save` and reopened with `:snapshot load`.
+//!
+//! Snapshots are JSON files under `<config_dir>/snapshots/`, so a result set can
+//! be captured before a change and compared with the live one in a later session.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+
+use crate::config::config_dir;
+use crate::ui::GridModel;
+
+/// Current snapshot file schema version.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// A result grid as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GridSnapshot {
+    pub version: u32,
+    pub saved_at: DateTime<Utc>,
+    /// SQL that produced the grid, if it came from a query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    #[serde(default)]
+    pub null_cells: Vec<Vec<bool>>,
+    #[serde(default)]
+    pub col_types: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_table: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub primary_keys: Vec<String>,
+}
+
+impl GridSnapshot {
+    pub fn capture(grid: &GridModel, query: Option<&str>, saved_at: DateTime<Utc>) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            saved_at,
+            query: query.map(str::to_string),
+            headers: grid.headers.clone(),
+            rows: grid.rows.clone(),
+            null_cells: grid.null_cells.clone(),
+            col_types: grid.col_types.clone(),
+            source_table: grid.source_table.clone(),
+            primary_keys: grid.primary_keys.clone(),
+        }
+    }
+
+    /// Rebuilds the grid; column widths are recomputed for the current rows.
+    pub fn to_grid(&self) -> GridModel {
+        let mut col_types = self.col_types.clone();
+        col_types.resize(self.headers.len(), String::new());
+        GridModel::new(self.headers.clone(), self.rows.clone())
+            .with_null_cells(self.null_cells.clone())
+            .with_col_types(col_types)
+            .with_source_table(self.source_table.clone())
+            .with_primary_keys(self.primary_keys.clone())
+    }
+}
+
+/// Returns the file for a named snapshot (`<config_dir>/snapshots/<name>.json`).
+pub fn snapshot_path(name: &str) -> Option<PathBuf> {
+    let file_name = snapshot_file_name(name)?;
+    config_dir().map(|dir| dir.join("snapshots").join(file_name))
+}
+
+/// File name for a snapshot, keeping only filename-safe characters.
+fn snapshot_file_name(name: &str) -> Option<String> {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    (!stem.is_empty()).then(|| format!("{stem}.json"))
+}
+
+/// Loads a snapshot saved by this or an older version of tsql.
+pub fn load_snapshot_from_path(path: &Path) -> Result<GridSnapshot> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read snapshot file: {}", path.display()))?;
+    let snapshot: GridSnapshot = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse snapshot file: {}", path.display()))?;
+    anyhow::ensure!(
+        snapshot.version <= SNAPSHOT_VERSION,
+        "Snapshot file version {} is newer than the supported version {}",
+        snapshot.version,
+        SNAPSHOT_VERSION
+    );
+    Ok(snapshot)
+}
+
+/// Saves a snapshot atomically. Result rows are database data, so the file is
+/// owner-readable on Unix.
+pub fn save_snapshot_to_path(snapshot: &GridSnapshot, path: &Path) -> Result<()> {
+    let parent = path
+        .parent()
+        .context("Snapshot path has no parent directory")?;
+    fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create snapshot directory: {}", parent.display()))?;
+
+    let content = serde_json::to_string(snapshot).context("Failed to serialize snapshot")?;
+    let mut tmp = NamedTempFile::new_in(parent).with_context(|| {
+        format!(
+            "Failed to create temp snapshot file in: {}",
+            parent.display()
+        )
+    })?;
+    tmp.write_all(content.as_bytes())
+        .context("Failed to write temp snapshot file")?;
+    tmp.flush().context("Failed to flush temp snapshot file")?;
+    tmp.persist(path)
+        .map_err(|e| anyhow::anyhow!("Failed to persist snapshot file: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn file_names_are_filename_safe() {
+        assert_eq!(
+            snapshot_file_name("before-fix").as_deref(),
+            Some("before-fix.json")
+        );
+        assert_eq!(
+            snapshot_file_name("../orders 1").as_deref(),
+            Some("___orders_1.json")
+        );
+        assert_eq!(snapshot_file_name("  "), None);
+    }
+
+    #[test]
+    fn snapshots_round_trip_the_grid_and_query() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("snapshots").join("before.json");
+        let grid = GridModel::new(
+            vec!["id".to_string(), "note".to_string()],
+            vec![
+                vec!["1".to_string(), "NULL".to_string()],
+                vec!["2".to_string(), "ok".to_string()],
+            ],
+        )
+        .with_null_cells(vec![vec![false, true], vec![false, false]])
+        .with_col_types(vec!["int4".to_string(), "text".to_string()])
+        .with_source_table(Some("orders".to_string()))
+        .with_primary_keys(vec!["id".to_string()]);
+
+        let snapshot = GridSnapshot::capture(&grid, Some("SELECT * FROM orders"), Utc::now());
+        save_snapshot_to_path(&snapshot, &path).unwrap();
+        let loaded = load_snapshot_from_path(&path).unwrap();
+        assert_eq!(loaded, snapshot);
+
+        let restored = loaded.to_grid();
+        assert_eq!(restored.rows, grid.rows);
+        assert_eq!(restored.col_types, grid.col_types);
+        assert!(restored.cell_is_null(0, 1));
+        assert_eq!(restored.primary_keys, ["id"]);
+    }
+
+    #[test]
+    fn newer_snapshot_versions_are_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("future.json");
+        let mut snapshot = GridSnapshot::capture(&GridModel::empty(), None, Utc::now());
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        save_snapshot_to_path(&snapshot, &path).unwrap();
+        assert!(load_snapshot_from_path(&path).is_err());
+    }
+}
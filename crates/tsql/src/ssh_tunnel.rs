@@ -0,0 +1,264 @@
+//! SSH tunnels for saved PostgreSQL connections.
+//!
+//! A tunnel is the system `ssh` client running a local port forward, so keys,
+//! agents, `~/.ssh/config` and known hosts behave as they do in a terminal.
+
+use std::io::Read;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use percent_encoding::percent_decode_str;
+use url::{Host, Url};
+
+use crate::config::SshTunnel;
+
+/// How long ssh gets to connect, authenticate and open the forward.
+const OPEN_TIMEOUT: Duration = Duration::from_secs(20);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A running `ssh -L` forward. Dropping it stops ssh.
+pub struct Tunnel {
+    child: Child,
+    local_port: u16,
+}
+
+impl Tunnel {
+    /// The port on 127.0.0.1 that forwards to the database server.
+    pub fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    /// Starts ssh and waits until the local end of the forward accepts
+    /// connections, which with `ExitOnForwardFailure` means ssh is logged in.
+    fn start(config: &SshTunnel, target_host: &str, target_port: u16) -> Result<Self, String> {
+        let local_port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|listener| listener.local_addr())
+            .map_err(|e| format!("SSH tunnel: no free local port: {e}"))?
+            .port();
+        let mut child = ssh_command(config, local_port, target_host, target_port)
+            .spawn()
+            .map_err(|e| format!("SSH tunnel: cannot run ssh: {e}"))?;
+
+        let local = SocketAddr::from((Ipv4Addr::LOCALHOST, local_port));
+        let deadline = Instant::now() + OPEN_TIMEOUT;
+        loop {
+            if let Ok(Some(status)) = child.try_wait() {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = child.stderr.take() {
+                    let _ = pipe.read_to_string(&mut stderr);
+                }
+                let reason = stderr
+                    .lines()
+                    .map(str::trim)
+                    .rfind(|line| !line.is_empty())
+                    .map_or_else(|| format!("ssh {status}"), str::to_string);
+                return Err(format!("SSH tunnel via {} failed: {reason}", config.host));
+            }
+            if TcpStream::connect_timeout(&local, POLL_INTERVAL).is_ok() {
+                // Keep draining ssh's warnings so a full pipe never stalls it.
+                if let Some(mut pipe) = child.stderr.take() {
+                    std::thread::spawn(move || std::io::copy(&mut pipe, &mut std::io::sink()));
+                }
+                return Ok(Self { child, local_port });
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("SSH tunnel via {} timed out", config.host));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for Tunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Opens `config`'s tunnel to the server `url` points at, and returns `url`
+/// rewritten to go through it.
+pub async fn open(config: &SshTunnel, url: &str) -> Result<(Tunnel, String), String> {
+    let parsed = parse_url(url)?;
+    let target_host = forward_host(&parsed)?;
+    let target_port = parsed.port().unwrap_or(5432);
+    let config = config.clone();
+    let tunnel =
+        tokio::task::spawn_blocking(move || Tunnel::start(&config, &target_host, target_port))
+            .await
+            .map_err(|e| format!("SSH tunnel failed: {e}"))??;
+    let url = tunneled_url(url, tunnel.local_port())?;
+    Ok((tunnel, url))
+}
+
+/// Points `url` at a tunnel's local port. The host stays as typed and only
+/// `hostaddr` moves to 127.0.0.1, so TLS still checks the server's real name.
+pub fn tunneled_url(url: &str, local_port: u16) -> Result<String, String> {
+    let mut parsed = parse_url(url)?;
+    parsed
+        .set_port(Some(local_port))
+        .map_err(|()| "SSH tunnel needs a host in the connection URL".to_string())?;
+    let mut params: Vec<&str> = parsed
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|param| {
+            let key = param.split_once('=').map_or(*param, |(key, _)| key);
+            !param.is_empty() && !key.eq_ignore_ascii_case("hostaddr")
+        })
+        .collect();
+    params.push("hostaddr=127.0.0.1");
+    let query = params.join("&");
+    parsed.set_query(Some(&query));
+    Ok(parsed.into())
+}
+
+fn parse_url(url: &str) -> Result<Url, String> {
+    if !url.starts_with("postgres://") && !url.starts_with("postgresql://") {
+        return Err("SSH tunnels need a postgres:// connection URL".to_string());
+    }
+    Url::parse(url).map_err(|e| format!("Invalid connection URL: {e}"))
+}
+
+/// The database host as ssh's `-L` expects it, resolved on the bastion.
+fn forward_host(url: &Url) -> Result<String, String> {
+    match url.host() {
+        Some(Host::Domain(host)) if !host.is_empty() => {
+            Ok(percent_decode_str(host).decode_utf8_lossy().into_owned())
+        }
+        Some(Host::Ipv4(addr)) => Ok(addr.to_string()),
+        Some(Host::Ipv6(addr)) => Ok(format!("[{addr}]")),
+        _ => Err("SSH tunnel needs a host in the connection URL".to_string()),
+    }
+}
+
+fn ssh_command(
+    config: &SshTunnel,
+    local_port: u16,
+    target_host: &str,
+    target_port: u16,
+) -> Command {
+    let mut command = Command::new("ssh");
+    // BatchMode keeps ssh from prompting on the terminal tsql is drawing to.
+    command
+        .args([
+            "-N",
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "ExitOnForwardFailure=yes",
+        ])
+        .arg("-L")
+        .arg(format!(
+            "127.0.0.1:{local_port}:{target_host}:{target_port}"
+        ));
+    // Leave the default port to ssh so `~/.ssh/config` can still set one.
+    if config.port != 22 {
+        command.arg("-p").arg(config.port.to_string());
+    }
+    if let Some(user) = config.user.as_deref().filter(|user| !user.is_empty()) {
+        command.arg("-l").arg(user);
+    }
+    if let Some(key) = &config.key_path {
+        command
+            .arg("-i")
+            .arg(key)
+            .args(["-o", "IdentitiesOnly=yes"]);
+    }
+    command
+        .arg(&config.host)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn bastion() -> SshTunnel {
+        SshTunnel {
+            host: "bastion.example.com".to_string(),
+            port: 22,
+            user: None,
+            key_path: None,
+        }
+    }
+
+    #[test]
+    fn tunneled_url_keeps_host_for_tls_and_connects_to_local_port() {
+        let url = tunneled_url(
+            "postgres://app@db.internal:6543/main?sslmode=verify-full&hostaddr=10.0.0.5&application_name=my%20app",
+            40123,
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "postgres://app@db.internal:40123/main?sslmode=verify-full&application_name=my%20app&hostaddr=127.0.0.1"
+        );
+
+        assert_eq!(
+            tunneled_url("postgres://app@db.internal/main", 40123).unwrap(),
+            "postgres://app@db.internal:40123/main?hostaddr=127.0.0.1"
+        );
+        assert!(tunneled_url("host=db.internal dbname=main", 40123).is_err());
+    }
+
+    #[test]
+    fn forward_host_brackets_ipv6() {
+        let host = |url| forward_host(&parse_url(url).unwrap()).unwrap();
+        assert_eq!(host("postgres://u@db.internal/db"), "db.internal");
+        assert_eq!(host("postgres://u@10.1.2.3:5433/db"), "10.1.2.3");
+        assert_eq!(host("postgres://u@[fd00::1]/db"), "[fd00::1]");
+    }
+
+    #[test]
+    fn ssh_command_forwards_through_the_bastion() {
+        let args = |config: &SshTunnel| {
+            ssh_command(config, 40123, "db.internal", 5432)
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            args(&bastion()),
+            [
+                "-N",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ExitOnForwardFailure=yes",
+                "-L",
+                "127.0.0.1:40123:db.internal:5432",
+                "bastion.example.com",
+            ]
+        );
+
+        let config = SshTunnel {
+            port: 2222,
+            user: Some("deploy".to_string()),
+            key_path: Some(PathBuf::from("~/.ssh/bastion")),
+            ..bastion()
+        };
+        let args = args(&config);
+        assert_eq!(
+            args[7..],
+            [
+                "-p",
+                "2222",
+                "-l",
+                "deploy",
+                "-i",
+                "~/.ssh/bastion",
+                "-o",
+                "IdentitiesOnly=yes",
+                "bastion.example.com",
+            ]
+        );
+    }
+}
@@ -14,6 +14,7 @@ pub enum PaletteAction {
     GenerateInsert,
     GenerateUpdate,
     GenerateDelete,
+    DrillDown,
     SortAscending,
     SortDescending,
     AddSortAscending,
@@ -155,6 +156,11 @@ pub fn action_entries(context: ActionContext) -> Vec<ActionEntry> {
                 "Generate DELETE template",
                 ":gen delete",
             ),
+            ActionEntry::new(
+                PaletteAction::DrillDown,
+                "Drill down to current value",
+                ":gen where",
+            ),
         ]);
     }
 
@@ -399,6 +405,7 @@ mod tests {
         assert!(actions.contains(&PaletteAction::GenerateInsert));
         assert!(actions.contains(&PaletteAction::GenerateUpdate));
         assert!(actions.contains(&PaletteAction::GenerateDelete));
+        assert!(actions.contains(&PaletteAction::DrillDown));
         assert!(actions.contains(&PaletteAction::RefineCell));
         assert!(actions.contains(&PaletteAction::RunDependents));
         assert!(actions.contains(&PaletteAction::CellHistory));
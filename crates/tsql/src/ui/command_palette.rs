@@ -0,0 +1,172 @@
+//! Entries for the `Ctrl+P` command palette: every keymap action bound in the
+//! focused pane plus every `:command` from the help screen.
+
+use std::collections::HashMap;
+
+use crossterm::event::KeyEvent;
+
+use super::help_popup::command_help;
+use crate::config::{Action, KeyBinding, Keymap};
+
+/// What running a palette entry does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandTarget {
+    /// Replay the action's key binding in the focused pane.
+    Key(KeyEvent),
+    /// Run a `:command` that needs no arguments.
+    Command(String),
+    /// Open the command line with a command that still needs arguments.
+    Prefill(String),
+}
+
+/// A searchable command palette row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommandEntry {
+    pub target: CommandTarget,
+    pub label: String,
+    pub detail: String,
+}
+
+impl CommandEntry {
+    /// Text used by the fuzzy picker for both display and matching.
+    pub fn display(&self) -> String {
+        format!("{}  {}", self.label, self.detail)
+    }
+}
+
+/// Builds the palette: bound actions of `keymap` (alphabetical, with all
+/// their keys) followed by the `:commands` in help order.
+pub fn command_entries(keymap: &Keymap) -> Vec<CommandEntry> {
+    let mut bound: HashMap<Action, Vec<KeyBinding>> = HashMap::new();
+    for (binding, action) in keymap.bindings() {
+        bound.entry(*action).or_default().push(*binding);
+    }
+
+    let mut entries: Vec<CommandEntry> = bound
+        .into_iter()
+        .map(|(action, mut bindings)| {
+            // Plain keys first, so the replayed binding is the simplest one.
+            bindings.sort_by_key(|binding| (binding.modifiers.bits(), binding.to_string()));
+            let keys = bindings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" / ");
+            CommandEntry {
+                target: CommandTarget::Key(KeyEvent::new(bindings[0].code, bindings[0].modifiers)),
+                label: action.description().to_string(),
+                detail: keys,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.label.cmp(&b.label));
+
+    entries.extend(command_help().map(|(keys, description)| CommandEntry {
+        target: command_target(keys),
+        label: description.to_string(),
+        detail: keys.to_string(),
+    }));
+    entries
+}
+
+/// Target for a help entry such as `:export <fmt> <path>` or `:ai [prompt]`.
+/// Only the first of several `/`-separated spellings is used.
+fn command_target(keys: &str) -> CommandTarget {
+    let first = keys.split(" / ").next().unwrap_or(keys);
+    let words: Vec<&str> = first.trim_start_matches(':').split_whitespace().collect();
+    let fixed = words
+        .iter()
+        .take_while(|word| !word.contains(['<', '[', '|']))
+        .count();
+    let command = words[..fixed].join(" ");
+    if only_optional(&words[fixed..].join(" ")) {
+        CommandTarget::Command(command)
+    } else {
+        CommandTarget::Prefill(format!("{command} "))
+    }
+}
+
+/// Whether `rest` consists only of `[optional]` arguments.
+fn only_optional(rest: &str) -> bool {
+    let mut depth = 0usize;
+    for c in rest.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            c if depth == 0 && !c.is_whitespace() => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    #[test]
+    fn commands_run_directly_only_without_required_arguments() {
+        assert_eq!(
+            command_target(":history"),
+            CommandTarget::Command("history".into())
+        );
+        assert_eq!(
+            command_target(":sbt / :sidebar-toggle"),
+            CommandTarget::Command("sbt".into())
+        );
+        assert_eq!(
+            command_target(":mode classic"),
+            CommandTarget::Command("mode classic".into())
+        );
+        assert_eq!(
+            command_target(":ai [prompt]"),
+            CommandTarget::Command("ai".into())
+        );
+        assert_eq!(
+            command_target(":export <fmt> <path>"),
+            CommandTarget::Prefill("export ".into())
+        );
+        assert_eq!(
+            command_target(":result-sql copy|open"),
+            CommandTarget::Prefill("result-sql ".into())
+        );
+        assert_eq!(
+            command_target(":filter [#column|name] eq|ne|null [value]"),
+            CommandTarget::Prefill("filter ".into())
+        );
+        assert_eq!(
+            command_target(":import-connections <path>"),
+            CommandTarget::Prefill("import-connections ".into())
+        );
+    }
+
+    #[test]
+    fn actions_list_every_binding_and_replay_the_plainest_key() {
+        let mut keymap = Keymap::new();
+        keymap.bind(
+            KeyBinding::new(KeyCode::Char('b'), KeyModifiers::CONTROL),
+            Action::ToggleSidebar,
+        );
+        keymap.bind(
+            KeyBinding::new(KeyCode::Char('B'), KeyModifiers::NONE),
+            Action::ToggleSidebar,
+        );
+        keymap.bind(
+            KeyBinding::new(KeyCode::Char('G'), KeyModifiers::NONE),
+            Action::MoveToBottom,
+        );
+
+        let entries = command_entries(&keymap);
+        assert_eq!(entries[0].label, "Move to bottom");
+        assert_eq!(entries[1].label, "Toggle sidebar");
+        assert_eq!(entries[1].detail, "B / Ctrl+B");
+        assert_eq!(
+            entries[1].target,
+            CommandTarget::Key(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::NONE))
+        );
+        assert!(entries
+            .iter()
+            .any(|entry| entry.target == CommandTarget::Command("results".into())));
+    }
+}
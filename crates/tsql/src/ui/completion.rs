@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CompletionKind {
     Keyword,
@@ -191,14 +193,14 @@ pub struct SchemaCache {
     pub loaded: bool,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TableInfo {
     pub schema: String,
     pub name: String,
     pub columns: Vec<ColumnInfo>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ColumnInfo {
     pub name: String,
     #[allow(dead_code)]
@@ -64,6 +64,24 @@ pub enum ConfirmContext {
     ReplaceQuery { query: String },
     /// Replacing the current query with a generated schema query and executing it.
     ReplaceAndExecuteQuery { query: String },
+    /// Running a query whose planner estimate exceeds the cost guard limits.
+    ExpensiveQuery { query: String },
+    /// Running an UPDATE or DELETE from the editor (`sql.confirm_destructive`).
+    DestructiveQuery { query: String },
+    /// Cancelling the current query of another backend (`pg_cancel_backend`).
+    CancelBackend { pid: i32 },
+    /// Terminating another backend's connection (`pg_terminate_backend`).
+    TerminateBackend { pid: i32 },
+    /// Rolling back a pending transaction change and every change after it.
+    RollbackPendingChanges { index: usize },
+    /// Undoing an applied grid edit (`:undo-edit`).
+    UndoEdit { index: usize },
+    /// Deleting grid rows (`dd`) with one DELETE per row.
+    DeleteRows {
+        sql: String,
+        table: String,
+        rows: u64,
+    },
 }
 
 /// A reusable confirmation dialog for unsaved changes.
@@ -142,6 +160,13 @@ impl ConfirmPrompt {
             ConfirmContext::DeleteNotebookCell { .. } => " Delete Notebook Cell ",
             ConfirmContext::ClearNotebookCellExecution { .. } => " Clear Cell Execution ",
             ConfirmContext::ApplyUpdate { .. } => " Apply Update ",
+            ConfirmContext::ExpensiveQuery { .. } => " Expensive Query ",
+            ConfirmContext::DestructiveQuery { .. } => " Confirm Statement ",
+            ConfirmContext::CancelBackend { .. } => " Cancel Backend Query ",
+            ConfirmContext::TerminateBackend { .. } => " Terminate Backend ",
+            ConfirmContext::RollbackPendingChanges { .. } => " Roll Back Changes ",
+            ConfirmContext::UndoEdit { .. } => " Undo Edit ",
+            ConfirmContext::DeleteRows { .. } => " Delete Rows ",
         }
     }
 
@@ -7,6 +7,8 @@
 //! - Save to keychain option
 //! - Test connection functionality
 
+use std::path::PathBuf;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
@@ -17,7 +19,7 @@ use super::{overlay_block, UiTheme};
 use ratatui::Frame;
 use url::Url;
 
-use crate::config::{Action, ConnectionColor, ConnectionEntry, DbKind, Keymap, SslMode};
+use crate::config::{Action, ConnectionColor, ConnectionEntry, DbKind, Keymap, SshTunnel, SslMode};
 
 /// Which field is currently focused in the form
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -40,6 +42,13 @@ pub enum FormField {
     Folder,
     AppName,
     ConnectTimeout,
+    RootCert,
+    ClientCert,
+    ClientKey,
+    SshHost,
+    SshPort,
+    SshUser,
+    SshKey,
 }
 
 impl FormField {
@@ -63,7 +72,14 @@ impl FormField {
             FormField::Tags => FormField::Description,
             FormField::Description => FormField::AppName,
             FormField::AppName => FormField::ConnectTimeout,
-            FormField::ConnectTimeout => FormField::UrlPaste,
+            FormField::ConnectTimeout => FormField::RootCert,
+            FormField::RootCert => FormField::ClientCert,
+            FormField::ClientCert => FormField::ClientKey,
+            FormField::ClientKey => FormField::SshHost,
+            FormField::SshHost => FormField::SshPort,
+            FormField::SshPort => FormField::SshUser,
+            FormField::SshUser => FormField::SshKey,
+            FormField::SshKey => FormField::UrlPaste,
             FormField::UrlPaste => FormField::Name,
         }
     }
@@ -87,7 +103,14 @@ impl FormField {
             FormField::Description => FormField::Tags,
             FormField::AppName => FormField::Description,
             FormField::ConnectTimeout => FormField::AppName,
-            FormField::UrlPaste => FormField::ConnectTimeout,
+            FormField::RootCert => FormField::ConnectTimeout,
+            FormField::ClientCert => FormField::RootCert,
+            FormField::ClientKey => FormField::ClientCert,
+            FormField::SshHost => FormField::ClientKey,
+            FormField::SshPort => FormField::SshHost,
+            FormField::SshUser => FormField::SshPort,
+            FormField::SshKey => FormField::SshUser,
+            FormField::UrlPaste => FormField::SshKey,
         }
     }
 }
@@ -151,6 +174,20 @@ pub struct ConnectionFormModal {
     application_name: String,
     /// Per-connection connect timeout (seconds as a string for editing).
     connect_timeout_secs: String,
+    /// Postgres sslrootcert path.
+    ssl_root_cert: String,
+    /// Postgres sslcert path.
+    ssl_client_cert: String,
+    /// Postgres sslkey path.
+    ssl_client_key: String,
+    /// SSH bastion host; the tunnel is off while it is empty.
+    ssh_host: String,
+    /// SSH bastion port (empty means 22).
+    ssh_port: String,
+    /// SSH login user.
+    ssh_user: String,
+    /// SSH private key path (empty uses the agent).
+    ssh_key: String,
 
     /// Cursor positions for each text field
     name_cursor: usize,
@@ -166,6 +203,13 @@ pub struct ConnectionFormModal {
     folder_cursor: usize,
     application_name_cursor: usize,
     connect_timeout_cursor: usize,
+    ssl_root_cert_cursor: usize,
+    ssl_client_cert_cursor: usize,
+    ssl_client_key_cursor: usize,
+    ssh_host_cursor: usize,
+    ssh_port_cursor: usize,
+    ssh_user_cursor: usize,
+    ssh_key_cursor: usize,
 
     /// Currently focused field
     focused: FormField,
@@ -218,6 +262,13 @@ struct OriginalFormValues {
     folder: String,
     application_name: String,
     connect_timeout_secs: String,
+    ssl_root_cert: String,
+    ssl_client_cert: String,
+    ssl_client_key: String,
+    ssh_host: String,
+    ssh_port: String,
+    ssh_user: String,
+    ssh_key: String,
 }
 
 impl ConnectionFormModal {
@@ -253,6 +304,13 @@ impl ConnectionFormModal {
             folder: String::new(),
             application_name: String::new(),
             connect_timeout_secs: String::new(),
+            ssl_root_cert: String::new(),
+            ssl_client_cert: String::new(),
+            ssl_client_key: String::new(),
+            ssh_host: String::new(),
+            ssh_port: String::new(),
+            ssh_user: String::new(),
+            ssh_key: String::new(),
 
             name_cursor: 0,
             host_cursor: 9, // "localhost".len()
@@ -267,6 +325,13 @@ impl ConnectionFormModal {
             folder_cursor: 0,
             application_name_cursor: 0,
             connect_timeout_cursor: 0,
+            ssl_root_cert_cursor: 0,
+            ssl_client_cert_cursor: 0,
+            ssl_client_key_cursor: 0,
+            ssh_host_cursor: 0,
+            ssh_port_cursor: 0,
+            ssh_user_cursor: 0,
+            ssh_key_cursor: 0,
 
             focused: FormField::Name,
             color_index: 0,
@@ -321,6 +386,19 @@ impl ConnectionFormModal {
             .connect_timeout_secs
             .map(|v| v.to_string())
             .unwrap_or_default();
+        let path_text = |path: &Option<PathBuf>| {
+            path.as_deref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default()
+        };
+        let ssl_root_cert = path_text(&entry.ssl_root_cert);
+        let ssl_client_cert = path_text(&entry.ssl_client_cert);
+        let ssl_client_key = path_text(&entry.ssl_client_key);
+        let tunnel = entry.ssh_tunnel.as_ref();
+        let ssh_host = tunnel.map(|t| t.host.clone()).unwrap_or_default();
+        let ssh_port = tunnel.map(|t| t.port.to_string()).unwrap_or_default();
+        let ssh_user = tunnel.and_then(|t| t.user.clone()).unwrap_or_default();
+        let ssh_key = path_text(&tunnel.and_then(|t| t.key_path.clone()));
 
         let original_values = OriginalFormValues {
             name: entry.name.clone(),
@@ -340,6 +418,13 @@ impl ConnectionFormModal {
             folder: folder.clone(),
             application_name: application_name.clone(),
             connect_timeout_secs: connect_timeout_secs.clone(),
+            ssl_root_cert: ssl_root_cert.clone(),
+            ssl_client_cert: ssl_client_cert.clone(),
+            ssl_client_key: ssl_client_key.clone(),
+            ssh_host: ssh_host.clone(),
+            ssh_port: ssh_port.clone(),
+            ssh_user: ssh_user.clone(),
+            ssh_key: ssh_key.clone(),
         };
 
         let ssl_mode = entry.ssl_mode.unwrap_or(SslMode::Disable);
@@ -349,6 +434,13 @@ impl ConnectionFormModal {
         let folder_cursor = folder.chars().count();
         let application_name_cursor = application_name.chars().count();
         let connect_timeout_cursor = connect_timeout_secs.chars().count();
+        let ssl_root_cert_cursor = ssl_root_cert.chars().count();
+        let ssl_client_cert_cursor = ssl_client_cert.chars().count();
+        let ssl_client_key_cursor = ssl_client_key.chars().count();
+        let ssh_host_cursor = ssh_host.chars().count();
+        let ssh_port_cursor = ssh_port.chars().count();
+        let ssh_user_cursor = ssh_user.chars().count();
+        let ssh_key_cursor = ssh_key.chars().count();
 
         Self {
             name: entry.name.clone(),
@@ -370,6 +462,13 @@ impl ConnectionFormModal {
             folder,
             application_name,
             connect_timeout_secs,
+            ssl_root_cert,
+            ssl_client_cert,
+            ssl_client_key,
+            ssh_host,
+            ssh_port,
+            ssh_user,
+            ssh_key,
 
             name_cursor: entry.name.chars().count(),
             host_cursor: entry.host.chars().count(),
@@ -384,6 +483,13 @@ impl ConnectionFormModal {
             folder_cursor,
             application_name_cursor,
             connect_timeout_cursor,
+            ssl_root_cert_cursor,
+            ssl_client_cert_cursor,
+            ssl_client_key_cursor,
+            ssh_host_cursor,
+            ssh_port_cursor,
+            ssh_user_cursor,
+            ssh_key_cursor,
 
             focused: FormField::Name,
             color_index,
@@ -438,7 +544,14 @@ impl ConnectionFormModal {
                 || !self.tags_input.is_empty()
                 || !self.folder.is_empty()
                 || !self.application_name.is_empty()
-                || !self.connect_timeout_secs.is_empty();
+                || !self.connect_timeout_secs.is_empty()
+                || !self.ssl_root_cert.is_empty()
+                || !self.ssl_client_cert.is_empty()
+                || !self.ssl_client_key.is_empty()
+                || !self.ssh_host.is_empty()
+                || !self.ssh_port.is_empty()
+                || !self.ssh_user.is_empty()
+                || !self.ssh_key.is_empty();
         }
 
         // For editing, compare with original values
@@ -459,7 +572,14 @@ impl ConnectionFormModal {
                 || self.tags_input != orig.tags_input
                 || self.folder != orig.folder
                 || self.application_name != orig.application_name
-                || self.connect_timeout_secs != orig.connect_timeout_secs;
+                || self.connect_timeout_secs != orig.connect_timeout_secs
+                || self.ssl_root_cert != orig.ssl_root_cert
+                || self.ssl_client_cert != orig.ssl_client_cert
+                || self.ssl_client_key != orig.ssl_client_key
+                || self.ssh_host != orig.ssh_host
+                || self.ssh_port != orig.ssh_port
+                || self.ssh_user != orig.ssh_user
+                || self.ssh_key != orig.ssh_key;
         }
 
         false
@@ -701,6 +821,17 @@ impl ConnectionFormModal {
                 &mut self.connect_timeout_secs,
                 &mut self.connect_timeout_cursor,
             )),
+            FormField::RootCert => Some((&mut self.ssl_root_cert, &mut self.ssl_root_cert_cursor)),
+            FormField::ClientCert => {
+                Some((&mut self.ssl_client_cert, &mut self.ssl_client_cert_cursor))
+            }
+            FormField::ClientKey => {
+                Some((&mut self.ssl_client_key, &mut self.ssl_client_key_cursor))
+            }
+            FormField::SshHost => Some((&mut self.ssh_host, &mut self.ssh_host_cursor)),
+            FormField::SshPort => Some((&mut self.ssh_port, &mut self.ssh_port_cursor)),
+            FormField::SshUser => Some((&mut self.ssh_user, &mut self.ssh_user_cursor)),
+            FormField::SshKey => Some((&mut self.ssh_key, &mut self.ssh_key_cursor)),
             FormField::Kind | FormField::SavePassword | FormField::SslMode | FormField::Color => {
                 None
             }
@@ -719,8 +850,10 @@ impl ConnectionFormModal {
 
     fn insert_char(&mut self, c: char) {
         // For port / timeout fields, only allow digits
-        if (self.focused == FormField::Port || self.focused == FormField::ConnectTimeout)
-            && !c.is_ascii_digit()
+        if matches!(
+            self.focused,
+            FormField::Port | FormField::ConnectTimeout | FormField::SshPort
+        ) && !c.is_ascii_digit()
         {
             return;
         }
@@ -913,6 +1046,18 @@ impl ConnectionFormModal {
     }
 
     fn build_entry(&self, name: String, password_for_uri: Option<&str>) -> ConnectionEntry {
+        let path_field = |text: &str| {
+            let text = text.trim();
+            (!text.is_empty()).then(|| PathBuf::from(text))
+        };
+        let ssh_host = self.ssh_host.trim();
+        let ssh_user = self.ssh_user.trim();
+        let ssh_tunnel = (!ssh_host.is_empty()).then(|| SshTunnel {
+            host: ssh_host.to_string(),
+            port: self.ssh_port.trim().parse().unwrap_or(22),
+            user: (!ssh_user.is_empty()).then(|| ssh_user.to_string()),
+            key_path: path_field(&self.ssh_key),
+        });
         let op_ref = self.op_ref.trim();
         let has_op_ref = !op_ref.is_empty();
         let no_password_required = self.password.is_empty() && !self.save_password && !has_op_ref;
@@ -964,6 +1109,10 @@ impl ConnectionFormModal {
                         .parse::<u64>()
                         .ok()
                         .filter(|v| *v > 0),
+                    ssl_root_cert: path_field(&self.ssl_root_cert),
+                    ssl_client_cert: path_field(&self.ssl_client_cert),
+                    ssl_client_key: path_field(&self.ssl_client_key),
+                    ssh_tunnel,
                     ..Default::default()
                 }
             }
@@ -1030,6 +1179,13 @@ impl ConnectionFormModal {
                     .connect_timeout_secs
                     .map(|secs| secs.to_string())
                     .unwrap_or_default();
+                let path_text = |path: Option<PathBuf>| {
+                    path.map(|path| path.display().to_string())
+                        .unwrap_or_default()
+                };
+                self.ssl_root_cert = path_text(entry.ssl_root_cert);
+                self.ssl_client_cert = path_text(entry.ssl_client_cert);
+                self.ssl_client_key = path_text(entry.ssl_client_key);
 
                 if let Some(pwd) = password {
                     self.password = pwd;
@@ -1043,6 +1199,9 @@ impl ConnectionFormModal {
                 self.user_cursor = Self::char_count(&self.user);
                 self.application_name_cursor = Self::char_count(&self.application_name);
                 self.connect_timeout_cursor = Self::char_count(&self.connect_timeout_secs);
+                self.ssl_root_cert_cursor = Self::char_count(&self.ssl_root_cert);
+                self.ssl_client_cert_cursor = Self::char_count(&self.ssl_client_cert);
+                self.ssl_client_key_cursor = Self::char_count(&self.ssl_client_key);
 
                 // Clear URL paste field
                 self.url_paste.clear();
@@ -1061,6 +1220,12 @@ impl ConnectionFormModal {
         }
     }
 
+    /// An empty SSH port means ssh's default.
+    fn ssh_port_is_valid(&self) -> bool {
+        let port = self.ssh_port.trim();
+        port.is_empty() || port.parse::<u16>().is_ok_and(|p| p > 0)
+    }
+
     fn try_save(&mut self) -> ConnectionFormAction {
         // Validate required fields
         if self.name.is_empty() {
@@ -1098,6 +1263,10 @@ impl ConnectionFormModal {
                 }
             };
         }
+        if !self.ssh_port_is_valid() {
+            self.focused = FormField::SshPort;
+            return ConnectionFormAction::StatusMessage("Invalid SSH port number".to_string());
+        }
 
         // --- Issue #16 UX guard ---
         // If the user typed a non-empty password but hasn't selected any
@@ -1160,6 +1329,9 @@ impl ConnectionFormModal {
                 }
             };
         }
+        if !self.ssh_port_is_valid() {
+            return ConnectionFormAction::StatusMessage("Invalid SSH port number".to_string());
+        }
 
         let test_password = if self.password.is_empty() {
             None
@@ -1186,7 +1358,7 @@ impl ConnectionFormModal {
         // Calculate modal size. Taller now that we have metadata fields
         // below the core form.
         let modal_width = 72u16.min(area.width.saturating_sub(4));
-        let modal_height = 28u16.min(area.height.saturating_sub(2));
+        let modal_height = 35u16.min(area.height.saturating_sub(2));
         let modal_x = area.width.saturating_sub(modal_width) / 2;
         let modal_y = area.height.saturating_sub(modal_height) / 2;
 
@@ -1231,6 +1403,13 @@ impl ConnectionFormModal {
             Constraint::Length(1), // Description
             Constraint::Length(1), // AppName
             Constraint::Length(1), // Connect timeout
+            Constraint::Length(1), // Root cert
+            Constraint::Length(1), // Client cert
+            Constraint::Length(1), // Client key
+            Constraint::Length(1), // SSH host
+            Constraint::Length(1), // SSH port
+            Constraint::Length(1), // SSH user
+            Constraint::Length(1), // SSH key
             Constraint::Length(1), // URL paste
             Constraint::Length(1), // Separator
             Constraint::Length(1), // Help line
@@ -1373,6 +1552,76 @@ impl ConnectionFormModal {
             theme,
         );
         i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "CA cert:",
+            &self.ssl_root_cert,
+            self.ssl_root_cert_cursor,
+            FormField::RootCert,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "TLS cert:",
+            &self.ssl_client_cert,
+            self.ssl_client_cert_cursor,
+            FormField::ClientCert,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "TLS key:",
+            &self.ssl_client_key,
+            self.ssl_client_key_cursor,
+            FormField::ClientKey,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "SSH host:",
+            &self.ssh_host,
+            self.ssh_host_cursor,
+            FormField::SshHost,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "SSH port:",
+            &self.ssh_port,
+            self.ssh_port_cursor,
+            FormField::SshPort,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "SSH user:",
+            &self.ssh_user,
+            self.ssh_user_cursor,
+            FormField::SshUser,
+            theme,
+        );
+        i += 1;
+        self.render_text_field(
+            frame,
+            chunks[i],
+            "SSH key:",
+            &self.ssh_key,
+            self.ssh_key_cursor,
+            FormField::SshKey,
+            theme,
+        );
+        i += 1;
         self.render_url_paste_field(frame, chunks[i], theme);
         i += 1;
         self.render_separator(frame, chunks[i], theme);
@@ -1872,6 +2121,10 @@ mod tests {
                 Box::new(|f: &mut ConnectionFormModal| f.connect_timeout_secs = "30".to_string()),
                 "connect_timeout_secs",
             ),
+            (
+                Box::new(|f: &mut ConnectionFormModal| f.ssl_root_cert = "/ca.pem".to_string()),
+                "ssl_root_cert",
+            ),
         ] {
             let mut form = ConnectionFormModal::edit(&entry, None);
             assert!(
@@ -2167,6 +2420,94 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ssl_cert_paths_survive_edit_and_url_paste() {
+        let entry = ConnectionEntry {
+            name: "prod".to_string(),
+            host: "db".to_string(),
+            port: 5432,
+            database: "app".to_string(),
+            user: "u".to_string(),
+            ssl_mode: Some(SslMode::VerifyFull),
+            ssl_root_cert: Some(PathBuf::from("/etc/ssl/ca.pem")),
+            ..Default::default()
+        };
+        let mut form = ConnectionFormModal::edit(&entry, None);
+        assert_eq!(form.ssl_root_cert, "/etc/ssl/ca.pem");
+        form.focused = FormField::ClientCert;
+        for c in "/me.pem".chars() {
+            form.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        form.focused = FormField::RootCert;
+        form.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(form.focused, FormField::ClientCert);
+
+        let built = form.build_entry("prod".to_string(), None);
+        assert_eq!(built.ssl_root_cert, entry.ssl_root_cert);
+        assert_eq!(built.ssl_client_cert, Some(PathBuf::from("/me.pem")));
+        assert_eq!(built.ssl_client_key, None);
+
+        let mut form = ConnectionFormModal::new();
+        form.focused = FormField::UrlPaste;
+        form.url_paste =
+            "postgres://u@db/app?sslmode=verify-ca&sslcert=%2Fc.pem&sslkey=%2Fc.key".to_string();
+        form.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(form.ssl_client_cert, "/c.pem");
+        assert_eq!(form.ssl_client_key, "/c.key");
+        assert_eq!(form.ssl_client_key_cursor, 6);
+        assert!(form.ssl_root_cert.is_empty());
+    }
+
+    #[test]
+    fn test_ssh_tunnel_fields_build_and_edit_the_tunnel() {
+        let type_text = |form: &mut ConnectionFormModal, field, text: &str| {
+            form.focused = field;
+            for c in text.chars() {
+                form.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+            }
+        };
+        let mut form = ConnectionFormModal::new();
+        assert_eq!(form.build_entry("db".to_string(), None).ssh_tunnel, None);
+
+        type_text(&mut form, FormField::SshHost, "bastion");
+        type_text(&mut form, FormField::SshPort, "2x222");
+        type_text(&mut form, FormField::SshKey, "~/.ssh/id_ed25519");
+        assert!(form.is_modified());
+        let built = form.build_entry("db".to_string(), None);
+        let tunnel = SshTunnel {
+            host: "bastion".to_string(),
+            port: 2222,
+            user: None,
+            key_path: Some(PathBuf::from("~/.ssh/id_ed25519")),
+        };
+        assert_eq!(built.ssh_tunnel, Some(tunnel.clone()));
+
+        let entry = ConnectionEntry {
+            name: "prod".to_string(),
+            database: "app".to_string(),
+            user: "u".to_string(),
+            ssh_tunnel: Some(tunnel),
+            ..Default::default()
+        };
+        let mut form = ConnectionFormModal::edit(&entry, None);
+        assert_eq!(form.ssh_port, "2222");
+        assert!(!form.is_modified());
+        type_text(&mut form, FormField::SshUser, "deploy");
+        assert!(form.is_modified());
+        let built = form.build_entry("prod".to_string(), None);
+        assert_eq!(
+            built.ssh_tunnel.and_then(|tunnel| tunnel.user).as_deref(),
+            Some("deploy")
+        );
+
+        form.ssh_port = "70000".to_string();
+        assert_eq!(
+            form.try_save(),
+            ConnectionFormAction::StatusMessage("Invalid SSH port number".to_string())
+        );
+        assert_eq!(form.focused, FormField::SshPort);
+    }
+
     #[test]
     fn test_url_paste_decodes_postgres_username() {
         let mut form = ConnectionFormModal::new();
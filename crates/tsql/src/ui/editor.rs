@@ -107,6 +107,19 @@ impl QueryEditor {
         self.textarea.lines().join("\n")
     }
 
+    /// Byte offset of the cursor in `text()`.
+    pub fn cursor_offset(&self) -> usize {
+        let (row, col) = self.textarea.cursor();
+        let lines = self.textarea.lines();
+        let before: usize = lines.iter().take(row).map(|line| line.len() + 1).sum();
+        let line = lines.get(row).map_or("", String::as_str);
+        before
+            + line
+                .char_indices()
+                .nth(col)
+                .map_or(line.len(), |(index, _)| index)
+    }
+
     /// Check if content differs from last save point.
     pub fn is_modified(&self) -> bool {
         self.text() != self.saved_content
@@ -245,6 +258,29 @@ impl QueryEditor {
         }
     }
 
+    /// The text of the active selection, as `y` would yank it, without
+    /// touching the yank buffer.
+    pub fn selected_text(&self) -> Option<String> {
+        let ((start_row, start_col), (end_row, end_col)) = self.textarea.selection_range()?;
+        let lines = self.textarea.lines();
+        let byte = |row: usize, col: usize| {
+            let line = lines.get(row).map_or("", String::as_str);
+            line.char_indices()
+                .nth(col)
+                .map_or(line.len(), |(index, _)| index)
+        };
+        let text = if start_row == end_row {
+            let line = lines.get(start_row)?;
+            line[byte(start_row, start_col)..byte(end_row, end_col)].to_string()
+        } else {
+            let mut chunk = vec![&lines.get(start_row)?[byte(start_row, start_col)..]];
+            chunk.extend(lines[start_row + 1..end_row].iter().map(String::as_str));
+            chunk.push(&lines.get(end_row)?[..byte(end_row, end_col)]);
+            chunk.join("\n")
+        };
+        (!text.is_empty()).then_some(text)
+    }
+
     fn move_cursor_to(&mut self, row: usize, col: usize) {
         self.textarea.move_cursor(CursorMove::Top);
         for _ in 0..row {
@@ -528,6 +564,40 @@ mod tests {
         assert_eq!(result.unwrap(), "selected text");
     }
 
+    #[test]
+    fn test_cursor_offset_counts_bytes_across_lines() {
+        let mut editor = QueryEditor::new();
+        editor.set_text(
+            "SELECT 'é';
+SELECT 2"
+                .to_string(),
+        );
+        assert_eq!(editor.cursor_offset(), 0);
+        editor.textarea.move_cursor(CursorMove::Jump(0, 10));
+        assert_eq!(editor.cursor_offset(), 11);
+        editor.textarea.move_cursor(CursorMove::Jump(1, 7));
+        assert_eq!(&editor.text()[editor.cursor_offset()..], "2");
+    }
+
+    #[test]
+    fn test_selected_text_spans_lines_and_leaves_yank_buffer() {
+        let mut editor = QueryEditor::new();
+        editor.set_text("SELECT 1;\nSELECT 'é',\n  2;".to_string());
+        assert_eq!(editor.selected_text(), None);
+
+        editor.textarea.move_cursor(CursorMove::Jump(1, 0));
+        editor.textarea.start_selection();
+        editor.textarea.move_cursor(CursorMove::Jump(2, 3));
+        assert_eq!(editor.selected_text().as_deref(), Some("SELECT 'é',\n  2"));
+
+        editor.textarea.cancel_selection();
+        editor.textarea.move_cursor(CursorMove::Jump(1, 10));
+        editor.textarea.start_selection();
+        editor.textarea.move_cursor(CursorMove::Jump(1, 7));
+        assert_eq!(editor.selected_text().as_deref(), Some("'é'"));
+        assert_eq!(editor.textarea.yank_text(), "");
+    }
+
     #[test]
     fn test_replace_char_under_cursor_replaces_single_char() {
         let mut editor = QueryEditor::new();
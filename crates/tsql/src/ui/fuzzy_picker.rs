@@ -24,6 +24,7 @@ use super::{overlay_block, UiTheme};
 
 /// A function that returns an optional styled prefix `(text, style)` for a picker item.
 type PrefixFn<T> = fn(&T) -> Option<(&'static str, Style)>;
+type FilterFn<T> = Box<dyn Fn(&T) -> bool>;
 
 /// Result of handling a key event in the picker.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -77,7 +78,7 @@ pub struct FuzzyPicker<T> {
     /// Optional pre-filter: items where this returns false are hidden entirely.
     /// Crucially, `original_index` still refers to the full `items` slice so that
     /// callers can use it as a stable index into the underlying data source.
-    filter_fn: Option<fn(&T) -> bool>,
+    filter_fn: Option<FilterFn<T>>,
     /// Optional function returning a styled prefix string for an item (not fuzzy-matched).
     prefix_fn: Option<PrefixFn<T>>,
     /// Whether an empty query presents source items newest-first.
@@ -141,8 +142,8 @@ impl<T: Clone> FuzzyPicker<T> {
     /// Items that return `false` are excluded from the visible list, but they
     /// remain in `items` so `original_index` continues to reflect stable positions
     /// into the source collection.
-    pub fn with_filter(mut self, f: fn(&T) -> bool) -> Self {
-        self.filter_fn = Some(f);
+    pub fn with_filter(mut self, f: impl Fn(&T) -> bool + 'static) -> Self {
+        self.filter_fn = Some(Box::new(f));
         self.update_filtered();
         self
     }
@@ -599,7 +600,7 @@ impl<T: Clone> FuzzyPicker<T> {
                 .items
                 .iter()
                 .enumerate()
-                .filter(|(_, item)| self.filter_fn.is_none_or(|f| f(item)))
+                .filter(|(_, item)| self.filter_fn.as_ref().is_none_or(|f| f(item)))
                 .map(|(i, item)| FilteredItem {
                     item: item.clone(),
                     original_index: i,
@@ -617,7 +618,7 @@ impl<T: Clone> FuzzyPicker<T> {
                 .items
                 .iter()
                 .enumerate()
-                .filter(|(_, item)| self.filter_fn.is_none_or(|f| f(item)))
+                .filter(|(_, item)| self.filter_fn.as_ref().is_none_or(|f| f(item)))
                 .filter_map(|(i, item)| {
                     let text = (self.display_fn)(item);
                     let mut indices = Vec::new();
@@ -0,0 +1,327 @@
+//! Preview of a long `:gen` script before it lands anywhere.
+//!
+//! Generating statements for hundreds of selected rows would bury the editor
+//! buffer, so the script is shown here first: its opening statements and how
+//! many there are, with a choice of copying it, inserting it into the editor
+//! or writing it to a file.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use tui_syntax::Theme;
+
+use super::{create_sql_highlighter, overlay_block, UiTheme};
+
+/// Statements highlighted in the preview; the rest are only counted.
+const PREVIEW_STATEMENTS: usize = 50;
+
+/// Result of handling input in the script preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenPreviewResult {
+    /// Still waiting for user input.
+    Pending,
+    /// Copy the script to the clipboard (`y`).
+    Copy,
+    /// Put the script in the editor (`i` or `Enter`).
+    Insert,
+    /// Write the script to the typed path (`w`, then `Enter`).
+    Write(String),
+    /// Discard the script (`q` or `Esc`).
+    Cancelled,
+}
+
+/// A generated script, one statement per line, awaiting a destination.
+pub struct GenPreview {
+    title: String,
+    script: String,
+    statements: usize,
+    lines: Vec<Line<'static>>,
+    /// Scroll offset in wrapped lines.
+    scroll: usize,
+    /// Wrapped line count and viewport height from the last render.
+    total_lines: usize,
+    visible_height: usize,
+    /// The file path being typed after `w`.
+    path: Option<String>,
+    default_path: String,
+}
+
+impl GenPreview {
+    pub fn new(
+        title: impl Into<String>,
+        script: String,
+        default_path: impl Into<String>,
+        syntax_theme: Theme,
+    ) -> Self {
+        let statements = script.lines().filter(|l| !l.trim().is_empty()).count();
+        let shown = script
+            .lines()
+            .take(PREVIEW_STATEMENTS)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut lines = create_sql_highlighter(syntax_theme)
+            .highlight("sql", &shown)
+            .unwrap_or_else(|_| shown.lines().map(|l| Line::from(l.to_string())).collect());
+        let hidden = statements.saturating_sub(PREVIEW_STATEMENTS);
+        if hidden > 0 {
+            lines.push(Line::from(format!(
+                "-- … {hidden} more statement{}",
+                if hidden == 1 { "" } else { "s" }
+            )));
+        }
+        Self {
+            title: title.into(),
+            script,
+            statements,
+            lines,
+            scroll: 0,
+            total_lines: 0,
+            visible_height: 0,
+            path: None,
+            default_path: default_path.into(),
+        }
+    }
+
+    /// The whole script, including the statements not shown.
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+
+    pub fn statement_count(&self) -> usize {
+        self.statements
+    }
+
+    /// Pasted text goes into the file path while one is being typed.
+    pub fn paste(&mut self, text: &str) {
+        if let Some(path) = self.path.as_mut() {
+            path.extend(text.chars().filter(|c| !c.is_control()));
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> GenPreviewResult {
+        if let Some(path) = self.path.as_mut() {
+            match key.code {
+                KeyCode::Enter => {
+                    let path = path.trim().to_string();
+                    if !path.is_empty() {
+                        self.path = None;
+                        return GenPreviewResult::Write(path);
+                    }
+                }
+                KeyCode::Esc => self.path = None,
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Char('u') if key.modifiers == KeyModifiers::CONTROL => path.clear(),
+                KeyCode::Char(c)
+                    if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+                {
+                    path.push(c);
+                }
+                _ => {}
+            }
+            return GenPreviewResult::Pending;
+        }
+
+        let page = self.visible_height.saturating_sub(2).max(1);
+        match key.code {
+            KeyCode::Char('y' | 'c') => return GenPreviewResult::Copy,
+            KeyCode::Char('i') | KeyCode::Enter => return GenPreviewResult::Insert,
+            KeyCode::Char('w') => self.path = Some(self.default_path.clone()),
+            KeyCode::Char('q') | KeyCode::Esc => return GenPreviewResult::Cancelled,
+            KeyCode::Char('j') | KeyCode::Down => self.scroll_down(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll_up(1),
+            KeyCode::Char('d') if key.modifiers == KeyModifiers::CONTROL => {
+                self.scroll_down(page / 2);
+            }
+            KeyCode::Char('u') if key.modifiers == KeyModifiers::CONTROL => {
+                self.scroll_up(page / 2);
+            }
+            KeyCode::PageDown => self.scroll_down(page),
+            KeyCode::PageUp => self.scroll_up(page),
+            KeyCode::Char('g') | KeyCode::Home => self.scroll = 0,
+            KeyCode::Char('G') | KeyCode::End => self.scroll_down(usize::MAX),
+            _ => {}
+        }
+        GenPreviewResult::Pending
+    }
+
+    fn scroll_down(&mut self, amount: usize) {
+        let max_scroll = self.total_lines.saturating_sub(self.visible_height);
+        self.scroll = self.scroll.saturating_add(amount).min(max_scroll);
+    }
+
+    fn scroll_up(&mut self, amount: usize) {
+        self.scroll = self.scroll.saturating_sub(amount);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 100u16.min(area.width.saturating_sub(4));
+        let content_width = usize::from(dialog_width.saturating_sub(2).max(1));
+        self.total_lines = self
+            .lines
+            .iter()
+            .map(|line| line.width().max(1).div_ceil(content_width))
+            .sum();
+        let dialog_height = u16::try_from(self.total_lines)
+            .unwrap_or(u16::MAX)
+            .saturating_add(6)
+            .min(area.height.saturating_sub(2));
+
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        let block =
+            overlay_block(&self.title, theme).border_style(Style::default().fg(theme.warning));
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Summary
+            Constraint::Length(1), // Spacer
+            Constraint::Min(1),    // Statements
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Help text or file path
+        ])
+        .split(inner);
+
+        let summary = if self.statements > PREVIEW_STATEMENTS {
+            format!(
+                "{} statements, showing the first {PREVIEW_STATEMENTS}",
+                self.statements
+            )
+        } else {
+            format!(
+                "{} statement{}",
+                self.statements,
+                if self.statements == 1 { "" } else { "s" }
+            )
+        };
+        frame.render_widget(
+            Paragraph::new(summary).style(Style::default().fg(theme.text_muted)),
+            chunks[0],
+        );
+
+        self.visible_height = usize::from(chunks[2].height);
+        let max_scroll = self.total_lines.saturating_sub(self.visible_height);
+        self.scroll = self.scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(self.lines.clone())
+                .wrap(Wrap { trim: false })
+                .scroll((u16::try_from(self.scroll).unwrap_or(u16::MAX), 0)),
+            chunks[2],
+        );
+
+        let key_style = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        let footer = if let Some(path) = &self.path {
+            Paragraph::new(Line::from(vec![
+                Span::styled("Write to: ", Style::default().fg(theme.text)),
+                Span::styled(
+                    format!("{path}\u{2588}"),
+                    Style::default().fg(theme.warning),
+                ),
+                Span::styled(
+                    "  Enter write · Esc back",
+                    Style::default().fg(theme.text_muted),
+                ),
+            ]))
+        } else {
+            Paragraph::new(Line::from(vec![
+                Span::styled("i/Enter", key_style(theme.success)),
+                Span::raw(" insert  "),
+                Span::styled("y", key_style(theme.success)),
+                Span::raw(" copy  "),
+                Span::styled("w", key_style(theme.success)),
+                Span::raw(" write file  "),
+                Span::styled("j/k", key_style(theme.text_muted)),
+                Span::raw(" scroll  "),
+                Span::styled("q/Esc", key_style(theme.error)),
+                Span::raw(" cancel"),
+            ]))
+            .alignment(Alignment::Center)
+        };
+        frame.render_widget(footer, chunks[4]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn script(statements: usize) -> String {
+        (1..=statements)
+            .map(|id| format!("DELETE FROM users WHERE \"id\" = {id};"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn preview_counts_every_statement_but_highlights_only_the_first() {
+        let preview = GenPreview::new(
+            "DELETE users",
+            script(120),
+            "delete_users.sql",
+            tui_syntax::themes::one_dark(),
+        );
+        assert_eq!(preview.statement_count(), 120);
+        assert_eq!(preview.script().lines().count(), 120);
+        assert_eq!(preview.lines.len(), PREVIEW_STATEMENTS + 1);
+        assert_eq!(
+            preview.lines.last().unwrap().to_string(),
+            "-- … 70 more statements"
+        );
+    }
+
+    #[test]
+    fn keys_choose_where_the_script_goes() {
+        let mut preview = GenPreview::new(
+            "DELETE users",
+            script(20),
+            "delete_users.sql",
+            tui_syntax::themes::one_dark(),
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Char('y'))),
+            GenPreviewResult::Copy
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Enter)),
+            GenPreviewResult::Insert
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Esc)),
+            GenPreviewResult::Cancelled
+        );
+
+        // `w` asks for a path, prefilled; Esc goes back to the choices.
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Char('w'))),
+            GenPreviewResult::Pending
+        );
+        assert_eq!(preview.path.as_deref(), Some("delete_users.sql"));
+        preview.handle_key(key(KeyCode::Esc));
+        assert_eq!(preview.path, None);
+
+        preview.handle_key(key(KeyCode::Char('w')));
+        preview.handle_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        preview.paste("/tmp/");
+        for c in "out.sql".chars() {
+            preview.handle_key(key(KeyCode::Char(c)));
+        }
+        preview.handle_key(key(KeyCode::Backspace));
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Enter)),
+            GenPreviewResult::Write("/tmp/out.sq".to_string())
+        );
+        assert_eq!(preview.path, None);
+    }
+}
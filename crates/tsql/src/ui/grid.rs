@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 
 use std::collections::HashSet;
@@ -24,6 +25,9 @@ const MAX_COLUMN_WIDTH: u16 = 40;
 /// Display width for UUIDs (8 hex chars + ellipsis).
 const UUID_DISPLAY_WIDTH: u16 = 9;
 
+/// Shown, copied and exported in place of a masked value.
+pub const MASKED_VALUE: &str = "********";
+
 /// Action for column resize operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResizeAction {
@@ -54,10 +58,27 @@ pub enum GridKeyResult {
     EditCell { row: usize, col: usize },
     /// Open row detail view.
     OpenRowDetail { row: usize },
+    /// Open the form for inserting a row into the source table.
+    InsertRow,
+    /// Delete the cursor row, or the selected rows, from the source table.
+    DeleteRows,
     /// Display a status message.
     StatusMessage(String),
     /// Go to the first row (from `gg` sequence, handled at app level).
     GotoFirstRow,
+    /// Fetch rows past the `max_rows` cut-off of a truncated result.
+    LoadMore,
+}
+
+/// Rows copied by a pending `y` chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YankTarget {
+    /// The selected rows, or the cursor row when nothing is selected.
+    Selection,
+    /// Only the cursor row, even when rows are selected.
+    Row,
+    /// Every loaded row.
+    All,
 }
 
 /// A match location in the grid (row, column).
@@ -101,7 +122,10 @@ impl GridSearch {
         }
 
         // Find all matches (case-insensitive)
-        for (row_idx, row) in model.rows.iter().enumerate() {
+        for row_idx in 0..model.rows.len() {
+            let Some(row) = model.shown_row(row_idx) else {
+                continue;
+            };
             for (col_idx, cell) in row.iter().enumerate() {
                 if cell.to_lowercase().contains(&self.pattern) {
                     self.matches.push(GridMatch {
@@ -199,26 +223,98 @@ pub struct GridState {
     pub search: GridSearch,
     /// Whether to show full UUIDs (true) or truncated (false, default).
     pub uuid_expanded: bool,
-    /// True when the user has pressed `y` and we are waiting for the format key.
-    pub pending_yank: bool,
+    /// Leading columns kept in view while the rest scroll horizontally.
+    pub pinned_cols: usize,
+    /// Set when the user has pressed `y` and we are waiting for the target or
+    /// format key.
+    pub pending_yank: Option<YankTarget>,
 }
 
 impl GridState {
+    /// This state's cursor, scroll offsets and row selection for `model`, a
+    /// fresh result of the same query. Whatever lies past its end is dropped,
+    /// and so is the search, whose matches belonged to the old rows.
+    pub fn carried_over_to(&self, model: &GridModel) -> Self {
+        let last_row = model.rows.len().saturating_sub(1);
+        let last_col = model.headers.len().saturating_sub(1);
+        let mut selected_rows = self.selected_rows.clone();
+        selected_rows.retain(|&row| row < model.rows.len());
+        Self {
+            row_offset: self.row_offset.min(last_row),
+            col_offset: self.col_offset.min(last_col),
+            cursor_row: self.cursor_row.min(last_row),
+            cursor_col: self.cursor_col.min(last_col),
+            selected_rows,
+            uuid_expanded: self.uuid_expanded,
+            pinned_cols: self.pinned_cols,
+            ..Self::default()
+        }
+    }
+
     /// Returns true if this key should trigger a search prompt (handled by App).
     pub fn handle_key(&mut self, key: KeyEvent, model: &GridModel) -> GridKeyResult {
         let row_count = model.rows.len();
         let col_count = model.headers.len();
 
-        // Pending yank: y was pressed, now waiting for the format key.
-        if self.pending_yank {
-            self.pending_yank = false;
+        // Pending yank: y was pressed, now waiting for a target or format key.
+        if let Some(target) = self.pending_yank.take() {
             if row_count == 0 {
                 return GridKeyResult::None;
             }
-            let indices: Vec<usize> = if self.selected_rows.is_empty() {
-                vec![self.cursor_row]
-            } else {
-                self.selected_rows.iter().copied().collect()
+            if target == YankTarget::Selection {
+                match (key.code, key.modifiers) {
+                    // yv - current cell value
+                    (KeyCode::Char('v'), KeyModifiers::NONE) => {
+                        return match model.shown_cell(self.cursor_row, self.cursor_col) {
+                            Some(cell) => GridKeyResult::Yank {
+                                text: cell.to_string(),
+                                status: "Yanked cell".to_string(),
+                            },
+                            None => GridKeyResult::None,
+                        };
+                    }
+                    // ys - current cell as a SQL literal of its column type
+                    (KeyCode::Char('s'), KeyModifiers::NONE) => {
+                        return match model.cell_as_sql_literal(self.cursor_row, self.cursor_col) {
+                            Some(text) => GridKeyResult::Yank {
+                                text,
+                                status: "Yanked cell as SQL literal".to_string(),
+                            },
+                            None => GridKeyResult::None,
+                        };
+                    }
+                    // y| - every loaded value in the cursor column
+                    (KeyCode::Char('|'), _) => {
+                        let Some(header) = model.headers.get(self.cursor_col) else {
+                            return GridKeyResult::None;
+                        };
+                        let text = (0..row_count)
+                            .filter_map(|row| model.shown_cell(row, self.cursor_col))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        return GridKeyResult::Yank {
+                            text,
+                            status: format!("Yanked column {}", header),
+                        };
+                    }
+                    // yr / ya - pick the rows, then the format
+                    (KeyCode::Char('r'), KeyModifiers::NONE) => {
+                        self.pending_yank = Some(YankTarget::Row);
+                        return GridKeyResult::None;
+                    }
+                    (KeyCode::Char('a'), KeyModifiers::NONE) => {
+                        self.pending_yank = Some(YankTarget::All);
+                        return GridKeyResult::None;
+                    }
+                    _ => {}
+                }
+            }
+            let indices: Vec<usize> = match target {
+                YankTarget::Selection if !self.selected_rows.is_empty() => {
+                    self.selected_rows.iter().copied().collect()
+                }
+                YankTarget::Selection | YankTarget::Row => vec![self.cursor_row],
+                YankTarget::All => (0..row_count).collect(),
             };
             let n = indices.len();
             let label = if n == 1 {
@@ -371,10 +467,11 @@ impl GridState {
                 }
             }
 
-            // Copy controls — y enters pending-yank mode; format key follows.
+            // Copy controls — y opens the yank menu; a target or format key follows.
             // yy=TSV  yY=TSV+headers  yj=JSON  yc=CSV  yC=CSV+headers  ym=Markdown
+            // yv=cell  ys=cell as SQL  y|=column  yr=cursor row  ya=all rows (then a format key)
             (KeyCode::Char('y'), KeyModifiers::NONE) => {
-                self.pending_yank = true;
+                self.pending_yank = Some(YankTarget::Selection);
                 return GridKeyResult::None;
             }
             // c - copy current cell
@@ -383,7 +480,7 @@ impl GridState {
                     return GridKeyResult::None;
                 }
 
-                if let Some(cell) = model.cell(self.cursor_row, self.cursor_col) {
+                if let Some(cell) = model.shown_cell(self.cursor_row, self.cursor_col) {
                     return GridKeyResult::CopyToClipboard(cell.to_string());
                 }
             }
@@ -447,6 +544,18 @@ impl GridState {
                 };
             }
 
+            // O to insert a new row
+            (KeyCode::Char('O'), KeyModifiers::SHIFT)
+            | (KeyCode::Char('O'), KeyModifiers::NONE) => {
+                return GridKeyResult::InsertRow;
+            }
+
+            // M to load the rows past a truncated result's limit
+            (KeyCode::Char('M'), KeyModifiers::SHIFT)
+            | (KeyCode::Char('M'), KeyModifiers::NONE) => {
+                return GridKeyResult::LoadMore;
+            }
+
             _ => {}
         }
 
@@ -579,7 +688,7 @@ impl GridState {
                 if row_count == 0 || col_count == 0 {
                     return GridKeyResult::None;
                 }
-                if let Some(cell) = model.cell(self.cursor_row, self.cursor_col) {
+                if let Some(cell) = model.shown_cell(self.cursor_row, self.cursor_col) {
                     return GridKeyResult::CopyToClipboard(cell.to_string());
                 }
             }
@@ -628,6 +737,8 @@ impl GridState {
                     };
                 }
             }
+            Action::InsertRow => return GridKeyResult::InsertRow,
+            Action::DeleteRows => return GridKeyResult::DeleteRows,
 
             // Display
             Action::ToggleUuidExpand => {
@@ -707,8 +818,21 @@ impl GridState {
         } else {
             self.cursor_col = self.cursor_col.min(col_count - 1);
 
+            // Pinned columns are always drawn; the rest scroll in the width
+            // they leave, starting after them.
+            let pinned = self.pinned_cols.min(col_count);
+            let pinned_width = col_widths
+                .iter()
+                .take(pinned)
+                .filter(|&&w| w > 0)
+                .fold(0u16, |total, &w| total.saturating_add(w).saturating_add(1));
+            let viewport_width = viewport_width.saturating_sub(pinned_width);
+            if pinned < col_count {
+                self.col_offset = self.col_offset.max(pinned);
+            }
+
             // If cursor is before visible area, scroll left
-            if self.cursor_col < self.col_offset {
+            if self.cursor_col >= pinned && self.cursor_col < self.col_offset {
                 self.col_offset = self.cursor_col;
             }
 
@@ -810,6 +934,15 @@ impl GridState {
     }
 }
 
+/// A result column that references another table through a foreign key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignKeyColumn {
+    pub column: String,
+    /// Referenced table, schema-qualified when it is outside the search path.
+    pub references: String,
+}
+
+#[derive(Clone)]
 pub struct GridModel {
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
@@ -820,8 +953,18 @@ pub struct GridModel {
     pub source_table: Option<String>,
     /// Primary key column names for the source table, if known.
     pub primary_keys: Vec<String>,
+    /// Foreign key columns of the source table, if known.
+    pub foreign_keys: Vec<ForeignKeyColumn>,
     /// Column data types from PostgreSQL (e.g., "jsonb", "text", "int4").
     pub col_types: Vec<String>,
+    /// Whether each column of the source table accepts NULL, if known.
+    pub col_nullable: Vec<Option<bool>>,
+    /// Columns whose values are masked (see `display.mask_columns`).
+    pub masked_cols: Vec<bool>,
+    /// Patterns and headers `masked_cols` was last computed for.
+    mask_key: Option<(Vec<String>, Vec<String>)>,
+    /// Columns the user resized, whose widths survive a re-run of the query.
+    pub resized_cols: BTreeSet<usize>,
 }
 
 impl GridModel {
@@ -836,7 +979,12 @@ impl GridModel {
             col_widths,
             source_table: None,
             primary_keys: Vec::new(),
+            foreign_keys: Vec::new(),
             col_types: vec![String::new(); col_count],
+            col_nullable: vec![None; col_count],
+            masked_cols: Vec::new(),
+            mask_key: None,
+            resized_cols: BTreeSet::new(),
         }
     }
 
@@ -847,9 +995,61 @@ impl GridModel {
 
     pub fn with_primary_keys(mut self, keys: Vec<String>) -> Self {
         self.primary_keys = keys;
+        self.fit_header_badges();
+        self
+    }
+
+    pub fn with_foreign_keys(mut self, keys: Vec<ForeignKeyColumn>) -> Self {
+        self.foreign_keys = keys;
+        self.fit_header_badges();
         self
     }
 
+    /// Applies key metadata that was loaded after the rows were shown.
+    pub fn set_key_columns(
+        &mut self,
+        primary_keys: Vec<String>,
+        foreign_keys: Vec<ForeignKeyColumn>,
+    ) {
+        self.primary_keys = primary_keys;
+        self.foreign_keys = foreign_keys;
+        self.fit_header_badges();
+    }
+
+    /// Header text with its key badges, e.g. `id 🔑` or `user_id →users`.
+    pub fn header_label(&self, col: usize) -> String {
+        let Some(header) = self.headers.get(col) else {
+            return String::new();
+        };
+        let mut label = header.clone();
+        if self.primary_keys.contains(header) {
+            label.push_str(" 🔑");
+        }
+        if let Some(key) = self.foreign_keys.iter().find(|key| &key.column == header) {
+            label.push_str(" →");
+            label.push_str(&key.references);
+        }
+        label
+    }
+
+    pub fn header_labels(&self) -> Vec<String> {
+        (0..self.headers.len())
+            .map(|col| self.header_label(col))
+            .collect()
+    }
+
+    /// Widens columns so key badges fit next to their headers.
+    fn fit_header_badges(&mut self) {
+        for col in 0..self.headers.len().min(self.col_widths.len()) {
+            let width = clamp_u16(
+                display_width(&self.header_label(col)) as u16,
+                MIN_COLUMN_WIDTH,
+                MAX_COLUMN_WIDTH,
+            );
+            self.col_widths[col] = self.col_widths[col].max(width);
+        }
+    }
+
     pub fn with_col_types(mut self, types: Vec<String>) -> Self {
         self.col_types = types;
         self
@@ -884,7 +1084,12 @@ impl GridModel {
             col_widths: Vec::new(),
             source_table: None,
             primary_keys: Vec::new(),
+            foreign_keys: Vec::new(),
             col_types: Vec::new(),
+            col_nullable: Vec::new(),
+            masked_cols: Vec::new(),
+            mask_key: None,
+            resized_cols: BTreeSet::new(),
         }
     }
 
@@ -964,9 +1169,83 @@ impl GridModel {
             .map(|s| s.as_str())
     }
 
+    /// Masks the columns whose header matches one of `patterns`; an empty
+    /// list shows every value. The patterns are only matched again once
+    /// they or the headers change.
+    pub fn set_column_masks(&mut self, patterns: &[String]) {
+        if self
+            .mask_key
+            .as_ref()
+            .is_some_and(|(p, h)| p.as_slice() == patterns && *h == self.headers)
+        {
+            return;
+        }
+        self.mask_key = Some((patterns.to_vec(), self.headers.clone()));
+        if patterns.is_empty() {
+            self.masked_cols.clear();
+            return;
+        }
+        self.masked_cols = self
+            .headers
+            .iter()
+            .map(|header| {
+                patterns
+                    .iter()
+                    .any(|pattern| mask_pattern_matches(pattern, header))
+            })
+            .collect();
+    }
+
+    pub fn is_masked(&self, col: usize) -> bool {
+        self.masked_cols.get(col).copied().unwrap_or(false)
+    }
+
+    /// A row as it is shown, copied and exported: masked values are replaced,
+    /// SQL NULLs stay visible.
+    pub fn shown_row(&self, row_idx: usize) -> Option<Cow<'_, [String]>> {
+        let row = self.rows.get(row_idx)?;
+        if !self.masked_cols.contains(&true) {
+            return Some(Cow::Borrowed(row));
+        }
+        Some(Cow::Owned(
+            row.iter()
+                .enumerate()
+                .map(|(col, value)| {
+                    if self.is_masked(col) && !self.cell_is_null(row_idx, col) {
+                        MASKED_VALUE.to_string()
+                    } else {
+                        value.clone()
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// A cell as it is shown and copied; see [`GridModel::shown_row`].
+    pub fn shown_cell(&self, row: usize, col: usize) -> Option<&str> {
+        let value = self.cell(row, col)?;
+        if self.is_masked(col) && !self.cell_is_null(row, col) {
+            Some(MASKED_VALUE)
+        } else {
+            Some(value)
+        }
+    }
+
+    /// A cell as a SQL literal for its column type, ready to paste into a
+    /// predicate: bare numbers and booleans, `E'\\x…'` for bytea, quoted text
+    /// otherwise. Without a known type the value decides, as in `:gen`.
+    pub fn cell_as_sql_literal(&self, row: usize, col: usize) -> Option<String> {
+        let value = self.shown_cell(row, col)?;
+        if self.cell_is_null(row, col) {
+            return Some("NULL".to_string());
+        }
+        let col_type = self.col_type(col).unwrap_or_default();
+        Some(sql_literal(value, col_type))
+    }
+
     /// Format a single row as tab-separated values.
     pub fn row_as_tsv(&self, row_idx: usize) -> Option<String> {
-        self.rows.get(row_idx).map(|row| row.join("\t"))
+        self.shown_row(row_idx).map(|row| row.join("\t"))
     }
 
     /// Format multiple rows as tab-separated values (with headers).
@@ -978,7 +1257,7 @@ impl GridModel {
         }
 
         for &idx in row_indices {
-            if let Some(row) = self.rows.get(idx) {
+            if let Some(row) = self.shown_row(idx) {
                 lines.push(row.join("\t"));
             }
         }
@@ -988,7 +1267,7 @@ impl GridModel {
 
     /// Format a single row as CSV.
     pub fn row_as_csv(&self, row_idx: usize) -> Option<String> {
-        self.rows.get(row_idx).map(|row| {
+        self.shown_row(row_idx).map(|row| {
             row.iter()
                 .map(|cell| escape_csv(cell))
                 .collect::<Vec<_>>()
@@ -1011,7 +1290,7 @@ impl GridModel {
         }
 
         for &idx in row_indices {
-            if let Some(row) = self.rows.get(idx) {
+            if let Some(row) = self.shown_row(idx) {
                 lines.push(
                     row.iter()
                         .map(|cell| escape_csv(cell))
@@ -1026,7 +1305,7 @@ impl GridModel {
 
     /// Format a single row as JSON object.
     pub fn row_as_json(&self, row_idx: usize) -> Option<String> {
-        self.rows.get(row_idx).map(|row| {
+        self.shown_row(row_idx).map(|row| {
             let pairs: Vec<String> = self
                 .headers
                 .iter()
@@ -1036,7 +1315,11 @@ impl GridModel {
                     if self.cell_is_null(row_idx, col) {
                         format!("  \"{}\": null", escape_json(header))
                     } else {
-                        format!("  \"{}\": \"{}\"", escape_json(header), escape_json(value))
+                        format!(
+                            "  \"{}\": {}",
+                            escape_json(header),
+                            json_value(value, self.col_type(col).unwrap_or_default())
+                        )
                     }
                 })
                 .collect();
@@ -1049,7 +1332,7 @@ impl GridModel {
         let objects: Vec<String> = row_indices
             .iter()
             .filter_map(|&idx| {
-                self.rows.get(idx).map(|row| {
+                self.shown_row(idx).map(|row| {
                     let pairs: Vec<String> = self
                         .headers
                         .iter()
@@ -1060,9 +1343,9 @@ impl GridModel {
                                 format!("    \"{}\": null", escape_json(header))
                             } else {
                                 format!(
-                                    "    \"{}\": \"{}\"",
+                                    "    \"{}\": {}",
                                     escape_json(header),
-                                    escape_json(value)
+                                    json_value(value, self.col_type(col).unwrap_or_default())
                                 )
                             }
                         })
@@ -1081,11 +1364,7 @@ impl GridModel {
             return String::new();
         }
 
-        let table = table
-            .split('.')
-            .map(quote_identifier)
-            .collect::<Vec<_>>()
-            .join(".");
+        let table = quote_qualified_identifier(table);
         let columns = self
             .headers
             .iter()
@@ -1094,7 +1373,7 @@ impl GridModel {
             .join(", ");
         row_indices
             .iter()
-            .filter_map(|&row_index| self.rows.get(row_index).map(|row| (row_index, row)))
+            .filter_map(|&row_index| self.shown_row(row_index).map(|row| (row_index, row)))
             .map(|(row_index, row)| {
                 let values = row
                     .iter()
@@ -1133,7 +1412,7 @@ impl GridModel {
         let sep_row = format!("| {} |", vec!["---"; self.headers.len()].join(" | "));
         let data_rows: Vec<String> = row_indices
             .iter()
-            .filter_map(|&idx| self.rows.get(idx))
+            .filter_map(|&idx| self.shown_row(idx))
             .map(|row| {
                 format!(
                     "| {} |",
@@ -1154,6 +1433,7 @@ impl GridModel {
     pub fn widen_column(&mut self, col: usize, amount: u16) {
         if let Some(width) = self.col_widths.get_mut(col) {
             *width = width.saturating_add(amount).min(200); // Max width of 200
+            self.resized_cols.insert(col);
         }
     }
 
@@ -1161,6 +1441,23 @@ impl GridModel {
     pub fn narrow_column(&mut self, col: usize, amount: u16) {
         if let Some(width) = self.col_widths.get_mut(col) {
             *width = width.saturating_sub(amount).max(3); // Min width of 3
+            self.resized_cols.insert(col);
+        }
+    }
+
+    /// Gives the columns resized in `previous`, an earlier result of the same
+    /// query, their widths again. Columns are matched by name.
+    pub fn keep_resized_widths(&mut self, previous: &GridModel) {
+        for &col in &previous.resized_cols {
+            let (Some(header), Some(&width)) =
+                (previous.headers.get(col), previous.col_widths.get(col))
+            else {
+                continue;
+            };
+            if let Some(new_col) = self.headers.iter().position(|h| h == header) {
+                self.col_widths[new_col] = width;
+                self.resized_cols.insert(new_col);
+            }
         }
     }
 
@@ -1184,6 +1481,7 @@ impl GridModel {
         };
         if let Some(width) = self.col_widths.get_mut(col) {
             *width = next;
+            self.resized_cols.insert(col);
         }
     }
 
@@ -1191,7 +1489,7 @@ impl GridModel {
         let min_w: u16 = 3;
         let max_w: u16 = 40;
 
-        let header_width = display_width(&self.headers[col]) as u16;
+        let header_width = display_width(&self.header_label(col)) as u16;
         let max_data_width = self
             .rows
             .iter()
@@ -1213,7 +1511,7 @@ impl GridModel {
         let min_w: u16 = 3;
         let max_w: u16 = 100;
 
-        let header_width = display_width(&self.headers[col]) as u16;
+        let header_width = display_width(&self.header_label(col)) as u16;
         let max_data_width = self
             .rows
             .iter()
@@ -1326,6 +1624,59 @@ impl GridModel {
         }
     }
 
+    /// One UPDATE setting `column` to `value` in every row of `row_indices`,
+    /// found by primary key: `pk IN (...)`, or `(a, b) IN ((...), ...)` for a
+    /// composite key. `None` without the key columns in the result.
+    pub fn generate_bulk_update_sql(
+        &self,
+        table: &str,
+        column: &str,
+        value: &str,
+        row_indices: &[usize],
+    ) -> Option<String> {
+        if !self.has_valid_pk() {
+            return None;
+        }
+        let key_indices: Vec<usize> = self
+            .primary_keys
+            .iter()
+            .filter_map(|key| self.headers.iter().position(|header| header == key))
+            .collect();
+        let keys: Vec<String> = row_indices
+            .iter()
+            .filter_map(|&row_idx| {
+                let row = self.rows.get(row_idx)?;
+                let values = key_indices
+                    .iter()
+                    .map(|&i| row.get(i).map(|value| escape_sql_value(value)))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(match values.as_slice() {
+                    [value] => value.clone(),
+                    _ => format!("({})", values.join(", ")),
+                })
+            })
+            .collect();
+        if keys.is_empty() {
+            return None;
+        }
+        let key_columns: Vec<String> = self
+            .primary_keys
+            .iter()
+            .map(|key| quote_identifier(key))
+            .collect();
+        let key_columns = match key_columns.as_slice() {
+            [key] => key.clone(),
+            _ => format!("({})", key_columns.join(", ")),
+        };
+
+        Some(format!(
+            "UPDATE {table}\nSET {} = {}\nWHERE {key_columns} IN ({})",
+            quote_identifier(column),
+            escape_sql_value(value),
+            keys.join(", ")
+        ))
+    }
+
     /// Generate DELETE SQL statements for specified rows.
     ///
     /// # Arguments
@@ -1413,7 +1764,7 @@ impl GridModel {
 
         let values: Vec<String> = row_indices
             .iter()
-            .filter_map(|&idx| self.rows.get(idx))
+            .filter_map(|&idx| self.shown_row(idx))
             .map(|row| {
                 let vals: Vec<String> = row.iter().map(|v| escape_sql_value(v)).collect();
                 format!("({})", vals.join(", "))
@@ -1431,6 +1782,98 @@ impl GridModel {
             values.join(",\n")
         )
     }
+
+    /// A filtered `SELECT * FROM table` on the values of column `col` in
+    /// `row_indices`: `col = value` for one value, `col IN (...)` for several.
+    ///
+    /// When `current` is already a drill-down into the same table the condition
+    /// is added to it with AND, so repeated drill-downs narrow the query.
+    /// Returns None for a masked column or when none of the rows exist.
+    pub fn drill_down_sql(
+        &self,
+        table: &str,
+        col: usize,
+        row_indices: &[usize],
+        current: &str,
+    ) -> Option<String> {
+        let condition = self.where_condition(col, row_indices)?;
+        let select = format!("SELECT * FROM {}", quote_qualified_identifier(table));
+        let prefix = format!("{} WHERE ", select);
+
+        let current = current.trim().trim_end_matches(';').trim_end();
+        if let Some(conditions) = current.strip_prefix(&prefix) {
+            if conditions.split("\n  AND ").any(|c| c == condition) {
+                return Some(format!("{};", current));
+            }
+            return Some(format!("{}\n  AND {};", current, condition));
+        }
+        Some(format!("{}{};", prefix, condition))
+    }
+
+    fn where_condition(&self, col: usize, row_indices: &[usize]) -> Option<String> {
+        if self.is_masked(col) {
+            return None;
+        }
+        let column = quote_identifier(self.headers.get(col)?);
+        let col_type = self.col_type(col).unwrap_or("");
+
+        let mut has_null = false;
+        let mut values: Vec<String> = Vec::new();
+        for &row in row_indices {
+            let Some(value) = self.cell(row, col) else {
+                continue;
+            };
+            if self.cell_is_null(row, col) {
+                has_null = true;
+                continue;
+            }
+            let literal = sql_literal(value, col_type);
+            if !values.contains(&literal) {
+                values.push(literal);
+            }
+        }
+
+        let mut conditions = Vec::new();
+        match values.as_slice() {
+            [] => {}
+            [value] => conditions.push(format!("{} = {}", column, value)),
+            _ => conditions.push(format!("{} IN ({})", column, values.join(", "))),
+        }
+        if has_null {
+            conditions.push(format!("{} IS NULL", column));
+        }
+        match conditions.len() {
+            0 => None,
+            1 => conditions.pop(),
+            _ => Some(format!("({})", conditions.join(" OR "))),
+        }
+    }
+}
+
+/// Case-insensitive match of a `display.mask_columns` pattern against a column
+/// name, where `*` matches any run of characters.
+pub fn mask_pattern_matches(pattern: &str, header: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let header: Vec<char> = header.to_lowercase().chars().collect();
+    let (mut p, mut h) = (0, 0);
+    // Last `*` seen and the header position it currently stands for.
+    let mut star: Option<(usize, usize)> = None;
+    while h < header.len() {
+        if pattern.get(p) == Some(&'*') {
+            star = Some((p, h));
+            p += 1;
+        } else if pattern.get(p) == Some(&header[h]) {
+            p += 1;
+            h += 1;
+        } else if let Some((star_p, star_h)) = star {
+            p = star_p + 1;
+            h = star_h + 1;
+            star = Some((star_p, star_h + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// Quote a SQL identifier (column/table name).
@@ -1446,6 +1889,37 @@ pub fn quote_identifier(s: &str) -> String {
     }
 }
 
+/// Quote each part of a possibly schema-qualified table name. Parts that are
+/// already double-quoted, like those of a query's source table, are kept as written.
+pub(crate) fn quote_qualified_identifier(name: &str) -> String {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut in_double = false;
+    for ch in name.chars() {
+        match ch {
+            '.' if !in_double => parts.push(std::mem::take(&mut part)),
+            '"' => {
+                in_double = !in_double;
+                part.push(ch);
+            }
+            _ => part.push(ch),
+        }
+    }
+    parts.push(part);
+
+    parts
+        .iter()
+        .map(|part| {
+            if part.len() >= 2 && part.starts_with('"') && part.ends_with('"') {
+                part.clone()
+            } else {
+                quote_identifier(part)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 /// Escape a SQL value for use in a statement.
 pub fn escape_sql_value(s: &str) -> String {
     // Handle NULL
@@ -1467,6 +1941,74 @@ pub fn escape_sql_value(s: &str) -> String {
     format!("'{}'", s.replace('\'', "''"))
 }
 
+/// A non-NULL value as a SQL literal of `col_type`, a type name as
+/// `information_schema.columns` reports it (or `int4`-style).
+pub fn sql_literal(value: &str, col_type: &str) -> String {
+    let quoted = || format!("'{}'", value.replace('\'', "''"));
+    let col_type = col_type.trim().to_ascii_lowercase();
+    match col_type.as_str() {
+        "" if value.is_empty() => quoted(),
+        "" => escape_sql_value(value),
+        // NaN and Infinity are only numbers inside quotes.
+        _ if is_numeric_type(&col_type) => {
+            if is_finite_number(value) {
+                value.to_string()
+            } else {
+                quoted()
+            }
+        }
+        "boolean" | "bool" => match value.to_ascii_lowercase().as_str() {
+            "t" | "true" => "TRUE".to_string(),
+            "f" | "false" => "FALSE".to_string(),
+            _ => quoted(),
+        },
+        "bytea" => format!("E'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        _ => quoted(),
+    }
+}
+
+/// Whether `col_type` (either naming, as for `sql_literal`) holds numbers.
+fn is_numeric_type(col_type: &str) -> bool {
+    let col_type = col_type.trim().to_ascii_lowercase();
+    matches!(
+        col_type.as_str(),
+        "smallint"
+            | "integer"
+            | "bigint"
+            | "int"
+            | "int2"
+            | "int4"
+            | "int8"
+            | "numeric"
+            | "decimal"
+            | "real"
+            | "double precision"
+            | "float4"
+            | "float8"
+            | "oid"
+    ) || col_type.starts_with("numeric(")
+        || col_type.starts_with("decimal(")
+}
+
+fn is_finite_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// A non-NULL value as JSON: numbers, booleans and `json`/`jsonb` keep their
+/// type when the column's is known, anything else is a string.
+fn json_value(value: &str, col_type: &str) -> String {
+    let col_type = col_type.trim().to_ascii_lowercase();
+    match col_type.as_str() {
+        _ if is_numeric_type(&col_type) && is_finite_number(value) => value.to_string(),
+        "boolean" | "bool" if value == "t" || value == "true" => "true".to_string(),
+        "boolean" | "bool" if value == "f" || value == "false" => "false".to_string(),
+        "json" | "jsonb" if serde_json::from_str::<serde_json::Value>(value).is_ok() => {
+            value.to_string()
+        }
+        _ => format!("\"{}\"", escape_json(value)),
+    }
+}
+
 /// Escape a string for CSV output.
 fn escape_csv(s: &str) -> String {
     if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
@@ -1633,13 +2175,20 @@ impl GridViewport<'_> {
             row_number_width,
             self.theme,
         );
+        let layout = column_layout(
+            &self.model.col_widths,
+            self.state.pinned_cols,
+            self.state.col_offset,
+            data_x,
+            data_w,
+        );
         render_row_cells(
             data_x,
             header_area.y,
             data_w,
-            &self.model.headers,
-            &self.model.col_widths,
-            self.state.col_offset,
+            &self.model.header_labels(),
+            &layout,
+            self.state.pinned_cols,
             self.theme.grid_header,
             None,  // No search highlighting for headers
             false, // Headers never have UUID expansion
@@ -1654,6 +2203,12 @@ impl GridViewport<'_> {
             return;
         }
 
+        let numeric_cols: Vec<bool> = self
+            .model
+            .col_types
+            .iter()
+            .map(|col_type| is_numeric_type(col_type))
+            .collect();
         for i in 0..(body_area.height as usize) {
             let row_idx = self.state.row_offset + i;
             if row_idx >= self.model.rows.len() {
@@ -1695,9 +2250,10 @@ impl GridViewport<'_> {
                 data_x,
                 y,
                 data_w,
-                &self.model.rows[row_idx],
-                &self.model.col_widths,
-                self.state.col_offset,
+                &self.model.shown_row(row_idx).unwrap_or_default(),
+                &layout,
+                &numeric_cols,
+                self.state.pinned_cols,
                 row_style,
                 row_idx,
                 cursor_col,
@@ -1726,6 +2282,114 @@ impl GridViewport<'_> {
     }
 }
 
+/// Where a column is drawn on a grid line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSpan {
+    pub col: usize,
+    pub x: u16,
+    /// Drawn width; the last column may be cut short.
+    pub width: u16,
+}
+
+/// The columns drawn in the `width` cells from `x`: the first `pinned_cols`,
+/// then the rest from `col_offset`, each followed by a one-cell separator.
+pub fn column_layout(
+    col_widths: &[u16],
+    pinned_cols: usize,
+    col_offset: usize,
+    x: u16,
+    width: u16,
+) -> Vec<ColumnSpan> {
+    let pinned = pinned_cols.min(col_widths.len());
+    let max_x = x.saturating_add(width);
+    let mut spans = Vec::new();
+    let mut current_x = x;
+    for col in (0..pinned).chain(col_offset.max(pinned)..col_widths.len()) {
+        if current_x >= max_x {
+            break;
+        }
+        let w = col_widths[col];
+        if w == 0 {
+            continue;
+        }
+        let width = w.min(max_x - current_x);
+        spans.push(ColumnSpan {
+            col,
+            x: current_x,
+            width,
+        });
+        current_x = current_x.saturating_add(width).saturating_add(1).min(max_x);
+    }
+    spans
+}
+
+/// A visible body cell whose value is a URL, in screen coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridLink {
+    pub area: Rect,
+    pub url: String,
+    /// The text rendered in `area`, used to check nothing was drawn over it.
+    pub text: String,
+}
+
+/// Lists the URL cells visible in a grid viewport laid out like
+/// [`GridViewport`] renders it into `area`.
+pub fn visible_links(
+    model: &GridModel,
+    state: &GridState,
+    area: Rect,
+    show_row_numbers: bool,
+) -> Vec<GridLink> {
+    let mut links = Vec::new();
+    if area.width == 0 || area.height < 2 || model.headers.is_empty() {
+        return links;
+    }
+    let row_number_width = if show_row_numbers && !model.rows.is_empty() {
+        (model.rows.len().to_string().len() as u16) + 1
+    } else {
+        0
+    };
+    let marker_w: u16 = 3 + row_number_width;
+    let layout = column_layout(
+        &model.col_widths,
+        state.pinned_cols,
+        state.col_offset,
+        area.x.saturating_add(marker_w),
+        area.width.saturating_sub(marker_w),
+    );
+
+    for i in 0..usize::from(area.height - 1) {
+        let row_idx = state.row_offset + i;
+        let Some(row) = model.shown_row(row_idx) else {
+            break;
+        };
+        let y = area.y + 1 + i as u16;
+        for span in &layout {
+            let Some(cell) = row.get(span.col) else {
+                continue;
+            };
+            let Some(url) = crate::hyperlink::cell_url(cell) else {
+                continue;
+            };
+            if model.cell_is_null(row_idx, span.col) {
+                continue;
+            }
+            let text = format_cell_for_display(cell, span.width, state.uuid_expanded)
+                .trim_end()
+                .to_string();
+            let text_w = display_width(&text) as u16;
+            if text_w > 0 {
+                links.push(GridLink {
+                    area: Rect::new(span.x, y, text_w, 1),
+                    url: url.to_string(),
+                    text,
+                });
+            }
+        }
+    }
+    links
+}
+
 fn render_marker_header(
     area: Rect,
     buf: &mut Buffer,
@@ -1794,12 +2458,12 @@ fn render_marker_cell(
 
 #[allow(clippy::too_many_arguments)]
 fn render_row_cells(
-    mut x: u16,
+    x: u16,
     y: u16,
     available_w: u16,
     cells: &[String],
-    col_widths: &[u16],
-    col_offset: usize,
+    layout: &[ColumnSpan],
+    pinned_cols: usize,
     style: Style,
     _search: Option<&GridSearch>, // Optional search state for highlighting
     uuid_expanded: bool,
@@ -1809,51 +2473,33 @@ fn render_row_cells(
         return;
     }
 
-    let padding: u16 = 1;
-    let max_x = x.saturating_add(available_w);
-
-    let mut col = col_offset;
-    while col < cells.len() && col < col_widths.len() && x < max_x {
-        let w = col_widths[col];
-        if w == 0 {
-            col += 1;
-            continue;
-        }
-
-        let remaining = max_x - x;
-        if remaining == 0 {
-            break;
+    buf.set_string(x, y, " ".repeat(usize::from(available_w)), style);
+    for span in layout {
+        if let Some(cell) = cells.get(span.col) {
+            let content = format_cell_for_display(cell, span.width, uuid_expanded);
+            buf.set_string(span.x, y, content, style);
         }
-
-        // Allow a partially visible last column.
-        let draw_w = w.min(remaining);
-        let content = format_cell_for_display(&cells[col], draw_w, uuid_expanded);
-        buf.set_string(x, y, content, style);
-        x += draw_w;
-
-        if x < max_x {
-            buf.set_string(x, y, " ", style);
-            x = x.saturating_add(padding).min(max_x);
-        }
-
-        col += 1;
-    }
-
-    while x < max_x {
-        buf.set_string(x, y, " ", style);
-        x += 1;
     }
+    render_pinned_edge(
+        layout,
+        pinned_cols,
+        x.saturating_add(available_w),
+        y,
+        style,
+        buf,
+    );
 }
 
 /// Render row cells with search highlighting and cursor column.
 #[allow(clippy::too_many_arguments)]
 fn render_row_cells_with_search(
-    mut x: u16,
+    x: u16,
     y: u16,
     available_w: u16,
     cells: &[String],
-    col_widths: &[u16],
-    col_offset: usize,
+    layout: &[ColumnSpan],
+    numeric_cols: &[bool],
+    pinned_cols: usize,
     base_style: Style,
     row_idx: usize,
     cursor_col: Option<usize>,
@@ -1866,51 +2512,57 @@ fn render_row_cells_with_search(
         return;
     }
 
-    let padding: u16 = 1;
-    let max_x = x.saturating_add(available_w);
-
-    let mut col = col_offset;
-    while col < cells.len() && col < col_widths.len() && x < max_x {
-        let w = col_widths[col];
-        if w == 0 {
-            col += 1;
+    buf.set_string(x, y, " ".repeat(usize::from(available_w)), base_style);
+    for span in layout {
+        let Some(cell) = cells.get(span.col) else {
             continue;
-        }
-
-        let remaining = max_x - x;
-        if remaining == 0 {
-            break;
-        }
+        };
 
         // Determine cell style based on cursor position and search state
-        let is_cursor_cell = cursor_col == Some(col);
+        let is_cursor_cell = cursor_col == Some(span.col);
         let cell_style = if is_cursor_cell {
             theme.cursor_cell
-        } else if search.is_current_match(row_idx, col) {
+        } else if search.is_current_match(row_idx, span.col) {
             theme.search_match_current
-        } else if search.is_match(row_idx, col) {
+        } else if search.is_match(row_idx, span.col) {
             theme.search_match
         } else {
             base_style
         };
 
-        // Allow a partially visible last column.
-        let draw_w = w.min(remaining);
-        let content = format_cell_for_display(&cells[col], draw_w, uuid_expanded);
-        buf.set_string(x, y, content, cell_style);
-        x += draw_w;
+        // Numbers line up on their last digit.
+        let content = if numeric_cols.get(span.col).copied().unwrap_or(false) {
+            fit_to_width_right(cell, span.width)
+        } else {
+            format_cell_for_display(cell, span.width, uuid_expanded)
+        };
+        buf.set_string(span.x, y, content, cell_style);
+    }
+    render_pinned_edge(
+        layout,
+        pinned_cols,
+        x.saturating_add(available_w),
+        y,
+        base_style,
+        buf,
+    );
+}
 
+/// Draws the separator after the last pinned column as a rule, so it is
+/// clear where the scrolling columns start.
+fn render_pinned_edge(
+    layout: &[ColumnSpan],
+    pinned_cols: usize,
+    max_x: u16,
+    y: u16,
+    style: Style,
+    buf: &mut Buffer,
+) {
+    if let Some(edge) = layout.iter().rev().find(|span| span.col < pinned_cols) {
+        let x = edge.x.saturating_add(edge.width);
         if x < max_x {
-            buf.set_string(x, y, " ", base_style);
-            x = x.saturating_add(padding).min(max_x);
+            buf.set_string(x, y, "│", style);
         }
-
-        col += 1;
-    }
-
-    while x < max_x {
-        buf.set_string(x, y, " ", base_style);
-        x += 1;
     }
 }
 
@@ -1993,6 +2645,15 @@ fn format_cell_for_display(s: &str, width: u16, uuid_expanded: bool) -> String {
     fit_to_width(s, width)
 }
 
+/// Like `fit_to_width`, but pads on the left.
+fn fit_to_width_right(s: &str, width: u16) -> String {
+    let current = display_width(s);
+    match usize::from(width).checked_sub(current) {
+        Some(padding) if padding > 0 => format!("{}{s}", " ".repeat(padding)),
+        _ => fit_to_width(s, width),
+    }
+}
+
 fn fit_to_width(s: &str, width: u16) -> String {
     let width = width as usize;
     if width == 0 {
@@ -2104,6 +2765,58 @@ mod tests {
         assert_eq!(result, "1\tAlice", "Should not include header row");
     }
 
+    #[test]
+    fn mask_patterns_are_case_insensitive_globs() {
+        assert!(mask_pattern_matches("password", "Password"));
+        assert!(mask_pattern_matches("*_token", "api_TOKEN"));
+        assert!(mask_pattern_matches("*ssn*", "user_ssn_hash"));
+        assert!(!mask_pattern_matches("*_token", "token"));
+        assert!(!mask_pattern_matches("ssn", "ssn_last4"));
+    }
+
+    #[test]
+    fn masked_columns_are_hidden_in_copies_but_keep_nulls() {
+        let mut model = GridModel::new(
+            vec!["id".to_string(), "api_token".to_string()],
+            vec![
+                vec!["1".to_string(), "secret".to_string()],
+                vec!["2".to_string(), "NULL".to_string()],
+            ],
+        )
+        .with_null_cells(vec![vec![false, false], vec![false, true]]);
+        model.set_column_masks(&["*_token".to_string()]);
+
+        assert_eq!(model.shown_cell(0, 0), Some("1"));
+        assert_eq!(model.shown_cell(0, 1), Some(MASKED_VALUE));
+        assert_eq!(model.shown_cell(1, 1), Some("NULL"));
+        assert_eq!(
+            model.rows_as_csv(&[0, 1], true),
+            "id,api_token\n1,********\n2,NULL"
+        );
+        assert_eq!(model.cell(0, 1), Some("secret"));
+
+        model.set_column_masks(&[]);
+        assert_eq!(model.row_as_tsv(0).as_deref(), Some("1\tsecret"));
+    }
+
+    #[test]
+    fn column_masks_follow_header_changes() {
+        let patterns = vec!["*_token".to_string()];
+        let mut model = GridModel::new(
+            vec!["id".to_string(), "api_token".to_string()],
+            vec![vec!["1".to_string(), "secret".to_string()]],
+        );
+        model.set_column_masks(&patterns);
+        assert_eq!(model.masked_cols, vec![false, true]);
+
+        model.set_column_masks(&patterns);
+        assert_eq!(model.masked_cols, vec![false, true]);
+
+        model.headers.swap(0, 1);
+        model.set_column_masks(&patterns);
+        assert_eq!(model.masked_cols, vec![true, false]);
+    }
+
     #[test]
     fn rows_as_json_escapes_all_control_characters() {
         let model = GridModel::new(
@@ -2143,6 +2856,46 @@ mod tests {
         assert_eq!(rows[0]["empty"], "");
     }
 
+    #[test]
+    fn rows_as_json_keeps_numbers_booleans_and_json_typed() {
+        let model = GridModel::new(
+            vec![
+                "id".to_string(),
+                "price".to_string(),
+                "ratio".to_string(),
+                "active".to_string(),
+                "doc".to_string(),
+                "note".to_string(),
+            ],
+            vec![vec![
+                "7".to_string(),
+                "12.50".to_string(),
+                "NaN".to_string(),
+                "t".to_string(),
+                "{\"a\": [1, 2]}".to_string(),
+                "42".to_string(),
+            ]],
+        )
+        .with_col_types(vec![
+            "int4".to_string(),
+            "numeric(10,2)".to_string(),
+            "float8".to_string(),
+            "boolean".to_string(),
+            "jsonb".to_string(),
+            "text".to_string(),
+        ]);
+
+        let rows: serde_json::Value = serde_json::from_str(&model.rows_as_json(&[0])).unwrap();
+        assert_eq!(rows[0]["id"], 7);
+        assert_eq!(rows[0]["price"], 12.5);
+        assert_eq!(rows[0]["ratio"], "NaN");
+        assert_eq!(rows[0]["active"], true);
+        assert_eq!(rows[0]["doc"]["a"][1], 2);
+        assert_eq!(rows[0]["note"], "42");
+        let row: serde_json::Value = serde_json::from_str(&model.row_as_json(0).unwrap()).unwrap();
+        assert_eq!(row, rows[0]);
+    }
+
     #[test]
     fn rows_as_sql_inserts_quotes_identifiers_escapes_values_and_skips_missing_rows() {
         let model = GridModel::new(
@@ -2188,6 +2941,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rows_as_sql_inserts_keeps_already_quoted_table_parts() {
+        let model = GridModel::new(vec!["id".to_string()], vec![vec!["1".to_string()]]);
+        assert_eq!(
+            model.rows_as_sql_inserts(&[0], "\"My.Schema\".\"Users\""),
+            "INSERT INTO \"My.Schema\".\"Users\" (id) VALUES ('1');"
+        );
+    }
+
     #[test]
     fn rows_as_sql_inserts_returns_empty_when_there_is_nothing_to_export() {
         let model = create_test_model();
@@ -2210,7 +2972,11 @@ mod tests {
             matches!(result, GridKeyResult::None),
             "First 'y' should return None (pending)"
         );
-        assert!(state.pending_yank, "'y' should set pending_yank");
+        assert_eq!(
+            state.pending_yank,
+            Some(YankTarget::Selection),
+            "'y' should set pending_yank"
+        );
 
         let upper_y = KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT);
         let result = state.handle_key(upper_y, &model);
@@ -2228,6 +2994,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ys_yanks_cell_as_sql_literal_of_its_type() {
+        let model = GridModel::new(
+            vec![
+                "id".to_string(),
+                "name".to_string(),
+                "active".to_string(),
+                "payload".to_string(),
+                "note".to_string(),
+            ],
+            vec![vec![
+                "42".to_string(),
+                "O'Brien".to_string(),
+                "t".to_string(),
+                "\\x0aff".to_string(),
+                "NULL".to_string(),
+            ]],
+        )
+        .with_col_types(vec![
+            "integer".to_string(),
+            "text".to_string(),
+            "boolean".to_string(),
+            "bytea".to_string(),
+            "text".to_string(),
+        ])
+        .with_null_cells(vec![vec![false, false, false, false, true]]);
+        let literal = |col| model.cell_as_sql_literal(0, col).unwrap();
+        assert_eq!(literal(0), "42");
+        assert_eq!(literal(1), "'O''Brien'");
+        assert_eq!(literal(2), "TRUE");
+        assert_eq!(literal(3), "E'\\\\x0aff'");
+        assert_eq!(literal(4), "NULL");
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut state = GridState {
+            cursor_col: 1,
+            ..Default::default()
+        };
+        state.handle_key(key('y'), &model);
+        assert_eq!(
+            state.handle_key(key('s'), &model),
+            GridKeyResult::Yank {
+                text: "'O''Brien'".to_string(),
+                status: "Yanked cell as SQL literal".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_sql_literal_follows_the_column_type() {
+        // Numbers stay bare only where the column is numeric.
+        assert_eq!(sql_literal("10", "numeric(10,2)"), "10");
+        assert_eq!(sql_literal("NaN", "double precision"), "'NaN'");
+        assert_eq!(sql_literal("10", "character varying"), "'10'");
+        assert_eq!(
+            sql_literal("2024-01-02 03:04:05+00", "timestamp with time zone"),
+            "'2024-01-02 03:04:05+00'"
+        );
+        // Unknown types fall back to guessing from the value.
+        assert_eq!(sql_literal("10", ""), "10");
+        assert_eq!(sql_literal("", ""), "''");
+    }
+
+    #[test]
+    fn test_yank_menu_targets_cell_column_row_and_all_rows() {
+        let model = create_test_model();
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let mut state = GridState {
+            cursor_row: 1,
+            cursor_col: 1,
+            ..Default::default()
+        };
+        state.selected_rows.insert(0);
+
+        state.handle_key(key('y'), &model);
+        assert_eq!(
+            state.handle_key(key('v'), &model),
+            GridKeyResult::Yank {
+                text: "Bob".to_string(),
+                status: "Yanked cell".to_string(),
+            }
+        );
+
+        state.handle_key(key('y'), &model);
+        assert_eq!(
+            state.handle_key(key('|'), &model),
+            GridKeyResult::Yank {
+                text: "Alice\nBob".to_string(),
+                status: "Yanked column name".to_string(),
+            }
+        );
+
+        // yr ignores the selection and copies only the cursor row.
+        state.handle_key(key('y'), &model);
+        assert_eq!(state.handle_key(key('r'), &model), GridKeyResult::None);
+        assert_eq!(state.pending_yank, Some(YankTarget::Row));
+        assert_eq!(
+            state.handle_key(key('c'), &model),
+            GridKeyResult::Yank {
+                text: "2,Bob".to_string(),
+                status: "Yanked row as CSV".to_string(),
+            }
+        );
+
+        state.handle_key(key('y'), &model);
+        state.handle_key(key('a'), &model);
+        match state.handle_key(key('m'), &model) {
+            GridKeyResult::Yank { text, status } => {
+                assert!(text.contains("Alice") && text.contains("Bob"));
+                assert_eq!(status, "Yanked 2 rows as Markdown");
+            }
+            other => panic!("Expected Yank result, got {:?}", other),
+        }
+
+        // Target keys are not formats, so `yav` cancels the chord.
+        state.handle_key(key('y'), &model);
+        state.handle_key(key('a'), &model);
+        assert_eq!(state.handle_key(key('v'), &model), GridKeyResult::None);
+        assert_eq!(state.pending_yank, None);
+    }
+
     #[test]
     fn test_h_l_move_column_cursor() {
         let mut state = GridState::default();
@@ -2378,6 +3265,37 @@ mod tests {
         assert_ne!(buffer.cell((9, 2)).unwrap().bg, theme.selection.bg.unwrap());
     }
 
+    #[test]
+    fn numeric_columns_are_right_aligned() {
+        let model = GridModel::new(
+            vec!["amount".to_string(), "label".to_string()],
+            vec![vec!["7".to_string(), "x".to_string()]],
+        )
+        .with_col_types(vec!["numeric".to_string(), "text".to_string()]);
+        let state = GridState::default();
+        let theme = UiTheme::fallback();
+        let area = Rect::new(0, 0, 30, 2);
+        let mut buffer = Buffer::empty(area);
+
+        GridViewport {
+            model: &model,
+            state: &state,
+            theme: &theme,
+            focused: false,
+            show_row_numbers: false,
+            show_scrollbar: false,
+        }
+        .render(area, &mut buffer);
+
+        let row: String = (0..area.width)
+            .map(|x| buffer.cell((x, 1)).unwrap().symbol())
+            .collect();
+        let amount_width = usize::from(model.col_widths[0]);
+        assert_eq!(row[3..3 + amount_width].trim_start(), "7");
+        assert!(row[3 + amount_width..].trim_start().starts_with('x'));
+        assert_eq!(fit_to_width_right("12345", 3), fit_to_width("12345", 3));
+    }
+
     #[test]
     fn test_built_in_themes_style_labels_and_ordinary_cells() {
         let model = create_test_model();
@@ -2416,6 +3334,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn visible_links_locate_url_cells_on_screen() {
+        let model = GridModel::new(
+            vec!["id".to_string(), "site".to_string()],
+            vec![
+                vec!["1".to_string(), "https://example.com".to_string()],
+                vec!["2".to_string(), "not a link".to_string()],
+                vec!["3".to_string(), "http://a.io/x".to_string()],
+            ],
+        );
+        let mut state = GridState::default();
+        let area = Rect::new(0, 0, 40, 5);
+
+        let links = visible_links(&model, &state, area, false);
+        let data_x = 3 + model.col_widths[0] + 1;
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].url, "https://example.com");
+        assert_eq!(links[0].text, "https://example.com");
+        assert_eq!(links[0].area, Rect::new(data_x, 1, 19, 1));
+        assert_eq!(links[1].url, "http://a.io/x");
+        assert_eq!(links[1].area.y, 3);
+
+        state.row_offset = 1;
+        let links = visible_links(&model, &state, area, false);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].area.y, 2);
+    }
+
     #[test]
     fn test_shift_h_l_scroll_viewport() {
         let mut state = GridState::default();
@@ -2488,6 +3434,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pinned_columns_stay_in_view_while_the_rest_scroll() {
+        let model = create_wide_test_model();
+        let theme = UiTheme::fallback();
+        let mut state = GridState {
+            cursor_col: 4,
+            pinned_cols: 1,
+            ..Default::default()
+        };
+
+        // col1 and its separator take 5 of the 12 cells, leaving room for one more.
+        state.ensure_cursor_visible(10, 1, 5, &model.col_widths, 12);
+        assert_eq!(state.col_offset, 4);
+        // Moving onto the pinned column needs no scrolling back.
+        state.cursor_col = 0;
+        state.ensure_cursor_visible(10, 1, 5, &model.col_widths, 12);
+        assert_eq!(state.col_offset, 4);
+
+        let area = Rect::new(0, 0, 15, 2);
+        let mut buffer = Buffer::empty(area);
+        GridViewport {
+            model: &model,
+            state: &state,
+            theme: &theme,
+            focused: true,
+            show_row_numbers: false,
+            show_scrollbar: false,
+        }
+        .render(area, &mut buffer);
+        let line = |y| {
+            (3..15)
+                .map(|x| buffer.cell((x, y)).unwrap().symbol().to_string())
+                .collect::<String>()
+        };
+        assert_eq!(line(0), "col1│col5   ");
+        assert_eq!(line(1), "a   │e      ");
+    }
+
     #[test]
     fn test_header_scrolls_with_body() {
         use ratatui::buffer::Buffer;
@@ -2748,6 +3732,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rerun_keeps_resized_widths_and_clamped_cursor() {
+        let rows = |n: usize| {
+            (0..n)
+                .map(|i| vec![i.to_string(), format!("name {i}"), "x".to_string()])
+                .collect::<Vec<_>>()
+        };
+        let headers = || vec!["id".to_string(), "name".to_string(), "note".to_string()];
+        let mut previous = GridModel::new(headers(), rows(10));
+        previous.widen_column(1, 10);
+        let resized = previous.col_widths[1];
+
+        // The re-run reorders columns; widths follow the column name.
+        let mut fresh = GridModel::new(
+            vec!["name".to_string(), "id".to_string(), "note".to_string()],
+            rows(4),
+        );
+        let natural_id = fresh.col_widths[1];
+        fresh.keep_resized_widths(&previous);
+        assert_eq!(fresh.col_widths[0], resized);
+        assert_eq!(fresh.col_widths[1], natural_id);
+        assert_eq!(fresh.resized_cols, BTreeSet::from([0]));
+
+        let state = GridState {
+            row_offset: 5,
+            col_offset: 1,
+            cursor_row: 8,
+            cursor_col: 2,
+            selected_rows: BTreeSet::from([1, 3, 7]),
+            uuid_expanded: true,
+            pending_yank: Some(YankTarget::Selection),
+            ..GridState::default()
+        };
+        let kept = state.carried_over_to(&GridModel::new(headers(), rows(4)));
+        assert_eq!(
+            (
+                kept.row_offset,
+                kept.col_offset,
+                kept.cursor_row,
+                kept.cursor_col
+            ),
+            (3, 1, 3, 2)
+        );
+        assert_eq!(kept.selected_rows, BTreeSet::from([1, 3]));
+        assert!(kept.uuid_expanded);
+        assert_eq!(kept.pending_yank, None);
+    }
+
     #[test]
     fn test_generate_update_sql_with_key_column() {
         let model = GridModel::new(
@@ -2805,6 +3837,38 @@ mod tests {
         assert!(sql.contains("name = 'Alice'"), "Should have name in WHERE");
     }
 
+    #[test]
+    fn test_generate_bulk_update_sql_by_single_and_composite_key() {
+        let mut model = GridModel::new(
+            vec!["id".to_string(), "region".to_string(), "status".to_string()],
+            vec![
+                vec!["1".to_string(), "eu".to_string(), "new".to_string()],
+                vec!["2".to_string(), "us".to_string(), "new".to_string()],
+                vec!["3".to_string(), "eu".to_string(), "done".to_string()],
+            ],
+        );
+        assert_eq!(
+            model.generate_bulk_update_sql("orders", "status", "done", &[0, 1]),
+            None
+        );
+
+        model.primary_keys = vec!["id".to_string()];
+        assert_eq!(
+            model
+                .generate_bulk_update_sql("orders", "status", "it's done", &[0, 2])
+                .unwrap(),
+            "UPDATE orders\nSET status = 'it''s done'\nWHERE id IN (1, 3)"
+        );
+
+        model.primary_keys = vec!["id".to_string(), "region".to_string()];
+        assert_eq!(
+            model
+                .generate_bulk_update_sql("orders", "status", "", &[0, 1])
+                .unwrap(),
+            "UPDATE orders\nSET status = NULL\nWHERE (id, region) IN ((1, 'eu'), (2, 'us'))"
+        );
+    }
+
     #[test]
     fn test_generate_delete_sql_with_key_column() {
         let model = GridModel::new(
@@ -2881,6 +3945,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_drill_down_sql_filters_and_narrows() {
+        let model = GridModel::new(
+            vec!["id".to_string(), "Status".to_string()],
+            vec![
+                vec!["1".to_string(), "it's open".to_string()],
+                vec!["2".to_string(), "closed".to_string()],
+                vec!["3".to_string(), "it's open".to_string()],
+                vec!["4".to_string(), "NULL".to_string()],
+            ],
+        )
+        .with_col_types(vec!["integer".to_string(), "text".to_string()])
+        .with_null_cells(vec![
+            vec![false, false],
+            vec![false, false],
+            vec![false, false],
+            vec![false, true],
+        ]);
+
+        let sql = model.drill_down_sql("public.orders", 0, &[1], "").unwrap();
+        assert_eq!(sql, "SELECT * FROM public.orders WHERE id = 2;");
+
+        // A repeated value is listed once, and NULL needs IS NULL.
+        assert_eq!(
+            model.drill_down_sql("orders", 1, &[0, 1, 2], "").unwrap(),
+            "SELECT * FROM orders WHERE \"Status\" IN ('it''s open', 'closed');"
+        );
+        assert_eq!(
+            model.drill_down_sql("orders", 1, &[1, 3], "").unwrap(),
+            "SELECT * FROM orders WHERE (\"Status\" = 'closed' OR \"Status\" IS NULL);"
+        );
+
+        // A drill-down into another table starts over.
+        let other = model.drill_down_sql("orders", 1, &[1], &sql).unwrap();
+        assert_eq!(other, "SELECT * FROM orders WHERE \"Status\" = 'closed';");
+
+        // Drilling into the query again adds to its conditions, once.
+        let sql = model.drill_down_sql("orders", 0, &[1], "").unwrap();
+        let narrowed = model.drill_down_sql("orders", 1, &[1], &sql).unwrap();
+        assert_eq!(
+            narrowed,
+            "SELECT * FROM orders WHERE id = 2\n  AND \"Status\" = 'closed';"
+        );
+        assert_eq!(
+            model.drill_down_sql("orders", 1, &[1], &narrowed).unwrap(),
+            narrowed
+        );
+    }
+
     #[test]
     fn test_e_key_opens_cell_editor() {
         let mut state = GridState::default();
@@ -2936,6 +4049,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shift_o_inserts_a_row_even_without_rows() {
+        let mut state = GridState::default();
+        let model = GridModel::new(vec!["id".to_string()], vec![]);
+
+        let key = KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT);
+        assert_eq!(state.handle_key(key, &model), GridKeyResult::InsertRow);
+    }
+
+    #[test]
+    fn test_shift_m_loads_more_rows() {
+        let mut state = GridState::default();
+        let model = create_test_model();
+
+        let key = KeyEvent::new(KeyCode::Char('M'), KeyModifiers::SHIFT);
+        assert_eq!(state.handle_key(key, &model), GridKeyResult::LoadMore);
+    }
+
     #[test]
     fn test_has_valid_pk() {
         let mut model = GridModel::new(
@@ -2964,6 +4095,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_header_labels_badge_key_columns_and_widen_them() {
+        let mut model = GridModel::new(
+            vec!["id".to_string(), "user_id".to_string(), "note".to_string()],
+            vec![vec!["1".to_string(), "7".to_string(), "hi".to_string()]],
+        );
+        let widths = model.col_widths.clone();
+
+        model.set_key_columns(
+            vec!["id".to_string()],
+            vec![ForeignKeyColumn {
+                column: "user_id".to_string(),
+                references: "users".to_string(),
+            }],
+        );
+
+        assert_eq!(model.header_labels(), ["id 🔑", "user_id →users", "note"]);
+        assert_eq!(model.col_widths[0], 5);
+        assert_eq!(model.col_widths[1], 14);
+        assert_eq!(model.col_widths[2], widths[2]);
+    }
+
     #[test]
     fn test_move_left_does_not_scroll_when_cursor_visible() {
         let mut state = GridState::default();
@@ -81,9 +81,16 @@ const GLOBAL: HelpSection = HelpSection::new(
             "Cycle panes clockwise / counter-clockwise (Normal)",
         ),
         KeyBinding::new("Ctrl-h/j/k/l", "Move between panes in Normal mode"),
+        KeyBinding::new("Ctrl-w h/j/k/l, w", "Move between panes / next pane"),
+        KeyBinding::new("Ctrl-w = / _ / |", "Equalize / maximize height / width"),
+        KeyBinding::new("Ctrl-w s / o", "Split editor / close other buffer"),
         KeyBinding::new("Alt-h/j/k/l", "Move between panes in any mode"),
         KeyBinding::new("Alt+M", "Toggle maximized results view"),
         KeyBinding::new("Ctrl+Shift+P / Cmd+K", "Open contextual Actions palette"),
+        KeyBinding::new(
+            "Ctrl+P",
+            "Command palette: all bound actions and :commands (not in the editor)",
+        ),
         KeyBinding::new("Esc", "Return to normal mode / close popup"),
         KeyBinding::new("q", "Quit application"),
         KeyBinding::new("?", "Toggle this help  (/ to filter inside)"),
@@ -106,6 +113,7 @@ const GOTO: HelpSection = HelpSection::new(
         KeyBinding::new("gs", "Go to schema sidebar"),
         KeyBinding::new("gr", "Go to results grid"),
         KeyBinding::new("gm", "Open connection manager"),
+        KeyBinding::new("gx", "Open URL in the current cell"),
     ],
 );
 
@@ -150,6 +158,7 @@ const SIDEBAR_SCHEMA: HelpSection = HelpSection::new(
         KeyBinding::new("Enter (table) then u", "Replace with UPDATE template"),
         KeyBinding::new("Enter (table) then d", "Replace with DELETE template"),
         KeyBinding::new("Enter (table) then n", "Insert table name"),
+        KeyBinding::new("Enter (table) then b", "Browse table pages in key order"),
         KeyBinding::new("r / Ctrl-r", "Refresh schema"),
     ],
 );
@@ -241,6 +250,8 @@ const QUERY_VISUAL: HelpSection = HelpSection::new(
         KeyBinding::new("y", "Yank (copy) selection"),
         KeyBinding::new("d", "Delete selection"),
         KeyBinding::new("c", "Change selection"),
+        KeyBinding::new("Enter / Ctrl+E", "Execute selection"),
+        KeyBinding::new("=", "Format selection"),
         KeyBinding::new("Esc", "Cancel visual mode"),
     ],
 );
@@ -253,6 +264,8 @@ const QUERY_OTHER: HelpSection = HelpSection::new(
         KeyBinding::new("/", "Search in editor"),
         KeyBinding::new("n/N", "Next/previous search match"),
         KeyBinding::new("Enter / Ctrl+E", "Execute query"),
+        KeyBinding::new("Alt+Enter / Ctrl+Enter", "Execute statement under cursor"),
+        KeyBinding::new("Alt+E", "Explain query plan"),
         KeyBinding::new("Ctrl-p/n", "Previous/next history"),
         KeyBinding::new("Ctrl-r", "Fuzzy history search"),
         KeyBinding::new("Tab", "Trigger completion"),
@@ -280,6 +293,8 @@ const GRID_SELECTION: HelpSection = HelpSection::new(
         KeyBinding::new("a", "Select all rows"),
         KeyBinding::new("A", "Invert selection"),
         KeyBinding::new("Esc", "Clear selection"),
+        KeyBinding::new("Shift+click", "Select rows up to the clicked row"),
+        KeyBinding::new("Drag header border", "Resize column"),
     ],
 );
 
@@ -291,8 +306,17 @@ const GRID_ACTIONS: HelpSection = HelpSection::new(
         KeyBinding::new("yj", "Yank row(s) as JSON"),
         KeyBinding::new("yc / yC", "Yank row(s) as CSV / CSV+headers"),
         KeyBinding::new("ym", "Yank row(s) as Markdown table"),
-        KeyBinding::new("e / Enter", "Edit cell"),
+        KeyBinding::new("yv / y|", "Yank cell / column values"),
+        KeyBinding::new("ys", "Yank cell as a SQL literal"),
+        KeyBinding::new("yr / ya", "Yank cursor row / all rows, then format"),
+        KeyBinding::new("e / Enter", "Edit cell (every selected row's, if several)"),
         KeyBinding::new("o", "Open row detail view"),
+        KeyBinding::new("O", "Insert a row into the source table"),
+        KeyBinding::new("dd", "Delete cursor/selected rows (confirms SQL)"),
+        KeyBinding::new("M", "Load rows past the row limit"),
+        KeyBinding::new("gx", "Open URL cell in browser"),
+        KeyBinding::new("[g / ]g", "Show previous/next result set"),
+        KeyBinding::new("zp", "Pin columns through the cursor (again to unpin)"),
         KeyBinding::new("/", "Search in results"),
         KeyBinding::new("n/N", "Next/previous match"),
         KeyBinding::new(
@@ -336,8 +360,11 @@ const COMMANDS: HelpSection = HelpSection::new(
             "Export result or selected rows (csv/json/tsv/sql); retained results stream",
         ),
         KeyBinding::new(":gen <type>", "Generate SQL (update/delete/insert)"),
+        KeyBinding::new(":gen where", "Filter the source table on the cell's value"),
         KeyBinding::new(":history", "Open history picker"),
+        KeyBinding::new(":history <tag>", "History tagged by -- tag: comments"),
         KeyBinding::new(":actions / :palette", "Open contextual Actions palette"),
+        KeyBinding::new(":commands", "Open the command palette"),
         KeyBinding::new(
             ":sort asc|desc|add-asc|add-desc|toggle",
             "Sort the focused Classic/PostgreSQL result",
@@ -359,15 +386,76 @@ const COMMANDS: HelpSection = HelpSection::new(
             "Copy transformed SQL or open it in the editor",
         ),
         KeyBinding::new(
-            ":snippets / :snippet-save <name>",
+            ":snippets / :save <name>",
             "Load or save reusable named query snippets",
         ),
         KeyBinding::new(
             ":snippet <name> / :snippet-delete <name>",
             "Load or delete a named query snippet",
         ),
+        KeyBinding::new(
+            ":sizes",
+            "Table sizes, dead tuples, and vacuum/analyze times (r refresh)",
+        ),
+        KeyBinding::new(
+            ":browse <table>",
+            "Page through a table by primary key (click headers to sort)",
+        ),
+        KeyBinding::new(":prepare <name> <sql>", "Prepare a named statement"),
+        KeyBinding::new(
+            ":stash <name>",
+            "Save the shown result as a TEMP TABLE to join against",
+        ),
+        KeyBinding::new(
+            ":exec <name> [arg...]",
+            "Run a prepared statement (Tab completes)",
+        ),
+        KeyBinding::new(":let <name> = <value>", "Set a {{name}} query variable"),
+        KeyBinding::new(":unlet <name>", "Remove a query variable"),
+        KeyBinding::new(
+            ":activity / :pg-activity",
+            "Live pg_stat_activity grid (x cancel, X terminate, Esc stop)",
+        ),
         KeyBinding::new(":notebook / :mode notebook", "Switch to Notebook mode"),
         KeyBinding::new(":mode classic", "Switch to Classic mode"),
+        KeyBinding::new(
+            ":layout [stacked|side-by-side]",
+            "Toggle editor above results or side by side",
+        ),
+        KeyBinding::new(":results / :tabs", "Show a recent result set again"),
+        KeyBinding::new(":snapshot save|load <name>", "Save or reopen a result set"),
+        KeyBinding::new(":open <file>", "Show a CSV/TSV file as a result set"),
+        KeyBinding::new(
+            ":source <file.sql>",
+            "Run a SQL file statement by statement",
+        ),
+        KeyBinding::new(
+            ":import csv|tsv|json <path> <table>",
+            "Load a file into a table with COPY",
+        ),
+        KeyBinding::new(":notes", "Scratch notes for this connection"),
+        KeyBinding::new(":messages", "Status message and error log"),
+        KeyBinding::new(":listen <channel>", "LISTEN for NOTIFY on a channel"),
+        KeyBinding::new(":unlisten [channel]", "Stop listening (all without one)"),
+        KeyBinding::new(":notifications", "NOTIFY payloads received"),
+        KeyBinding::new(":begin / :commit", "Open or commit a transaction"),
+        KeyBinding::new(":rollback", "Roll back the open transaction"),
+        KeyBinding::new(":clipboard", "Copy a recent grid copy again"),
+        KeyBinding::new(":drafts", "Recover drafts of a crashed run"),
+        KeyBinding::new(":mask / :unmask", "Hide or show masked columns"),
+        KeyBinding::new(":detail", "Row detail pane beside the grid"),
+        KeyBinding::new(":notices", "Collapse or expand server notices"),
+        KeyBinding::new(":info", "Panel describing the shown result"),
+        KeyBinding::new(":count", "Count all rows of the shown query"),
+        KeyBinding::new(":explain", "Plan tree of the editor query"),
+        KeyBinding::new(":format", "Pretty-print the editor query"),
+        KeyBinding::new(":more", "Load rows past the row limit"),
+        KeyBinding::new(
+            ":lock / :unlock",
+            "Refuse edits, DML generation and destructive commands",
+        ),
+        KeyBinding::new(":pending", "Review, commit or roll back pending changes"),
+        KeyBinding::new(":edits / :undo-edit [n]", "List or undo applied grid edits"),
         KeyBinding::new(
             ":rebase / :rebind",
             "Rebind a cell to the latest source result",
@@ -458,6 +546,15 @@ const SCHEMA_COMMANDS: HelpSection = HelpSection::new(
     ],
 );
 
+/// `(keys, description)` of every documented `:command`, for the command palette.
+pub(super) fn command_help() -> impl Iterator<Item = (&'static str, &'static str)> {
+    COMMANDS
+        .bindings
+        .iter()
+        .chain(SCHEMA_COMMANDS.bindings)
+        .map(|binding| (binding.keys, binding.description))
+}
+
 const ALL_SECTIONS: &[HelpSection] = &[
     GLOBAL,
     GOTO,
@@ -854,10 +951,7 @@ impl HelpPopup {
             // Section header
             lines.push(Line::from(vec![Span::styled(
                 format!(" {} ", title),
-                Style::default()
-                    .fg(theme.pill_fg)
-                    .bg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
+                theme.pill(theme.accent),
             )]));
 
             // Separator under header
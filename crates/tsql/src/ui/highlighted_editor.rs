@@ -1,5 +1,7 @@
 //! Highlighted editor widget that combines tui-textarea editing with tui-syntax highlighting.
 
+use std::ops::Range;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Position, Rect};
 use ratatui::style::{Color, Modifier, Style};
@@ -42,6 +44,11 @@ pub struct HighlightedTextArea<'a> {
     show_cursor: bool,
     /// The shape of the cursor. Defaults to Block.
     cursor_shape: CursorShape,
+    /// Columns of one row patched with `mark_style`, e.g. a query error.
+    mark: Option<(usize, Range<usize>)>,
+    mark_style: Style,
+    /// Columns of rows patched with their own style, under the mark.
+    highlights: Vec<(usize, Range<usize>, Style)>,
 }
 
 impl<'a> HighlightedTextArea<'a> {
@@ -55,6 +62,9 @@ impl<'a> HighlightedTextArea<'a> {
             scroll_offset: (0, 0),
             show_cursor: true,
             cursor_shape: CursorShape::Block,
+            mark: None,
+            mark_style: Style::default(),
+            highlights: Vec::new(),
         }
     }
 
@@ -91,6 +101,19 @@ impl<'a> HighlightedTextArea<'a> {
         self
     }
 
+    /// Patch `style` over the columns of a row, on top of the highlighting.
+    pub fn mark(mut self, mark: Option<(usize, Range<usize>)>, style: Style) -> Self {
+        self.mark = mark;
+        self.mark_style = style;
+        self
+    }
+
+    /// Patch each style over the columns of its row, e.g. `{{variable}}` placeholders.
+    pub fn highlights(mut self, highlights: Vec<(usize, Range<usize>, Style)>) -> Self {
+        self.highlights = highlights;
+        self
+    }
+
     /// Calculate the screen position of the cursor for use with frame.set_cursor_position().
     /// Returns None if the cursor is not visible (scrolled out of view or show_cursor is false).
     /// The returned position is an absolute screen position (frame coordinates) within the
@@ -214,6 +237,22 @@ impl Widget for HighlightedTextArea<'_> {
             // Convert Line to mutable spans for manipulation
             let mut line_spans: Vec<Span<'static>> = line.spans;
 
+            for (row, columns, style) in &self.highlights {
+                if *row == row_idx {
+                    line_spans = restyle_range(line_spans, columns.start, columns.end, |base| {
+                        base.patch(*style)
+                    });
+                }
+            }
+
+            if let Some((mark_row, columns)) = &self.mark {
+                if *mark_row == row_idx {
+                    line_spans = restyle_range(line_spans, columns.start, columns.end, |style| {
+                        style.patch(self.mark_style)
+                    });
+                }
+            }
+
             // Apply selection highlighting if this line is in the selection range
             if let Some(((start_row, start_col), (end_row, end_col))) = selection {
                 if row_idx >= start_row && row_idx <= end_row {
@@ -377,6 +416,16 @@ fn apply_style_to_range(
     start_col: usize,
     end_col: usize,
     style: Style,
+) -> Vec<Span<'static>> {
+    restyle_range(spans, start_col, end_col, |_| style)
+}
+
+/// Replace the style of a range of columns within spans with `restyle` of it.
+fn restyle_range(
+    spans: Vec<Span<'static>>,
+    start_col: usize,
+    end_col: usize,
+    restyle: impl Fn(Style) -> Style,
 ) -> Vec<Span<'static>> {
     let mut result: Vec<Span<'static>> = Vec::new();
     let mut current_col = 0;
@@ -391,7 +440,7 @@ fn apply_style_to_range(
             result.push(span);
         } else if current_col >= start_col && span_end <= end_col {
             // Span is completely inside the selection range
-            result.push(Span::styled(span_text, style));
+            result.push(Span::styled(span_text, restyle(span.style)));
         } else {
             // Span partially overlaps with selection
             let chars: Vec<char> = span_text.chars().collect();
@@ -408,7 +457,7 @@ fn apply_style_to_range(
             let sel_end = (end_col - current_col).min(chars.len());
             if sel_start < sel_end {
                 let selected: String = chars[sel_start..sel_end].iter().collect();
-                result.push(Span::styled(selected, style));
+                result.push(Span::styled(selected, restyle(span.style)));
             }
 
             // Part after selection
@@ -509,6 +558,32 @@ mod tests {
         assert_eq!(result[1].content.as_ref(), "ELECT");
     }
 
+    #[test]
+    fn test_widget_marks_columns_over_the_highlighting() {
+        let textarea = TextArea::new(vec!["SELECT naem FROM t".to_string()]);
+        let keyword = Style::default().fg(Color::Magenta);
+        let lines = vec![Line::from(vec![
+            Span::styled("SELECT", keyword),
+            Span::raw(" naem FROM t"),
+        ])];
+        let mark = Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::UNDERLINED);
+        let widget = HighlightedTextArea::new(&textarea, lines)
+            .show_cursor(false)
+            .mark(Some((0, 7..11)), mark);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 1));
+        widget.render(Rect::new(0, 0, 20, 1), &mut buf);
+
+        let cell = |x: u16| buf.cell((x, 0)).unwrap().clone();
+        assert_eq!(cell(0).fg, Color::Magenta);
+        assert!(!cell(6).modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(cell(7).fg, Color::Red);
+        assert!(cell(7).modifier.contains(Modifier::UNDERLINED));
+        assert!(cell(10).modifier.contains(Modifier::UNDERLINED));
+        assert!(!cell(11).modifier.contains(Modifier::UNDERLINED));
+    }
+
     #[test]
     fn test_apply_cursor_at_end() {
         let spans = vec![Span::raw("SELECT")];
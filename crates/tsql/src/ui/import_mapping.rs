@@ -0,0 +1,303 @@
+//! Form for mapping the columns of an `:import` file to the target table's,
+//! shown when some file columns have no column of the same name.
+//!
+//! Each file column gets a line with its first value and the table column it
+//! loads into; `←`/`→` cycle through the columns no other line uses yet, and
+//! `Backspace` skips the file column.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use super::{overlay_block, UiTheme};
+
+/// Result of handling input in the mapping form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportMappingAction {
+    /// Still editing.
+    Continue,
+    /// Import with this table column (by index) for each file column.
+    Submit(Vec<Option<usize>>),
+    /// Close the form without importing (`Esc`).
+    Cancel,
+}
+
+struct MappingField {
+    header: String,
+    sample: Option<String>,
+    target: Option<usize>,
+}
+
+/// File columns and the table column each one goes to.
+pub struct ImportMappingForm {
+    title: String,
+    columns: Vec<String>,
+    fields: Vec<MappingField>,
+    selected: usize,
+    scroll: usize,
+    /// Fields that fit in the list at the last render.
+    visible_height: usize,
+}
+
+impl ImportMappingForm {
+    /// A form for loading the file `headers` (with a `samples` value each)
+    /// into `table`'s `columns`, starting from `mapping`.
+    pub fn new(
+        table: &str,
+        headers: Vec<String>,
+        samples: Vec<Option<String>>,
+        columns: Vec<String>,
+        mapping: Vec<Option<usize>>,
+    ) -> Self {
+        let mut samples = samples.into_iter();
+        let mut mapping = mapping.into_iter();
+        let fields = headers
+            .into_iter()
+            .map(|header| MappingField {
+                header,
+                sample: samples.next().flatten(),
+                target: mapping.next().flatten(),
+            })
+            .collect();
+        Self {
+            title: format!("Import into {table}"),
+            columns,
+            fields,
+            selected: 0,
+            scroll: 0,
+            visible_height: 0,
+        }
+    }
+
+    fn mapping(&self) -> Vec<Option<usize>> {
+        self.fields.iter().map(|field| field.target).collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ImportMappingAction {
+        let ctrl = key.modifiers == KeyModifiers::CONTROL;
+        match key.code {
+            KeyCode::Esc => ImportMappingAction::Cancel,
+            KeyCode::Enter => self.submit(),
+            KeyCode::Char('s') if ctrl => self.submit(),
+            KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => {
+                self.select(self.selected + 1);
+                ImportMappingAction::Continue
+            }
+            KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => {
+                self.select(self.selected.saturating_sub(1));
+                ImportMappingAction::Continue
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                self.cycle(true);
+                ImportMappingAction::Continue
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.cycle(false);
+                ImportMappingAction::Continue
+            }
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Char('x') => {
+                if let Some(field) = self.fields.get_mut(self.selected) {
+                    field.target = None;
+                }
+                ImportMappingAction::Continue
+            }
+            _ => ImportMappingAction::Continue,
+        }
+    }
+
+    /// Nothing to import until at least one column is mapped.
+    fn submit(&self) -> ImportMappingAction {
+        if self.fields.iter().any(|field| field.target.is_some()) {
+            ImportMappingAction::Submit(self.mapping())
+        } else {
+            ImportMappingAction::Continue
+        }
+    }
+
+    /// Moves the selected field to the next (or previous) table column that
+    /// is free, passing through "skip" between the last and the first.
+    fn cycle(&mut self, forward: bool) {
+        let Some(current) = self.fields.get(self.selected).map(|field| field.target) else {
+            return;
+        };
+        let taken: Vec<usize> = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.selected)
+            .filter_map(|(_, field)| field.target)
+            .collect();
+        // Positions 0..len are columns and len is "skip".
+        let len = self.columns.len();
+        let mut position = current.unwrap_or(len);
+        for _ in 0..=len {
+            position = if forward {
+                (position + 1) % (len + 1)
+            } else {
+                (position + len) % (len + 1)
+            };
+            if position == len || !taken.contains(&position) {
+                break;
+            }
+        }
+        self.fields[self.selected].target = (position < len).then_some(position);
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.fields.len().saturating_sub(1));
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.visible_height > 0 && self.selected >= self.scroll + self.visible_height {
+            self.scroll = self.selected + 1 - self.visible_height;
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 80u16.min(area.width.saturating_sub(4));
+        let dialog_height = u16::try_from(self.fields.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(4)
+            .min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        let block = overlay_block(&self.title, theme);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Fields
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+        self.visible_height = usize::from(chunks[0].height);
+        self.select(self.selected);
+
+        let header_width = name_width(self.fields.iter().map(|field| field.header.as_str()));
+        let column_width = name_width(self.columns.iter().map(String::as_str)).max("skip".len());
+        let lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_height)
+            .map(|(index, field)| {
+                let selected = index == self.selected;
+                let header_style = if selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let target = match field.target.and_then(|target| self.columns.get(target)) {
+                    Some(column) => Span::styled(
+                        format!("{column:column_width$}"),
+                        Style::default().fg(theme.success),
+                    ),
+                    None => Span::styled(
+                        format!("{:column_width$}", "skip"),
+                        Style::default()
+                            .fg(theme.text_muted)
+                            .add_modifier(Modifier::ITALIC),
+                    ),
+                };
+                let sample = field.sample.as_deref().unwrap_or("NULL");
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, header_style),
+                    Span::styled(format!("{:header_width$}", field.header), header_style),
+                    Span::styled(" → ", Style::default().fg(theme.text_muted)),
+                    target,
+                    Span::styled(
+                        format!("  {}", sample.replace(['\n', '\r'], " ")),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let key_style = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Enter", key_style(theme.success)),
+                Span::raw(" import  "),
+                Span::styled("↑↓", key_style(theme.text_muted)),
+                Span::raw(" move  "),
+                Span::styled("←→", key_style(theme.text_muted)),
+                Span::raw(" column  "),
+                Span::styled("Bksp", key_style(theme.text_muted)),
+                Span::raw(" skip  "),
+                Span::styled("Esc", key_style(theme.error)),
+                Span::raw(" cancel"),
+            ]))
+            .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+}
+
+fn name_width<'a>(names: impl Iterator<Item = &'a str>) -> usize {
+    names
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(24)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn cycling_skips_columns_mapped_elsewhere() {
+        let mut form = ImportMappingForm::new(
+            "users",
+            vec![
+                "id".to_string(),
+                "full name".to_string(),
+                "junk".to_string(),
+            ],
+            vec![Some("1".to_string()), Some("Ann".to_string()), None],
+            vec!["id".to_string(), "name".to_string(), "email".to_string()],
+            vec![Some(0), None, None],
+        );
+        form.handle_key(key(KeyCode::Down));
+        form.handle_key(key(KeyCode::Right));
+        assert_eq!(form.fields[1].target, Some(1));
+        form.handle_key(key(KeyCode::Down));
+        form.handle_key(key(KeyCode::Left));
+        assert_eq!(form.fields[2].target, Some(2));
+        form.handle_key(key(KeyCode::Left));
+        assert_eq!(form.fields[2].target, None, "id and name are taken");
+        assert_eq!(
+            form.handle_key(key(KeyCode::Enter)),
+            ImportMappingAction::Submit(vec![Some(0), Some(1), None])
+        );
+
+        form.handle_key(key(KeyCode::Up));
+        form.handle_key(key(KeyCode::Backspace));
+        form.handle_key(key(KeyCode::Up));
+        form.handle_key(key(KeyCode::Backspace));
+        assert_eq!(
+            form.handle_key(key(KeyCode::Enter)),
+            ImportMappingAction::Continue,
+            "nothing mapped"
+        );
+        assert_eq!(
+            form.handle_key(key(KeyCode::Esc)),
+            ImportMappingAction::Cancel
+        );
+    }
+}
@@ -0,0 +1,381 @@
+//! Form for inserting a row into a result's source table (`O` in the grid).
+//!
+//! Every column of the result is listed with its type. A column starts out as
+//! DEFAULT and is left out of the INSERT; typing gives it a value, `Ctrl-n`
+//! makes it NULL and `Ctrl-d` puts it back to DEFAULT.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use super::grid::{quote_identifier, quote_qualified_identifier, sql_literal, GridModel};
+use super::{overlay_block, UiTheme};
+
+/// Result of handling input in the insert form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertRowAction {
+    /// Still editing.
+    Continue,
+    /// Run the INSERT (`Ctrl-s`).
+    Submit(String),
+    /// Close the form without inserting (`Esc`).
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldValue {
+    Default,
+    Null,
+    Text(String),
+}
+
+struct InsertField {
+    name: String,
+    col_type: String,
+    nullable: Option<bool>,
+    primary_key: bool,
+    value: FieldValue,
+}
+
+/// The columns of a new row and the values typed for them.
+pub struct InsertRowForm {
+    table: String,
+    fields: Vec<InsertField>,
+    selected: usize,
+    scroll: usize,
+    /// Fields that fit in the list at the last render.
+    visible_height: usize,
+}
+
+impl InsertRowForm {
+    /// A form for `grid`'s source table, None when it is not known.
+    pub fn for_grid(grid: &GridModel) -> Option<Self> {
+        let table = grid.source_table.clone()?;
+        let fields = grid
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(col, name)| InsertField {
+                name: name.clone(),
+                col_type: grid.col_type(col).unwrap_or("").to_string(),
+                nullable: grid.col_nullable.get(col).copied().flatten(),
+                primary_key: grid.primary_keys.contains(name),
+                value: FieldValue::Default,
+            })
+            .collect();
+        Some(Self {
+            table,
+            fields,
+            selected: 0,
+            scroll: 0,
+            visible_height: 0,
+        })
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// The INSERT for the values given so far; DEFAULT columns are left out.
+    pub fn sql(&self) -> String {
+        let table = quote_qualified_identifier(&self.table);
+        let (columns, values): (Vec<String>, Vec<String>) = self
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let value = match &field.value {
+                    FieldValue::Default => return None,
+                    FieldValue::Null => "NULL".to_string(),
+                    FieldValue::Text(text) => sql_literal(text, &field.col_type),
+                };
+                Some((quote_identifier(&field.name), value))
+            })
+            .unzip();
+        if columns.is_empty() {
+            format!("INSERT INTO {table} DEFAULT VALUES;")
+        } else {
+            format!(
+                "INSERT INTO {table} ({}) VALUES ({});",
+                columns.join(", "),
+                values.join(", ")
+            )
+        }
+    }
+
+    /// Pasted text goes into the selected field's value.
+    pub fn paste(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if let Some(field) = self.fields.get_mut(self.selected) {
+            match &mut field.value {
+                FieldValue::Text(value) => value.push_str(&text),
+                value => *value = FieldValue::Text(text),
+            }
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> InsertRowAction {
+        let ctrl = key.modifiers == KeyModifiers::CONTROL;
+        match key.code {
+            KeyCode::Esc => return InsertRowAction::Cancel,
+            KeyCode::Char('s') if ctrl => return InsertRowAction::Submit(self.sql()),
+            KeyCode::Down | KeyCode::Tab | KeyCode::Enter => self.select(self.selected + 1),
+            KeyCode::Up | KeyCode::BackTab => self.select(self.selected.saturating_sub(1)),
+            _ => {}
+        }
+        let Some(field) = self.fields.get_mut(self.selected) else {
+            return InsertRowAction::Continue;
+        };
+        match key.code {
+            KeyCode::Char('n') if ctrl => {
+                field.value = if field.value == FieldValue::Null {
+                    FieldValue::Default
+                } else {
+                    FieldValue::Null
+                };
+            }
+            KeyCode::Char('d') | KeyCode::Char('u') if ctrl => field.value = FieldValue::Default,
+            KeyCode::Backspace => match &mut field.value {
+                // Deleting past an empty value goes back to DEFAULT.
+                FieldValue::Text(text) if text.is_empty() => field.value = FieldValue::Default,
+                FieldValue::Text(text) => {
+                    text.pop();
+                }
+                FieldValue::Null => field.value = FieldValue::Default,
+                FieldValue::Default => {}
+            },
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                match &mut field.value {
+                    FieldValue::Text(text) => text.push(c),
+                    value => *value = FieldValue::Text(c.to_string()),
+                }
+            }
+            _ => {}
+        }
+        InsertRowAction::Continue
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.fields.len().saturating_sub(1));
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.visible_height > 0 && self.selected >= self.scroll + self.visible_height {
+            self.scroll = self.selected + 1 - self.visible_height;
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 90u16.min(area.width.saturating_sub(4));
+        let dialog_height = u16::try_from(self.fields.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(6)
+            .min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        let block = overlay_block(&format!("Insert into {}", self.table), theme);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Fields
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Statement
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+        self.visible_height = usize::from(chunks[0].height);
+        self.select(self.selected);
+
+        let name_width = self
+            .fields
+            .iter()
+            .map(|field| field.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(30);
+        let hint_width = self
+            .fields
+            .iter()
+            .map(|field| type_hint(field).chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(30);
+        let lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_height)
+            .map(|(index, field)| {
+                let selected = index == self.selected;
+                let name_style = if selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let cursor = if selected { "\u{2588}" } else { "" };
+                let value = match &field.value {
+                    FieldValue::Default => Span::styled(
+                        if selected {
+                            "DEFAULT (type to set)"
+                        } else {
+                            "DEFAULT"
+                        },
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    FieldValue::Null => Span::styled(
+                        "NULL",
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::ITALIC),
+                    ),
+                    FieldValue::Text(text) => Span::styled(
+                        format!("{text}{cursor}"),
+                        Style::default().fg(theme.success),
+                    ),
+                };
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, name_style),
+                    Span::styled(format!("{:name_width$}  ", field.name), name_style),
+                    Span::styled(
+                        format!("{:hint_width$}  ", type_hint(field)),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                    value,
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        frame.render_widget(
+            Paragraph::new(self.sql()).style(Style::default().fg(theme.text_muted)),
+            chunks[2],
+        );
+
+        let key_style = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Ctrl-s", key_style(theme.success)),
+                Span::raw(" insert  "),
+                Span::styled("Tab/↑↓", key_style(theme.text_muted)),
+                Span::raw(" move  "),
+                Span::styled("Ctrl-n", key_style(theme.text_muted)),
+                Span::raw(" NULL  "),
+                Span::styled("Ctrl-d", key_style(theme.text_muted)),
+                Span::raw(" DEFAULT  "),
+                Span::styled("Esc", key_style(theme.error)),
+                Span::raw(" cancel"),
+            ]))
+            .alignment(Alignment::Center),
+            chunks[4],
+        );
+    }
+}
+
+/// The column's type, with its key and NOT NULL constraint when known.
+fn type_hint(field: &InsertField) -> String {
+    let mut hint = if field.col_type.is_empty() {
+        "?".to_string()
+    } else {
+        field.col_type.clone()
+    };
+    if field.primary_key {
+        hint.push_str(" PK");
+    } else if field.nullable == Some(false) {
+        hint.push_str(" NOT NULL");
+    }
+    hint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn form() -> InsertRowForm {
+        let mut grid = GridModel::new(
+            vec!["id".to_string(), "name".to_string(), "Age".to_string()],
+            vec![],
+        )
+        .with_source_table(Some("public.people".to_string()))
+        .with_primary_keys(vec!["id".to_string()])
+        .with_col_types(vec![
+            "integer".to_string(),
+            "text".to_string(),
+            "integer".to_string(),
+        ]);
+        grid.col_nullable = vec![Some(false), Some(false), Some(true)];
+        InsertRowForm::for_grid(&grid).unwrap()
+    }
+
+    #[test]
+    fn untouched_columns_keep_their_defaults() {
+        let mut form = form();
+        assert_eq!(form.sql(), "INSERT INTO public.people DEFAULT VALUES;");
+        assert_eq!(type_hint(&form.fields[0]), "integer PK");
+        assert_eq!(type_hint(&form.fields[1]), "text NOT NULL");
+
+        form.handle_key(key(KeyCode::Tab));
+        for c in "O'Brien".chars() {
+            form.handle_key(key(KeyCode::Char(c)));
+        }
+        form.handle_key(key(KeyCode::Down));
+        form.handle_key(ctrl('n'));
+        assert_eq!(
+            form.handle_key(ctrl('s')),
+            InsertRowAction::Submit(
+                "INSERT INTO public.people (name, \"Age\") VALUES ('O''Brien', NULL);".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn keys_switch_between_value_null_and_default() {
+        let mut form = form();
+        form.handle_key(key(KeyCode::Enter));
+        form.handle_key(key(KeyCode::Enter));
+        form.paste("42\n");
+        assert_eq!(form.fields[2].value, FieldValue::Text("42".to_string()));
+        assert_eq!(
+            form.sql(),
+            "INSERT INTO public.people (\"Age\") VALUES (42);"
+        );
+
+        form.handle_key(ctrl('n'));
+        assert_eq!(form.fields[2].value, FieldValue::Null);
+        form.handle_key(ctrl('n'));
+        assert_eq!(form.fields[2].value, FieldValue::Default);
+
+        // Typing over NULL starts a value; deleting it all returns to DEFAULT.
+        form.handle_key(ctrl('n'));
+        form.handle_key(key(KeyCode::Char('7')));
+        assert_eq!(form.fields[2].value, FieldValue::Text("7".to_string()));
+        form.handle_key(key(KeyCode::Backspace));
+        assert_eq!(form.fields[2].value, FieldValue::Text(String::new()));
+        form.handle_key(key(KeyCode::Backspace));
+        assert_eq!(form.fields[2].value, FieldValue::Default);
+
+        // Moving stops at the last field.
+        form.handle_key(key(KeyCode::Down));
+        assert_eq!(form.selected, 2);
+        assert_eq!(form.handle_key(key(KeyCode::Esc)), InsertRowAction::Cancel);
+    }
+}
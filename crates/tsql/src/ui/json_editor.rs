@@ -3,7 +3,8 @@
 //! This modal provides:
 //! - Auto-detected syntax highlighting (JSON, HTML, SQL, plain text)
 //! - Vim-like keybindings (Normal/Insert/Visual modes) via unified VimHandler
-//! - JSON validation with error display
+//! - JSON validation with error display, optionally against a JSON Schema
+//! - Relaxed (JSON5-style) input, normalized to strict JSON on save
 //! - Auto-formatting on open for JSON content
 //! - Virtual scrolling for large content
 
@@ -15,11 +16,14 @@ use ratatui::widgets::{Clear, Paragraph, Scrollbar, ScrollbarOrientation, Scroll
 use ratatui::Frame;
 use tui_textarea::{CursorMove, TextArea};
 
+use serde_json::Value as JsonValue;
 use tui_syntax::{html, json, Highlighter, Theme};
 
+use crate::config::JsonSaveFormat;
+use crate::json_schema;
 use crate::ui::HighlightedTextArea;
 use crate::util::{
-    detect_content_type, is_json_column_type, is_valid_json, try_format_json, ContentType,
+    detect_content_type, is_json_column_type, parse_relaxed_json, try_format_json, ContentType,
 };
 use crate::vim::{Motion, VimCommand, VimConfig, VimHandler, VimMode};
 
@@ -62,6 +66,14 @@ pub struct JsonEditorModal<'a> {
     col: usize,
     /// Whether current content is valid JSON
     is_valid_json: bool,
+    /// Accept comments, trailing commas, single quotes and bare keys
+    relaxed: bool,
+    /// How the value is written back on save
+    save_format: JsonSaveFormat,
+    /// Schema name and document the value must satisfy before saving
+    schema: Option<(String, JsonValue)>,
+    /// Schema violations in the current content
+    schema_errors: usize,
     /// Scroll offset for HighlightedTextArea
     scroll_offset: (u16, u16),
     /// Current vim mode
@@ -88,7 +100,7 @@ impl<'a> JsonEditorModal<'a> {
     ) -> Self {
         // Try to pretty-print the JSON
         let formatted_value = try_format_json(&value).unwrap_or_else(|| value.clone());
-        let is_valid = is_valid_json(&formatted_value);
+        let is_valid = serde_json::from_str::<JsonValue>(&formatted_value).is_ok();
 
         // Create textarea with the formatted value
         let lines: Vec<String> = formatted_value.lines().map(|s| s.to_string()).collect();
@@ -120,6 +132,10 @@ impl<'a> JsonEditorModal<'a> {
             row,
             col,
             is_valid_json: is_valid,
+            relaxed: false,
+            save_format: JsonSaveFormat::Keep,
+            schema: None,
+            schema_errors: 0,
             scroll_offset: (0, 0),
             mode: VimMode::Normal, // Start in normal mode (vim default)
             vim_handler,
@@ -128,6 +144,27 @@ impl<'a> JsonEditorModal<'a> {
         }
     }
 
+    /// Accept relaxed JSON (comments, trailing commas, single quotes, bare
+    /// keys). It is saved as strict JSON.
+    pub fn with_relaxed_json(mut self, relaxed: bool) -> Self {
+        self.relaxed = relaxed;
+        self.update_validity();
+        self
+    }
+
+    pub fn with_save_format(mut self, save_format: JsonSaveFormat) -> Self {
+        self.save_format = save_format;
+        self
+    }
+
+    /// Refuse to save values that violate `schema`; `name` identifies it in
+    /// messages.
+    pub fn with_schema(mut self, name: impl Into<String>, schema: JsonValue) -> Self {
+        self.schema = Some((name.into(), schema));
+        self.update_validity();
+        self
+    }
+
     /// Get the current content as a string.
     pub fn content(&self) -> String {
         self.textarea.lines().join("\n")
@@ -149,15 +186,33 @@ impl<'a> JsonEditorModal<'a> {
         is_json_column_type(&self.column_type)
     }
 
+    /// Parses `content`, relaxed or strictly as configured.
+    fn parse(&self, content: &str) -> Result<JsonValue, serde_json::Error> {
+        if self.relaxed {
+            parse_relaxed_json(content)
+        } else {
+            serde_json::from_str(content)
+        }
+    }
+
     /// Update the JSON validity status.
     fn update_validity(&mut self) {
-        self.is_valid_json = is_valid_json(&self.content());
+        let parsed = self.parse(&self.content());
+        self.is_valid_json = parsed.is_ok();
+        self.schema_errors = match (&self.schema, parsed) {
+            (Some((_, schema)), Ok(value)) => json_schema::validate(schema, &value).len(),
+            _ => 0,
+        };
     }
 
     /// Format the JSON content (pretty-print).
     pub fn format_json(&mut self) {
         let content = self.content();
-        if let Some(formatted) = try_format_json(&content) {
+        let formatted = self
+            .parse(&content)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok());
+        if let Some(formatted) = formatted {
             let lines: Vec<String> = formatted.lines().map(|s| s.to_string()).collect();
             let lines = if lines.is_empty() {
                 vec![String::new()]
@@ -168,7 +223,7 @@ impl<'a> JsonEditorModal<'a> {
             self.textarea.set_cursor_line_style(Style::default());
             self.textarea
                 .set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
-            self.is_valid_json = true;
+            self.update_validity();
         }
     }
 
@@ -259,14 +314,7 @@ impl<'a> JsonEditorModal<'a> {
             // Quit (cancel)
             "q" | "quit" => JsonEditorAction::Cancel,
             // Save and quit
-            "wq" | "x" => match self.try_save() {
-                JsonEditorAction::Error(e) => JsonEditorAction::Error(e),
-                _ => JsonEditorAction::Save {
-                    value: self.content(),
-                    row: self.row,
-                    col: self.col,
-                },
-            },
+            "wq" | "x" => self.try_save(),
             // Unknown command
             _ => JsonEditorAction::Error(format!("Unknown command: {}", cmd)),
         }
@@ -519,17 +567,47 @@ impl<'a> JsonEditorModal<'a> {
     /// Try to save the content, checking validation rules.
     fn try_save(&mut self) -> JsonEditorAction {
         let content = self.content();
+        if !self.is_json_column() && self.schema.is_none() {
+            return JsonEditorAction::Save {
+                value: content,
+                row: self.row,
+                col: self.col,
+            };
+        }
 
-        // For jsonb columns, require valid JSON
-        if self.is_json_column() && !is_valid_json(&content) {
-            return JsonEditorAction::Error(
-                "Cannot save invalid JSON to a JSONB column. Fix the JSON or press Esc twice to cancel."
+        // For jsonb columns and columns with a schema, require valid JSON
+        let Ok(value) = self.parse(&content) else {
+            return JsonEditorAction::Error(match &self.schema {
+                Some((name, _)) if !self.is_json_column() => format!(
+                    "Cannot save invalid JSON to a column checked by {name}. Fix the JSON or press Esc twice to cancel."
+                ),
+                _ => "Cannot save invalid JSON to a JSONB column. Fix the JSON or press Esc twice to cancel."
                     .to_string(),
-            );
+            });
+        };
+
+        if let Some((name, schema)) = &self.schema {
+            let errors = json_schema::validate(schema, &value);
+            if let Some(first) = errors.first() {
+                let more = match errors.len() - 1 {
+                    0 => String::new(),
+                    n => format!(" (+{n} more)"),
+                };
+                return JsonEditorAction::Error(format!(
+                    "{name} rejects this value: {first}{more}"
+                ));
+            }
         }
 
+        let value = match self.save_format {
+            JsonSaveFormat::Keep if serde_json::from_str::<JsonValue>(&content).is_ok() => content,
+            JsonSaveFormat::Compact => value.to_string(),
+            JsonSaveFormat::Keep | JsonSaveFormat::Pretty => {
+                serde_json::to_string_pretty(&value).unwrap_or(content)
+            }
+        };
         JsonEditorAction::Save {
-            value: content,
+            value,
             row: self.row,
             col: self.col,
         }
@@ -581,8 +659,8 @@ impl<'a> JsonEditorModal<'a> {
 
         // Semantic border override: validation state matters more than the
         // calm overlay recipe here (green = valid JSON, red = invalid).
-        let border_color = if self.is_json_column() {
-            if self.is_valid_json {
+        let border_color = if self.is_json_column() || self.schema.is_some() {
+            if self.is_valid_json && self.schema_errors == 0 {
                 Some(theme.success)
             } else {
                 Some(theme.error)
@@ -687,7 +765,23 @@ impl<'a> JsonEditorModal<'a> {
                 Style::default().fg(theme.error),
             ))
         } else {
-            None
+            match &self.schema {
+                Some((name, _)) if self.is_valid_json && self.schema_errors > 0 => {
+                    Some(Span::styled(
+                        format!(
+                            " ✗ {name}: {} issue{} ",
+                            self.schema_errors,
+                            if self.schema_errors == 1 { "" } else { "s" }
+                        ),
+                        Style::default().fg(theme.error),
+                    ))
+                }
+                Some((name, _)) if self.is_valid_json => Some(Span::styled(
+                    format!(" ✓ {name} "),
+                    Style::default().fg(theme.success),
+                )),
+                _ => None,
+            }
         };
 
         let mode_color = match self.mode {
@@ -695,13 +789,7 @@ impl<'a> JsonEditorModal<'a> {
             VimMode::Insert => theme.accent_insert,
             VimMode::Visual => theme.accent_visual,
         };
-        let mode_span = Span::styled(
-            format!(" {} ", self.mode.label()),
-            Style::default()
-                .fg(theme.pill_fg)
-                .bg(mode_color)
-                .add_modifier(Modifier::BOLD),
-        );
+        let mode_span = Span::styled(format!(" {} ", self.mode.label()), theme.pill(mode_color));
 
         let pos_span = Span::raw(format!(
             " Ln {}/{}, Col {} ",
@@ -1217,4 +1305,86 @@ mod tests {
             "'q' with changes should return RequestClose"
         );
     }
+
+    #[test]
+    fn test_json_editor_relaxed_input_schema_and_save_format() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["theme"],
+            "properties": {"theme": {"enum": ["light", "dark"]}}
+        });
+        let mut editor = JsonEditorModal::new(
+            r#"{"theme": "dark"}"#.to_string(),
+            "preferences".to_string(),
+            "jsonb".to_string(),
+            2,
+            3,
+            themes::one_dark(),
+        )
+        .with_relaxed_json(true)
+        .with_save_format(JsonSaveFormat::Compact)
+        .with_schema("preferences.json", schema);
+        fn set_content(editor: &mut JsonEditorModal<'_>, text: &str) {
+            editor.textarea = TextArea::new(text.lines().map(str::to_string).collect());
+            editor.update_validity();
+        }
+
+        // Relaxed input that breaks the schema is refused before any UPDATE.
+        set_content(
+            &mut editor,
+            "{\n  // pick one\n  theme: 'blue', size: 3,\n}",
+        );
+        assert!(editor.is_valid_json);
+        assert_eq!(editor.schema_errors, 1);
+        match editor.try_save() {
+            JsonEditorAction::Error(message) => assert_eq!(
+                message,
+                r#"preferences.json rejects this value: /theme: must be one of "light", "dark""#
+            ),
+            _ => panic!("schema violation should block the save"),
+        }
+        set_content(&mut editor, "{}");
+        match editor.try_save() {
+            JsonEditorAction::Error(message) => {
+                assert!(message.ends_with("missing required property \"theme\""))
+            }
+            _ => panic!("schema violation should block the save"),
+        }
+
+        // Valid relaxed input is saved as strict, compact JSON.
+        set_content(&mut editor, "{theme: 'light', size: 3,}");
+        match editor.try_save() {
+            JsonEditorAction::Save { value, row, col } => {
+                assert_eq!(value, r#"{"theme":"light","size":3}"#);
+                assert_eq!((row, col), (2, 3));
+            }
+            _ => panic!("valid value should save"),
+        }
+
+        // Keep saves strict JSON as typed and still refuses broken JSON.
+        editor.save_format = JsonSaveFormat::Keep;
+        set_content(&mut editor, r#"{ "theme" : "dark" }"#);
+        assert!(matches!(
+            editor.try_save(),
+            JsonEditorAction::Save { value, .. } if value == r#"{ "theme" : "dark" }"#
+        ));
+        set_content(&mut editor, "{theme: ");
+        assert!(matches!(editor.try_save(), JsonEditorAction::Error(_)));
+    }
+
+    #[test]
+    fn test_json_editor_strict_by_default() {
+        let mut editor = JsonEditorModal::new(
+            "{}".to_string(),
+            "data".to_string(),
+            "jsonb".to_string(),
+            0,
+            0,
+            themes::one_dark(),
+        );
+        editor.textarea = TextArea::new(vec!["{a: 1}".to_string()]);
+        editor.update_validity();
+        assert!(!editor.is_valid_json);
+        assert!(matches!(editor.try_save(), JsonEditorAction::Error(_)));
+    }
 }
@@ -10,69 +10,110 @@ use ratatui::{
     widgets::{Clear, Paragraph},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use super::grid::YankTarget;
 use super::key_sequence::PendingKey;
 use super::{overlay_block, UiTheme};
 
 /// A single hint entry showing a key and its description.
 #[derive(Debug, Clone)]
 pub struct KeyHint {
-    /// The key to press (e.g., "g", "e", "c")
-    pub key: &'static str,
+    /// The key to press (e.g., 'g', 'e', 'c')
+    pub key: char,
     /// Short description of what the key does
     pub description: &'static str,
 }
 
 impl KeyHint {
-    pub const fn new(key: &'static str, description: &'static str) -> Self {
+    pub const fn new(key: char, description: &'static str) -> Self {
         Self { key, description }
     }
 }
 
-/// Hints for the 'g' (goto) prefix
-const G_HINTS: &[KeyHint] = &[
-    KeyHint::new("g", "first row"),
-    KeyHint::new("e", "editor"),
-    KeyHint::new("c", "connections"),
-    KeyHint::new("s", "schema"),
-    KeyHint::new("r", "results"),
-    KeyHint::new("h", "history"),
-    KeyHint::new("m", "manager"),
+/// Hints for the grid yank menu opened by `y`
+const YANK_HINTS: &[KeyHint] = &[
+    KeyHint::new('y', "TSV"),
+    KeyHint::new('Y', "TSV + headers"),
+    KeyHint::new('c', "CSV"),
+    KeyHint::new('C', "CSV + headers"),
+    KeyHint::new('j', "JSON"),
+    KeyHint::new('m', "Markdown"),
+    KeyHint::new('v', "cell"),
+    KeyHint::new('s', "cell as SQL"),
+    KeyHint::new('|', "column"),
+    KeyHint::new('r', "row as…"),
+    KeyHint::new('a', "all rows as…"),
 ];
 
-/// Hints for schema table actions (started by Enter on a table in the schema panel)
-const SCHEMA_TABLE_HINTS: &[KeyHint] = &[
-    KeyHint::new("s", "select"),
-    KeyHint::new("i", "insert"),
-    KeyHint::new("u", "update"),
-    KeyHint::new("d", "delete"),
-    KeyHint::new("n", "name"),
+/// Format hints once `yr` or `ya` has picked the rows to yank
+const YANK_FORMAT_HINTS: &[KeyHint] = &[
+    KeyHint::new('y', "TSV"),
+    KeyHint::new('Y', "TSV + headers"),
+    KeyHint::new('c', "CSV"),
+    KeyHint::new('C', "CSV + headers"),
+    KeyHint::new('j', "JSON"),
+    KeyHint::new('m', "Markdown"),
 ];
 
 /// The key hint popup widget.
 pub struct KeyHintPopup {
-    /// The currently pending key
-    pending_key: PendingKey,
+    /// Popup title, usually the keys typed so far
+    title: String,
+    /// Completions offered for those keys
+    hints: Vec<KeyHint>,
 }
 
 impl KeyHintPopup {
-    /// Creates a new popup for the given pending key.
+    /// Creates a new popup for the given pending key, listing the keys the
+    /// sequence handler accepts after it.
     pub fn new(pending_key: PendingKey) -> Self {
-        Self { pending_key }
+        let hints = pending_key
+            .continuations()
+            .iter()
+            .map(|continuation| KeyHint::new(continuation.key, continuation.description))
+            .collect();
+        let title = match pending_key {
+            PendingKey::Window => "^W".to_string(),
+            _ => pending_key.display_char().to_string(),
+        };
+        Self { title, hints }
     }
 
-    /// Returns the hints for the current pending key.
-    fn hints(&self) -> &'static [KeyHint] {
-        match self.pending_key {
-            PendingKey::G => G_HINTS,
-            PendingKey::SchemaTable => SCHEMA_TABLE_HINTS,
+    /// Creates the grid yank menu for a pending `y` chord.
+    pub fn yank(target: YankTarget) -> Self {
+        let (title, hints) = match target {
+            YankTarget::Selection => ("y", YANK_HINTS),
+            YankTarget::Row => ("yr", YANK_FORMAT_HINTS),
+            YankTarget::All => ("ya", YANK_FORMAT_HINTS),
+        };
+        Self {
+            title: title.to_string(),
+            hints: hints.to_vec(),
         }
     }
 
-    /// Returns the title character for the popup.
-    fn title_char(&self) -> char {
-        self.pending_key.display_char()
+    /// Returns the hints offered by this popup.
+    fn hints(&self) -> &[KeyHint] {
+        &self.hints
+    }
+
+    /// The hints on one line for the status line, e.g. `g: g first row  e editor`,
+    /// ending in `…` when not all of them fit in `max_width` columns.
+    pub fn inline(&self, max_width: usize) -> String {
+        let mut line = format!("{}:", self.title);
+        for (index, hint) in self.hints.iter().enumerate() {
+            let entry = format!(" {} {}", hint.key, hint.description);
+            let separator = if index == 0 { "" } else { " " };
+            let more = if index + 1 < self.hints.len() { 2 } else { 0 };
+            if line.width() + separator.len() + entry.width() + more > max_width {
+                line.push_str(" …");
+                break;
+            }
+            line.push_str(separator);
+            line.push_str(&entry);
+        }
+        line
     }
 
     /// Calculates the popup area positioned in the bottom-right corner.
@@ -89,7 +130,7 @@ impl KeyHintPopup {
         let max_content_width = hints
             .iter()
             .map(|h| {
-                let key_width = 1 + h.key.width(); // leading space + key
+                let key_width = 1 + h.key.width().unwrap_or(1); // leading space + key
                 let desc_width = h.description.width();
                 key_width + 2 + desc_width + 1 // " key" + "  " + desc + " "
             })
@@ -155,7 +196,7 @@ impl KeyHintPopup {
             })
             .collect();
 
-        let block = overlay_block(&self.title_char().to_string(), theme);
+        let block = overlay_block(&self.title, theme);
         let paragraph = Paragraph::new(lines).block(block);
 
         frame.render_widget(paragraph, area);
@@ -171,12 +212,13 @@ mod tests {
         let popup = KeyHintPopup::new(PendingKey::G);
         let hints = popup.hints();
 
-        assert_eq!(hints.len(), 7);
-        assert_eq!(hints[0].key, "g");
+        assert_eq!(hints.len(), 8);
+        assert_eq!(hints[0].key, 'g');
         assert_eq!(hints[0].description, "first row");
-        assert_eq!(hints[1].key, "e");
-        assert_eq!(hints[5].key, "h");
-        assert_eq!(hints[6].key, "m");
+        assert_eq!(hints[1].key, 'e');
+        assert_eq!(hints[5].key, 'h');
+        assert_eq!(hints[6].key, 'm');
+        assert_eq!(hints[7].key, 'x');
     }
 
     #[test]
@@ -184,23 +226,76 @@ mod tests {
         let popup = KeyHintPopup::new(PendingKey::SchemaTable);
         let hints = popup.hints();
 
-        assert_eq!(hints.len(), 5);
-        assert_eq!(hints[0].key, "s");
+        assert_eq!(hints.len(), 6);
+        assert_eq!(hints[0].key, 's');
         assert_eq!(hints[0].description, "select");
-        assert_eq!(hints[1].key, "i");
-        assert_eq!(hints[4].key, "n");
+        assert_eq!(hints[1].key, 'i');
+        assert_eq!(hints[4].key, 'n');
+        assert_eq!(hints[5].key, 'b');
     }
 
     #[test]
-    fn test_title_char() {
-        let popup = KeyHintPopup::new(PendingKey::G);
-        assert_eq!(popup.title_char(), 'g');
+    fn test_title() {
+        assert_eq!(KeyHintPopup::new(PendingKey::G).title, "g");
+        assert_eq!(KeyHintPopup::new(PendingKey::Window).title, "^W");
     }
 
     #[test]
-    fn test_title_char_schema_table() {
+    fn test_title_schema_table() {
         let popup = KeyHintPopup::new(PendingKey::SchemaTable);
-        assert_eq!(popup.title_char(), '⏎');
+        assert_eq!(popup.title, "⏎");
+    }
+
+    #[test]
+    fn test_yank_menu_offers_targets_then_formats() {
+        let menu = KeyHintPopup::yank(YankTarget::Selection);
+        assert_eq!(menu.title, "y");
+        let keys: Vec<_> = menu.hints().iter().map(|hint| hint.key).collect();
+        assert_eq!(
+            keys,
+            ['y', 'Y', 'c', 'C', 'j', 'm', 'v', 's', '|', 'r', 'a']
+        );
+
+        let formats = KeyHintPopup::yank(YankTarget::All);
+        assert_eq!(formats.title, "ya");
+        assert!(formats.hints().iter().all(|hint| hint.key != 'r'));
+    }
+
+    #[test]
+    fn test_hints_follow_the_sequence_handler() {
+        for pending in [
+            PendingKey::G,
+            PendingKey::SchemaTable,
+            PendingKey::Previous,
+            PendingKey::Next,
+            PendingKey::Window,
+            PendingKey::Z,
+            PendingKey::D,
+        ] {
+            let keys: Vec<_> = KeyHintPopup::new(pending)
+                .hints()
+                .iter()
+                .map(|hint| hint.key)
+                .collect();
+            let accepted: Vec<_> = pending
+                .continuations()
+                .iter()
+                .map(|continuation| continuation.key)
+                .collect();
+            assert_eq!(keys, accepted);
+        }
+    }
+
+    #[test]
+    fn test_inline_hints() {
+        assert_eq!(
+            KeyHintPopup::new(PendingKey::Previous).inline(80),
+            "[: g previous result"
+        );
+        assert_eq!(
+            KeyHintPopup::new(PendingKey::G).inline(45),
+            "g: g first row  e editor  c connections …"
+        );
     }
 
     #[test]
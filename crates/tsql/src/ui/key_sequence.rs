@@ -12,7 +12,16 @@ pub enum PendingKey {
     G,
     /// Schema table actions (started by Enter on a table in the schema panel)
     SchemaTable,
-    // Future: Add more pending keys here (e.g., Z for fold commands)
+    /// The `[` (previous) key prefix in the results grid
+    Previous,
+    /// The `]` (next) key prefix in the results grid
+    Next,
+    /// The `Ctrl+W` window-command prefix
+    Window,
+    /// The `z` (view) key prefix in the results grid
+    Z,
+    /// The `d` (delete) key prefix in the results grid
+    D,
 }
 
 impl PendingKey {
@@ -21,10 +30,111 @@ impl PendingKey {
         match self {
             PendingKey::G => 'g',
             PendingKey::SchemaTable => '⏎',
+            PendingKey::Previous => '[',
+            PendingKey::Next => ']',
+            PendingKey::Window => 'w',
+            PendingKey::Z => 'z',
+            PendingKey::D => 'd',
+        }
+    }
+
+    /// The keys that can complete this prefix, in the order hints list them.
+    pub fn continuations(self) -> &'static [KeyContinuation] {
+        match self {
+            PendingKey::G => G_CONTINUATIONS,
+            PendingKey::SchemaTable => SCHEMA_TABLE_CONTINUATIONS,
+            PendingKey::Previous => PREVIOUS_CONTINUATIONS,
+            PendingKey::Next => NEXT_CONTINUATIONS,
+            PendingKey::Window => WINDOW_CONTINUATIONS,
+            PendingKey::Z => Z_CONTINUATIONS,
+            PendingKey::D => D_CONTINUATIONS,
         }
     }
 }
 
+/// A key that completes a pending prefix, with the hint shown for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyContinuation {
+    pub key: char,
+    pub action: KeySequenceAction,
+    /// Short description of what the key does
+    pub description: &'static str,
+}
+
+impl KeyContinuation {
+    const fn new(key: char, action: KeySequenceAction, description: &'static str) -> Self {
+        Self {
+            key,
+            action,
+            description,
+        }
+    }
+}
+
+/// Continuations of the 'g' (goto) prefix
+const G_CONTINUATIONS: &[KeyContinuation] = &[
+    KeyContinuation::new('g', KeySequenceAction::GotoFirst, "first row"),
+    KeyContinuation::new('e', KeySequenceAction::GotoEditor, "editor"),
+    KeyContinuation::new('c', KeySequenceAction::GotoConnections, "connections"),
+    KeyContinuation::new('s', KeySequenceAction::GotoTables, "schema"),
+    KeyContinuation::new('r', KeySequenceAction::GotoResults, "results"),
+    KeyContinuation::new('h', KeySequenceAction::GotoHistory, "history"),
+    KeyContinuation::new('m', KeySequenceAction::OpenConnectionManager, "manager"),
+    KeyContinuation::new('x', KeySequenceAction::OpenLink, "open URL"),
+];
+
+/// Continuations of schema table actions (started by Enter on a table in the schema panel)
+const SCHEMA_TABLE_CONTINUATIONS: &[KeyContinuation] = &[
+    KeyContinuation::new('s', KeySequenceAction::SchemaTableSelect, "select"),
+    KeyContinuation::new('i', KeySequenceAction::SchemaTableInsert, "insert"),
+    KeyContinuation::new('u', KeySequenceAction::SchemaTableUpdate, "update"),
+    KeyContinuation::new('d', KeySequenceAction::SchemaTableDelete, "delete"),
+    KeyContinuation::new('n', KeySequenceAction::SchemaTableName, "name"),
+    KeyContinuation::new('b', KeySequenceAction::SchemaTableBrowse, "browse"),
+];
+
+/// Continuations of the '[' (previous) prefix in the results grid
+const PREVIOUS_CONTINUATIONS: &[KeyContinuation] = &[KeyContinuation::new(
+    'g',
+    KeySequenceAction::PreviousResult,
+    "previous result",
+)];
+
+/// Continuations of the ']' (next) prefix in the results grid
+const NEXT_CONTINUATIONS: &[KeyContinuation] = &[KeyContinuation::new(
+    'g',
+    KeySequenceAction::NextResult,
+    "next result",
+)];
+
+/// Continuations of the 'z' (view) prefix in the results grid
+const Z_CONTINUATIONS: &[KeyContinuation] = &[KeyContinuation::new(
+    'p',
+    KeySequenceAction::PinColumns,
+    "pin columns through cursor",
+)];
+
+/// Continuations of the 'd' (delete) prefix in the results grid
+const D_CONTINUATIONS: &[KeyContinuation] = &[KeyContinuation::new(
+    'd',
+    KeySequenceAction::DeleteRows,
+    "delete row(s)",
+)];
+
+/// Continuations of the `Ctrl+W` window-command prefix
+const WINDOW_CONTINUATIONS: &[KeyContinuation] = &[
+    KeyContinuation::new('h', KeySequenceAction::WindowLeft, "left pane"),
+    KeyContinuation::new('j', KeySequenceAction::WindowDown, "pane below"),
+    KeyContinuation::new('k', KeySequenceAction::WindowUp, "pane above"),
+    KeyContinuation::new('l', KeySequenceAction::WindowRight, "right pane"),
+    KeyContinuation::new('w', KeySequenceAction::WindowNext, "next pane"),
+    KeyContinuation::new('s', KeySequenceAction::WindowSplit, "split editor"),
+    KeyContinuation::new('o', KeySequenceAction::WindowOnly, "close other editor"),
+    KeyContinuation::new('=', KeySequenceAction::WindowEqualize, "equalize"),
+    KeyContinuation::new('|', KeySequenceAction::WindowMaximizeWidth, "max width"),
+    KeyContinuation::new('_', KeySequenceAction::WindowMaximizeHeight, "max height"),
+];
+
 /// Result of processing a key in a sequence
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum KeySequenceResult<C = ()> {
@@ -62,6 +172,36 @@ pub enum KeySequenceAction {
     GotoHistory,
     /// Open the connection manager
     OpenConnectionManager,
+    /// Open the URL in the current result cell
+    OpenLink,
+    /// Show the previous result set from result history
+    PreviousResult,
+    /// Show the next result set from result history
+    NextResult,
+    /// Pin the grid columns through the cursor's, or unpin them
+    PinColumns,
+    /// Delete the cursor row, or the selected rows, from the source table
+    DeleteRows,
+
+    // ─────────────────────────────────────────────────────────────────────
+    // Window commands (Ctrl+W + key)
+    // ─────────────────────────────────────────────────────────────────────
+    WindowLeft,
+    WindowDown,
+    WindowUp,
+    WindowRight,
+    /// Focus the next pane
+    WindowNext,
+    /// Split the editor and results evenly
+    WindowEqualize,
+    /// Give the focused pane all the width
+    WindowMaximizeWidth,
+    /// Give the focused pane all the height
+    WindowMaximizeHeight,
+    /// Split the query editor into two buffers
+    WindowSplit,
+    /// Close the query buffer not being edited
+    WindowOnly,
 
     // ─────────────────────────────────────────────────────────────────────
     // Schema panel table templates (Enter + key)
@@ -71,6 +211,7 @@ pub enum KeySequenceAction {
     SchemaTableUpdate,
     SchemaTableDelete,
     SchemaTableName,
+    SchemaTableBrowse,
 }
 
 /// Handles multi-key sequences with timeout-based hint display.
@@ -191,6 +332,22 @@ impl<C> KeySequenceHandlerWithContext<C> {
                 self.start(PendingKey::G);
                 KeySequenceResult::Started(PendingKey::G)
             }
+            '[' => {
+                self.start(PendingKey::Previous);
+                KeySequenceResult::Started(PendingKey::Previous)
+            }
+            ']' => {
+                self.start(PendingKey::Next);
+                KeySequenceResult::Started(PendingKey::Next)
+            }
+            'z' => {
+                self.start(PendingKey::Z);
+                KeySequenceResult::Started(PendingKey::Z)
+            }
+            'd' => {
+                self.start(PendingKey::D);
+                KeySequenceResult::Started(PendingKey::D)
+            }
             _ => KeySequenceResult::NotConsumed,
         }
     }
@@ -202,26 +359,11 @@ impl<C> KeySequenceHandlerWithContext<C> {
             return KeySequenceResult::NotConsumed;
         };
 
-        let action = match pending {
-            PendingKey::G => match c {
-                'g' => Some(KeySequenceAction::GotoFirst),
-                'e' => Some(KeySequenceAction::GotoEditor),
-                'c' => Some(KeySequenceAction::GotoConnections),
-                's' => Some(KeySequenceAction::GotoTables),
-                'r' => Some(KeySequenceAction::GotoResults),
-                'h' => Some(KeySequenceAction::GotoHistory),
-                'm' => Some(KeySequenceAction::OpenConnectionManager),
-                _ => None,
-            },
-            PendingKey::SchemaTable => match c {
-                's' => Some(KeySequenceAction::SchemaTableSelect),
-                'i' => Some(KeySequenceAction::SchemaTableInsert),
-                'u' => Some(KeySequenceAction::SchemaTableUpdate),
-                'd' => Some(KeySequenceAction::SchemaTableDelete),
-                'n' => Some(KeySequenceAction::SchemaTableName),
-                _ => None,
-            },
-        };
+        let action = pending
+            .continuations()
+            .iter()
+            .find(|continuation| continuation.key == c)
+            .map(|continuation| continuation.action);
 
         match action {
             Some(action) => {
@@ -278,6 +420,29 @@ mod tests {
         assert!(!handler.is_waiting());
     }
 
+    #[test]
+    fn test_window_sequence() {
+        let mut handler = KeySequenceHandler::new(500);
+
+        handler.start(PendingKey::Window);
+        assert_eq!(handler.pending(), Some(PendingKey::Window));
+        let result = handler.process_second_key('|');
+        assert_eq!(
+            result,
+            KeySequenceResult::Completed(KeySequenceCompletion {
+                action: KeySequenceAction::WindowMaximizeWidth,
+                context: None
+            })
+        );
+
+        handler.start(PendingKey::Window);
+        assert_eq!(
+            handler.process_second_key('x'),
+            KeySequenceResult::Cancelled
+        );
+        assert!(!handler.is_waiting());
+    }
+
     #[test]
     fn test_g_sequence_ge() {
         let mut handler = KeySequenceHandler::new(500);
@@ -368,12 +533,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_g_sequence_gx() {
+        let mut handler = KeySequenceHandler::new(500);
+
+        handler.process_first_key('g');
+        let result = handler.process_second_key('x');
+        assert_eq!(
+            result,
+            KeySequenceResult::Completed(KeySequenceCompletion {
+                action: KeySequenceAction::OpenLink,
+                context: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_bracket_g_sequences_flip_results() {
+        let mut handler = KeySequenceHandler::new(500);
+
+        let result = handler.process_first_key('[');
+        assert_eq!(result, KeySequenceResult::Started(PendingKey::Previous));
+        let result = handler.process_second_key('g');
+        assert_eq!(
+            result,
+            KeySequenceResult::Completed(KeySequenceCompletion {
+                action: KeySequenceAction::PreviousResult,
+                context: None
+            })
+        );
+
+        handler.process_first_key(']');
+        let result = handler.process_second_key('g');
+        assert_eq!(
+            result,
+            KeySequenceResult::Completed(KeySequenceCompletion {
+                action: KeySequenceAction::NextResult,
+                context: None
+            })
+        );
+
+        handler.process_first_key(']');
+        assert_eq!(
+            handler.process_second_key('e'),
+            KeySequenceResult::Cancelled
+        );
+    }
+
+    #[test]
+    fn test_d_sequence_dd() {
+        let mut handler = KeySequenceHandler::new(500);
+
+        let result = handler.process_first_key('d');
+        assert_eq!(result, KeySequenceResult::Started(PendingKey::D));
+        let result = handler.process_second_key('d');
+        assert_eq!(
+            result,
+            KeySequenceResult::Completed(KeySequenceCompletion {
+                action: KeySequenceAction::DeleteRows,
+                context: None
+            })
+        );
+    }
+
     #[test]
     fn test_cancelled_sequence() {
         let mut handler = KeySequenceHandler::new(500);
 
         handler.process_first_key('g');
-        let result = handler.process_second_key('x'); // Invalid second key
+        let result = handler.process_second_key('z'); // Invalid second key
         assert_eq!(result, KeySequenceResult::Cancelled);
         assert!(!handler.is_waiting());
     }
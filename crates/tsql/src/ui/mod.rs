@@ -1,28 +1,37 @@
 mod action_palette;
 mod ai_query_modal;
+mod command_palette;
 mod completion;
 mod confirm_prompt;
 mod connection_form;
 mod connection_manager;
 mod editor;
 pub mod fuzzy_picker;
+mod gen_preview;
 mod grid;
 mod help_popup;
 mod highlighted_editor;
+mod import_mapping;
+mod insert_row_form;
 mod json_editor;
 mod key_hint_popup;
 mod key_sequence;
 mod mouse_util;
 pub use mouse_util::{is_inside, MOUSE_SCROLL_LINES};
+mod notes_editor;
 mod password_prompt;
+mod plan_view;
+mod query_params_form;
 mod row_detail;
 pub mod sidebar;
+mod sql_preview;
 mod status_line;
 mod style;
 mod theme;
 
 pub use action_palette::{action_entries, ActionContext, ActionEntry, PaletteAction};
 pub use ai_query_modal::{AiQueryModal, AiQueryModalAction};
+pub use command_palette::{command_entries, CommandEntry, CommandTarget};
 pub use completion::{
     determine_context, get_word_before_cursor, ColumnInfo, CompletionContext, CompletionItem,
     CompletionKind, CompletionPopup, SchemaCache, TableInfo,
@@ -32,22 +41,31 @@ pub use connection_form::{ConnectionFormAction, ConnectionFormModal, FormField};
 pub use connection_manager::{ConnectionManagerAction, ConnectionManagerModal};
 pub use editor::{CommandPrompt, QueryEditor, SearchPrompt};
 pub use fuzzy_picker::{FilteredItem, FuzzyPicker, PickerAction};
+pub use gen_preview::{GenPreview, GenPreviewResult};
 pub use grid::{
-    escape_sql_value, quote_identifier, DataGrid, GridKeyResult, GridModel, GridSearch, GridState,
-    GridViewport, ResizeAction,
+    column_layout, escape_sql_value, mask_pattern_matches, quote_identifier, sql_literal,
+    visible_links, DataGrid, ForeignKeyColumn, GridKeyResult, GridLink, GridModel, GridSearch,
+    GridState, GridViewport, ResizeAction, YankTarget, MASKED_VALUE,
 };
 pub use help_popup::{HelpAction, HelpPopup};
 pub use highlighted_editor::{create_sql_highlighter, CursorShape, HighlightedTextArea};
+pub use import_mapping::{ImportMappingAction, ImportMappingForm};
+pub use insert_row_form::{InsertRowAction, InsertRowForm};
 pub use json_editor::{JsonEditorAction, JsonEditorModal};
 pub use key_hint_popup::KeyHintPopup;
 pub use key_sequence::{
     KeySequenceAction, KeySequenceCompletion, KeySequenceHandler, KeySequenceHandlerWithContext,
     KeySequenceResult, PendingKey,
 };
+pub use notes_editor::{NotesAction, NotesEditor};
 pub use password_prompt::{PasswordPrompt, PasswordPromptResult};
+pub use plan_view::{PlanView, PlanViewResult};
+pub use query_params_form::{QueryParamsAction, QueryParamsForm};
 pub use row_detail::{RowDetailAction, RowDetailModal, YankFormat};
 pub use sidebar::{Sidebar, SidebarAction};
+pub use sql_preview::{SqlPreview, SqlPreviewResult};
 pub use status_line::{ConnectionInfo, Priority, StatusLineBuilder, StatusSegment};
 pub use theme::{
-    load_theme, overlay_block, zone_block, zone_inner, zone_label, zone_scrollbar_area, UiTheme,
+    load_theme, overlay_block, strip_colors, zone_block, zone_inner, zone_label,
+    zone_scrollbar_area, UiTheme,
 };
@@ -0,0 +1,369 @@
+//! Floating scratch-notes buffer opened with `:notes`.
+//!
+//! A markdown-highlighted text area with the same Vim bindings as the cell
+//! editor. Notes are saved by the app on close; `Ctrl+Y` hands the visual
+//! selection (or the current line) to the query editor.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+use tui_textarea::{CursorMove, TextArea};
+
+use tui_syntax::{markdown, Highlighter, Theme};
+
+use crate::ui::HighlightedTextArea;
+use crate::vim::{Motion, VimCommand, VimConfig, VimHandler, VimMode};
+
+use super::{overlay_block, UiTheme};
+
+/// The result of handling a key event in the notes editor.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NotesAction {
+    /// Keep editing.
+    Continue,
+    /// Persist the notes and keep the buffer open.
+    Save(String),
+    /// Persist the notes and close the buffer.
+    Close(String),
+    /// Insert text into the query editor, persisting and closing the buffer.
+    InsertIntoEditor { text: String, notes: String },
+}
+
+/// The `:notes` scratch buffer.
+pub struct NotesEditor<'a> {
+    textarea: TextArea<'a>,
+    highlighter: Highlighter,
+    /// Connection the notes belong to, shown in the title.
+    label: String,
+    /// Content as last saved, for the `[+]` indicator.
+    saved: String,
+    scroll_offset: (u16, u16),
+    mode: VimMode,
+    vim_handler: VimHandler,
+}
+
+impl<'a> NotesEditor<'a> {
+    pub fn new(notes: String, label: String, syntax_theme: Theme) -> Self {
+        let lines: Vec<String> = notes.lines().map(str::to_string).collect();
+        let mut textarea = if lines.is_empty() {
+            TextArea::default()
+        } else {
+            TextArea::new(lines)
+        };
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_cursor_style(Style::default().add_modifier(Modifier::REVERSED));
+        textarea.move_cursor(CursorMove::Bottom);
+        textarea.move_cursor(CursorMove::End);
+
+        let mut highlighter = Highlighter::new(syntax_theme);
+        let _ = highlighter.register_language(markdown());
+
+        Self {
+            textarea,
+            highlighter,
+            label,
+            saved: notes,
+            scroll_offset: (0, 0),
+            mode: VimMode::Normal,
+            vim_handler: VimHandler::new(VimConfig::json_editor()),
+        }
+    }
+
+    pub fn content(&self) -> String {
+        self.textarea.lines().join("\n")
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.content() != self.saved
+    }
+
+    /// Records that the current content has been written to disk.
+    pub fn mark_saved(&mut self) {
+        self.saved = self.content();
+    }
+
+    /// Insert bracketed-paste text literally, independent of the current Vim mode.
+    pub fn paste_text(&mut self, text: &str) {
+        self.textarea.insert_str(text);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> NotesAction {
+        if key.code == KeyCode::Char('y') && key.modifiers == KeyModifiers::CONTROL {
+            return NotesAction::InsertIntoEditor {
+                text: self.yank_for_editor(),
+                notes: self.content(),
+            };
+        }
+        if self.mode == VimMode::Normal
+            && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            && key.modifiers == KeyModifiers::NONE
+            && !self.vim_handler.has_pending()
+        {
+            return NotesAction::Close(self.content());
+        }
+
+        let command = self.vim_handler.handle_key(key, self.mode);
+        self.execute_command(command, key)
+    }
+
+    /// The visual selection, or the current line when nothing is selected.
+    fn yank_for_editor(&mut self) -> String {
+        if self.mode == VimMode::Visual {
+            self.textarea.copy();
+            self.textarea.cancel_selection();
+            self.mode = VimMode::Normal;
+            return self.textarea.yank_text();
+        }
+        let (row, _) = self.textarea.cursor();
+        self.textarea.lines().get(row).cloned().unwrap_or_default()
+    }
+
+    fn execute_command(&mut self, command: VimCommand, key: KeyEvent) -> NotesAction {
+        match command {
+            VimCommand::ChangeMode(mode) => self.mode = mode,
+            VimCommand::Move(motion) => self.apply_motion(motion),
+            VimCommand::EnterInsertAt { motion, mode } => {
+                if let Some(motion) = motion {
+                    self.apply_motion(motion);
+                }
+                self.mode = mode;
+            }
+            VimCommand::OpenLine { above } => {
+                if above {
+                    self.textarea.move_cursor(CursorMove::Head);
+                    self.textarea.insert_newline();
+                    self.textarea.move_cursor(CursorMove::Up);
+                } else {
+                    self.textarea.move_cursor(CursorMove::End);
+                    self.textarea.insert_newline();
+                }
+                self.mode = VimMode::Insert;
+            }
+            VimCommand::DeleteChar => {
+                self.textarea.delete_next_char();
+            }
+            VimCommand::DeleteCharBefore => {
+                self.textarea.delete_char();
+            }
+            VimCommand::DeleteToEnd => {
+                self.textarea.delete_line_by_end();
+            }
+            VimCommand::DeleteLine => {
+                self.textarea.move_cursor(CursorMove::Head);
+                self.textarea.delete_line_by_end();
+                self.textarea.delete_next_char();
+            }
+            VimCommand::DeleteMotion(motion) => self.cut_motion(motion),
+            VimCommand::ChangeToEnd => {
+                self.textarea.delete_line_by_end();
+                self.mode = VimMode::Insert;
+            }
+            VimCommand::ChangeLine => {
+                self.textarea.move_cursor(CursorMove::Head);
+                self.textarea.delete_line_by_end();
+                self.mode = VimMode::Insert;
+            }
+            VimCommand::ChangeMotion(motion) => {
+                self.cut_motion(motion);
+                self.mode = VimMode::Insert;
+            }
+            VimCommand::YankLine | VimCommand::YankMotion(_) => {
+                let (row, _) = self.textarea.cursor();
+                if let Some(line) = self.textarea.lines().get(row) {
+                    self.textarea.set_yank_text(line.clone() + "\n");
+                }
+            }
+            VimCommand::PasteAfter | VimCommand::PasteBefore => {
+                self.textarea.paste();
+            }
+            VimCommand::Undo => {
+                self.textarea.undo();
+            }
+            VimCommand::Redo => {
+                self.textarea.redo();
+            }
+            VimCommand::StartVisual => {
+                self.textarea.start_selection();
+                self.mode = VimMode::Visual;
+            }
+            VimCommand::CancelVisual => {
+                self.textarea.cancel_selection();
+                self.mode = VimMode::Normal;
+            }
+            VimCommand::VisualYank => {
+                self.textarea.copy();
+                self.textarea.cancel_selection();
+                self.mode = VimMode::Normal;
+            }
+            VimCommand::VisualDelete => {
+                self.textarea.cut();
+                self.textarea.cancel_selection();
+                self.mode = VimMode::Normal;
+            }
+            VimCommand::VisualChange => {
+                self.textarea.cut();
+                self.textarea.cancel_selection();
+                self.mode = VimMode::Insert;
+            }
+            VimCommand::PassThrough => {
+                self.textarea.input(key);
+            }
+            VimCommand::Custom(name) => match name.as_str() {
+                "save" => return NotesAction::Save(self.content()),
+                "cancel" => return NotesAction::Close(self.content()),
+                _ => {}
+            },
+            VimCommand::None => {}
+        }
+        NotesAction::Continue
+    }
+
+    fn apply_motion(&mut self, motion: Motion) {
+        match motion {
+            Motion::Cursor(cursor) => self.textarea.move_cursor(cursor),
+            Motion::Up(n) => (0..n).for_each(|_| self.textarea.move_cursor(CursorMove::Up)),
+            Motion::Down(n) => (0..n).for_each(|_| self.textarea.move_cursor(CursorMove::Down)),
+        }
+    }
+
+    fn cut_motion(&mut self, motion: Motion) {
+        self.textarea.start_selection();
+        self.apply_motion(motion);
+        self.textarea.cut();
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let width = (area.width as f32 * 0.7) as u16;
+        let height = (area.height as f32 * 0.7) as u16;
+        let modal_area = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(modal_area);
+        let (editor_area, status_area) = (chunks[0], chunks[1]);
+
+        let modified = if self.is_modified() { " [+]" } else { "" };
+        let title = format!("Notes: {}{}", self.label, modified);
+        let block = overlay_block(&title, theme);
+
+        let content = self.content();
+        let highlighted = self
+            .highlighter
+            .highlight("markdown", &content)
+            .unwrap_or_else(|_| content.lines().map(|l| Line::from(l.to_string())).collect());
+        let widget = HighlightedTextArea::new(&self.textarea, highlighted)
+            .block(block)
+            .cursor_style(theme.editor_cursor)
+            .selection_style(theme.editor_selection)
+            .scroll(self.scroll_offset);
+        frame.render_widget(widget, editor_area);
+
+        let (cursor_row, cursor_col) = self.textarea.cursor();
+        let inner_height = editor_area.height.saturating_sub(2) as usize;
+        if cursor_row >= self.scroll_offset.0 as usize + inner_height {
+            self.scroll_offset.0 = (cursor_row + 1).saturating_sub(inner_height) as u16;
+        } else if cursor_row < self.scroll_offset.0 as usize {
+            self.scroll_offset.0 = cursor_row as u16;
+        }
+
+        let mode_color = match self.mode {
+            VimMode::Normal => theme.accent,
+            VimMode::Insert => theme.accent_insert,
+            VimMode::Visual => theme.accent_visual,
+        };
+        let hint = match self.mode {
+            VimMode::Normal => " Ctrl+Y:line to editor  Ctrl+S:save  q/Esc:close ",
+            VimMode::Insert => " Esc:normal  Ctrl+S:save ",
+            VimMode::Visual => " Ctrl+Y:selection to editor  y:yank  Esc:cancel ",
+        };
+        let status = Line::from(vec![
+            Span::styled(format!(" {} ", self.mode.label()), theme.pill(mode_color)),
+            Span::raw(format!(" Ln {}, Col {} ", cursor_row + 1, cursor_col + 1)),
+            Span::styled(hint, Style::default().fg(theme.text_muted)),
+        ]);
+        frame.render_widget(
+            Paragraph::new(status).style(Style::default().fg(theme.text).bg(theme.bg_status)),
+            status_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::style::assert_nonblank_cells_have_explicit_fg;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+    use tui_syntax::themes;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn notes(text: &str) -> NotesEditor<'static> {
+        NotesEditor::new(text.to_string(), "local".to_string(), themes::one_dark())
+    }
+
+    #[test]
+    fn typing_marks_notes_modified_and_close_returns_content() {
+        let mut editor = notes("# Findings");
+        assert!(!editor.is_modified());
+
+        editor.handle_key(key(KeyCode::Char('o')));
+        for c in "- id 42".chars() {
+            editor.handle_key(key(KeyCode::Char(c)));
+        }
+        editor.handle_key(key(KeyCode::Esc));
+        assert!(editor.is_modified());
+
+        assert_eq!(
+            editor.handle_key(key(KeyCode::Char('q'))),
+            NotesAction::Close("# Findings\n- id 42".to_string())
+        );
+        editor.mark_saved();
+        assert!(!editor.is_modified());
+    }
+
+    #[test]
+    fn ctrl_y_hands_the_current_line_to_the_editor() {
+        let mut editor = notes("SELECT 1;\nSELECT 2;");
+        let action = editor.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::CONTROL));
+        assert_eq!(
+            action,
+            NotesAction::InsertIntoEditor {
+                text: "SELECT 2;".to_string(),
+                notes: "SELECT 1;\nSELECT 2;".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn ctrl_s_saves_without_closing() {
+        let mut editor = notes("todo");
+        let action = editor.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert_eq!(action, NotesAction::Save("todo".to_string()));
+    }
+
+    #[test]
+    fn render_uses_visible_foregrounds() {
+        let mut editor = notes("# Heading\n\n- item");
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        terminal
+            .draw(|frame| editor.render(frame, frame.area(), &UiTheme::fallback()))
+            .unwrap();
+
+        assert_nonblank_cells_have_explicit_fg(terminal.backend().buffer());
+    }
+}
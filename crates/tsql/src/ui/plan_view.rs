@@ -0,0 +1,859 @@
+//! Tree view of a PostgreSQL query plan from `EXPLAIN (FORMAT JSON)`.
+//!
+//! Each plan node is one line: its type and target, the time spent in the
+//! node itself (with `ANALYZE`) or its own share of the cost, and estimated
+//! against actual rows. The nodes that take most of the query are colored so
+//! the expensive part of a deep plan stands out. `i` turns what the plan
+//! shows into follow-up statements for the editor, such as an index on the
+//! columns a sequential scan filters on.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use serde_json::Value;
+use unicode_width::UnicodeWidthStr;
+
+use super::{overlay_block, quote_identifier, UiTheme};
+
+/// A node's share of the query at which it is drawn as a hot spot.
+const HOT_SHARE: f64 = 0.5;
+/// A node's share of the query at which it is drawn as worth a look.
+const WARM_SHARE: f64 = 0.2;
+/// Actual rows off from the estimate by this factor are flagged.
+const MISESTIMATE_FACTOR: f64 = 10.0;
+
+/// Words of a plan's filter expressions that are not column names.
+const FILTER_KEYWORDS: &[&str] = &[
+    "all", "and", "any", "array", "between", "case", "collate", "distinct", "else", "end",
+    "escape", "false", "from", "hashed", "ilike", "in", "initplan", "is", "like", "not", "null",
+    "or", "some", "subplan", "then", "true", "unknown", "when",
+];
+
+/// Plan fields shown under the selected node, in this order.
+const DETAIL_KEYS: &[&str] = &[
+    "Index Cond",
+    "Recheck Cond",
+    "Hash Cond",
+    "Merge Cond",
+    "Join Filter",
+    "Filter",
+    "Rows Removed by Filter",
+    "Rows Removed by Join Filter",
+    "Sort Key",
+    "Sort Method",
+    "Group Key",
+    "Workers Planned",
+    "Workers Launched",
+];
+
+/// Result of handling input in the plan view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanViewResult {
+    /// Still open.
+    Pending,
+    /// Copy the plan's JSON (`y`).
+    Copy(String),
+    /// Put follow-up statements in the editor (`i`); empty when the plan
+    /// suggests none.
+    Insert(String),
+    /// Close the view (`q` or `Esc`).
+    Close,
+}
+
+/// One node of the plan, with its children's indices.
+#[derive(Debug, Clone, PartialEq)]
+struct PlanNode {
+    depth: usize,
+    /// "Seq Scan", "Hash Join (Left)", ...
+    label: String,
+    /// "on users u", "using users_pkey", ...
+    target: String,
+    /// The scanned table, quoted and schema-qualified when EXPLAIN names the
+    /// schema.
+    relation: Option<String>,
+    total_cost: f64,
+    /// Cost of this node without its children.
+    self_cost: f64,
+    plan_rows: f64,
+    /// Rows over all loops, with `ANALYZE`.
+    actual_rows: Option<f64>,
+    /// Milliseconds over all loops, with `ANALYZE`.
+    total_ms: Option<f64>,
+    /// Milliseconds without the children, with `ANALYZE`.
+    self_ms: Option<f64>,
+    loops: Option<f64>,
+    details: Vec<(String, String)>,
+    children: Vec<usize>,
+    expanded: bool,
+}
+
+/// The plan of one statement, browsable node by node.
+pub struct PlanView {
+    title: String,
+    json: String,
+    /// Nodes in plan order; index 0 is the root.
+    nodes: Vec<PlanNode>,
+    planning_ms: Option<f64>,
+    execution_ms: Option<f64>,
+    /// Index into `visible()` of the selected line.
+    selected: usize,
+    scroll: usize,
+    visible_height: usize,
+}
+
+impl PlanView {
+    /// Parses the single-row output of `EXPLAIN (FORMAT JSON)`, with or
+    /// without `ANALYZE`.
+    pub fn from_explain_json(title: impl Into<String>, json: &str) -> Result<Self, String> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| format!("invalid EXPLAIN output: {e}"))?;
+        let entry = value
+            .get(0)
+            .ok_or_else(|| "EXPLAIN output has no plan".to_string())?;
+        let plan = entry
+            .get("Plan")
+            .ok_or_else(|| "EXPLAIN output has no plan".to_string())?;
+        let mut nodes = Vec::new();
+        push_node(plan, 0, &mut nodes);
+        Ok(Self {
+            title: title.into(),
+            json: json.to_string(),
+            nodes,
+            planning_ms: entry.get("Planning Time").and_then(Value::as_f64),
+            execution_ms: entry.get("Execution Time").and_then(Value::as_f64),
+            selected: 0,
+            scroll: 0,
+            visible_height: 0,
+        })
+    }
+
+    /// Whether the plan carries actual timings (`EXPLAIN ANALYZE`).
+    pub fn analyzed(&self) -> bool {
+        self.nodes
+            .first()
+            .is_some_and(|root| root.total_ms.is_some())
+    }
+
+    /// Indices of the nodes not hidden under a collapsed parent, in order.
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            visible.push(index);
+            if node.expanded {
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        visible
+    }
+
+    fn selected_node(&self) -> Option<usize> {
+        self.visible().get(self.selected).copied()
+    }
+
+    /// The node's share of the whole query: its own time with `ANALYZE`,
+    /// otherwise its own cost.
+    fn share(&self, node: &PlanNode) -> f64 {
+        let root = &self.nodes[0];
+        let (part, whole) = match (node.self_ms, root.total_ms) {
+            (Some(part), Some(whole)) => (part, whole),
+            _ => (node.self_cost, root.total_cost),
+        };
+        if whole > 0.0 {
+            (part / whole).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PlanViewResult {
+        let last = self.visible().len().saturating_sub(1);
+        let page = self.visible_height.saturating_sub(1).max(1);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return PlanViewResult::Close,
+            KeyCode::Char('y') => return PlanViewResult::Copy(self.json.clone()),
+            KeyCode::Char('i') => return PlanViewResult::Insert(self.follow_ups()),
+            KeyCode::Char('j') | KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('d') if key.modifiers == KeyModifiers::CONTROL => {
+                self.selected = (self.selected + page / 2).min(last);
+            }
+            KeyCode::Char('u') if key.modifiers == KeyModifiers::CONTROL => {
+                self.selected = self.selected.saturating_sub(page / 2);
+            }
+            KeyCode::PageDown => self.selected = (self.selected + page).min(last),
+            KeyCode::PageUp => self.selected = self.selected.saturating_sub(page),
+            KeyCode::Char('g') | KeyCode::Home => self.selected = 0,
+            KeyCode::Char('G') | KeyCode::End => self.selected = last,
+            KeyCode::Enter | KeyCode::Char(' ' | 'o') => {
+                if let Some(index) = self.selected_node() {
+                    let node = &mut self.nodes[index];
+                    node.expanded = !node.expanded;
+                }
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                if let Some(index) = self.selected_node() {
+                    self.nodes[index].expanded = true;
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Left => self.collapse_or_select_parent(),
+            KeyCode::Char('E') => self.set_all_expanded(true),
+            KeyCode::Char('C') => {
+                self.set_all_expanded(false);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        PlanViewResult::Pending
+    }
+
+    /// Statements worth trying after reading the plan, each under a comment
+    /// saying why: an index on the columns a sequential scan filters on, and
+    /// `ANALYZE` for tables whose row estimates were far off.
+    pub fn follow_ups(&self) -> String {
+        let mut statements: Vec<(String, String)> = Vec::new();
+        let mut push = |why: String, sql: String| {
+            if !statements.iter().any(|(_, existing)| *existing == sql) {
+                statements.push((why, sql));
+            }
+        };
+        for node in &self.nodes {
+            let Some(relation) = node.relation.as_deref() else {
+                continue;
+            };
+            let detail = |key: &str| {
+                node.details
+                    .iter()
+                    .find(|(name, _)| name == key)
+                    .map(|(_, value)| value.as_str())
+            };
+            if node.label.ends_with("Seq Scan") {
+                if let Some(filter) = detail("Filter") {
+                    let columns = filter_columns(filter);
+                    if !columns.is_empty() {
+                        let mut why =
+                            format!("-- {} {} filters by {filter}", node.label, node.target);
+                        if let Some(removed) = detail("Rows Removed by Filter")
+                            .and_then(|removed| removed.parse::<f64>().ok())
+                        {
+                            why.push_str(&format!(", removing {} rows", format_count(removed)));
+                        }
+                        push(
+                            why,
+                            format!("CREATE INDEX ON {relation} ({});", columns.join(", ")),
+                        );
+                    }
+                }
+            }
+            if misestimated(node) {
+                push(
+                    format!("-- Row estimates for {relation} were off by more than 10x"),
+                    format!("ANALYZE {relation};"),
+                );
+            }
+        }
+        statements
+            .iter()
+            .map(|(why, sql)| format!("{why}\n{sql}"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn collapse_or_select_parent(&mut self) {
+        let Some(index) = self.selected_node() else {
+            return;
+        };
+        if self.nodes[index].expanded && !self.nodes[index].children.is_empty() {
+            self.nodes[index].expanded = false;
+            return;
+        }
+        let parent = self
+            .nodes
+            .iter()
+            .position(|node| node.children.contains(&index));
+        if let Some(parent) = parent {
+            if let Some(line) = self.visible().iter().position(|&i| i == parent) {
+                self.selected = line;
+            }
+        }
+    }
+
+    fn set_all_expanded(&mut self, expanded: bool) {
+        for node in &mut self.nodes {
+            node.expanded = expanded;
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 120u16.min(area.width.saturating_sub(4));
+        let dialog_height = area.height.saturating_sub(4);
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        let block = overlay_block(&self.title, theme);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(1), // Summary
+            Constraint::Min(3),    // Plan tree
+            Constraint::Length(5), // Selected node details
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(self.summary()).style(Style::default().fg(theme.text_muted)),
+            chunks[0],
+        );
+
+        let visible = self.visible();
+        self.selected = self.selected.min(visible.len().saturating_sub(1));
+        self.visible_height = usize::from(chunks[1].height);
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.visible_height > 0 && self.selected >= self.scroll + self.visible_height {
+            self.scroll = self.selected + 1 - self.visible_height;
+        }
+        let width = usize::from(chunks[1].width);
+        let lines: Vec<Line> = visible
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_height)
+            .map(|(line, &index)| self.node_line(index, line == self.selected, width, theme))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+        if let Some(index) = self.selected_node() {
+            frame.render_widget(
+                Paragraph::new(self.detail_lines(&self.nodes[index], theme))
+                    .wrap(Wrap { trim: true }),
+                chunks[2],
+            );
+        }
+
+        let key_style = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("j/k", key_style(theme.text_muted)),
+            Span::raw(" move  "),
+            Span::styled("Enter/h/l", key_style(theme.text_muted)),
+            Span::raw(" fold  "),
+            Span::styled("E/C", key_style(theme.text_muted)),
+            Span::raw(" expand/collapse all  "),
+            Span::styled("y", key_style(theme.success)),
+            Span::raw(" copy JSON  "),
+            Span::styled("i", key_style(theme.success)),
+            Span::raw(" follow-ups to editor  "),
+            Span::styled("q/Esc", key_style(theme.error)),
+            Span::raw(" close"),
+        ]))
+        .alignment(Alignment::Center);
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn summary(&self) -> String {
+        let mut parts = vec![format!(
+            "{} node{}",
+            self.nodes.len(),
+            if self.nodes.len() == 1 { "" } else { "s" }
+        )];
+        if let Some(ms) = self.planning_ms {
+            parts.push(format!("planning {}", format_ms(ms)));
+        }
+        if let Some(ms) = self.execution_ms {
+            parts.push(format!("execution {}", format_ms(ms)));
+        }
+        if !self.analyzed() {
+            parts.push("estimates only".to_string());
+        }
+        parts.join(" · ")
+    }
+
+    fn node_line(
+        &self,
+        index: usize,
+        selected: bool,
+        width: usize,
+        theme: &UiTheme,
+    ) -> Line<'static> {
+        let node = &self.nodes[index];
+        let marker = match (node.children.is_empty(), node.expanded) {
+            (true, _) => "  ",
+            (false, true) => "▾ ",
+            (false, false) => "▸ ",
+        };
+        let share = self.share(node);
+        let (time, rows) = node_stats(node);
+        let stats = format!("{time:>10} {:>4.0}%  {rows}", share * 100.0);
+
+        let indent = "  ".repeat(node.depth);
+        let mut name = format!("{indent}{marker}{}", node.label);
+        if !node.target.is_empty() {
+            name.push(' ');
+            name.push_str(&node.target);
+        }
+        let name_width = width.saturating_sub(stats.width() + 1).max(1);
+        let name = fit(&name, name_width);
+
+        let name_style = if share >= HOT_SHARE {
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD)
+        } else if share >= WARM_SHARE {
+            Style::default().fg(theme.warning)
+        } else {
+            Style::default().fg(theme.text)
+        };
+        let rows_style = if misestimated(node) {
+            Style::default().fg(theme.warning)
+        } else {
+            Style::default().fg(theme.text_muted)
+        };
+        let (time, percent_rows) = stats.split_at(10);
+        let line = Line::from(vec![
+            Span::styled(format!("{name} "), name_style),
+            Span::styled(time.to_string(), name_style),
+            Span::styled(percent_rows.to_string(), rows_style),
+        ]);
+        if selected {
+            line.style(theme.selection)
+        } else {
+            line
+        }
+    }
+
+    fn detail_lines(&self, node: &PlanNode, theme: &UiTheme) -> Vec<Line<'static>> {
+        let label = Style::default().fg(theme.text_muted);
+        let mut summary = vec![Span::styled(
+            format!("cost {:.2}", node.total_cost),
+            Style::default().fg(theme.text),
+        )];
+        if let Some(ms) = node.total_ms {
+            summary.push(Span::styled(
+                format!(
+                    " · {} total, {} in this node",
+                    format_ms(ms),
+                    format_ms(node.self_ms.unwrap_or(ms))
+                ),
+                Style::default().fg(theme.text),
+            ));
+        }
+        if let Some(loops) = node.loops.filter(|&loops| loops > 1.0) {
+            summary.push(Span::styled(
+                format!(" · {} loops", format_count(loops)),
+                Style::default().fg(theme.text),
+            ));
+        }
+        if misestimated(node) {
+            summary.push(Span::styled(
+                " · row estimate off by more than 10x".to_string(),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        let mut lines = vec![Line::from(summary)];
+        lines.extend(node.details.iter().map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(format!("{key}: "), label),
+                Span::styled(value.clone(), Style::default().fg(theme.text)),
+            ])
+        }));
+        lines
+    }
+}
+
+/// Appends `plan` and its subplans to `nodes` in plan order.
+fn push_node(plan: &Value, depth: usize, nodes: &mut Vec<PlanNode>) -> usize {
+    let text = |key: &str| plan.get(key).and_then(Value::as_str);
+    let number = |key: &str| plan.get(key).and_then(Value::as_f64);
+
+    let mut label = text("Node Type").unwrap_or("?").to_string();
+    if let Some(join) = text("Join Type").filter(|join| *join != "Inner") {
+        label.push_str(&format!(" ({join})"));
+    }
+    if let Some(strategy) = text("Strategy").filter(|s| *s != "Plain") {
+        label.push_str(&format!(" ({strategy})"));
+    }
+    if let Some(subplan) = text("Subplan Name") {
+        label = format!("{subplan}: {label}");
+    }
+
+    let mut target = Vec::new();
+    if let Some(index) = text("Index Name") {
+        target.push(format!("using {index}"));
+    }
+    let relation = text("Relation Name").map(|relation| match text("Schema") {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_identifier(schema),
+            quote_identifier(relation)
+        ),
+        None => quote_identifier(relation),
+    });
+    if let Some(relation) = text("Relation Name") {
+        let mut on = match text("Schema") {
+            Some(schema) => format!("on {schema}.{relation}"),
+            None => format!("on {relation}"),
+        };
+        if let Some(alias) = text("Alias").filter(|alias| *alias != relation) {
+            on.push_str(&format!(" {alias}"));
+        }
+        target.push(on);
+    } else if let Some(cte) = text("CTE Name") {
+        target.push(format!("on {cte}"));
+    } else if let Some(function) = text("Function Name") {
+        target.push(format!("on {function}()"));
+    }
+
+    let loops = number("Actual Loops");
+    let per_loop = |value: Option<f64>| value.map(|value| value * loops.unwrap_or(1.0));
+    let total_cost = number("Total Cost").unwrap_or(0.0);
+    let details = DETAIL_KEYS
+        .iter()
+        .filter_map(|&key| {
+            let value = match plan.get(key)? {
+                Value::String(text) => text.clone(),
+                Value::Array(items) => items
+                    .iter()
+                    .map(|item| {
+                        item.as_str()
+                            .map_or_else(|| item.to_string(), str::to_string)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                other => other.to_string(),
+            };
+            Some((key.to_string(), value))
+        })
+        .collect();
+
+    let index = nodes.len();
+    nodes.push(PlanNode {
+        depth,
+        label,
+        target: target.join(" "),
+        relation,
+        total_cost,
+        self_cost: total_cost,
+        plan_rows: number("Plan Rows").unwrap_or(0.0),
+        actual_rows: per_loop(number("Actual Rows")),
+        total_ms: per_loop(number("Actual Total Time")),
+        self_ms: None,
+        loops,
+        details,
+        children: Vec::new(),
+        expanded: true,
+    });
+
+    let children: Vec<usize> = plan
+        .get("Plans")
+        .and_then(Value::as_array)
+        .map(|plans| {
+            plans
+                .iter()
+                .map(|child| push_node(child, depth + 1, nodes))
+                .collect()
+        })
+        .unwrap_or_default();
+    let child_cost: f64 = children.iter().map(|&c| nodes[c].total_cost).sum();
+    let child_ms: f64 = children.iter().filter_map(|&c| nodes[c].total_ms).sum();
+    let node = &mut nodes[index];
+    node.self_cost = (node.total_cost - child_cost).max(0.0);
+    node.self_ms = node.total_ms.map(|ms| (ms - child_ms).max(0.0));
+    node.children = children;
+    index
+}
+
+/// The columns a plan's filter expression compares, in order, without
+/// their table qualifiers. Literals, casts, function names and keywords are
+/// skipped; quoted names keep their quotes.
+fn filter_columns(filter: &str) -> Vec<String> {
+    let chars: Vec<char> = filter.chars().collect();
+    let next_significant = |from: usize| chars[from..].iter().find(|c| !c.is_whitespace());
+    let mut columns: Vec<String> = Vec::new();
+    // Inside the type name of a `::` cast, which may be several words.
+    let mut in_cast = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let word = if c == '\'' {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' && chars.get(i + 1) != Some(&'\'') {
+                    break;
+                }
+                i += if chars[i] == '\'' { 2 } else { 1 };
+            }
+            i += 1;
+            in_cast = false;
+            continue;
+        } else if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '"' && chars.get(i + 1) != Some(&'"') {
+                    break;
+                }
+                i += if chars[i] == '"' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            chars[start..i].iter().collect::<String>()
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '$')) {
+                i += 1;
+            }
+            chars[start..i].iter().collect::<String>()
+        } else if c.is_ascii_digit() || c == '$' {
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            continue;
+        } else if c == ':' && chars.get(i + 1) == Some(&':') {
+            in_cast = true;
+            i += 2;
+            continue;
+        } else {
+            if !c.is_whitespace() {
+                in_cast = false;
+            }
+            i += 1;
+            continue;
+        };
+
+        let is_call_or_qualifier = matches!(next_significant(i), Some('(' | '.'));
+        if in_cast
+            || is_call_or_qualifier
+            || FILTER_KEYWORDS.contains(&word.to_ascii_lowercase().as_str())
+        {
+            continue;
+        }
+        if !columns.contains(&word) {
+            columns.push(word);
+        }
+    }
+    columns
+}
+
+/// The time column and the rows column of a node's line.
+fn node_stats(node: &PlanNode) -> (String, String) {
+    let time = node.self_ms.map(format_ms).unwrap_or_default();
+    let rows = match node.actual_rows {
+        Some(actual) => format!(
+            "rows {} est / {} actual",
+            format_count(node.plan_rows),
+            format_count(actual)
+        ),
+        None => format!("rows {} est", format_count(node.plan_rows)),
+    };
+    (time, rows)
+}
+
+/// Whether the actual rows are far from the planner's estimate.
+fn misestimated(node: &PlanNode) -> bool {
+    let Some(actual) = node.actual_rows else {
+        return false;
+    };
+    // Estimates are per loop and never below one row.
+    let estimate = node.plan_rows.max(1.0) * node.loops.unwrap_or(1.0).max(1.0);
+    let actual = actual.max(1.0);
+    actual / estimate >= MISESTIMATE_FACTOR || estimate / actual >= MISESTIMATE_FACTOR
+}
+
+fn format_ms(ms: f64) -> String {
+    if ms >= 1_000.0 {
+        format!("{:.2} s", ms / 1_000.0)
+    } else if ms >= 10.0 {
+        format!("{ms:.1} ms")
+    } else {
+        format!("{ms:.3} ms")
+    }
+}
+
+/// Formats a row count compactly (`1234` -> `1.2k`).
+fn format_count(value: f64) -> String {
+    const UNITS: [(f64, &str); 3] = [(1e9, "G"), (1e6, "M"), (1e3, "k")];
+    for (scale, suffix) in UNITS {
+        if value >= scale {
+            return format!("{:.1}{suffix}", value / scale);
+        }
+    }
+    format!("{value:.0}")
+}
+
+/// Cuts `text` to `width` columns, marking the cut with an ellipsis.
+fn fit(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return format!("{text:<width$}");
+    }
+    let mut out = String::new();
+    for c in text.chars() {
+        if out.width() + c.to_string().width() + 1 > width {
+            break;
+        }
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANALYZED: &str = r#"[{"Plan": {
+        "Node Type": "Hash Join", "Join Type": "Left", "Total Cost": 100.0,
+        "Plan Rows": 50, "Actual Rows": 40, "Actual Loops": 1, "Actual Total Time": 20.0,
+        "Hash Cond": "(o.user_id = u.id)",
+        "Plans": [
+          {"Node Type": "Seq Scan", "Relation Name": "orders", "Alias": "o",
+           "Total Cost": 60.0, "Plan Rows": 10, "Actual Rows": 5000, "Actual Loops": 1,
+           "Actual Total Time": 15.0, "Filter": "(total > 10)"},
+          {"Node Type": "Hash", "Total Cost": 30.0, "Plan Rows": 100,
+           "Actual Rows": 100, "Actual Loops": 1, "Actual Total Time": 2.0,
+           "Plans": [
+             {"Node Type": "Index Scan", "Index Name": "users_pkey",
+              "Relation Name": "users", "Alias": "u", "Total Cost": 25.0,
+              "Plan Rows": 100, "Actual Rows": 50, "Actual Loops": 2,
+              "Actual Total Time": 0.5}
+           ]}
+        ]},
+      "Planning Time": 0.25, "Execution Time": 20.5}]"#;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn analyzed_plan_times_each_node_without_its_children() {
+        let view = PlanView::from_explain_json("Plan", ANALYZED).unwrap();
+        assert!(view.analyzed());
+        assert_eq!(view.planning_ms, Some(0.25));
+        assert_eq!(view.execution_ms, Some(20.5));
+
+        let labels: Vec<_> = view
+            .nodes
+            .iter()
+            .map(|node| (node.depth, node.label.as_str(), node.target.as_str()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (0, "Hash Join (Left)", ""),
+                (1, "Seq Scan", "on orders o"),
+                (1, "Hash", ""),
+                (2, "Index Scan", "using users_pkey on users u"),
+            ]
+        );
+
+        // 20 ms total, minus 15 ms and 2 ms spent in the children.
+        assert_eq!(view.nodes[0].self_ms, Some(3.0));
+        // Loops multiply the per-loop timing and rows.
+        assert_eq!(view.nodes[3].total_ms, Some(1.0));
+        assert_eq!(view.nodes[3].actual_rows, Some(100.0));
+        assert_eq!(view.nodes[2].self_ms, Some(1.0));
+
+        // The scan dominates the query and its estimate was far off.
+        assert!(view.share(&view.nodes[1]) >= HOT_SHARE);
+        assert!(view.share(&view.nodes[0]) < WARM_SHARE);
+        assert!(misestimated(&view.nodes[1]));
+        assert!(!misestimated(&view.nodes[3]));
+        assert_eq!(
+            view.nodes[1].details,
+            [("Filter".to_string(), "(total > 10)".to_string())]
+        );
+    }
+
+    #[test]
+    fn plain_explain_ranks_nodes_by_their_own_cost() {
+        let json = r#"[{"Plan": {"Node Type": "Aggregate", "Strategy": "Hashed",
+            "Total Cost": 100.0, "Plan Rows": 10,
+            "Plans": [{"Node Type": "Seq Scan", "Relation Name": "t",
+                       "Total Cost": 80.0, "Plan Rows": 1000}]}}]"#;
+        let view = PlanView::from_explain_json("Plan", json).unwrap();
+        assert!(!view.analyzed());
+        assert_eq!(view.nodes[0].label, "Aggregate (Hashed)");
+        assert_eq!(view.nodes[0].self_cost, 20.0);
+        assert!((view.share(&view.nodes[1]) - 0.8).abs() < f64::EPSILON);
+        assert_eq!(view.summary(), "2 nodes · estimates only");
+        assert_eq!(node_stats(&view.nodes[1]).1, "rows 1.0k est");
+
+        assert!(PlanView::from_explain_json("Plan", "[]").is_err());
+        assert!(PlanView::from_explain_json("Plan", "not json").is_err());
+    }
+
+    #[test]
+    fn keys_fold_and_walk_the_tree() {
+        let mut view = PlanView::from_explain_json("Plan", ANALYZED).unwrap();
+        assert_eq!(view.visible(), [0, 1, 2, 3]);
+
+        // Collapsing the Hash node hides the index scan under it.
+        view.handle_key(key(KeyCode::Char('G')));
+        view.handle_key(key(KeyCode::Char('k')));
+        assert_eq!(view.selected_node(), Some(2));
+        view.handle_key(key(KeyCode::Enter));
+        assert_eq!(view.visible(), [0, 1, 2]);
+        view.handle_key(key(KeyCode::Char('l')));
+        assert_eq!(view.visible(), [0, 1, 2, 3]);
+
+        // `h` on a leaf moves to its parent, then collapses it.
+        view.handle_key(key(KeyCode::Char('j')));
+        view.handle_key(key(KeyCode::Char('h')));
+        assert_eq!(view.selected_node(), Some(2));
+        view.handle_key(key(KeyCode::Char('h')));
+        assert_eq!(view.visible(), [0, 1, 2]);
+
+        view.handle_key(key(KeyCode::Char('C')));
+        assert_eq!(view.visible(), [0]);
+        view.handle_key(key(KeyCode::Char('E')));
+        assert_eq!(view.visible(), [0, 1, 2, 3]);
+
+        assert_eq!(
+            view.handle_key(key(KeyCode::Char('y'))),
+            PlanViewResult::Copy(ANALYZED.to_string())
+        );
+        assert_eq!(view.handle_key(key(KeyCode::Esc)), PlanViewResult::Close);
+    }
+
+    #[test]
+    fn follow_ups_index_filtered_scans_and_analyze_misestimated_tables() {
+        let view = PlanView::from_explain_json("Plan", ANALYZED).unwrap();
+        assert_eq!(
+            view.follow_ups(),
+            "-- Seq Scan on orders o filters by (total > 10)\n\
+             CREATE INDEX ON orders (total);\n\n\
+             -- Row estimates for orders were off by more than 10x\n\
+             ANALYZE orders;"
+        );
+
+        let json = r#"[{"Plan": {"Node Type": "Seq Scan", "Relation Name": "Events",
+            "Schema": "app", "Total Cost": 10.0, "Plan Rows": 3,
+            "Filter": "(((e.kind)::text = 'sign''up'::text) AND (lower(\"Email\") ~~ 'a%'::text) AND (e.created_at > '2024-01-01 00:00:00'::timestamp without time zone) AND (NOT (hashed SubPlan 1)) AND (e.kind IS NOT NULL))",
+            "Rows Removed by Filter": 4900}}]"#;
+        let view = PlanView::from_explain_json("Plan", json).unwrap();
+        let follow_ups = view.follow_ups();
+        assert!(follow_ups.ends_with(
+            "removing 4.9k rows\nCREATE INDEX ON app.\"Events\" (kind, \"Email\", created_at);"
+        ));
+
+        let json = r#"[{"Plan": {"Node Type": "Index Scan", "Relation Name": "t",
+            "Index Name": "t_pkey", "Total Cost": 1.0, "Plan Rows": 1}}]"#;
+        let view = PlanView::from_explain_json("Plan", json).unwrap();
+        assert_eq!(view.follow_ups(), "");
+    }
+
+    #[test]
+    fn fit_pads_or_cuts_to_width() {
+        assert_eq!(fit("Seq Scan", 10), "Seq Scan  ");
+        assert_eq!(fit("Seq Scan on orders", 10), "Seq Scan …");
+    }
+}
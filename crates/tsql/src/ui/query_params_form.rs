@@ -0,0 +1,259 @@
+//! Form for the values of a query's `$1` and `:name` placeholders, and of
+//! `{{name}}` template variables that are not set yet.
+//!
+//! Each placeholder gets a line; values are typed as plain text (no quoting)
+//! and the server reads them as it would a literal of the parameter's type.
+//! `Ctrl-n` sends NULL instead.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use super::{overlay_block, UiTheme};
+
+/// Result of handling input in the parameter form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryParamsAction {
+    /// Still editing.
+    Continue,
+    /// Run the query with these values, in placeholder order; None is NULL.
+    Submit(Vec<Option<String>>),
+    /// Close the form without running the query (`Esc`).
+    Cancel,
+}
+
+struct ParamField {
+    label: String,
+    value: Option<String>,
+}
+
+/// The placeholders of a query and the values typed for them.
+pub struct QueryParamsForm {
+    title: &'static str,
+    fields: Vec<ParamField>,
+    selected: usize,
+    scroll: usize,
+    /// Fields that fit in the list at the last render.
+    visible_height: usize,
+}
+
+impl QueryParamsForm {
+    /// A form for `labels` (`$1`, `:name`, ...) starting from `values`.
+    pub fn new(labels: Vec<String>, values: Vec<Option<String>>) -> Self {
+        let mut values = values.into_iter();
+        let fields = labels
+            .into_iter()
+            .map(|label| ParamField {
+                label,
+                value: values.next().unwrap_or(Some(String::new())),
+            })
+            .collect();
+        Self {
+            title: "Query Parameters",
+            fields,
+            selected: 0,
+            scroll: 0,
+            visible_height: 0,
+        }
+    }
+
+    pub fn with_title(mut self, title: &'static str) -> Self {
+        self.title = title;
+        self
+    }
+
+    fn values(&self) -> Vec<Option<String>> {
+        self.fields
+            .iter()
+            .map(|field| field.value.clone())
+            .collect()
+    }
+
+    /// Pasted text goes into the selected field's value.
+    pub fn paste(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if let Some(field) = self.fields.get_mut(self.selected) {
+            field.value.get_or_insert_with(String::new).push_str(&text);
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> QueryParamsAction {
+        let ctrl = key.modifiers == KeyModifiers::CONTROL;
+        let last = self.fields.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return QueryParamsAction::Cancel,
+            KeyCode::Char('s') if ctrl => return QueryParamsAction::Submit(self.values()),
+            KeyCode::Enter if self.selected >= last => {
+                return QueryParamsAction::Submit(self.values())
+            }
+            KeyCode::Down | KeyCode::Tab | KeyCode::Enter => self.select(self.selected + 1),
+            KeyCode::Up | KeyCode::BackTab => self.select(self.selected.saturating_sub(1)),
+            _ => {}
+        }
+        let Some(field) = self.fields.get_mut(self.selected) else {
+            return QueryParamsAction::Continue;
+        };
+        match key.code {
+            KeyCode::Char('n') if ctrl => {
+                field.value = match field.value {
+                    Some(_) => None,
+                    None => Some(String::new()),
+                };
+            }
+            KeyCode::Char('u') if ctrl => field.value = Some(String::new()),
+            KeyCode::Backspace => match &mut field.value {
+                Some(text) => {
+                    text.pop();
+                }
+                None => field.value = Some(String::new()),
+            },
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                field.value.get_or_insert_with(String::new).push(c);
+            }
+            _ => {}
+        }
+        QueryParamsAction::Continue
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.fields.len().saturating_sub(1));
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.visible_height > 0 && self.selected >= self.scroll + self.visible_height {
+            self.scroll = self.selected + 1 - self.visible_height;
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 70u16.min(area.width.saturating_sub(4));
+        let dialog_height = u16::try_from(self.fields.len())
+            .unwrap_or(u16::MAX)
+            .saturating_add(4)
+            .min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        let block = overlay_block(self.title, theme);
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Fields
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+        self.visible_height = usize::from(chunks[0].height);
+        self.select(self.selected);
+
+        let label_width = self
+            .fields
+            .iter()
+            .map(|field| field.label.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(30);
+        let lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_height)
+            .map(|(index, field)| {
+                let selected = index == self.selected;
+                let label_style = if selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let cursor = if selected { "\u{2588}" } else { "" };
+                let value = match &field.value {
+                    None => Span::styled(
+                        "NULL",
+                        Style::default()
+                            .fg(theme.warning)
+                            .add_modifier(Modifier::ITALIC),
+                    ),
+                    Some(text) => Span::styled(
+                        format!("{text}{cursor}"),
+                        Style::default().fg(theme.success),
+                    ),
+                };
+                Line::from(vec![
+                    Span::styled(if selected { "> " } else { "  " }, label_style),
+                    Span::styled(format!("{:label_width$}  ", field.label), label_style),
+                    value,
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let key_style = |color| Style::default().fg(color).add_modifier(Modifier::BOLD);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Ctrl-s", key_style(theme.success)),
+                Span::raw(" run  "),
+                Span::styled("Tab/↑↓", key_style(theme.text_muted)),
+                Span::raw(" move  "),
+                Span::styled("Ctrl-n", key_style(theme.text_muted)),
+                Span::raw(" NULL  "),
+                Span::styled("Esc", key_style(theme.error)),
+                Span::raw(" cancel"),
+            ]))
+            .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn enter_moves_down_then_runs_with_typed_values() {
+        let mut form = QueryParamsForm::new(
+            vec!["$1".to_string(), ":name".to_string(), ":since".to_string()],
+            vec![Some("42".to_string())],
+        );
+        form.handle_key(key(KeyCode::Backspace));
+        form.handle_key(key(KeyCode::Char('3')));
+        assert_eq!(
+            form.handle_key(key(KeyCode::Enter)),
+            QueryParamsAction::Continue
+        );
+        form.paste("O'Brien\n");
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(
+            form.handle_key(key(KeyCode::Enter)),
+            QueryParamsAction::Submit(vec![
+                Some("43".to_string()),
+                Some("O'Brien".to_string()),
+                None
+            ])
+        );
+
+        // Typing over NULL starts a value again.
+        form.handle_key(key(KeyCode::Char('x')));
+        assert_eq!(form.fields[2].value.as_deref(), Some("x"));
+        assert_eq!(
+            form.handle_key(key(KeyCode::Esc)),
+            QueryParamsAction::Cancel
+        );
+    }
+}
@@ -5,6 +5,9 @@
 //! - Full values (no truncation, unlike grid)
 //! - Syntax highlighting for JSON/HTML content
 //! - Vim-like navigation (j/k scroll, q/Esc close)
+//!
+//! With `:detail` the same view is also shown as a pane beside the grid,
+//! following the grid cursor instead of taking keys itself.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Layout, Rect};
@@ -17,7 +20,7 @@ use tui_syntax::{html, json, Highlighter, Theme};
 
 use crate::util::{detect_content_type, ContentType};
 
-use super::{overlay_block, UiTheme};
+use super::{overlay_block, zone_block, zone_label, UiTheme};
 
 /// Format to use when yanking from the row detail view.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -117,6 +120,28 @@ impl RowDetailModal {
         self.selected_field
     }
 
+    /// Show another row, selecting field `col`. Used by the side pane, which
+    /// follows the grid cursor.
+    pub fn set_row(
+        &mut self,
+        headers: &[String],
+        values: Vec<String>,
+        col_types: &[String],
+        row_index: usize,
+        col: usize,
+    ) {
+        if self.headers != headers {
+            self.headers = headers.to_vec();
+            self.field_count = headers.len();
+            self.scroll_offset = 0;
+        }
+        self.col_types = col_types.to_vec();
+        self.values = values;
+        self.row_index = row_index;
+        self.selected_field = col.min(self.field_count.saturating_sub(1));
+        self.ensure_selected_visible();
+    }
+
     /// Handle a key event and return the resulting action.
     pub fn handle_key(&mut self, key: KeyEvent) -> RowDetailAction {
         // Pending yank: y was pressed, waiting for format key.
@@ -322,6 +347,37 @@ impl RowDetailModal {
         }
     }
 
+    /// Render as the side pane beside the grid: a zone without the key footer.
+    pub fn render_pane(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let detail = if self.values.is_empty() {
+            " · no rows".to_string()
+        } else {
+            format!(
+                " · {} · {}/{}",
+                self.row_index + 1,
+                self.selected_field + 1,
+                self.field_count
+            )
+        };
+        let label = zone_label(
+            "ROW",
+            vec![Span::styled(detail, Style::default().fg(theme.text_muted))],
+            false,
+            theme.accent,
+            theme,
+        );
+        let block = zone_block(label, theme.bg_panel, theme.text, false, theme.accent);
+        let content_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        self.visible_height = content_area.height as usize;
+        self.ensure_selected_visible();
+        self.render_content(frame, content_area, theme);
+        if self.field_count > self.visible_height / 3 {
+            self.render_scrollbar(frame, content_area, theme);
+        }
+    }
+
     fn render_content(&mut self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
         // Selected-value spans keep their syntax fg but sit on the selection bg.
         let on_selection_bg = |style: Style| -> Style {
@@ -634,6 +690,37 @@ mod tests {
         assert!(!text.contains("e/Enter edit"));
     }
 
+    #[test]
+    fn test_pane_follows_the_row_and_column_it_is_given() {
+        let mut pane =
+            RowDetailModal::new(Vec::new(), Vec::new(), Vec::new(), 0, themes::one_dark());
+        let headers = vec!["id".to_string(), "name".to_string()];
+        let types = vec!["int4".to_string(), "text".to_string()];
+        pane.set_row(&headers, vec!["7".into(), "Grace".into()], &types, 6, 1);
+        assert_eq!(pane.field_count, 2);
+        assert_eq!(pane.selected_col(), 1);
+
+        pane.set_row(&headers, vec!["8".into(), "Linus".into()], &types, 7, 5);
+        assert_eq!(pane.selected_col(), 1);
+
+        let theme = UiTheme::fallback();
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal
+            .draw(|frame| pane.render_pane(frame, frame.area(), &theme))
+            .unwrap();
+        let text = terminal
+            .backend()
+            .buffer()
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(text.contains("ROW · 8 · 2/2"), "{text}");
+        assert!(text.contains("Linus"));
+        assert!(!text.contains("q/Esc"));
+        assert_nonblank_cells_have_explicit_fg(terminal.backend().buffer());
+    }
+
     #[test]
     fn test_navigation_j_k() {
         let mut modal = create_test_modal();
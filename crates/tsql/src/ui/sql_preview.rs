@@ -0,0 +1,150 @@
+//! Confirmation popup showing a generated statement before it runs.
+//!
+//! Grid edits build their `UPDATE` from the cell and the row's keys; the
+//! preview lets the user check the `WHERE` clause before anything is written.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use tui_syntax::Theme;
+
+use super::{create_sql_highlighter, overlay_block, UiTheme};
+
+/// Result of handling input in the statement preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlPreviewResult {
+    /// Still waiting for user input.
+    Pending,
+    /// Run the statement (`y` or `Enter`).
+    Confirmed,
+    /// Discard the statement (`n`, `q` or `Esc`).
+    Cancelled,
+}
+
+/// A syntax-highlighted statement awaiting confirmation.
+pub struct SqlPreview {
+    title: String,
+    lines: Vec<Line<'static>>,
+}
+
+impl SqlPreview {
+    pub fn new(title: impl Into<String>, sql: &str, syntax_theme: Theme) -> Self {
+        let lines = create_sql_highlighter(syntax_theme)
+            .highlight("sql", sql)
+            .unwrap_or_else(|_| sql.lines().map(|l| Line::from(l.to_string())).collect());
+        Self {
+            title: title.into(),
+            lines,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SqlPreviewResult {
+        match key.code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => SqlPreviewResult::Confirmed,
+            KeyCode::Char('n' | 'N' | 'q') | KeyCode::Esc => SqlPreviewResult::Cancelled,
+            _ => SqlPreviewResult::Pending,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &UiTheme) {
+        let dialog_width = 80u16.min(area.width.saturating_sub(4));
+        let content_width = usize::from(dialog_width.saturating_sub(2).max(1));
+        let wrapped_lines: usize = self
+            .lines
+            .iter()
+            .map(|line| line.width().max(1).div_ceil(content_width))
+            .sum();
+        let dialog_height = u16::try_from(wrapped_lines)
+            .unwrap_or(u16::MAX)
+            .saturating_add(4)
+            .min(area.height.saturating_sub(2));
+
+        let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
+        let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+        frame.render_widget(Clear, dialog_area);
+        // Like confirmations, the border is warning-colored: this writes data.
+        let block =
+            overlay_block(&self.title, theme).border_style(Style::default().fg(theme.warning));
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::vertical([
+            Constraint::Min(1),    // Statement
+            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Help text
+        ])
+        .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(self.lines.clone()).wrap(Wrap { trim: false }),
+            chunks[0],
+        );
+
+        let help_line = Line::from(vec![
+            Span::styled(
+                "y/Enter",
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" run  "),
+            Span::styled(
+                "n/Esc",
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(
+            Paragraph::new(help_line).alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn keys_confirm_or_cancel_the_statement() {
+        let mut preview = SqlPreview::new(
+            "Update Cell",
+            "UPDATE t\nSET a = 1\nWHERE id = 1",
+            tui_syntax::themes::one_dark(),
+        );
+        assert_eq!(preview.lines.len(), 3);
+
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Char('j'))),
+            SqlPreviewResult::Pending
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Enter)),
+            SqlPreviewResult::Confirmed
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Char('y'))),
+            SqlPreviewResult::Confirmed
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Esc)),
+            SqlPreviewResult::Cancelled
+        );
+        assert_eq!(
+            preview.handle_key(key(KeyCode::Char('n'))),
+            SqlPreviewResult::Cancelled
+        );
+    }
+}
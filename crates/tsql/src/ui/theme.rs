@@ -2,12 +2,13 @@
 
 use std::path::{Component, Path};
 
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::symbols::border;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding};
-use tui_syntax::{themes, Theme};
+use tui_syntax::{themes, ColorMode, Theme};
 
 use crate::app::Mode;
 use crate::config::config_dir;
@@ -49,6 +50,9 @@ pub struct UiTheme {
     pub notebook_output: Style,
     pub notebook_meta: Style,
     pub notebook_stale: Style,
+    /// Every color is `Reset` and states are drawn with modifiers only
+    /// (`display.color = "never"`).
+    pub monochrome: bool,
 }
 
 impl Default for UiTheme {
@@ -60,6 +64,12 @@ impl Default for UiTheme {
 impl UiTheme {
     /// Build the canonical One Dark UI fallback.
     pub fn fallback() -> Self {
+        Self::fallback_for(ColorMode::TrueColor)
+    }
+
+    /// The One Dark fallback mapped to the terminal's color depth.
+    fn fallback_for(mode: ColorMode) -> Self {
+        let rgb = |red, green, blue| mode.downgrade(rgb(red, green, blue));
         let text = rgb(0xDC, 0xDF, 0xE4);
         let text_muted = rgb(0x9D, 0xA5, 0xB4);
         let accent = rgb(0x56, 0xB6, 0xC2);
@@ -104,13 +114,60 @@ impl UiTheme {
             notebook_output: Style::default().fg(text).bg(rgb(0x28, 0x2C, 0x34)),
             notebook_meta: Style::default().fg(text_muted),
             notebook_stale: Style::default().fg(rgb(0xE5, 0xC0, 0x7B)),
+            monochrome: false,
+        }
+    }
+
+    /// Build the no-color UI: the terminal's own colors, with the cursor,
+    /// selections and matches told apart by reverse video, bold and underline.
+    pub fn monochrome() -> Self {
+        let plain = Style::default();
+        let reversed = plain.add_modifier(Modifier::REVERSED);
+        let bold = plain.add_modifier(Modifier::BOLD);
+
+        Self {
+            bg_base: Color::Reset,
+            bg_panel: Color::Reset,
+            bg_elevated: Color::Reset,
+            bg_status: Color::Reset,
+            text: Color::Reset,
+            text_muted: Color::Reset,
+            label: plain,
+            label_focused: bold.add_modifier(Modifier::UNDERLINED),
+            accent: Color::Reset,
+            accent_insert: Color::Reset,
+            accent_visual: Color::Reset,
+            selection: reversed,
+            cursor_cell: bold.add_modifier(Modifier::UNDERLINED),
+            editor_cursor: reversed,
+            editor_selection: reversed,
+            search_match: plain.add_modifier(Modifier::UNDERLINED),
+            search_match_current: reversed.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            success: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            transaction: Color::Reset,
+            pill_fg: Color::Reset,
+            scrollbar: plain,
+            grid_header: bold.add_modifier(Modifier::UNDERLINED),
+            overlay: plain,
+            overlay_border: plain,
+            overlay_title: bold,
+            notebook_canvas: plain,
+            notebook_composer: plain,
+            notebook_composer_focused: bold,
+            notebook_rail: bold,
+            notebook_output: plain,
+            notebook_meta: plain,
+            notebook_stale: plain.add_modifier(Modifier::ITALIC),
+            monochrome: true,
         }
     }
 
     /// Resolve UI chrome from a syntax theme, preserving complete fallbacks for
     /// fields omitted by parent or child scopes.
     pub fn from_theme(theme: &Theme) -> Self {
-        let fallback = Self::fallback();
+        let fallback = Self::fallback_for(theme.color_mode());
 
         let background = resolve_style(
             theme,
@@ -220,6 +277,7 @@ impl UiTheme {
                 Style::default().fg(text_muted),
             ),
             notebook_stale: resolve_style(theme, "ui.notebook.stale", Style::default().fg(warning)),
+            monochrome: false,
         }
     }
 
@@ -231,6 +289,28 @@ impl UiTheme {
             Mode::Visual => self.accent_visual,
         }
     }
+
+    /// A bold label painted on `color`, like the mode indicator; reverse video
+    /// when there are no colors to paint with.
+    pub fn pill(&self, color: Color) -> Style {
+        let style = if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.pill_fg).bg(color)
+        };
+        style.add_modifier(Modifier::BOLD)
+    }
+}
+
+/// Drop every color from a drawn frame, keeping its modifiers, so colors
+/// hard-coded outside the theme (connection colors, syntax highlighting)
+/// don't show in no-color mode.
+pub fn strip_colors(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+        cell.underline_color = Color::Reset;
+    }
 }
 
 fn rgb(red: u8, green: u8, blue: u8) -> Color {
@@ -286,11 +366,21 @@ pub fn zone_block<'a>(
     } else {
         Style::default().fg(tone).bg(tone)
     };
+    // The unfocused edge hides in the zone's tone; without one to hide in
+    // (no-color mode) it is left blank instead.
+    let edge_set = if focused || tone != Color::Reset {
+        ZONE_EDGE_SET
+    } else {
+        border::Set {
+            vertical_left: " ",
+            ..ZONE_EDGE_SET
+        }
+    };
 
     Block::default()
         .style(Style::default().fg(text).bg(tone))
         .borders(Borders::LEFT)
-        .border_set(ZONE_EDGE_SET)
+        .border_set(edge_set)
         .border_style(edge)
         .title_top(label)
         .padding(Padding::new(1, 1, 0, 0))
@@ -367,10 +457,11 @@ pub fn load_theme(name: &str) -> (Theme, Option<String>) {
 
 /// Load a built-in or custom theme from an explicit themes directory.
 pub fn load_theme_from(name: &str, themes_dir: Option<&Path>) -> (Theme, Option<String>) {
-    match name {
-        "" | "default" | "one_dark" => return (themes::one_dark(), None),
-        "github_light" => return (themes::github_light(), None),
-        _ => {}
+    if matches!(name, "" | "default") {
+        return (themes::one_dark(), None);
+    }
+    if let Some(theme) = themes::by_name(name) {
+        return (theme, None);
     }
 
     if !is_valid_theme_name(name) {
@@ -426,6 +517,28 @@ mod tests {
         assert_eq!(UiTheme::from_theme(&theme), UiTheme::fallback());
     }
 
+    #[test]
+    fn fallback_follows_the_theme_color_mode() {
+        let theme = Theme::from_toml("")
+            .unwrap()
+            .with_color_mode(ColorMode::Ansi256);
+        let ui = UiTheme::from_theme(&theme);
+
+        assert_eq!(
+            ui.bg_base,
+            ColorMode::Ansi256.downgrade(rgb(0x28, 0x2C, 0x34))
+        );
+        assert!(matches!(ui.accent, Color::Indexed(_)));
+        assert!(matches!(ui.grid_header.bg, Some(Color::Indexed(_))));
+        assert!(matches!(ui.notebook_canvas.bg, Some(Color::Indexed(_))));
+
+        let theme = Theme::from_toml("")
+            .unwrap()
+            .with_color_mode(ColorMode::Ansi16);
+        let ui = UiTheme::from_theme(&theme);
+        assert!(!matches!(ui.text, Color::Rgb(..) | Color::Indexed(_)));
+    }
+
     #[test]
     fn child_style_merges_parent_and_fallback_components() {
         let theme = Theme::from_toml(
@@ -504,7 +617,10 @@ mod tests {
 
     #[test]
     fn built_in_themes_define_required_ui_scopes() {
-        for theme in [themes::one_dark(), themes::github_light()] {
+        for theme in themes::BUILT_IN
+            .iter()
+            .filter_map(|name| themes::by_name(name))
+        {
             for scope in [
                 "ui.background",
                 "ui.background.panel",
@@ -552,7 +668,10 @@ mod tests {
 
     #[test]
     fn built_in_base_text_has_readable_contrast() {
-        for theme in [themes::one_dark(), themes::github_light()] {
+        for theme in themes::BUILT_IN
+            .iter()
+            .filter_map(|name| themes::by_name(name))
+        {
             let ui = UiTheme::from_theme(&theme);
             for background in [ui.bg_base, ui.bg_panel, ui.bg_elevated, ui.bg_status] {
                 assert!(
@@ -566,7 +685,10 @@ mod tests {
 
     #[test]
     fn built_in_explicit_styles_have_readable_contrast_and_distinct_tones() {
-        for theme in [themes::one_dark(), themes::github_light()] {
+        for theme in themes::BUILT_IN
+            .iter()
+            .filter_map(|name| themes::by_name(name))
+        {
             let ui = UiTheme::from_theme(&theme);
             assert_ne!(ui.bg_base, ui.bg_panel);
             assert_ne!(ui.bg_base, ui.bg_elevated);
@@ -610,6 +732,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn monochrome_theme_tells_states_apart_without_colors() {
+        let ui = UiTheme::monochrome();
+        let states = [
+            ui.selection,
+            ui.cursor_cell,
+            ui.search_match,
+            ui.search_match_current,
+        ];
+        for (index, style) in states.iter().enumerate() {
+            assert_eq!((style.fg, style.bg), (None, None), "{style:?}");
+            assert!(!style.add_modifier.is_empty(), "{style:?}");
+            assert!(states[..index].iter().all(|other| other != style));
+        }
+        assert!(ui.pill(ui.accent).add_modifier.contains(Modifier::REVERSED));
+        assert!(!UiTheme::fallback()
+            .pill(ui.accent)
+            .add_modifier
+            .contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn unfocused_zone_edge_stays_hidden_without_colors() {
+        let theme = UiTheme::monochrome();
+        let area = Rect::new(0, 0, 12, 4);
+        for (focused, edge) in [(true, "▍"), (false, " ")] {
+            let mut buffer = Buffer::empty(area);
+            zone_block(
+                Line::from(" QUERY"),
+                theme.bg_elevated,
+                theme.text,
+                focused,
+                theme.accent,
+            )
+            .render(area, &mut buffer);
+            strip_colors(&mut buffer);
+
+            let cell = buffer.cell((0, 2)).unwrap();
+            assert_eq!(cell.symbol(), edge);
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        }
+    }
+
     #[test]
     fn zone_block_sets_base_foreground_and_background() {
         let theme = UiTheme::fallback();
@@ -94,6 +94,31 @@ pub(crate) fn pg_error_cursor_position(error: &str, source: &str) -> Option<(usi
     )
 }
 
+/// Columns of the token an error cursor from [`pg_error_cursor_position`] points
+/// at on `line`: an identifier or number, a quoted identifier or literal, or a
+/// single character. At the end of the line it is the last character.
+pub(crate) fn pg_error_token_columns(line: &str, column: usize) -> std::ops::Range<usize> {
+    let chars: Vec<char> = line.chars().collect();
+    let Some(&first) = chars.get(column) else {
+        return chars.len().saturating_sub(1)..chars.len();
+    };
+    let is_word = |character: char| character.is_alphanumeric() || matches!(character, '_' | '$');
+    let end = if first == '"' || first == '\'' {
+        chars[column + 1..]
+            .iter()
+            .position(|&character| character == first)
+            .map_or(chars.len(), |closing| column + closing + 2)
+    } else if is_word(first) {
+        chars[column..]
+            .iter()
+            .position(|&character| !is_word(character))
+            .map_or(chars.len(), |word_end| column + word_end)
+    } else {
+        column + 1
+    };
+    column..end
+}
+
 /// Strip the password component from a connection URL, preserving the
 /// rest of the URL exactly. Used before displaying a URL to the user or
 /// writing it to a log line. Leaves the input unchanged if it isn't a
@@ -157,6 +182,130 @@ pub fn is_valid_json(value: &str) -> bool {
     serde_json::from_str::<JsonValue>(value).is_ok()
 }
 
+/// Parse JSON, also accepting the relaxed (JSON5-style) conveniences people
+/// type by hand: `//` and `/* */` comments, trailing commas, single-quoted
+/// strings and unquoted object keys. Line numbers in errors match the input.
+pub fn parse_relaxed_json(value: &str) -> Result<JsonValue, serde_json::Error> {
+    serde_json::from_str(value).or_else(|_| serde_json::from_str(&relax_json(value)))
+}
+
+/// Rewrites the relaxed syntax [`parse_relaxed_json`] accepts into strict JSON.
+fn relax_json(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '"' | '\'' => i = copy_json_string(&chars, i, &mut out),
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        out.push('\n');
+                    }
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+            }
+            ',' => {
+                let next = skip_json_insignificant(&chars, i + 1);
+                if !matches!(chars.get(next), Some('}' | ']')) {
+                    out.push(',');
+                }
+                i += 1;
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if chars.get(skip_json_insignificant(&chars, i)) == Some(&':') {
+                    out.push('"');
+                    out.push_str(&word);
+                    out.push('"');
+                } else {
+                    out.push_str(&word);
+                }
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Copies the string starting at `start` as a double-quoted JSON string and
+/// returns the index after it. An unterminated string stays unterminated.
+fn copy_json_string(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.push('"');
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => match chars.get(i + 1) {
+                Some('\'') => {
+                    out.push('\'');
+                    i += 2;
+                }
+                Some(&next) => {
+                    out.push('\\');
+                    out.push(next);
+                    i += 2;
+                }
+                None => {
+                    out.push('\\');
+                    i += 1;
+                }
+            },
+            '"' if quote == '\'' => {
+                out.push_str("\\\"");
+                i += 1;
+            }
+            c if c == quote => {
+                out.push('"');
+                return i + 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+/// Index of the next character that is not whitespace or inside a comment.
+fn skip_json_insignificant(chars: &[char], mut i: usize) -> usize {
+    loop {
+        match (chars.get(i), chars.get(i + 1)) {
+            (Some(c), _) if c.is_whitespace() => i += 1,
+            (Some('/'), Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            (Some('/'), Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i = (i + 2).min(chars.len());
+            }
+            _ => return i,
+        }
+    }
+}
+
 /// Determine if value should open in multiline editor.
 /// Returns true if:
 /// - Value contains newlines, OR
@@ -395,6 +544,35 @@ mod tests {
         assert!(!is_valid_json("not json"));
     }
 
+    #[test]
+    fn test_parse_relaxed_json() {
+        let relaxed = r#"{
+            // the user's display settings
+            theme: 'dark', /* or 'light' */
+            "quote": 'say "hi" and it\'s fine',
+            tags: ['a', 'b',],
+            url: "http://example.com//x",
+        }"#;
+        assert_eq!(
+            parse_relaxed_json(relaxed).unwrap(),
+            serde_json::json!({
+                "theme": "dark",
+                "quote": "say \"hi\" and it's fine",
+                "tags": ["a", "b"],
+                "url": "http://example.com//x",
+            })
+        );
+        assert_eq!(
+            parse_relaxed_json(r#"{"a": 1e5, "b": true}"#).unwrap(),
+            serde_json::json!({"a": 1e5, "b": true})
+        );
+
+        let error = parse_relaxed_json("{\n  // note\n  a: 'open\n}").unwrap_err();
+        assert!(error.line() >= 3);
+        assert!(parse_relaxed_json("{a: undefined}").is_err());
+        assert!(parse_relaxed_json("").is_err());
+    }
+
     #[test]
     fn test_should_use_multiline_editor() {
         // JSON should always use multiline
@@ -575,6 +753,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pg_error_token_columns_cover_words_quotes_and_line_ends() {
+        let line = "SELECT naem, \"Mixed Case\", 'x' FORM t;";
+        let columns = |token: &str| {
+            let column = line[..line.find(token).unwrap()].chars().count();
+            pg_error_token_columns(line, column)
+        };
+        assert_eq!(columns("naem"), 7..11);
+        assert_eq!(columns("\"Mixed"), 13..25);
+        assert_eq!(columns("'x'"), 27..30);
+        assert_eq!(columns(";"), 37..38);
+        assert_eq!(pg_error_token_columns(line, 38), 37..38);
+        assert_eq!(pg_error_token_columns("", 0), 0..0);
+    }
+
     #[test]
     fn test_pg_error_cursor_position_rejects_internal_stale_and_invalid_locations() {
         let source = "SELECT 1";
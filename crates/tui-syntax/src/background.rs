@@ -0,0 +1,208 @@
+//! Off-thread highlighting for large inputs.
+//!
+//! [`BackgroundHighlighter`] moves a [`Highlighter`] onto a worker thread.
+//! Requests return immediately; the styled lines arrive later through a
+//! channel or a callback. While a result is pending, callers can render
+//! [`plain_lines`] so the UI never blocks on tree-sitter.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use ratatui::text::Line;
+
+use crate::highlighter::{HighlightError, Highlighter};
+use crate::theme::Theme;
+
+/// A finished background highlight.
+#[derive(Debug)]
+pub struct HighlightResult {
+    /// Identifier returned by [`BackgroundHighlighter::request`]
+    pub id: u64,
+    /// Language the source was highlighted as
+    pub language: String,
+    /// Styled lines, or the error produced while highlighting
+    pub lines: Result<Vec<Line<'static>>, HighlightError>,
+}
+
+/// Messages sent to the worker thread.
+enum Command {
+    Highlight {
+        id: u64,
+        language: String,
+        source: String,
+    },
+    SetTheme(Theme),
+}
+
+/// A highlighter running on its own thread.
+///
+/// Requests that pile up while the worker is busy are coalesced: only the
+/// newest one is highlighted, so a fast typist does not queue stale work.
+/// Dropping the handle stops the worker.
+pub struct BackgroundHighlighter {
+    commands: Option<Sender<Command>>,
+    results: Option<Receiver<HighlightResult>>,
+    worker: Option<JoinHandle<()>>,
+    next_id: u64,
+}
+
+impl BackgroundHighlighter {
+    /// Start a worker whose results are collected with [`Self::try_recv`].
+    pub fn spawn(highlighter: Highlighter) -> Self {
+        let (result_tx, result_rx) = mpsc::channel();
+        let mut handle = Self::spawn_with_callback(highlighter, move |result| {
+            let _ = result_tx.send(result);
+        });
+        handle.results = Some(result_rx);
+        handle
+    }
+
+    /// Start a worker that hands each result to `callback` on the worker thread.
+    ///
+    /// This suits event loops that want to forward results into their own
+    /// channel or wake the UI.
+    pub fn spawn_with_callback<F>(mut highlighter: Highlighter, mut callback: F) -> Self
+    where
+        F: FnMut(HighlightResult) + Send + 'static,
+    {
+        let (command_tx, command_rx) = mpsc::channel::<Command>();
+        let worker = thread::Builder::new()
+            .name("tui-syntax-highlight".to_string())
+            .spawn(move || {
+                while let Ok(command) = command_rx.recv() {
+                    // Apply queued theme changes, keep only the newest request.
+                    let mut pending = None;
+                    for command in std::iter::once(command).chain(command_rx.try_iter()) {
+                        match command {
+                            Command::SetTheme(theme) => highlighter.set_theme(theme),
+                            Command::Highlight {
+                                id,
+                                language,
+                                source,
+                            } => pending = Some((id, language, source)),
+                        }
+                    }
+
+                    if let Some((id, language, source)) = pending {
+                        let lines = highlighter.highlight_cached(&language, &source);
+                        callback(HighlightResult {
+                            id,
+                            language,
+                            lines,
+                        });
+                    }
+                }
+            })
+            .expect("failed to spawn highlight worker");
+
+        Self {
+            commands: Some(command_tx),
+            results: None,
+            worker: Some(worker),
+            next_id: 0,
+        }
+    }
+
+    /// Queue `source` for highlighting and return the request id.
+    ///
+    /// Results for superseded requests may be skipped; compare ids to discard
+    /// stale output.
+    pub fn request(&mut self, language: &str, source: &str) -> u64 {
+        self.next_id = self.next_id.wrapping_add(1);
+        if let Some(commands) = &self.commands {
+            let _ = commands.send(Command::Highlight {
+                id: self.next_id,
+                language: language.to_string(),
+                source: source.to_string(),
+            });
+        }
+        self.next_id
+    }
+
+    /// Replace the worker's theme. Applies to subsequent requests.
+    pub fn set_theme(&self, theme: Theme) {
+        if let Some(commands) = &self.commands {
+            let _ = commands.send(Command::SetTheme(theme));
+        }
+    }
+
+    /// Id of the most recent request, if any.
+    pub fn latest_id(&self) -> Option<u64> {
+        (self.next_id > 0).then_some(self.next_id)
+    }
+
+    /// Take a finished result without blocking.
+    ///
+    /// Always `None` for workers started with [`Self::spawn_with_callback`].
+    pub fn try_recv(&self) -> Option<HighlightResult> {
+        self.results.as_ref()?.try_recv().ok()
+    }
+
+    /// Wait up to `timeout` for a finished result.
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> Option<HighlightResult> {
+        self.results.as_ref()?.recv_timeout(timeout).ok()
+    }
+}
+
+impl Drop for BackgroundHighlighter {
+    fn drop(&mut self) {
+        // Closing the command channel ends the worker loop.
+        self.commands.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Unstyled lines to show while a background highlight is pending.
+pub fn plain_lines(source: &str) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = source
+        .lines()
+        .map(|line| Line::raw(line.to_string()))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::raw(String::new()));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::languages::sql;
+    use crate::themes;
+
+    #[test]
+    fn test_background_highlight_round_trip() {
+        let mut highlighter = Highlighter::new(themes::one_dark());
+        highlighter.register_language(sql()).unwrap();
+        let mut background = BackgroundHighlighter::spawn(highlighter);
+
+        let id = background.request("sql", "SELECT 1\nFROM t");
+        let result = background
+            .recv_timeout(Duration::from_secs(5))
+            .expect("worker should respond");
+        assert_eq!(result.id, id);
+        assert_eq!(result.language, "sql");
+        assert_eq!(result.lines.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_background_unknown_language_reports_error() {
+        let mut background = BackgroundHighlighter::spawn(Highlighter::new(themes::one_dark()));
+        background.request("nope", "text");
+        let result = background.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(
+            result.lines,
+            Err(HighlightError::UnknownLanguage(_))
+        ));
+    }
+
+    #[test]
+    fn test_plain_lines() {
+        assert_eq!(plain_lines("a\nb").len(), 2);
+        assert_eq!(plain_lines("").len(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+//! Terminal color capability detection and truecolor downgrading.
+//!
+//! Themes are authored in 24-bit color. Terminals without truecolor support
+//! either approximate those values poorly or drop them entirely, so styles can
+//! be mapped to the nearest xterm 256-color or ANSI-16 entry instead.
+
+use ratatui::style::Color;
+
+/// Color depth supported by the target terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorMode {
+    /// 24-bit RGB colors are passed through unchanged.
+    #[default]
+    TrueColor,
+    /// RGB colors are mapped to the xterm 256-color palette.
+    Ansi256,
+    /// RGB colors are mapped to the 16 standard ANSI colors.
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Guess the terminal color depth from `COLORTERM` and `TERM`.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Guess the color depth from explicit `COLORTERM`/`TERM` values.
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+            return ColorMode::TrueColor;
+        }
+
+        match term {
+            Some(term) if term.contains("truecolor") || term.contains("direct") => {
+                ColorMode::TrueColor
+            }
+            Some(term) if term.contains("256color") => ColorMode::Ansi256,
+            Some("dumb") | Some("linux") | Some("vt100") => ColorMode::Ansi16,
+            // Most modern terminals without explicit hints still handle 256 colors.
+            Some(_) => ColorMode::Ansi256,
+            None => ColorMode::TrueColor,
+        }
+    }
+
+    /// Map a color to this mode, leaving non-RGB colors untouched.
+    pub fn downgrade(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorMode::TrueColor, _) => color,
+            (ColorMode::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorMode::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorMode::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_to_rgb(index);
+                nearest_16(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// Channel levels of the 6x6x6 xterm color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// xterm's default RGB values for the 16 ANSI colors.
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    // Weighted toward green, which the eye is most sensitive to.
+    (2 * dr * dr + 4 * dg * dg + 3 * db * db) as u32
+}
+
+fn nearest_cube_level(value: u8) -> usize {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, level)| (**level as i32 - value as i32).abs())
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+/// Nearest xterm 256-color index, considering both the cube and gray ramp.
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, gi, bi) = (
+        nearest_cube_level(r),
+        nearest_cube_level(g),
+        nearest_cube_level(b),
+    );
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(8) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * gray_step;
+    let gray_index = 232 + gray_step;
+
+    let target = (r, g, b);
+    if distance(target, (gray_value, gray_value, gray_value)) < distance(target, cube_rgb) {
+        gray_index
+    } else {
+        cube_index as u8
+    }
+}
+
+/// Nearest ANSI-16 color.
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// RGB value of an xterm 256-color index.
+fn indexed_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI_16[index as usize].1,
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[((index / 6) % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let value = 8 + 10 * (index - 232);
+            (value, value, value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truecolor_is_passthrough() {
+        let color = Color::Rgb(12, 34, 56);
+        assert_eq!(ColorMode::TrueColor.downgrade(color), color);
+    }
+
+    #[test]
+    fn test_256_color_mapping() {
+        assert_eq!(
+            ColorMode::Ansi256.downgrade(Color::Rgb(255, 0, 0)),
+            Color::Indexed(196)
+        );
+        assert_eq!(
+            ColorMode::Ansi256.downgrade(Color::Rgb(0, 0, 0)),
+            Color::Indexed(16)
+        );
+        // Mid grays prefer the grayscale ramp over the cube.
+        assert_eq!(
+            ColorMode::Ansi256.downgrade(Color::Rgb(128, 128, 128)),
+            Color::Indexed(244)
+        );
+    }
+
+    #[test]
+    fn test_16_color_mapping() {
+        assert_eq!(
+            ColorMode::Ansi16.downgrade(Color::Rgb(250, 10, 10)),
+            Color::LightRed
+        );
+        assert_eq!(
+            ColorMode::Ansi16.downgrade(Color::Rgb(20, 20, 20)),
+            Color::Black
+        );
+        assert_eq!(
+            ColorMode::Ansi16.downgrade(Color::Indexed(196)),
+            Color::LightRed
+        );
+        assert_eq!(ColorMode::Ansi16.downgrade(Color::Blue), Color::Blue);
+    }
+
+    #[test]
+    fn test_detect_from_env() {
+        assert_eq!(
+            ColorMode::from_env(Some("truecolor"), Some("xterm-256color")),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            ColorMode::from_env(None, Some("xterm-256color")),
+            ColorMode::Ansi256
+        );
+        assert_eq!(ColorMode::from_env(None, Some("linux")), ColorMode::Ansi16);
+    }
+}
@@ -1,9 +1,11 @@
 //! Core syntax highlighting logic.
 
 use std::collections::HashMap;
+use std::ops::Range;
 
 use ratatui::style::Style as RatatuiStyle;
 use ratatui::text::{Line, Span};
+use tree_sitter::Language as TsLanguage;
 use tree_sitter_highlight::{HighlightConfiguration, HighlightEvent, Highlighter as TsHighlighter};
 
 use crate::languages::Language;
@@ -43,6 +45,7 @@ const CAPTURE_NAMES: &[&str] = &[
     "constant.builtin",
     "constructor",
     "embedded",
+    "error",
     "escape",
     "function",
     "function.builtin",
@@ -84,9 +87,122 @@ const CAPTURE_NAMES: &[&str] = &[
     "variable.parameter",
 ];
 
+/// Identifier for a highlight scope (capture name) such as `keyword` or
+/// `string.escape`.
+///
+/// Returned by [`Highlighter::highlight_ranges`] so consumers can map scopes to
+/// their own styling without going through ratatui types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScopeId(usize);
+
+impl ScopeId {
+    /// Look up the scope for a capture name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        CAPTURE_NAMES.iter().position(|n| *n == name).map(ScopeId)
+    }
+
+    /// The capture name for this scope (e.g. `"keyword.control"`).
+    pub fn name(self) -> &'static str {
+        CAPTURE_NAMES.get(self.0).copied().unwrap_or("text")
+    }
+
+    /// Index of this scope in [`Highlighter::scope_names`].
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Scope applied to tree-sitter ERROR nodes.
+///
+/// Themes can style it with a background or an underline such as
+/// `error = { underline = { color = "red", style = "curl" } }`.
+pub const ERROR_SCOPE: &str = "error";
+
+/// Capture every language's query gets for ERROR nodes, so syntax errors come
+/// out of the same parse as the highlights. It sits past the end of
+/// [`CAPTURE_NAMES`] and is never reported as a scope.
+const SYNTAX_ERROR_CAPTURE: &str = "syntax_error";
+const SYNTAX_ERROR_PATTERN: &str = "\n(ERROR) @syntax_error\n";
+
 /// Configuration for a registered language.
 struct LanguageConfig {
     config: HighlightConfiguration,
+    /// Grammar the configuration is rebuilt from
+    ts_language: TsLanguage,
+    /// Language name passed to tree-sitter
+    name: &'static str,
+    /// Base highlights query (built-in or replaced by the user)
+    highlights_query: String,
+    /// User snippets appended after the base highlights query
+    extra_highlights: Vec<String>,
+    injections_query: &'static str,
+    locals_query: &'static str,
+}
+
+impl LanguageConfig {
+    /// Compile a highlight configuration from the stored queries.
+    fn build(
+        ts_language: &TsLanguage,
+        name: &str,
+        highlights_query: &str,
+        extra_highlights: &[String],
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Result<HighlightConfiguration, HighlightError> {
+        let mut highlights = highlights_query.to_string();
+        for extra in extra_highlights {
+            highlights.push('\n');
+            highlights.push_str(extra);
+        }
+        highlights.push_str(SYNTAX_ERROR_PATTERN);
+
+        let mut config = HighlightConfiguration::new(
+            ts_language.clone(),
+            name,
+            &highlights,
+            injections_query,
+            locals_query,
+        )
+        .map_err(|e| HighlightError::Config(e.to_string()))?;
+
+        // Configure the capture names
+        let names: Vec<&str> = CAPTURE_NAMES
+            .iter()
+            .copied()
+            .chain([SYNTAX_ERROR_CAPTURE])
+            .collect();
+        config.configure(&names);
+        Ok(config)
+    }
+
+    /// Rebuild with new highlights, keeping the current config on failure.
+    fn rebuild(
+        &mut self,
+        highlights_query: String,
+        extra_highlights: Vec<String>,
+    ) -> Result<(), HighlightError> {
+        self.config = Self::build(
+            &self.ts_language,
+            self.name,
+            &highlights_query,
+            &extra_highlights,
+            self.injections_query,
+            self.locals_query,
+        )?;
+        self.highlights_query = highlights_query;
+        self.extra_highlights = extra_highlights;
+        Ok(())
+    }
+}
+
+/// The most recent result of [`Highlighter::highlight_cached`].
+struct CachedHighlight {
+    /// Language the source was highlighted as
+    language: String,
+    /// The highlighted source text
+    source: String,
+    /// Styled output for the source
+    lines: Vec<Line<'static>>,
 }
 
 /// Syntax highlighter that produces ratatui-compatible styled text.
@@ -97,6 +213,10 @@ pub struct Highlighter {
     ts_highlighter: TsHighlighter,
     /// Registered languages
     languages: HashMap<String, LanguageConfig>,
+    /// Last cached highlight result (invalidated on theme/language changes)
+    cache: Option<CachedHighlight>,
+    /// Whether ERROR nodes get the [`ERROR_SCOPE`] style
+    highlight_errors: bool,
 }
 
 impl Highlighter {
@@ -106,26 +226,90 @@ impl Highlighter {
             theme,
             ts_highlighter: TsHighlighter::new(),
             languages: HashMap::new(),
+            cache: None,
+            highlight_errors: false,
         }
     }
 
     /// Register a language for highlighting.
     pub fn register_language(&mut self, language: Language) -> Result<(), HighlightError> {
-        let mut config = HighlightConfiguration::new(
-            language.ts_language,
+        let config = LanguageConfig::build(
+            &language.ts_language,
             language.name,
             language.highlights_query,
+            &[],
             language.injections_query,
             language.locals_query,
-        )
-        .map_err(|e| HighlightError::Config(e.to_string()))?;
+        )?;
+
+        self.languages.insert(
+            language.name.to_string(),
+            LanguageConfig {
+                config,
+                ts_language: language.ts_language,
+                name: language.name,
+                highlights_query: language.highlights_query.to_string(),
+                extra_highlights: Vec::new(),
+                injections_query: language.injections_query,
+                locals_query: language.locals_query,
+            },
+        );
+        self.cache = None;
 
-        // Configure the capture names
-        config.configure(CAPTURE_NAMES);
+        Ok(())
+    }
+
+    /// Append a `highlights.scm` snippet to an already-registered language.
+    ///
+    /// The snippet is merged after the built-in query, which suits adding
+    /// captures the grammar's query does not cover (e.g. vendor-specific SQL
+    /// keywords). Where both queries capture the same node, tree-sitter keeps
+    /// the earlier, built-in pattern; use [`Highlighter::replace_highlights`]
+    /// to change those. An invalid snippet leaves the language unchanged.
+    pub fn extend_highlights(&mut self, language: &str, query: &str) -> Result<(), HighlightError> {
+        let lang_config = self
+            .languages
+            .get_mut(language)
+            .ok_or_else(|| HighlightError::UnknownLanguage(language.to_string()))?;
 
-        self.languages
-            .insert(language.name.to_string(), LanguageConfig { config });
+        let base = lang_config.highlights_query.clone();
+        let mut extra = lang_config.extra_highlights.clone();
+        extra.push(query.to_string());
+        lang_config.rebuild(base, extra)?;
+        self.cache = None;
+        Ok(())
+    }
+
+    /// Replace the base `highlights.scm` query of a registered language.
+    ///
+    /// Snippets added with [`Highlighter::extend_highlights`] are kept and
+    /// still merged after the new query.
+    pub fn replace_highlights(
+        &mut self,
+        language: &str,
+        query: &str,
+    ) -> Result<(), HighlightError> {
+        let lang_config = self
+            .languages
+            .get_mut(language)
+            .ok_or_else(|| HighlightError::UnknownLanguage(language.to_string()))?;
 
+        let extra = lang_config.extra_highlights.clone();
+        lang_config.rebuild(query.to_string(), extra)?;
+        self.cache = None;
+        Ok(())
+    }
+
+    /// Drop all snippets added with [`Highlighter::extend_highlights`].
+    pub fn clear_highlight_overrides(&mut self, language: &str) -> Result<(), HighlightError> {
+        let lang_config = self
+            .languages
+            .get_mut(language)
+            .ok_or_else(|| HighlightError::UnknownLanguage(language.to_string()))?;
+
+        let base = lang_config.highlights_query.clone();
+        lang_config.rebuild(base, Vec::new())?;
+        self.cache = None;
         Ok(())
     }
 
@@ -134,9 +318,59 @@ impl Highlighter {
         &self.theme
     }
 
+    /// Get the style for a scope from the current theme.
+    ///
+    /// Falls back along the scope hierarchy; see [`Theme::style_for`].
+    pub fn style_for(&self, scope: &str) -> RatatuiStyle {
+        self.theme.style_for(scope)
+    }
+
     /// Set a new theme.
     pub fn set_theme(&mut self, theme: Theme) {
         self.theme = theme;
+        self.cache = None;
+    }
+
+    /// Enable or disable styling of syntax errors.
+    ///
+    /// When enabled, [`Highlighter::highlight`] patches the theme's
+    /// [`ERROR_SCOPE`] style over text covered by tree-sitter ERROR nodes
+    /// (for example an unterminated string).
+    pub fn set_highlight_errors(&mut self, enabled: bool) {
+        if self.highlight_errors != enabled {
+            self.highlight_errors = enabled;
+            self.cache = None;
+        }
+    }
+
+    /// Highlight source code, reusing the previous result when unchanged.
+    ///
+    /// Intended for render loops that re-highlight the same buffer every
+    /// frame. The last result is kept keyed by language and the source
+    /// text; it is dropped whenever the theme or a language changes.
+    pub fn highlight_cached(
+        &mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<Vec<Line<'static>>, HighlightError> {
+        if let Some(cached) = &self.cache {
+            if cached.language == language && cached.source == source {
+                return Ok(cached.lines.clone());
+            }
+        }
+
+        let lines = self.highlight(language, source)?;
+        self.cache = Some(CachedHighlight {
+            language: language.to_string(),
+            source: source.to_string(),
+            lines: lines.clone(),
+        });
+        Ok(lines)
+    }
+
+    /// Drop any cached highlight result.
+    pub fn clear_cache(&mut self) {
+        self.cache = None;
     }
 
     /// Highlight source code and return styled lines.
@@ -152,6 +386,57 @@ impl Highlighter {
         language: &str,
         source: &str,
     ) -> Result<Vec<Line<'static>>, HighlightError> {
+        let (ranges, errors) = self.scan(language, source)?;
+
+        let mut spans: Vec<(usize, usize, RatatuiStyle)> = ranges
+            .into_iter()
+            .map(|(range, scope)| (range.start, range.end, self.theme.style_for(scope.name())))
+            .collect();
+
+        if self.highlight_errors && !errors.is_empty() {
+            let error_style = self.theme.style_for(ERROR_SCOPE);
+            spans = overlay_errors(source.len(), &spans, &errors, error_style);
+        }
+
+        // Convert spans to lines
+        Ok(self.spans_to_lines(source, &spans))
+    }
+
+    /// Highlight source code and return scoped byte ranges.
+    ///
+    /// Each range is tagged with the innermost scope covering it. Ranges are
+    /// sorted, non-overlapping, and only cover highlighted text; gaps are plain
+    /// text. This is independent of the theme and of ratatui, which makes it
+    /// suitable for custom renderers or overlays that work in byte offsets.
+    pub fn highlight_ranges(
+        &mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<Vec<(Range<usize>, ScopeId)>, HighlightError> {
+        Ok(self.scan(language, source)?.0)
+    }
+
+    /// Byte ranges covered by tree-sitter ERROR nodes.
+    ///
+    /// Zero-width nodes are widened to the preceding character so they
+    /// remain visible. Ranges are sorted and non-overlapping.
+    pub fn error_ranges(
+        &mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<Vec<Range<usize>>, HighlightError> {
+        Ok(self.scan(language, source)?.1)
+    }
+
+    /// Runs the highlight query once, returning the scoped ranges of
+    /// [`Highlighter::highlight_ranges`] and the syntax errors of
+    /// [`Highlighter::error_ranges`].
+    #[allow(clippy::type_complexity)]
+    fn scan(
+        &mut self,
+        language: &str,
+        source: &str,
+    ) -> Result<(Vec<(Range<usize>, ScopeId)>, Vec<Range<usize>>), HighlightError> {
         let lang_config = self
             .languages
             .get(language)
@@ -162,29 +447,66 @@ impl Highlighter {
             .highlight(&lang_config.config, source.as_bytes(), None, |_| None)
             .map_err(|e| HighlightError::Highlight(e.to_string()))?;
 
-        // Process highlight events into spans
-        let mut spans: Vec<(usize, usize, RatatuiStyle)> = Vec::new();
-        let mut style_stack: Vec<RatatuiStyle> = vec![RatatuiStyle::default()];
+        let error_capture = CAPTURE_NAMES.len();
+        let mut ranges: Vec<(Range<usize>, ScopeId)> = Vec::new();
+        let mut errors: Vec<Range<usize>> = Vec::new();
+        // Open captures, and whether any source has been seen inside them.
+        let mut scope_stack: Vec<(ScopeId, bool)> = Vec::new();
+        let mut pos = 0;
 
         for event in highlights {
             match event.map_err(|e| HighlightError::Highlight(e.to_string()))? {
                 HighlightEvent::Source { start, end } => {
-                    let current_style = *style_stack.last().unwrap_or(&RatatuiStyle::default());
-                    spans.push((start, end, current_style));
+                    pos = end;
+                    for (_, covered) in &mut scope_stack {
+                        *covered = true;
+                    }
+                    if scope_stack
+                        .iter()
+                        .any(|(scope, _)| scope.0 == error_capture)
+                    {
+                        push_merged(&mut errors, start..end);
+                    }
+                    let innermost = scope_stack
+                        .iter()
+                        .rev()
+                        .map(|&(scope, _)| scope)
+                        .find(|scope| scope.0 != error_capture);
+                    if let Some(scope) = innermost {
+                        // Merge with the previous range when tree-sitter splits a
+                        // single scope into adjacent source events.
+                        match ranges.last_mut() {
+                            Some((last, last_scope))
+                                if *last_scope == scope && last.end == start =>
+                            {
+                                last.end = end;
+                            }
+                            _ => ranges.push((start..end, scope)),
+                        }
+                    }
                 }
                 HighlightEvent::HighlightStart(highlight) => {
-                    let capture_name = CAPTURE_NAMES.get(highlight.0).copied().unwrap_or("text");
-                    let style = self.theme.style_for(capture_name);
-                    style_stack.push(style);
+                    scope_stack.push((ScopeId(highlight.0), false));
                 }
                 HighlightEvent::HighlightEnd => {
-                    style_stack.pop();
+                    if let Some((scope, false)) = scope_stack.pop() {
+                        if scope.0 == error_capture {
+                            let empty = widen_empty(source, pos);
+                            if !empty.is_empty() {
+                                push_merged(&mut errors, empty);
+                            }
+                        }
+                    }
                 }
             }
         }
 
-        // Convert spans to lines
-        Ok(self.spans_to_lines(source, &spans))
+        Ok((ranges, errors))
+    }
+
+    /// All scope names the highlighter can emit, indexed by [`ScopeId::index`].
+    pub fn scope_names() -> &'static [&'static str] {
+        CAPTURE_NAMES
     }
 
     /// Convert byte-indexed spans to line-based ratatui Lines.
@@ -268,10 +590,80 @@ impl Highlighter {
     }
 }
 
+/// The character before a zero-width error node at `at` (or after it, at the
+/// very start), so the node stays visible.
+fn widen_empty(source: &str, at: usize) -> Range<usize> {
+    match source[..at].char_indices().next_back() {
+        Some((start, _)) => start..at,
+        None => {
+            let end = source[at..]
+                .chars()
+                .next()
+                .map_or(at, |c| at + c.len_utf8());
+            at..end
+        }
+    }
+}
+
+/// Appends `range` to sorted `ranges`, merging it into the last one when they
+/// overlap or touch.
+fn push_merged(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+        _ => ranges.push(range),
+    }
+}
+
+/// Patch `error_style` over the parts of `spans` covered by `errors`.
+///
+/// `spans` may leave gaps (plain text); gaps inside an error range get the
+/// error style on its own.
+fn overlay_errors(
+    source_len: usize,
+    spans: &[(usize, usize, RatatuiStyle)],
+    errors: &[Range<usize>],
+    error_style: RatatuiStyle,
+) -> Vec<(usize, usize, RatatuiStyle)> {
+    // Fill gaps so every byte has a base style, then split at error edges.
+    let mut filled: Vec<(usize, usize, RatatuiStyle)> = Vec::with_capacity(spans.len() * 2);
+    let mut pos = 0;
+    for &(start, end, style) in spans {
+        if start > pos {
+            filled.push((pos, start, RatatuiStyle::default()));
+        }
+        filled.push((start, end, style));
+        pos = end;
+    }
+    if pos < source_len {
+        filled.push((pos, source_len, RatatuiStyle::default()));
+    }
+
+    let mut result = Vec::with_capacity(filled.len());
+    for (start, end, style) in filled {
+        let mut cursor = start;
+        for error in errors {
+            if error.end <= cursor || error.start >= end {
+                continue;
+            }
+            let error_start = error.start.max(cursor);
+            let error_end = error.end.min(end);
+            if error_start > cursor {
+                result.push((cursor, error_start, style));
+            }
+            result.push((error_start, error_end, style.patch(error_style)));
+            cursor = error_end;
+        }
+        if cursor < end {
+            result.push((cursor, end, style));
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::languages::{html, javascript, sql};
+    use crate::languages::{html, javascript, json, sql};
     use crate::themes;
 
     #[test]
@@ -312,6 +704,110 @@ mod tests {
         assert_eq!(lines.len(), 3);
     }
 
+    #[test]
+    fn test_highlight_cached_reuses_and_invalidates() {
+        let mut highlighter = Highlighter::new(themes::one_dark());
+        highlighter.register_language(sql()).unwrap();
+
+        let first = highlighter.highlight_cached("sql", "SELECT 1").unwrap();
+        assert!(highlighter.cache.is_some());
+        let second = highlighter.highlight_cached("sql", "SELECT 1").unwrap();
+        assert_eq!(first, second);
+
+        let changed = highlighter.highlight_cached("sql", "SELECT 2").unwrap();
+        assert_ne!(first, changed);
+        assert_eq!(highlighter.cache.as_ref().unwrap().source, "SELECT 2");
+
+        highlighter.set_theme(themes::github_light());
+        assert!(highlighter.cache.is_none());
+    }
+
+    #[test]
+    fn test_highlight_ranges() {
+        let mut highlighter = Highlighter::new(themes::one_dark());
+        highlighter.register_language(sql()).unwrap();
+
+        let source = "SELECT id FROM users";
+        let ranges = highlighter.highlight_ranges("sql", source).unwrap();
+        assert!(!ranges.is_empty());
+        assert!(ranges.windows(2).all(|w| w[0].0.end <= w[1].0.start));
+
+        let (range, scope) = ranges.first().unwrap().clone();
+        assert_eq!(&source[range], "SELECT");
+        assert!(scope.name().starts_with("keyword"));
+        assert_eq!(ScopeId::from_name(scope.name()), Some(scope));
+    }
+
+    #[test]
+    fn test_error_nodes_get_error_style() {
+        let theme = Theme::from_toml(
+            r##"
+            keyword = "#0000FF"
+            error = { bg = "#FF0000" }
+        "##,
+        )
+        .unwrap();
+        let mut highlighter = Highlighter::new(theme);
+        highlighter.register_language(json()).unwrap();
+
+        let source = "{\"a\": 1,, \"b\": 2}";
+        let errors = highlighter.error_ranges("json", source).unwrap();
+        assert_eq!(errors, vec![7..8]);
+
+        let plain = highlighter.highlight("json", source).unwrap();
+        assert!(plain[0]
+            .spans
+            .iter()
+            .all(|span| span.style.bg != Some(ratatui::style::Color::Rgb(255, 0, 0))));
+
+        highlighter.set_highlight_errors(true);
+        let lines = highlighter.highlight("json", source).unwrap();
+        let text: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, source);
+        assert!(lines[0]
+            .spans
+            .iter()
+            .any(|span| span.style.bg == Some(ratatui::style::Color::Rgb(255, 0, 0))));
+    }
+
+    #[test]
+    fn test_extend_and_replace_highlights() {
+        let mut highlighter = Highlighter::new(themes::one_dark());
+        highlighter.register_language(json()).unwrap();
+
+        let source = "{\"a\": 1}";
+        let before = highlighter.highlight_ranges("json", source).unwrap();
+
+        highlighter
+            .replace_highlights("json", "(number) @keyword")
+            .unwrap();
+        let replaced = highlighter.highlight_ranges("json", source).unwrap();
+        assert_ne!(before, replaced);
+        assert_eq!(replaced.len(), 1);
+        assert_eq!(replaced[0].1.name(), "keyword");
+
+        highlighter
+            .extend_highlights("json", "(string) @string")
+            .unwrap();
+        let extended = highlighter.highlight_ranges("json", source).unwrap();
+        assert!(extended.iter().any(|(_, scope)| scope.name() == "string"));
+
+        // Invalid snippets are rejected without losing the current query.
+        assert!(highlighter
+            .extend_highlights("json", "(not_a_node) @string")
+            .is_err());
+        assert_eq!(
+            highlighter.highlight_ranges("json", source).unwrap(),
+            extended
+        );
+
+        highlighter.clear_highlight_overrides("json").unwrap();
+        assert_eq!(
+            highlighter.highlight_ranges("json", source).unwrap(),
+            replaced
+        );
+    }
+
     #[test]
     fn test_unknown_language_error() {
         let theme = themes::one_dark();
@@ -352,6 +848,29 @@ mod tests {
         assert_eq!(lines.len(), 8);
     }
 
+    #[cfg(feature = "all-languages")]
+    #[test]
+    fn test_highlight_optional_languages() {
+        use crate::languages::{bash, markdown, python, toml, yaml};
+
+        let mut highlighter = Highlighter::new(themes::one_dark());
+        for language in [markdown(), yaml(), toml(), bash(), python()] {
+            highlighter.register_language(language).unwrap();
+        }
+
+        let samples = [
+            ("markdown", "# Title\n\n- item"),
+            ("yaml", "name: tsql\nversion: 1"),
+            ("toml", "[package]\nname = \"tsql\""),
+            ("bash", "echo \"$HOME\" | wc -c"),
+            ("python", "def main():\n    return 1"),
+        ];
+        for (language, source) in samples {
+            let lines = highlighter.highlight(language, source).unwrap();
+            assert_eq!(lines.len(), source.lines().count(), "{language}");
+        }
+    }
+
     #[test]
     fn test_highlight_mongo_shell_javascript() {
         let theme = themes::one_dark();
@@ -0,0 +1,16 @@
+//! Bash language support using tree-sitter-bash.
+
+use super::Language;
+
+/// Returns the Bash language configuration.
+///
+/// Uses the tree-sitter-bash grammar for shell script highlighting.
+pub fn bash() -> Language {
+    Language {
+        name: "bash",
+        ts_language: tree_sitter_bash::LANGUAGE.into(),
+        highlights_query: tree_sitter_bash::HIGHLIGHT_QUERY,
+        injections_query: "",
+        locals_query: "",
+    }
+}
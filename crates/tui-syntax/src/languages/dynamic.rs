@@ -0,0 +1,253 @@
+//! Runtime loading of compiled tree-sitter grammars.
+//!
+//! Grammars are shared libraries in the layout used by Helix and nvim-treesitter:
+//! a `<name>.so`/`<name>.dylib`/`<name>.dll` exporting `tree_sitter_<name>`, plus
+//! a query directory with `highlights.scm` and optional `injections.scm` and
+//! `locals.scm` files.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use tree_sitter::Language as TsLanguage;
+
+use super::{Language, LanguageError};
+
+/// Locates and loads grammars from a list of search directories.
+///
+/// Directories are searched in the order they were added, so user directories
+/// should be added before system-wide ones. Each language is loaded once;
+/// clones of a loader share what it has loaded.
+#[derive(Debug, Clone, Default)]
+pub struct GrammarLoader {
+    grammar_dirs: Vec<PathBuf>,
+    query_dirs: Vec<PathBuf>,
+    loaded: Arc<Mutex<HashMap<String, Language>>>,
+}
+
+impl GrammarLoader {
+    /// Create a loader with no search directories.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a Helix-style runtime directory (`<dir>/grammars` and `<dir>/queries`).
+    pub fn with_runtime_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        self.grammar_dirs.push(dir.join("grammars"));
+        self.query_dirs.push(dir.join("queries"));
+        self
+    }
+
+    /// Add a directory containing compiled grammar libraries.
+    pub fn add_grammar_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.grammar_dirs.push(dir.into());
+    }
+
+    /// Add a directory containing per-language query folders.
+    pub fn add_query_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.query_dirs.push(dir.into());
+    }
+
+    /// Load the grammar and queries for `name` from the search directories.
+    ///
+    /// A language this loader already loaded is returned from its cache.
+    pub fn load(&self, name: &str) -> Result<Language, LanguageError> {
+        let mut loaded = self.loaded.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(language) = loaded.get(name) {
+            return Ok(language.clone());
+        }
+        let language = self.load_uncached(name)?;
+        loaded.insert(name.to_string(), language.clone());
+        Ok(language)
+    }
+
+    fn load_uncached(&self, name: &str) -> Result<Language, LanguageError> {
+        let file_name = format!("{}.{}", name, std::env::consts::DLL_EXTENSION);
+        let library = self
+            .grammar_dirs
+            .iter()
+            .map(|dir| dir.join(&file_name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                LanguageError::Load(format!("grammar library not found: {}", file_name))
+            })?;
+
+        let ts_language = load_grammar(name, &library)?;
+        let highlights = self
+            .read_query(name, "highlights.scm", 0)?
+            .ok_or_else(|| LanguageError::Load(format!("highlights.scm not found for {}", name)))?;
+        let injections = self
+            .read_query(name, "injections.scm", 0)?
+            .unwrap_or_default();
+        let locals = self.read_query(name, "locals.scm", 0)?.unwrap_or_default();
+
+        Ok(Language {
+            name: leak(name.to_string()),
+            ts_language,
+            highlights_query: leak(highlights),
+            injections_query: leak(injections),
+            locals_query: leak(locals),
+        })
+    }
+
+    /// Read a query file, resolving Helix `; inherits: a,b` directives.
+    fn read_query(
+        &self,
+        name: &str,
+        file: &str,
+        depth: usize,
+    ) -> Result<Option<String>, LanguageError> {
+        // Guard against inheritance cycles in third-party query sets.
+        if depth > 8 {
+            return Err(LanguageError::Load(format!(
+                "query inheritance too deep for {}",
+                name
+            )));
+        }
+
+        let Some(path) = self
+            .query_dirs
+            .iter()
+            .map(|dir| dir.join(name).join(file))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| LanguageError::Load(format!("{}: {}", path.display(), e)))?;
+
+        let mut query = String::new();
+        for line in content.lines() {
+            if let Some(parents) = line.trim().strip_prefix("; inherits:") {
+                for parent in parents.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                    if let Some(inherited) = self.read_query(parent, file, depth + 1)? {
+                        query.push_str(&inherited);
+                        query.push('\n');
+                    }
+                }
+            }
+        }
+        query.push_str(&content);
+        Ok(Some(query))
+    }
+}
+
+/// Load a language from explicit grammar library and query directory paths.
+///
+/// `queries_dir` must contain `highlights.scm`; `injections.scm` and
+/// `locals.scm` are optional. Every call loads the language again; use a
+/// [`GrammarLoader`] to load each language once.
+pub fn load_language(
+    name: &str,
+    library: &Path,
+    queries_dir: &Path,
+) -> Result<Language, LanguageError> {
+    let read = |file: &str| -> Result<Option<String>, LanguageError> {
+        let path = queries_dir.join(file);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        std::fs::read_to_string(&path)
+            .map(Some)
+            .map_err(|e| LanguageError::Load(format!("{}: {}", path.display(), e)))
+    };
+
+    let ts_language = load_grammar(name, library)?;
+    let highlights = read("highlights.scm")?
+        .ok_or_else(|| LanguageError::Load(format!("highlights.scm not found for {}", name)))?;
+
+    Ok(Language {
+        name: leak(name.to_string()),
+        ts_language,
+        highlights_query: leak(highlights),
+        injections_query: leak(read("injections.scm")?.unwrap_or_default()),
+        locals_query: leak(read("locals.scm")?.unwrap_or_default()),
+    })
+}
+
+/// Open a grammar library and resolve its `tree_sitter_<name>` entry point.
+fn load_grammar(name: &str, library: &Path) -> Result<TsLanguage, LanguageError> {
+    let symbol = format!("tree_sitter_{}", name.replace('-', "_"));
+
+    // SAFETY: loading a grammar runs its initializers; callers opt into this by
+    // pointing the loader at the library. The entry point is the standard
+    // tree-sitter `const TSLanguage *tree_sitter_<name>(void)` signature.
+    unsafe {
+        let lib = libloading::Library::new(library)
+            .map_err(|e| LanguageError::Load(format!("{}: {}", library.display(), e)))?;
+        let entry: libloading::Symbol<
+            unsafe extern "C" fn() -> *const tree_sitter::ffi::TSLanguage,
+        > = lib
+            .get(symbol.as_bytes())
+            .map_err(|e| LanguageError::Load(format!("{}: {}", symbol, e)))?;
+        let language = TsLanguage::from_raw(entry());
+
+        // The language table lives inside the library, so it must stay mapped
+        // for the rest of the process.
+        std::mem::forget(lib);
+        Ok(language)
+    }
+}
+
+/// Leak a runtime string so it can back a `&'static str` language field.
+///
+/// [`GrammarLoader`] loads each language once, so the leak is bounded.
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_grammar_is_reported() {
+        let dir = std::env::temp_dir().join("tui-syntax-missing-grammar");
+        let loader = GrammarLoader::new().with_runtime_dir(&dir);
+        let err = loader.load("nope").expect_err("load should fail");
+        assert!(matches!(err, LanguageError::Load(_)));
+    }
+
+    #[test]
+    fn test_query_inherits_are_resolved() {
+        let dir = std::env::temp_dir().join(format!("tui-syntax-queries-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("base")).unwrap();
+        std::fs::create_dir_all(dir.join("child")).unwrap();
+        std::fs::write(dir.join("base/highlights.scm"), "(comment) @comment").unwrap();
+        std::fs::write(
+            dir.join("child/highlights.scm"),
+            "; inherits: base\n(string) @string",
+        )
+        .unwrap();
+
+        let mut loader = GrammarLoader::new();
+        loader.add_query_dir(&dir);
+        let query = loader
+            .read_query("child", "highlights.scm", 0)
+            .unwrap()
+            .unwrap();
+        assert!(query.starts_with("(comment) @comment"));
+        assert!(query.ends_with("(string) @string"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_loaded_languages_are_reused() {
+        let loader = GrammarLoader::new();
+        loader
+            .loaded
+            .lock()
+            .unwrap()
+            .insert("sql".to_string(), super::super::sql());
+
+        // No search directories, so only the cache can provide it.
+        let first = loader.load("sql").unwrap();
+        let again = loader.clone().load("sql").unwrap();
+        assert_eq!(first.name, "sql");
+        assert!(std::ptr::eq(first.highlights_query, again.highlights_query));
+        assert!(loader.load("nope").is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Markdown language support using tree-sitter-md.
+
+use super::Language;
+
+/// Returns the Markdown language configuration.
+///
+/// Uses the block-level tree-sitter-md grammar (headings, lists, code
+/// fences, block quotes). Inline markup is not highlighted.
+pub fn markdown() -> Language {
+    Language {
+        name: "markdown",
+        ts_language: tree_sitter_md::LANGUAGE.into(),
+        highlights_query: tree_sitter_md::HIGHLIGHT_QUERY_BLOCK,
+        injections_query: "",
+        locals_query: "",
+    }
+}
@@ -1,16 +1,43 @@
 //! Language definitions for syntax highlighting.
 //!
 //! Each language provides a tree-sitter grammar and highlight queries.
+//! SQL, JSON, HTML and JavaScript are always available; the remaining
+//! grammars are opt-in through `lang-*` cargo features. With the
+//! `dynamic-grammars` feature, compiled grammars can also be loaded at runtime.
 
+#[cfg(feature = "lang-bash")]
+mod bash;
+#[cfg(feature = "dynamic-grammars")]
+mod dynamic;
 mod html;
 mod javascript;
 mod json;
+#[cfg(feature = "lang-markdown")]
+mod markdown;
+#[cfg(feature = "lang-python")]
+mod python;
 mod sql;
+#[cfg(feature = "lang-toml")]
+mod toml;
+#[cfg(feature = "lang-yaml")]
+mod yaml;
 
+#[cfg(feature = "lang-toml")]
+pub use self::toml::toml;
+#[cfg(feature = "lang-bash")]
+pub use bash::bash;
+#[cfg(feature = "dynamic-grammars")]
+pub use dynamic::{load_language, GrammarLoader};
 pub use html::html;
 pub use javascript::javascript;
 pub use json::json;
+#[cfg(feature = "lang-markdown")]
+pub use markdown::markdown;
+#[cfg(feature = "lang-python")]
+pub use python::python;
 pub use sql::sql;
+#[cfg(feature = "lang-yaml")]
+pub use yaml::yaml;
 
 use tree_sitter::Language as TsLanguage;
 
@@ -19,12 +46,15 @@ use tree_sitter::Language as TsLanguage;
 pub enum LanguageError {
     /// Failed to create highlight configuration
     HighlightConfig(String),
+    /// Failed to load a grammar or its queries at runtime
+    Load(String),
 }
 
 impl std::fmt::Display for LanguageError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LanguageError::HighlightConfig(msg) => write!(f, "Highlight config error: {}", msg),
+            LanguageError::Load(msg) => write!(f, "Grammar load error: {}", msg),
         }
     }
 }
@@ -32,6 +62,7 @@ impl std::fmt::Display for LanguageError {
 impl std::error::Error for LanguageError {}
 
 /// A language configuration for syntax highlighting.
+#[derive(Debug, Clone)]
 pub struct Language {
     /// Language name (e.g., "sql", "rust", "python")
     pub name: &'static str,
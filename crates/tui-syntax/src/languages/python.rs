@@ -0,0 +1,16 @@
+//! Python language support using tree-sitter-python.
+
+use super::Language;
+
+/// Returns the Python language configuration.
+///
+/// Uses the tree-sitter-python grammar for Python syntax highlighting.
+pub fn python() -> Language {
+    Language {
+        name: "python",
+        ts_language: tree_sitter_python::LANGUAGE.into(),
+        highlights_query: tree_sitter_python::HIGHLIGHTS_QUERY,
+        injections_query: "",
+        locals_query: "",
+    }
+}
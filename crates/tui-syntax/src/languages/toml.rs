@@ -0,0 +1,16 @@
+//! TOML language support using tree-sitter-toml-ng.
+
+use super::Language;
+
+/// Returns the TOML language configuration.
+///
+/// Uses the tree-sitter-toml-ng grammar for TOML syntax highlighting.
+pub fn toml() -> Language {
+    Language {
+        name: "toml",
+        ts_language: tree_sitter_toml_ng::LANGUAGE.into(),
+        highlights_query: tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
+        injections_query: "",
+        locals_query: "",
+    }
+}
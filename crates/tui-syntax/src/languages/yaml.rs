@@ -0,0 +1,16 @@
+//! YAML language support using tree-sitter-yaml.
+
+use super::Language;
+
+/// Returns the YAML language configuration.
+///
+/// Uses the tree-sitter-yaml grammar for YAML syntax highlighting.
+pub fn yaml() -> Language {
+    Language {
+        name: "yaml",
+        ts_language: tree_sitter_yaml::LANGUAGE.into(),
+        highlights_query: tree_sitter_yaml::HIGHLIGHTS_QUERY,
+        injections_query: "",
+        locals_query: "",
+    }
+}
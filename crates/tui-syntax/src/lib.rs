@@ -9,8 +9,13 @@
 //!
 //! - Tree-sitter based highlighting (accurate, fast)
 //! - Helix-compatible TOML theme format
-//! - Built-in themes (One Dark, GitHub Light)
-//! - SQL language support built-in
+//! - Optional background-thread highlighting for very large inputs
+//! - Automatic 256-color / ANSI-16 downgrade for terminals without truecolor
+//! - Built-in themes (One Dark, GitHub Light, Catppuccin, Gruvbox, Solarized,
+//!   Dracula, Nord)
+//! - SQL, JSON, HTML and JavaScript support built-in
+//! - Markdown, YAML, TOML, Bash and Python behind `lang-*` cargo features
+//! - Runtime-loaded grammars behind the `dynamic-grammars` feature
 //! - Extensible to other languages via tree-sitter grammars
 //!
 //! ## Example
@@ -28,13 +33,34 @@
 //! let lines = highlighter.highlight("sql", "SELECT * FROM users WHERE id = 1;").unwrap();
 //!
 //! // `lines` is Vec<ratatui::text::Line> ready to render
+//!
+//! // Style non-code UI consistently with the syntax theme
+//! let header = highlighter.theme().style_for("ui.grid.header");
+//! # let _ = header;
 //! ```
 
+mod background;
+mod color;
 mod highlighter;
 pub mod languages;
 mod theme;
 pub mod themes;
 
-pub use highlighter::{HighlightError, Highlighter};
+pub use background::{plain_lines, BackgroundHighlighter, HighlightResult};
+pub use color::ColorMode;
+pub use highlighter::{HighlightError, Highlighter, ScopeId, ERROR_SCOPE};
 pub use languages::{html, javascript, json, sql, Language, LanguageError};
-pub use theme::{Style as ThemeStyle, StyleModifier, Theme, ThemeError};
+
+#[cfg(feature = "lang-bash")]
+pub use languages::bash;
+#[cfg(feature = "lang-markdown")]
+pub use languages::markdown;
+#[cfg(feature = "lang-python")]
+pub use languages::python;
+#[cfg(feature = "lang-toml")]
+pub use languages::toml;
+#[cfg(feature = "lang-yaml")]
+pub use languages::yaml;
+#[cfg(feature = "dynamic-grammars")]
+pub use languages::{load_language, GrammarLoader};
+pub use theme::{Style as ThemeStyle, StyleModifier, Theme, ThemeError, Underline, UnderlineStyle};
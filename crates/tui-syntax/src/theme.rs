@@ -3,11 +3,13 @@
 //! Themes define how syntax elements are styled. The format is compatible with
 //! [Helix editor themes](https://docs.helix-editor.com/themes.html).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use ratatui::style::{Color, Modifier, Style as RatatuiStyle};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::color::ColorMode;
 
 /// Error loading or parsing a theme.
 #[derive(Debug)]
@@ -18,6 +20,8 @@ pub enum ThemeError {
     Parse(toml::de::Error),
     /// Invalid color format
     InvalidColor(String),
+    /// TOML serialization error
+    Serialize(toml::ser::Error),
 }
 
 impl std::fmt::Display for ThemeError {
@@ -26,6 +30,7 @@ impl std::fmt::Display for ThemeError {
             ThemeError::Io(e) => write!(f, "IO error: {}", e),
             ThemeError::Parse(e) => write!(f, "Parse error: {}", e),
             ThemeError::InvalidColor(c) => write!(f, "Invalid color: {}", c),
+            ThemeError::Serialize(e) => write!(f, "Serialize error: {}", e),
         }
     }
 }
@@ -44,18 +49,31 @@ impl From<toml::de::Error> for ThemeError {
     }
 }
 
+impl From<toml::ser::Error> for ThemeError {
+    fn from(e: toml::ser::Error) -> Self {
+        ThemeError::Serialize(e)
+    }
+}
+
 /// Style modifiers (bold, italic, etc.)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+///
+/// Accepts both Helix's snake_case names (`slow_blink`, `crossed_out`) and
+/// the older concatenated spelling (`slowblink`, `crossedout`); serializes
+/// to the Helix spelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum StyleModifier {
     Bold,
     Dim,
     Italic,
     Underlined,
+    #[serde(rename = "slow_blink", alias = "slowblink")]
     SlowBlink,
+    #[serde(rename = "rapid_blink", alias = "rapidblink")]
     RapidBlink,
     Reversed,
     Hidden,
+    #[serde(rename = "crossed_out", alias = "crossedout")]
     CrossedOut,
 }
 
@@ -75,16 +93,46 @@ impl StyleModifier {
     }
 }
 
+/// Underline shape, as used by Helix's `underline.style` key.
+///
+/// Terminals that only support plain underlines render every shape as a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnderlineStyle {
+    Line,
+    Curl,
+    Dashed,
+    Dotted,
+    DoubleLine,
+}
+
+/// Underline definition (`underline = { color = "red", style = "curl" }`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Underline {
+    /// Underline color (name from palette or hex)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Underline shape
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<UnderlineStyle>,
+}
+
 /// A style definition for a syntax element.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Style {
     /// Foreground color (name from palette or hex)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fg: Option<String>,
     /// Background color (name from palette or hex)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bg: Option<String>,
+    /// Underline color and shape
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline: Option<Underline>,
     /// Style modifiers
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub modifiers: Vec<StyleModifier>,
 }
 
@@ -105,6 +153,19 @@ impl Style {
             }
         }
 
+        if let Some(ref underline) = self.underline {
+            if let Some(color) = underline
+                .color
+                .as_deref()
+                .and_then(|c| resolve_color(c, palette))
+            {
+                style = style.underline_color(color);
+            }
+            if underline.style.is_some() {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+        }
+
         for modifier in &self.modifiers {
             style = style.add_modifier(modifier.to_ratatui_modifier());
         }
@@ -152,8 +213,10 @@ fn parse_color(color: &str) -> Option<Color> {
         };
     }
 
-    // Named colors
-    match color.to_lowercase().as_str() {
+    // Named colors (Helix spells these with dashes, e.g. "light-red")
+    let name = color.to_lowercase().replace(['-', '_'], "");
+    match name.as_str() {
+        "reset" | "default" => Some(Color::Reset),
         "black" => Some(Color::Black),
         "red" => Some(Color::Red),
         "green" => Some(Color::Green),
@@ -161,7 +224,7 @@ fn parse_color(color: &str) -> Option<Color> {
         "blue" => Some(Color::Blue),
         "magenta" => Some(Color::Magenta),
         "cyan" => Some(Color::Cyan),
-        "gray" | "grey" => Some(Color::Gray),
+        "gray" | "grey" | "lightgray" | "lightgrey" => Some(Color::Gray),
         "darkgray" | "darkgrey" => Some(Color::DarkGray),
         "lightred" => Some(Color::LightRed),
         "lightgreen" => Some(Color::LightGreen),
@@ -183,6 +246,15 @@ struct RawTheme {
     styles: HashMap<String, StyleValue>,
 }
 
+/// Serializable view of a theme, with keys sorted for stable output.
+#[derive(Serialize)]
+struct RawThemeOut<'a> {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    palette: BTreeMap<&'a str, &'a str>,
+    #[serde(flatten)]
+    styles: BTreeMap<&'a str, &'a Style>,
+}
+
 /// A style value can be either a full Style object or a simple string.
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -199,8 +271,7 @@ impl StyleValue {
             StyleValue::Full(s) => s,
             StyleValue::Simple(fg) => Style {
                 fg: Some(fg),
-                bg: None,
-                modifiers: Vec::new(),
+                ..Style::default()
             },
         }
     }
@@ -217,6 +288,8 @@ pub struct Theme {
     palette: HashMap<String, String>,
     /// Styles for each capture name
     styles: HashMap<String, Style>,
+    /// Terminal color depth the cached styles are mapped to
+    color_mode: ColorMode,
     /// Cached ratatui styles
     cached_styles: HashMap<String, RatatuiStyle>,
 }
@@ -233,8 +306,9 @@ impl Theme {
 
         let mut styles = HashMap::new();
         for (key, value) in raw.styles {
-            // Skip the palette key
-            if key == "palette" {
+            // Skip the palette key and Helix's `inherits = "<theme>"`, which
+            // names a parent theme rather than a scope.
+            if key == "palette" || key == "inherits" {
                 continue;
             }
             styles.insert(key, value.into_style());
@@ -244,6 +318,7 @@ impl Theme {
             name: name.to_string(),
             palette: raw.palette,
             styles,
+            color_mode: ColorMode::default(),
             cached_styles: HashMap::new(),
         };
 
@@ -263,27 +338,31 @@ impl Theme {
         Self::from_toml_with_name(&content, name)
     }
 
-    /// Get the ratatui style for a capture name.
+    /// Get the ratatui style for a scope.
     ///
-    /// Uses hierarchical fallback: "keyword.control" falls back to "keyword".
-    pub fn style_for(&self, capture: &str) -> RatatuiStyle {
-        // Check exact match first
-        if let Some(style) = self.cached_styles.get(capture) {
-            return *style;
-        }
+    /// Uses hierarchical fallback: "keyword.control.import" falls back to
+    /// "keyword.control", then "keyword". UI scopes (`ui.*`) resolve the same
+    /// way, e.g. "ui.cursor.primary" falls back to "ui.cursor".
+    ///
+    /// Host applications can use this to style non-code elements (grid
+    /// headers, diff markers, status pills) consistently with the syntax
+    /// theme. Unknown scopes resolve to the default style.
+    pub fn style_for(&self, scope: &str) -> RatatuiStyle {
+        self.resolve(scope).unwrap_or_default()
+    }
 
-        // Try hierarchical fallback
-        let mut parts: Vec<&str> = capture.split('.').collect();
-        while parts.len() > 1 {
-            parts.pop();
-            let parent = parts.join(".");
-            if let Some(style) = self.cached_styles.get(&parent) {
-                return *style;
+    /// Get the ratatui style for a scope, if it or any parent scope is defined.
+    ///
+    /// Like [`Theme::style_for`], but distinguishes "no style" from an
+    /// explicitly empty one so callers can supply their own fallback.
+    pub fn resolve(&self, scope: &str) -> Option<RatatuiStyle> {
+        let mut current = scope;
+        loop {
+            if let Some(style) = self.cached_styles.get(current) {
+                return Some(*style);
             }
+            current = current.rsplit_once('.')?.0;
         }
-
-        // Default style
-        RatatuiStyle::default()
     }
 
     /// Get the ratatui style defined for an exact capture name.
@@ -295,15 +374,100 @@ impl Theme {
         self.cached_styles.get(capture).copied()
     }
 
+    /// The color depth styles are currently mapped to.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Map all theme colors to the given terminal color depth.
+    ///
+    /// Truecolor values are replaced by the nearest 256-color or ANSI-16
+    /// entry; switching back to [`ColorMode::TrueColor`] restores the
+    /// original values.
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        if self.color_mode != mode {
+            self.color_mode = mode;
+            self.cache_styles();
+        }
+    }
+
+    /// Builder-style variant of [`Theme::set_color_mode`].
+    pub fn with_color_mode(mut self, mode: ColorMode) -> Self {
+        self.set_color_mode(mode);
+        self
+    }
+
     /// Cache all styles as ratatui styles.
     fn cache_styles(&mut self) {
         self.cached_styles.clear();
         for (name, style) in &self.styles {
-            let ratatui_style = style.to_ratatui_style(&self.palette);
+            let mut ratatui_style = style.to_ratatui_style(&self.palette);
+            ratatui_style.fg = ratatui_style.fg.map(|c| self.color_mode.downgrade(c));
+            ratatui_style.bg = ratatui_style.bg.map(|c| self.color_mode.downgrade(c));
+            ratatui_style.underline_color = ratatui_style
+                .underline_color
+                .map(|c| self.color_mode.downgrade(c));
             self.cached_styles.insert(name.clone(), ratatui_style);
         }
     }
 
+    /// Serialize the theme back to Helix-compatible TOML.
+    ///
+    /// Palette entries and scopes are written in sorted order. Parsing the
+    /// output with [`Theme::from_toml`] yields an equivalent theme.
+    pub fn to_toml(&self) -> Result<String, ThemeError> {
+        let raw = RawThemeOut {
+            palette: self
+                .palette
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect(),
+            styles: self
+                .styles
+                .iter()
+                .map(|(name, style)| (name.as_str(), style))
+                .collect(),
+        };
+        Ok(toml::to_string(&raw)?)
+    }
+
+    /// Write the theme to a TOML file.
+    pub fn save(&self, path: &Path) -> Result<(), ThemeError> {
+        std::fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    /// Get the unresolved style definition for an exact scope.
+    pub fn style_definition(&self, capture: &str) -> Option<&Style> {
+        self.styles.get(capture)
+    }
+
+    /// Define or replace the style for a scope.
+    pub fn set_style(&mut self, capture: impl Into<String>, style: Style) {
+        self.styles.insert(capture.into(), style);
+        self.cache_styles();
+    }
+
+    /// Remove a scope's style, returning the previous definition.
+    pub fn remove_style(&mut self, capture: &str) -> Option<Style> {
+        let removed = self.styles.remove(capture);
+        if removed.is_some() {
+            self.cache_styles();
+        }
+        removed
+    }
+
+    /// Get a palette color by name.
+    pub fn palette_color(&self, name: &str) -> Option<&str> {
+        self.palette.get(name).map(String::as_str)
+    }
+
+    /// Define or replace a palette color.
+    pub fn set_palette_color(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.palette.insert(name.into(), value.into());
+        self.cache_styles();
+    }
+
     /// Get the list of all capture names this theme defines styles for.
     pub fn capture_names(&self) -> Vec<&str> {
         self.styles.keys().map(|s| s.as_str()).collect()
@@ -390,6 +554,127 @@ mod tests {
         assert_eq!(theme.style_for_exact("ui.selection.missing"), None);
     }
 
+    #[test]
+    fn test_helix_style_fields() {
+        let toml = r##"
+            inherits = "onedark"
+
+            [palette]
+            red = "#FF0000"
+
+            ["diagnostic.error"]
+            bg = "light-gray"
+            underline = { color = "red", style = "curl" }
+            modifiers = ["slow_blink", "crossed_out"]
+
+            ["ui.cursor"]
+            fg = "reset"
+        "##;
+
+        let theme = Theme::from_toml(toml).unwrap();
+        assert!(theme.style_for_exact("inherits").is_none());
+
+        let error = theme.style_for("diagnostic.error");
+        assert_eq!(error.bg, Some(Color::Gray));
+        assert_eq!(error.underline_color, Some(Color::Rgb(255, 0, 0)));
+        assert!(error
+            .add_modifier
+            .contains(Modifier::UNDERLINED | Modifier::SLOW_BLINK | Modifier::CROSSED_OUT));
+
+        let cursor = theme.style_for("ui.cursor.primary");
+        assert_eq!(cursor.fg, Some(Color::Reset));
+    }
+
+    #[test]
+    fn test_deep_hierarchical_fallback() {
+        let toml = r##"
+            keyword = "#FF0000"
+            "keyword.control" = "#00FF00"
+        "##;
+
+        let theme = Theme::from_toml(toml).unwrap();
+        let style = theme.style_for("keyword.control.import");
+        assert_eq!(style.fg, Some(Color::Rgb(0, 255, 0)));
+        let style = theme.style_for("keyword.function");
+        assert_eq!(style.fg, Some(Color::Rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn test_color_mode_downgrades_cached_styles() {
+        let toml = r##"
+            keyword = "#FF0000"
+        "##;
+
+        let mut theme = Theme::from_toml(toml).unwrap();
+        theme.set_color_mode(ColorMode::Ansi256);
+        assert_eq!(theme.style_for("keyword").fg, Some(Color::Indexed(196)));
+
+        theme.set_color_mode(ColorMode::Ansi16);
+        assert_eq!(theme.style_for("keyword").fg, Some(Color::LightRed));
+
+        theme.set_color_mode(ColorMode::TrueColor);
+        assert_eq!(theme.style_for("keyword").fg, Some(Color::Rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn test_to_toml_round_trip() {
+        let toml = r##"
+            [palette]
+            red = "#E06C75"
+
+            keyword = "red"
+
+            ["keyword.control"]
+            fg = "#00FF00"
+            bg = "#000000"
+            underline = { color = "red", style = "double_line" }
+            modifiers = ["bold", "crossed_out"]
+        "##;
+
+        let mut theme = Theme::from_toml(toml).unwrap();
+        theme.set_style(
+            "ui.cursor",
+            Style {
+                bg: Some("red".to_string()),
+                ..Style::default()
+            },
+        );
+        theme.set_palette_color("red", "#FF0000");
+
+        let serialized = theme.to_toml().unwrap();
+        assert!(serialized.contains("crossed_out"));
+        let reparsed = Theme::from_toml(&serialized).unwrap();
+
+        assert_eq!(reparsed.palette_color("red"), Some("#FF0000"));
+        for scope in ["keyword", "keyword.control", "ui.cursor"] {
+            assert_eq!(
+                reparsed.style_definition(scope),
+                theme.style_definition(scope),
+                "{scope}"
+            );
+            assert_eq!(reparsed.style_for(scope), theme.style_for(scope), "{scope}");
+        }
+        assert_eq!(
+            reparsed.style_for("ui.cursor").bg,
+            Some(Color::Rgb(255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_resolve_distinguishes_missing_scopes() {
+        let toml = r##"
+            "ui.grid" = "#FF0000"
+        "##;
+
+        let theme = Theme::from_toml(toml).unwrap();
+        assert_eq!(
+            theme.resolve("ui.grid.header").and_then(|s| s.fg),
+            Some(Color::Rgb(255, 0, 0))
+        );
+        assert_eq!(theme.resolve("ui.diff.plus"), None);
+        assert_eq!(theme.style_for("ui.diff.plus"), RatatuiStyle::default());
+    }
+
     #[test]
     fn test_simple_style_value() {
         let toml = r##"
@@ -2,6 +2,9 @@
 //!
 //! These themes are embedded at compile time for fast startup.
 //! Additional themes can be loaded from TOML files at runtime.
+//!
+//! One Dark and GitHub Light are defined inline; the other palettes live in
+//! sibling `.toml` files pulled in with `include_str!`.
 
 use crate::theme::Theme;
 
@@ -16,6 +19,113 @@ pub fn github_light() -> Theme {
         .expect("Built-in theme should be valid")
 }
 
+/// Catppuccin Latte theme (light background).
+pub fn catppuccin_latte() -> Theme {
+    Theme::from_toml_with_name(include_str!("catppuccin_latte.toml"), "catppuccin_latte")
+        .expect("Built-in theme should be valid")
+}
+
+/// Catppuccin Frappé theme (dark background).
+pub fn catppuccin_frappe() -> Theme {
+    Theme::from_toml_with_name(include_str!("catppuccin_frappe.toml"), "catppuccin_frappe")
+        .expect("Built-in theme should be valid")
+}
+
+/// Catppuccin Macchiato theme (dark background).
+pub fn catppuccin_macchiato() -> Theme {
+    Theme::from_toml_with_name(
+        include_str!("catppuccin_macchiato.toml"),
+        "catppuccin_macchiato",
+    )
+    .expect("Built-in theme should be valid")
+}
+
+/// Catppuccin Mocha theme (dark background).
+pub fn catppuccin_mocha() -> Theme {
+    Theme::from_toml_with_name(include_str!("catppuccin_mocha.toml"), "catppuccin_mocha")
+        .expect("Built-in theme should be valid")
+}
+
+/// Gruvbox Dark theme (dark background).
+pub fn gruvbox_dark() -> Theme {
+    Theme::from_toml_with_name(include_str!("gruvbox_dark.toml"), "gruvbox_dark")
+        .expect("Built-in theme should be valid")
+}
+
+/// Gruvbox Light theme (light background).
+pub fn gruvbox_light() -> Theme {
+    Theme::from_toml_with_name(include_str!("gruvbox_light.toml"), "gruvbox_light")
+        .expect("Built-in theme should be valid")
+}
+
+/// Solarized Dark theme (dark background).
+pub fn solarized_dark() -> Theme {
+    Theme::from_toml_with_name(include_str!("solarized_dark.toml"), "solarized_dark")
+        .expect("Built-in theme should be valid")
+}
+
+/// Solarized Light theme (light background).
+pub fn solarized_light() -> Theme {
+    Theme::from_toml_with_name(include_str!("solarized_light.toml"), "solarized_light")
+        .expect("Built-in theme should be valid")
+}
+
+/// Dracula theme (dark background).
+pub fn dracula() -> Theme {
+    Theme::from_toml_with_name(include_str!("dracula.toml"), "dracula")
+        .expect("Built-in theme should be valid")
+}
+
+/// Nord theme (dark background).
+pub fn nord() -> Theme {
+    Theme::from_toml_with_name(include_str!("nord.toml"), "nord")
+        .expect("Built-in theme should be valid")
+}
+
+/// High Contrast theme (black background, for low vision and monochrome-leaning
+/// terminals).
+pub fn high_contrast() -> Theme {
+    Theme::from_toml_with_name(include_str!("high_contrast.toml"), "high_contrast")
+        .expect("Built-in theme should be valid")
+}
+
+/// Names of all built-in themes, as accepted by [`by_name`].
+pub const BUILT_IN: &[&str] = &[
+    "one_dark",
+    "github_light",
+    "catppuccin_latte",
+    "catppuccin_frappe",
+    "catppuccin_macchiato",
+    "catppuccin_mocha",
+    "gruvbox_dark",
+    "gruvbox_light",
+    "solarized_dark",
+    "solarized_light",
+    "dracula",
+    "nord",
+    "high_contrast",
+];
+
+/// Look up a built-in theme by name.
+pub fn by_name(name: &str) -> Option<Theme> {
+    match name {
+        "one_dark" => Some(one_dark()),
+        "github_light" => Some(github_light()),
+        "catppuccin_latte" => Some(catppuccin_latte()),
+        "catppuccin_frappe" => Some(catppuccin_frappe()),
+        "catppuccin_macchiato" => Some(catppuccin_macchiato()),
+        "catppuccin_mocha" => Some(catppuccin_mocha()),
+        "gruvbox_dark" => Some(gruvbox_dark()),
+        "gruvbox_light" => Some(gruvbox_light()),
+        "solarized_dark" => Some(solarized_dark()),
+        "solarized_light" => Some(solarized_light()),
+        "dracula" => Some(dracula()),
+        "nord" => Some(nord()),
+        "high_contrast" => Some(high_contrast()),
+        _ => None,
+    }
+}
+
 /// One Dark theme TOML (Helix-compatible format).
 const ONE_DARK_TOML: &str = r##"
 [palette]
@@ -189,6 +299,10 @@ fg = "cyan"
 [escape]
 fg = "cyan"
 
+# Syntax errors
+[error]
+underline = { color = "red", style = "curl" }
+
 # UI chrome
 ["ui.background"]
 fg = "#ABB2BF"
@@ -480,6 +594,10 @@ fg = "dark_gray"
 [escape]
 fg = "cyan"
 
+# Syntax errors
+[error]
+underline = { color = "red", style = "curl" }
+
 # UI chrome
 ["ui.background"]
 fg = "#1F2328"
@@ -597,3 +715,19 @@ fg = "#57606A"
 ["ui.notebook.stale"]
 fg = "#9A6700"
 "##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes_parse_and_resolve_by_name() {
+        for name in BUILT_IN {
+            let theme = by_name(name).unwrap_or_else(|| panic!("{name} is not resolvable"));
+            assert_eq!(theme.name, *name);
+            assert!(theme.style_for_exact("keyword").is_some(), "{name}");
+            assert!(theme.style_for_exact("ui.background").is_some(), "{name}");
+        }
+        assert!(by_name("missing").is_none());
+    }
+}